@@ -1,11 +1,12 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use crypto::signatures::secp256k1_ecdsa::EvmAddress;
 #[cfg(feature = "participation")]
 use iota_sdk::{
     client::node_manager::node::Node,
     types::api::plugins::participation::types::{ParticipationEventId, ParticipationEventType},
-    wallet::account::types::participation::ParticipationEventRegistrationOptions,
+    wallet::account::{types::participation::ParticipationEventRegistrationOptions, ParticipationManagerOptions},
 };
 use iota_sdk::{
     client::{
@@ -13,16 +14,17 @@ use iota_sdk::{
         secret::GenerateAddressOptions,
     },
     types::block::{
-        address::Bech32Address,
-        output::{dto::OutputDto, OutputId, TokenId},
+        output::{dto::OutputDto, FoundryId, OutputId, TokenId},
         payload::transaction::TransactionId,
     },
+    utils::serde::evm_address,
     wallet::{
         account::{
-            ConsolidationParams, CreateAliasParams, CreateNativeTokenParams, FilterOptions, MintNftParams,
-            OutputParams, OutputsToClaim, SyncOptions, TransactionOptionsDto,
+            ConsolidationParams, CreateAliasParams, CreateNativeTokenParams, FilterOptions, HistoryExportFormat,
+            HistoryOptions, MintNftParams, OutputParams, OutputQuery, OutputsToClaim, SyncOptions,
+            TransactionOptionsDto, TypedData,
         },
-        SendNativeTokensParams, SendNftParams, SendParams,
+        AddressOrLabel, DepositToChainParams, SendNativeTokensParams, SendNftParams, SendParams, VestingScheduleParams,
     },
     U256,
 };
@@ -40,6 +42,13 @@ pub enum AccountMethod {
     /// Expected response:
     /// [`AddressesWithUnspentOutputs`](crate::Response::AddressesWithUnspentOutputs)
     AddressesWithUnspentOutputs,
+    /// Backfills the spent outputs and transactions of the account from the permanodes of the client, which need to
+    /// provide the Chronicle explorer API.
+    /// Expected response: [`OutputIds`](crate::Response::OutputIds)
+    BackfillHistory,
+    /// Returns the unspent outputs of the account which were sent by a chain.
+    /// Expected response: [`ChainWithdrawals`](crate::Response::ChainWithdrawals)
+    ChainWithdrawals,
     /// Get outputs with additional unlock conditions
     /// Expected response: [`OutputIds`](crate::Response::OutputIds)
     #[serde(rename_all = "camelCase")]
@@ -54,19 +63,38 @@ pub enum AccountMethod {
     #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
     #[serde(rename_all = "camelCase")]
     DeregisterParticipationEvent { event_id: ParticipationEventId },
+    /// List the generated EVM addresses.
+    /// Expected response: [`EvmAddresses`](crate::Response::EvmAddresses)
+    EvmAddresses,
+    /// Export the transaction history of the account, one row per movement.
+    /// Expected response: [`ExportedHistory`](crate::Response::ExportedHistory)
+    ExportHistory {
+        format: HistoryExportFormat,
+        options: Option<HistoryOptions>,
+    },
     /// Generate new Ed25519 addresses.
     /// Expected response: [`GeneratedEd25519Addresses`](crate::Response::GeneratedEd25519Addresses)
     GenerateEd25519Addresses {
         amount: u32,
         options: Option<GenerateAddressOptions>,
     },
+    /// Generate new EVM addresses.
+    /// Expected response: [`EvmAddresses`](crate::Response::EvmAddresses)
+    GenerateEvmAddresses { amount: u32 },
     /// Get account balance information.
     /// Expected response: [`Balance`](crate::Response::Balance)
     GetBalance,
+    /// Get the supply information of a foundry by the TokenId of its native token.
+    /// Expected response: [`FoundryInfo`](crate::Response::FoundryInfo)
+    #[serde(rename_all = "camelCase")]
+    GetFoundryInfo { token_id: TokenId },
     /// Get the [`Output`](iota_sdk::types::block::output::Output) that minted a native token by its TokenId
     /// Expected response: [`Output`](crate::Response::Output)
     #[serde(rename_all = "camelCase")]
     GetFoundryOutput { token_id: TokenId },
+    /// Get the transaction history of the account, one entry per movement.
+    /// Expected response: [`History`](crate::Response::History)
+    GetHistory { options: Option<HistoryOptions> },
     /// Get the transaction with inputs of an incoming transaction stored in the account
     /// List might not be complete, if the node pruned the data already
     /// Expected response: [`Transaction`](crate::Response::Transaction)
@@ -99,6 +127,12 @@ pub enum AccountMethod {
     #[cfg(feature = "participation")]
     #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
     GetParticipationEvents,
+    /// Get the options of the participation manager, `None` if it's disabled.
+    /// Expected response:
+    /// [`ParticipationManagerOptions`](crate::Response::ParticipationManagerOptions)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    GetParticipationManagerOptions,
     /// Calculates a participation overview for an account. If event_ids are provided, only return outputs and tracked
     /// participations for them.
     /// Expected response:
@@ -113,6 +147,18 @@ pub enum AccountMethod {
     /// Expected response: [`Transaction`](crate::Response::Transaction)
     #[serde(rename_all = "camelCase")]
     GetTransaction { transaction_id: TransactionId },
+    /// Get the storage deposits lent to recipients of outputs sent by the account.
+    /// Expected response: [`StorageDepositLedger`](crate::Response::StorageDepositLedger)
+    GetStorageDepositReturns,
+    /// Get the time-locked outputs of the account and the outputs it sent with an expiration.
+    /// Expected response: [`TimeLockedBalance`](crate::Response::TimeLockedBalance)
+    GetTimeLockedBalance,
+    /// Projects the staking rewards of the voting output for a staking event.
+    /// Expected response: [`StakingRewardsProjection`](crate::Response::StakingRewardsProjection)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    #[serde(rename_all = "camelCase")]
+    GetProjectedStakingRewards { event_id: ParticipationEventId },
     /// Get the account's total voting power (voting or NOT voting).
     /// Expected response: [`VotingPower`](crate::Response::VotingPower)
     #[cfg(feature = "participation")]
@@ -153,6 +199,12 @@ pub enum AccountMethod {
         params: Option<CreateAliasParams>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Prepare to create a vesting schedule, sending one time-locked output per installment.
+    /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
+    PrepareCreateVestingSchedule {
+        params: VestingScheduleParams,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Prepare to create a native token.
     /// Expected response:
     /// [`PreparedCreateNativeTokenTransaction`](crate::Response::PreparedCreateNativeTokenTransaction)
@@ -160,6 +212,19 @@ pub enum AccountMethod {
         params: CreateNativeTokenParams,
         options: Option<TransactionOptionsDto>,
     },
+    /// Prepare to deposit assets to an agent on a smart contract chain.
+    /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
+    PrepareDepositToChain {
+        params: DepositToChainParams,
+        options: Option<TransactionOptionsDto>,
+    },
+    /// Prepare to destroy a foundry, melting all its circulating tokens held by the account.
+    /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
+    #[serde(rename_all = "camelCase")]
+    PrepareDestroyFoundry {
+        foundry_id: FoundryId,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Reduces an account's "voting power" by a given amount.
     /// This will stop voting, but the voting data isn't lost and calling `Vote` without parameters will revote.
     /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
@@ -238,6 +303,15 @@ pub enum AccountMethod {
         outputs: Vec<OutputDto>,
         options: Option<TransactionOptionsDto>,
     },
+    /// Prepare to update the metadata feature of a foundry, or to remove it if no metadata is provided.
+    /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
+    #[serde(rename_all = "camelCase")]
+    PrepareUpdateFoundryMetadata {
+        token_id: TokenId,
+        #[serde(default, with = "iota_sdk::utils::serde::option_prefix_hex_bytes")]
+        metadata: Option<Vec<u8>>,
+        options: Option<TransactionOptionsDto>,
+    },
     /// Vote for a participation event.
     /// Expected response: [`PreparedTransaction`](crate::Response::PreparedTransaction)
     #[cfg(feature = "participation")]
@@ -247,6 +321,9 @@ pub enum AccountMethod {
         event_id: Option<ParticipationEventId>,
         answers: Option<Vec<u8>>,
     },
+    /// Returns the page of the synced outputs of the account matching the query.
+    /// Expected response: [`OutputQueryPage`](crate::Response::OutputQueryPage)
+    QueryOutputs { query: OutputQuery },
     /// Stores participation information locally and returns the event.
     ///
     /// This will NOT store the node url and auth inside the client options.
@@ -256,6 +333,15 @@ pub enum AccountMethod {
     RegisterParticipationEvents {
         options: ParticipationEventRegistrationOptions,
     },
+    /// Re-casts the votes of the latest participations which are missing in the voting output.
+    /// Expected response: [`Transaction`](crate::Response::Transaction)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    RenewVotes,
+    /// Resolves a label of the wallet's address book or a Bech32 encoded address.
+    /// Expected response: [`Bech32Address`](crate::Response::Bech32Address)
+    #[serde(rename_all = "camelCase")]
+    ResolveAddress { label_or_address: String },
     /// Retries (promotes or reattaches) a transaction sent from the account for a provided transaction id until it's
     /// included (referenced by a milestone). Returns the included block id.
     /// Expected response: [`BlockId`](crate::Response::BlockId)
@@ -268,12 +354,12 @@ pub enum AccountMethod {
         /// Maximum attempts
         max_attempts: Option<u64>,
     },
-    /// Send base coins.
+    /// Send base coins to a label of the wallet's address book or a Bech32 encoded address.
     /// Expected response: [`SentTransaction`](crate::Response::SentTransaction)
     Send {
         #[serde(with = "iota_sdk::utils::serde::string")]
         amount: u64,
-        address: Bech32Address,
+        address: AddressOrLabel,
         options: Option<TransactionOptionsDto>,
     },
     /// Send base coins to multiple addresses, or with additional parameters.
@@ -295,12 +381,45 @@ pub enum AccountMethod {
     /// If storage is enabled, will persist during restarts.
     /// Expected response: [`Ok`](crate::Response::Ok)
     SetDefaultSyncOptions { options: SyncOptions },
+    /// Set the options of the participation manager, which runs with the background syncing. `None` disables it.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    SetParticipationManagerOptions {
+        options: Option<ParticipationManagerOptions>,
+    },
     /// Validate the transaction, sign it, submit it to a node and store it in the account.
     /// Expected response: [`SentTransaction`](crate::Response::SentTransaction)
     #[serde(rename_all = "camelCase")]
     SignAndSubmitTransaction {
         prepared_transaction_data: PreparedTransactionDataDto,
     },
+    /// Sign a message with the key of an EVM address of the account (EIP-191).
+    /// Expected response: [`EvmSignature`](crate::Response::EvmSignature)
+    SignEvmMessage {
+        #[serde(with = "evm_address")]
+        address: EvmAddress,
+        /// The message to sign, hex encoded String
+        message: String,
+    },
+    /// Sign a raw RLP encoded EVM transaction with the key of an EVM address of the account (EIP-155).
+    /// Expected response: [`SignedEvmTransaction`](crate::Response::SignedEvmTransaction)
+    #[serde(rename_all = "camelCase")]
+    SignEvmTransaction {
+        #[serde(with = "evm_address")]
+        address: EvmAddress,
+        /// The unsigned transaction, hex encoded String
+        transaction: String,
+        chain_id: u64,
+    },
+    /// Sign EIP-712 typed data with the key of an EVM address of the account.
+    /// Expected response: [`EvmSignature`](crate::Response::EvmSignature)
+    #[serde(rename_all = "camelCase")]
+    SignEvmTypedData {
+        #[serde(with = "evm_address")]
+        address: EvmAddress,
+        typed_data: TypedData,
+    },
     /// Sign a prepared transaction.
     /// Expected response: [`SignedTransactionData`](crate::Response::SignedTransactionData)
     #[serde(rename_all = "camelCase")]
@@ -8,12 +8,13 @@ use iota_sdk::{
         address::{Bech32Address, Hrp},
         output::{dto::OutputDto, AliasId, NftId, OutputId, RentStructure},
         payload::{
-            dto::MilestonePayloadDto,
+            dto::{MilestonePayloadDto, PayloadDto},
             transaction::{
                 dto::{TransactionEssenceDto, TransactionPayloadDto},
                 TransactionId,
             },
         },
+        render::RenderFormat,
         signature::dto::Ed25519SignatureDto,
         BlockDto,
     },
@@ -168,4 +169,32 @@ pub enum UtilsMethod {
         transaction: TransactionPayloadDto,
         time: u32,
     },
+    /// Renders a block as an annotated tree, with Bech32 addresses, decoded tagged data and storage deposits.
+    /// Expected response: [`Rendered`](crate::Response::Rendered)
+    #[serde(rename_all = "camelCase")]
+    RenderBlock {
+        /// Block
+        block: BlockDto,
+        /// Human readable part of the addresses
+        bech32_hrp: Hrp,
+        /// Rent structure of the storage deposits
+        rent: RentStructure,
+        /// Text, JSON or markdown
+        #[serde(default)]
+        format: RenderFormat,
+    },
+    /// Renders a payload as an annotated tree, with Bech32 addresses, decoded tagged data and storage deposits.
+    /// Expected response: [`Rendered`](crate::Response::Rendered)
+    #[serde(rename_all = "camelCase")]
+    RenderPayload {
+        /// Payload
+        payload: PayloadDto,
+        /// Human readable part of the addresses
+        bech32_hrp: Hrp,
+        /// Rent structure of the storage deposits
+        rent: RentStructure,
+        /// Text, JSON or markdown
+        #[serde(default)]
+        format: RenderFormat,
+    },
 }
@@ -15,7 +15,7 @@ use iota_sdk::{
             types::{AccountAddress, AccountIdentifier},
             SyncOptions,
         },
-        ClientOptions,
+        AddressBookEntry, ClientOptions,
     },
     Url,
 };
@@ -57,6 +57,15 @@ pub enum WalletMethod {
     #[cfg(feature = "storage")]
     #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
     GetChrysalisData,
+    /// Returns the address book of the wallet.
+    /// Expected response: [`AddressBook`](crate::Response::AddressBook)
+    GetAddressBook,
+    /// Inserts an entry into the address book, replacing an existing entry with the same label.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    SetAddressBookEntry { entry: AddressBookEntry },
+    /// Removes the address book entry with the given label.
+    /// Expected response: [`Ok`](crate::Response::Ok)
+    RemoveAddressBookEntry { label: String },
     /// Consume an account method.
     /// Returns [`Response`](crate::Response)
     #[serde(rename_all = "camelCase")]
@@ -5,15 +5,17 @@
 use std::str::FromStr;
 
 use iota_sdk::{
-    client::api::{
-        PreparedTransactionData, PreparedTransactionDataDto, SignedTransactionData, SignedTransactionDataDto,
+    client::{
+        api::{PreparedTransactionData, PreparedTransactionDataDto, SignedTransactionData, SignedTransactionDataDto},
+        node_api::history::ChronicleHistoryProvider,
     },
     types::{
         block::output::{dto::OutputDto, Output},
         TryFromDto,
     },
     wallet::account::{
-        types::TransactionDto, Account, OutputDataDto, PreparedCreateNativeTokenTransactionDto, TransactionOptions,
+        types::TransactionDto, Account, OutputDataDto, OutputQueryPageDto, PreparedCreateNativeTokenTransactionDto,
+        TransactionOptions,
     },
 };
 
@@ -29,6 +31,11 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             let addresses = account.addresses_with_unspent_outputs().await?;
             Response::AddressesWithUnspentOutputs(addresses)
         }
+        AccountMethod::BackfillHistory => {
+            let history_provider = ChronicleHistoryProvider::new(account.client().clone());
+            Response::OutputIds(account.backfill_history(&history_provider).await?)
+        }
+        AccountMethod::ChainWithdrawals => Response::ChainWithdrawals(account.chain_withdrawals().await?),
         AccountMethod::ClaimableOutputs { outputs_to_claim } => {
             let output_ids = account.claimable_outputs(outputs_to_claim).await?;
             Response::OutputIds(output_ids)
@@ -42,15 +49,24 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             account.deregister_participation_event(&event_id).await?;
             Response::Ok
         }
+        AccountMethod::EvmAddresses => Response::EvmAddresses(account.details().await.evm_addresses().clone()),
         AccountMethod::GenerateEd25519Addresses { amount, options } => {
             let address = account.generate_ed25519_addresses(amount, options).await?;
             Response::GeneratedAccountAddresses(address)
         }
+        AccountMethod::GenerateEvmAddresses { amount } => {
+            Response::EvmAddresses(account.generate_evm_addresses(amount).await?)
+        }
+        AccountMethod::ExportHistory { format, options } => {
+            Response::ExportedHistory(account.export_history(format, options).await?)
+        }
         AccountMethod::GetBalance => Response::Balance(account.balance().await?),
+        AccountMethod::GetFoundryInfo { token_id } => Response::FoundryInfo(account.foundry_info(token_id).await?),
         AccountMethod::GetFoundryOutput { token_id } => {
             let output = account.get_foundry_output(token_id).await?;
             Response::Output(OutputDto::from(&output))
         }
+        AccountMethod::GetHistory { options } => Response::History(account.history(options).await?),
         AccountMethod::GetIncomingTransaction { transaction_id } => {
             let transaction = account.get_incoming_transaction(&transaction_id).await;
 
@@ -84,15 +100,27 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             Response::ParticipationEvents(events)
         }
         #[cfg(feature = "participation")]
+        AccountMethod::GetParticipationManagerOptions => {
+            Response::ParticipationManagerOptions(account.participation_manager_options().await?)
+        }
+        #[cfg(feature = "participation")]
         AccountMethod::GetParticipationOverview { event_ids } => {
             let overview = account.get_participation_overview(event_ids).await?;
             Response::AccountParticipationOverview(overview)
         }
+        AccountMethod::GetStorageDepositReturns => {
+            Response::StorageDepositLedger(account.storage_deposit_returns().await?)
+        }
+        AccountMethod::GetTimeLockedBalance => Response::TimeLockedBalance(account.time_locked_balance().await?),
         AccountMethod::GetTransaction { transaction_id } => {
             let transaction = account.get_transaction(&transaction_id).await;
             Response::Transaction(transaction.as_ref().map(TransactionDto::from).map(Box::new))
         }
         #[cfg(feature = "participation")]
+        AccountMethod::GetProjectedStakingRewards { event_id } => {
+            Response::StakingRewardsProjection(account.projected_staking_rewards(event_id).await?)
+        }
+        #[cfg(feature = "participation")]
         AccountMethod::GetVotingPower => {
             let voting_power = account.get_voting_power().await?;
             Response::VotingPower(voting_power.to_string())
@@ -129,6 +157,18 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
                 .await?;
             Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
         }
+        AccountMethod::PrepareDepositToChain { params, options } => {
+            let data = account
+                .prepare_deposit_to_chain(params, options.map(TransactionOptions::try_from_dto).transpose()?)
+                .await?;
+            Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
+        }
+        AccountMethod::PrepareCreateVestingSchedule { params, options } => {
+            let data = account
+                .prepare_create_vesting_schedule(params, options.map(TransactionOptions::try_from_dto).transpose()?)
+                .await?;
+            Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
+        }
         AccountMethod::PrepareMeltNativeToken {
             token_id,
             melt_amount,
@@ -152,6 +192,12 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
                 .await?;
             Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
         }
+        AccountMethod::PrepareDestroyFoundry { foundry_id, options } => {
+            let data = account
+                .prepare_destroy_foundry(foundry_id, options.map(TransactionOptions::try_from_dto).transpose()?)
+                .await?;
+            Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
+        }
         AccountMethod::PrepareMintNativeToken {
             token_id,
             mint_amount,
@@ -241,16 +287,41 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
                 .await?;
             Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
         }
+        AccountMethod::PrepareUpdateFoundryMetadata {
+            token_id,
+            metadata,
+            options,
+        } => {
+            let data = account
+                .prepare_update_foundry_metadata(
+                    token_id,
+                    metadata,
+                    options.map(TransactionOptions::try_from_dto).transpose()?,
+                )
+                .await?;
+            Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
+        }
         #[cfg(feature = "participation")]
         AccountMethod::PrepareVote { event_id, answers } => {
             let data = account.prepare_vote(event_id, answers).await?;
             Response::PreparedTransaction(PreparedTransactionDataDto::from(&data))
         }
+        AccountMethod::QueryOutputs { query } => {
+            Response::OutputQueryPage(OutputQueryPageDto::from(&account.query_outputs(query).await))
+        }
         #[cfg(feature = "participation")]
         AccountMethod::RegisterParticipationEvents { options } => {
             let events = account.register_participation_events(&options).await?;
             Response::ParticipationEvents(events)
         }
+        #[cfg(feature = "participation")]
+        AccountMethod::RenewVotes => {
+            let transaction = account.renew_votes().await?;
+            Response::Transaction(transaction.as_ref().map(TransactionDto::from).map(Box::new))
+        }
+        AccountMethod::ResolveAddress { label_or_address } => {
+            Response::Bech32Address(account.resolve_address(&label_or_address).await?)
+        }
         AccountMethod::RetryTransactionUntilIncluded {
             transaction_id,
             interval,
@@ -302,6 +373,11 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
             account.set_default_sync_options(options).await?;
             Response::Ok
         }
+        #[cfg(feature = "participation")]
+        AccountMethod::SetParticipationManagerOptions { options } => {
+            account.set_participation_manager_options(options).await?;
+            Response::Ok
+        }
         AccountMethod::SignAndSubmitTransaction {
             prepared_transaction_data,
         } => {
@@ -316,6 +392,22 @@ pub(crate) async fn call_account_method_internal(account: &Account, method: Acco
                 .await?;
             Response::SentTransaction(TransactionDto::from(&transaction))
         }
+        AccountMethod::SignEvmMessage { address, message } => {
+            let message: Vec<u8> = prefix_hex::decode(message)?;
+            Response::EvmSignature(account.sign_evm_message(&address, &message).await?)
+        }
+        AccountMethod::SignEvmTransaction {
+            address,
+            transaction,
+            chain_id,
+        } => {
+            let transaction: Vec<u8> = prefix_hex::decode(transaction)?;
+            let signed_transaction = account.sign_evm_transaction(&address, &transaction, chain_id).await?;
+            Response::SignedEvmTransaction(prefix_hex::encode(signed_transaction))
+        }
+        AccountMethod::SignEvmTypedData { address, typed_data } => {
+            Response::EvmSignature(account.sign_evm_typed_data(&address, &typed_data).await?)
+        }
         AccountMethod::SignTransactionEssence {
             prepared_transaction_data,
         } => {
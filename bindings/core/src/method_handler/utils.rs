@@ -12,7 +12,8 @@ use iota_sdk::{
             address::{dto::AddressDto, Address, AliasAddress, ToBech32Ext},
             input::UtxoInput,
             output::{AliasId, FoundryId, InputsCommitment, NftId, Output, OutputId, Rent, TokenId},
-            payload::{transaction::TransactionEssence, MilestonePayload, TransactionPayload},
+            payload::{transaction::TransactionEssence, MilestonePayload, Payload, TransactionPayload},
+            render::Renderer,
             signature::Ed25519Signature,
             Block,
         },
@@ -132,6 +133,24 @@ pub(crate) fn call_utils_method_internal(method: UtilsMethod) -> Result<Response
             )?;
             Response::ConflictReason(conflict)
         }
+        UtilsMethod::RenderBlock {
+            block,
+            bech32_hrp,
+            rent,
+            format,
+        } => {
+            let block = Block::try_from_dto(block)?;
+            Response::Rendered(Renderer::new(bech32_hrp, rent).block(&block).render(format))
+        }
+        UtilsMethod::RenderPayload {
+            payload,
+            bech32_hrp,
+            rent,
+            format,
+        } => {
+            let payload = Payload::try_from_dto(payload)?;
+            Response::Rendered(Renderer::new(bech32_hrp, rent).payload(&payload).render(format))
+        }
     };
     Ok(response)
 }
@@ -64,6 +64,15 @@ pub(crate) async fn call_wallet_method_internal(wallet: &Wallet, method: WalletM
             Response::Accounts(account_dtos)
         }
         WalletMethod::GetChrysalisData => Response::ChrysalisData(wallet.get_chrysalis_data().await?),
+        WalletMethod::GetAddressBook => Response::AddressBook(wallet.address_book().await),
+        WalletMethod::SetAddressBookEntry { entry } => {
+            wallet.set_address_book_entry(entry).await?;
+            Response::Ok
+        }
+        WalletMethod::RemoveAddressBookEntry { label } => {
+            wallet.remove_address_book_entry(&label).await?;
+            Response::Ok
+        }
         WalletMethod::CallAccountMethod { account_id, method } => {
             let account = wallet.get_account(account_id).await?;
             call_account_method_internal(&account, method).await?
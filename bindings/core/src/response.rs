@@ -38,16 +38,26 @@ use iota_sdk::{
             BlockDto, BlockId,
         },
     },
-    wallet::account::{
-        types::{AccountAddress, AddressWithUnspentOutputs, Balance, OutputDataDto, TransactionDto},
-        AccountDetailsDto, PreparedCreateNativeTokenTransactionDto,
+    wallet::{
+        account::{
+            types::{
+                AccountAddress, AddressWithUnspentOutputs, Balance, EvmAccountAddress, OutputDataDto,
+                TimeLockedBalance, TransactionDto,
+            },
+            AccountDetailsDto, ChainWithdrawal, EvmSignature, FoundryInfo, HistoryEntry, OutputQueryPageDto,
+            PreparedCreateNativeTokenTransactionDto, StorageDepositLedger,
+        },
+        AddressBook,
     },
 };
 use serde::Serialize;
 #[cfg(feature = "participation")]
 use {
     iota_sdk::types::api::plugins::participation::types::{ParticipationEventId, ParticipationEventStatus},
-    iota_sdk::wallet::account::{AccountParticipationOverview, ParticipationEventWithNodes},
+    iota_sdk::wallet::account::{
+        AccountParticipationOverview, ParticipationEventWithNodes, ParticipationManagerOptions,
+        StakingRewardsProjection,
+    },
 };
 
 use crate::{error::Error, OmittedDebug};
@@ -250,6 +260,7 @@ pub enum Response {
     /// - [`HexPublicKeyToBech32Address`](crate::method::ClientMethod::HexPublicKeyToBech32Address)
     /// - [`HexToBech32`](crate::method::ClientMethod::HexToBech32)
     /// - [`NftIdToBech32`](crate::method::ClientMethod::NftIdToBech32)
+    /// - [`ResolveAddress`](crate::method::AccountMethod::ResolveAddress)
     Bech32Address(Bech32Address),
     /// - [`Faucet`](crate::method::ClientMethod::RequestFundsFromFaucet)
     Faucet(String),
@@ -314,6 +325,9 @@ pub enum Response {
     /// - [`AddressesWithUnspentOutputs`](crate::method::AccountMethod::AddressesWithUnspentOutputs)
     AddressesWithUnspentOutputs(Vec<AddressWithUnspentOutputs>),
     /// Response for:
+    /// - [`GetAddressBook`](crate::method::WalletMethod::GetAddressBook)
+    AddressBook(AddressBook),
+    /// Response for:
     /// - [`GetChrysalisData`](crate::method::WalletMethod::GetChrysalisData)
     ChrysalisData(Option<HashMap<String, String>>),
     /// Response for:
@@ -321,6 +335,11 @@ pub enum Response {
     /// - [`ComputeStorageDeposit`](crate::method::UtilsMethod::ComputeStorageDeposit)
     MinimumRequiredStorageDeposit(String),
     /// Response for:
+    /// - [`RenderBlock`](crate::method::UtilsMethod::RenderBlock)
+    /// - [`RenderPayload`](crate::method::UtilsMethod::RenderPayload)
+    Rendered(String),
+    /// Response for:
+    /// - [`BackfillHistory`](crate::method::AccountMethod::BackfillHistory)
     /// - [`ClaimableOutputs`](crate::method::AccountMethod::ClaimableOutputs)
     OutputIds(Vec<OutputId>),
     /// Response for:
@@ -331,11 +350,17 @@ pub enum Response {
     /// - [`UnspentOutputs`](crate::method::AccountMethod::UnspentOutputs)
     OutputsData(Vec<OutputDataDto>),
     /// Response for:
+    /// - [`QueryOutputs`](crate::method::AccountMethod::QueryOutputs)
+    OutputQueryPage(OutputQueryPageDto),
+    /// Response for:
     /// - [`PrepareBurn`](crate::method::AccountMethod::PrepareBurn),
     /// - [`PrepareClaimOutputs`](crate::method::AccountMethod::PrepareClaimOutputs)
     /// - [`PrepareConsolidateOutputs`](crate::method::AccountMethod::PrepareConsolidateOutputs)
     /// - [`PrepareCreateAliasOutput`](crate::method::AccountMethod::PrepareCreateAliasOutput)
+    /// - [`PrepareCreateVestingSchedule`](crate::method::AccountMethod::PrepareCreateVestingSchedule)
+    /// - [`PrepareDepositToChain`](crate::method::AccountMethod::PrepareDepositToChain)
     /// - [`PrepareDecreaseVotingPower`](crate::method::AccountMethod::PrepareDecreaseVotingPower)
+    /// - [`PrepareDestroyFoundry`](crate::method::AccountMethod::PrepareDestroyFoundry)
     /// - [`PrepareIncreaseVotingPower`](crate::method::AccountMethod::PrepareIncreaseVotingPower)
     /// - [`PrepareMeltNativeToken`](crate::method::AccountMethod::PrepareMeltNativeToken)
     /// - [`PrepareMintNativeToken`](crate::method::AccountMethod::PrepareMintNativeToken),
@@ -345,6 +370,7 @@ pub enum Response {
     /// - [`PrepareSendNft`](crate::method::AccountMethod::PrepareSendNft),
     /// - [`PrepareStopParticipating`](crate::method::AccountMethod::PrepareStopParticipating)
     /// - [`PrepareTransaction`](crate::method::AccountMethod::PrepareTransaction)
+    /// - [`PrepareUpdateFoundryMetadata`](crate::method::AccountMethod::PrepareUpdateFoundryMetadata)
     /// - [`PrepareVote`](crate::method::AccountMethod::PrepareVote)
     PreparedTransaction(PreparedTransactionDataDto),
     /// Response for:
@@ -353,6 +379,7 @@ pub enum Response {
     /// Response for:
     /// - [`GetIncomingTransaction`](crate::method::AccountMethod::GetIncomingTransaction)
     /// - [`GetTransaction`](crate::method::AccountMethod::GetTransaction),
+    /// - [`RenewVotes`](crate::method::AccountMethod::RenewVotes),
     Transaction(Option<Box<TransactionDto>>),
     /// Response for:
     /// - [`IncomingTransactions`](crate::method::AccountMethod::IncomingTransactions)
@@ -360,6 +387,12 @@ pub enum Response {
     /// - [`Transactions`](crate::method::AccountMethod::Transactions),
     Transactions(Vec<TransactionDto>),
     /// Response for:
+    /// - [`GetHistory`](crate::method::AccountMethod::GetHistory)
+    History(Vec<HistoryEntry>),
+    /// Response for:
+    /// - [`ExportHistory`](crate::method::AccountMethod::ExportHistory)
+    ExportedHistory(String),
+    /// Response for:
     /// - [`SignTransactionEssence`](crate::method::AccountMethod::SignTransactionEssence)
     SignedTransactionData(SignedTransactionDataDto),
     /// GenerateAddress response.
@@ -367,10 +400,33 @@ pub enum Response {
     /// - [`GenerateEd25519Addresses`](crate::method::AccountMethod::GenerateEd25519Addresses)
     GeneratedAccountAddresses(Vec<AccountAddress>),
     /// Response for:
+    /// - [`EvmAddresses`](crate::method::AccountMethod::EvmAddresses)
+    /// - [`GenerateEvmAddresses`](crate::method::AccountMethod::GenerateEvmAddresses)
+    EvmAddresses(Vec<EvmAccountAddress>),
+    /// Response for:
+    /// - [`SignEvmMessage`](crate::method::AccountMethod::SignEvmMessage)
+    /// - [`SignEvmTypedData`](crate::method::AccountMethod::SignEvmTypedData)
+    EvmSignature(EvmSignature),
+    /// Response for:
+    /// - [`SignEvmTransaction`](crate::method::AccountMethod::SignEvmTransaction)
+    SignedEvmTransaction(String),
+    /// Response for:
+    /// - [`ChainWithdrawals`](crate::method::AccountMethod::ChainWithdrawals)
+    ChainWithdrawals(Vec<ChainWithdrawal>),
+    /// Response for:
     /// - [`GetBalance`](crate::method::AccountMethod::GetBalance),
     /// - [`Sync`](crate::method::AccountMethod::Sync)
     Balance(Balance),
     /// Response for:
+    /// - [`GetFoundryInfo`](crate::method::AccountMethod::GetFoundryInfo)
+    FoundryInfo(FoundryInfo),
+    /// Response for:
+    /// - [`GetStorageDepositReturns`](crate::method::AccountMethod::GetStorageDepositReturns)
+    StorageDepositLedger(StorageDepositLedger),
+    /// Response for:
+    /// - [`GetTimeLockedBalance`](crate::method::AccountMethod::GetTimeLockedBalance)
+    TimeLockedBalance(TimeLockedBalance),
+    /// Response for:
     /// - [`ClaimOutputs`](crate::method::AccountMethod::ClaimOutputs)
     /// - [`Send`](crate::method::AccountMethod::Send)
     /// - [`SendOutputs`](crate::method::AccountMethod::SendOutputs)
@@ -408,4 +464,14 @@ pub enum Response {
     #[cfg(feature = "participation")]
     #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
     AccountParticipationOverview(AccountParticipationOverview),
+    /// Response for:
+    /// - [`GetParticipationManagerOptions`](crate::method::AccountMethod::GetParticipationManagerOptions)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    ParticipationManagerOptions(Option<ParticipationManagerOptions>),
+    /// Response for:
+    /// - [`GetProjectedStakingRewards`](crate::method::AccountMethod::GetProjectedStakingRewards)
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    StakingRewardsProjection(StakingRewardsProjection),
 }
@@ -12,6 +12,22 @@ export interface LedgerNanoStatus {
     device?: LedgerDeviceType;
     /** Buffer size on device */
     bufferSize?: number;
+    /** Transaction contents the opened app can show for clear signing */
+    capabilities?: LedgerCapabilities;
+}
+
+/** The transaction contents a Ledger app can show to the user for clear signing */
+export interface LedgerCapabilities {
+    /** The essence is streamed to the device in chunks, so it can be larger than the buffer on the device. */
+    streamedSigning: boolean;
+    /** Native tokens of outputs are shown. */
+    nativeTokens: boolean;
+    /** NFT outputs are shown. */
+    nftOutputs: boolean;
+    /** Alias outputs are shown. */
+    aliasOutputs: boolean;
+    /** Unlock conditions other than the address unlock condition are shown. */
+    unlockConditions: boolean;
 }
 
 /** The current opened app */
@@ -108,6 +108,8 @@ export interface SyncOptions {
     syncOnlyMostBasicOutputs?: boolean;
     /** Sync native token foundries, so their metadata can be returned in the balance. Default: false. */
     syncNativeTokenFoundries?: boolean;
+    /** Claim the outputs which returned to the account because they expired before the recipient claimed them. Only used by the background syncing. Default: false. */
+    reclaimExpiredOutputs?: boolean;
 }
 
 /** Specifies what outputs should be synced for the ed25519 addresses from the account. */
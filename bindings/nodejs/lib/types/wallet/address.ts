@@ -18,14 +18,14 @@ export interface AccountAddress {
 
 /** Address with a base token amount */
 export interface SendParams {
-    /** The Bech32 address to send the amount to. */
+    /** The Bech32 address or a label of the wallet's address book to send the amount to. */
     address: Bech32Address;
     /** The amount to send. */
     amount: bigint | NumericString;
     /**
-     * Bech32 encoded address, to which the storage deposit will be returned if one is necessary
-     * given the provided amount. If a storage deposit is needed and a return address is not provided, it will
-     * default to the first address of the account.
+     * Bech32 encoded address or label of the wallet's address book, to which the storage deposit will be returned if
+     * one is necessary given the provided amount. If a storage deposit is needed and a return address is not provided,
+     * it will default to the first address of the account.
      */
     returnAddress?: string;
     /**
@@ -50,12 +50,12 @@ export interface AddressWithUnspentOutputs {
 
 /** Address with native tokens */
 export interface SendNativeTokensParams {
-    /** The Bech32 address. */
+    /** The Bech32 address or a label of the wallet's address book. */
     address: Bech32Address;
     /** The Native Tokens to send. */
     nativeTokens: [TokenId, bigint][];
     /**
-     * Bech32 encoded address, to which the storage deposit will be returned.
+     * Bech32 encoded address or label of the wallet's address book, to which the storage deposit will be returned.
      * Default will use the first address of the account.
      */
     returnAddress?: Bech32Address;
@@ -68,7 +68,7 @@ export interface SendNativeTokensParams {
 
 /** Address with an NftId */
 export interface SendNftParams {
-    /** The Bech32 address. */
+    /** The Bech32 address or a label of the wallet's address book. */
     address: Bech32Address;
     /** The ID of the NFT to send. */
     nftId: NftId;
@@ -12,6 +12,7 @@ export enum OutputsToClaim {
     NativeTokens = 'NativeTokens',
     Nfts = 'Nfts',
     Amount = 'Amount',
+    Expired = 'Expired',
     All = 'All',
 }
 
@@ -27,8 +27,22 @@ export interface TransactionOptions {
     note?: string;
     /** Whether to allow sending a micro amount. */
     allowMicroAmount?: boolean;
+    /** How storage deposits required to send assets or micro amounts are handled. */
+    storageDepositPolicy?: StorageDepositPolicy;
+    /** Whether to merge the outputs and move their native tokens into fewer outputs to reduce storage deposits. */
+    optimizeStorageDeposits?: boolean;
 }
 
+/** The possible storage deposit policies. */
+export type StorageDepositPolicy =
+    | { policy: 'Return' }
+    | { policy: 'Gift' }
+    | {
+          policy: 'GiftUpToLimit';
+          /** The maximum sum of gifted storage deposits in a transaction. */
+          value: string;
+      };
+
 /** The possible remainder value strategies. */
 export type RemainderValueStrategy =
     | ChangeAddress
@@ -1211,14 +1211,14 @@ export class Account {
      * Send base coins to an address.
      *
      * @param amount Amount of coins.
-     * @param address Receiving address.
+     * @param address Receiving address, or a label of the wallet's address book.
      * @param transactionOptions Additional transaction options
      * or custom inputs.
      * @returns The sent transaction.
      */
     async send(
         amount: bigint | NumericString,
-        address: Bech32Address,
+        address: Bech32Address | string,
         transactionOptions?: TransactionOptions,
     ): Promise<Transaction> {
         if (typeof amount === 'bigint') {
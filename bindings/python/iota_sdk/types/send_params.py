@@ -13,9 +13,9 @@ class SendParams():
     """Parameters for sending base coins.
 
     Attributes:
-        address: The address to send to.
+        address: The address to send to, or a label of the wallet's address book.
         amount: The amount to send.
-        returnAddress: The address to return the funds to if not claimed.
+        returnAddress: The address to return the funds to if not claimed, or a label of the wallet's address book.
         expiration: Expiration in seconds, after which the output will be available for the sender again, if not spent by the
         receiver already. The expiration will only be used if one is necessary given the provided amount. If an
         expiration is needed but not provided, it will default to one day.
@@ -40,9 +40,9 @@ class SendNativeTokensParams():
     """Parameters for sending native tokens
 
     Attributes:
-        address: The address to send to.
+        address: The address to send to, or a label of the wallet's address book.
         nativeTokens: The native tokens to send.
-        returnAddress: The address to return the native tokens to if not claimed.
+        returnAddress: The address to return the native tokens to if not claimed, or a label of the wallet's address book.
         expiration: The expiration timestamp until native tokens can be claimed.
     """
     address: str
@@ -61,7 +61,7 @@ class SendNftParams():
     """Parameters for sending NFTs.
 
     Attributes:
-        address: The address to send the NFT to.
+        address: The address to send the NFT to, or a label of the wallet's address book.
         nftId: The ID of the NFT to send.
     """
     address: str
@@ -454,7 +454,7 @@ class Account:
 
     def send(self, amount: str, address: str,
              options: Optional[TransactionOptions] = None) -> Transaction:
-        """Send base coins.
+        """Send base coins to a Bech32 address or a label of the wallet's address book.
         """
         return Transaction.from_dict(self._call_account_method(
             'send', {
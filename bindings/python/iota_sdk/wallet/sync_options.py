@@ -103,6 +103,9 @@ class SyncOptions():
         This will overwrite the `account`, `alias` and `nft` options.
     sync_native_token_foundries :
         Sync native token foundries, so their metadata can be returned in the balance.
+    reclaim_expired_outputs :
+        Claim the outputs which returned to the account because they expired before the recipient
+        claimed them. Only used by the background syncing.
     """
 
     def __init__(self,
@@ -116,7 +119,8 @@ class SyncOptions():
                  alias: Optional[AliasSyncOptions] = None,
                  nft: Optional[NftSyncOptions] = None,
                  sync_only_most_basic_outputs: Optional[bool] = None,
-                 sync_native_token_foundries: Optional[bool] = None):
+                 sync_native_token_foundries: Optional[bool] = None,
+                 reclaim_expired_outputs: Optional[bool] = None):
         """Initialize `Self`.
         """
         self.addresses = addresses
@@ -130,6 +134,7 @@ class SyncOptions():
         self.nft = nft
         self.syncOnlyMostBasicOutputs = sync_only_most_basic_outputs
         self.syncNativeTokenFoundries = sync_native_token_foundries
+        self.reclaimExpiredOutputs = reclaim_expired_outputs
 
     def as_dict(self):
         """Converts this object to a dict.
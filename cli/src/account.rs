@@ -9,15 +9,16 @@ use rustyline::{error::ReadlineError, history::MemHistory, Config, Editor};
 use crate::{
     command::{
         account::{
-            address_command, addresses_command, balance_command, burn_native_token_command, burn_nft_command,
-            claim_command, claimable_outputs_command, consolidate_command, create_alias_outputs_command,
-            create_native_token_command, decrease_voting_power_command, destroy_alias_command, destroy_foundry_command,
-            faucet_command, increase_voting_power_command, melt_native_token_command, mint_native_token,
-            mint_nft_command, new_address_command, node_info_command, output_command, outputs_command,
-            participation_overview_command, send_command, send_native_token_command, send_nft_command,
-            stop_participating_command, sync_command, transaction_command, transactions_command,
-            unspent_outputs_command, vote_command, voting_output_command, voting_power_command, AccountCli,
-            AccountCommand,
+            address_book_add_command, address_book_command, address_book_remove_command, address_command,
+            addresses_command, balance_command, burn_native_token_command, burn_nft_command, claim_command,
+            claimable_outputs_command, consolidate_command, create_alias_outputs_command, create_native_token_command,
+            decode_command, decrease_voting_power_command, destroy_alias_command, destroy_foundry_command,
+            encode_command, export_history_command, faucet_command, increase_voting_power_command,
+            melt_native_token_command, mint_native_token, mint_nft_command, new_address_command, node_info_command,
+            output_command, outputs_command, participation_overview_command, reclaim_deposits_command, send_command,
+            send_native_token_command, send_nft_command, stop_participating_command, sync_command, transaction_command,
+            transactions_command, unspent_outputs_command, vote_command, voting_output_command, voting_power_command,
+            AccountCli, AccountCommand,
         },
         account_completion::AccountPromptHelper,
     },
@@ -107,8 +108,16 @@ pub async fn account_prompt_internal(
                     };
                     match account_cli.command {
                         AccountCommand::Address { selector } => address_command(account, selector).await,
+                        AccountCommand::AddressBook { tag } => address_book_command(wallet, tag).await,
+                        AccountCommand::AddressBookAdd {
+                            label,
+                            target,
+                            kind,
+                            tag,
+                        } => address_book_add_command(wallet, label, target, kind, tag).await,
+                        AccountCommand::AddressBookRemove { label } => address_book_remove_command(wallet, label).await,
                         AccountCommand::Addresses => addresses_command(account).await,
-                        AccountCommand::Balance { addresses } => balance_command(account, addresses).await,
+                        AccountCommand::Balance { addresses } => balance_command(wallet, account, addresses).await,
                         AccountCommand::BurnNativeToken { token_id, amount } => {
                             burn_native_token_command(account, token_id, amount).await
                         }
@@ -131,13 +140,18 @@ pub async fn account_prompt_internal(
                             )
                             .await
                         }
+                        AccountCommand::Decode { hex, kind } => decode_command(account, hex, kind).await,
                         AccountCommand::DestroyAlias { alias_id } => destroy_alias_command(account, alias_id).await,
                         AccountCommand::DestroyFoundry { foundry_id } => {
                             destroy_foundry_command(account, foundry_id).await
                         }
+                        AccountCommand::Encode { json, kind } => encode_command(account, json, kind).await,
                         AccountCommand::Exit => {
                             return Ok(AccountPromptResponse::Done);
                         }
+                        AccountCommand::ExportHistory { path, format, from, to } => {
+                            export_history_command(account, path, format, from, to).await
+                        }
                         AccountCommand::Faucet { address, url } => faucet_command(account, address, url).await,
                         AccountCommand::MeltNativeToken { token_id, amount } => {
                             melt_native_token_command(account, token_id, amount).await
@@ -170,6 +184,7 @@ pub async fn account_prompt_internal(
                         AccountCommand::NodeInfo => node_info_command(account).await,
                         AccountCommand::Output { selector } => output_command(account, selector).await,
                         AccountCommand::Outputs => outputs_command(account).await,
+                        AccountCommand::ReclaimDeposits => reclaim_deposits_command(account).await,
                         AccountCommand::Send {
                             address,
                             amount,
@@ -197,8 +212,19 @@ pub async fn account_prompt_internal(
                             token_id,
                             amount,
                             gift_storage_deposit,
-                        } => send_native_token_command(account, address, token_id, amount, gift_storage_deposit).await,
-                        AccountCommand::SendNft { address, nft_id } => send_nft_command(account, address, nft_id).await,
+                        } => {
+                            send_native_token_command(
+                                account,
+                                account.resolve_address(&address).await?,
+                                token_id,
+                                amount,
+                                gift_storage_deposit,
+                            )
+                            .await
+                        }
+                        AccountCommand::SendNft { address, nft_id } => {
+                            send_nft_command(account, address, account.resolve_nft_id(&nft_id).await?).await
+                        }
                         AccountCommand::Switch { account_id } => {
                             return Ok(AccountPromptResponse::Switch(wallet.get_account(account_id).await?));
                         }
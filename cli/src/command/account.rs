@@ -3,32 +3,40 @@
 
 use std::str::FromStr;
 
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use iota_sdk::{
     client::request_funds_from_faucet,
     types::{
         api::plugins::participation::types::ParticipationEventId,
         block::{
             address::{Address, Bech32Address, ToBech32Ext},
+            codec::{PackableKind, Packed},
             output::{
                 unlock_condition::AddressUnlockCondition, AliasId, AliasOutput, BasicOutputBuilder, FoundryId,
                 NativeToken, NativeTokensBuilder, NftId, NftOutput, Output, OutputId, TokenId,
             },
             payload::transaction::TransactionId,
+            render::{RenderFormat, RenderNode, Renderer},
             ConvertTo,
         },
     },
     wallet::{
         account::{
             types::{AccountIdentifier, OutputData, Transaction},
-            Account, ConsolidationParams, FilterOptions, OutputsToClaim, SyncOptions, TransactionOptions,
+            Account, ConsolidationParams, FilterOptions, HistoryExportFormat, HistoryOptions, OutputsToClaim,
+            ReclaimDepositsParams, SyncOptions, TransactionOptions,
         },
-        CreateNativeTokenParams, MintNftParams, SendNativeTokensParams, SendNftParams, SendParams,
+        AddressBookEntry, AddressBookTarget, AddressOrLabel, CreateNativeTokenParams, MintNftParams,
+        SendNativeTokensParams, SendNftParams, SendParams, Wallet,
     },
     U256,
 };
 
-use crate::{error::Error, helper::to_utc_date_time, println_log_info};
+use crate::{
+    error::Error,
+    helper::{to_utc_date_time, utc_date_to_millis},
+    println_log_info,
+};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None, propagate_version = true)]
@@ -53,12 +61,37 @@ pub enum AccountCommand {
         /// Either by address (e.g. rms1qqtj7pvnl3lj9n9n6e9lc47mfutjfhjyprmprxtzz2g0uck8tr3gurtp7tq) or index.
         selector: AddressSelector,
     },
+    /// List the entries of the address book, shared by all accounts.
+    AddressBook {
+        /// Only list entries with this tag, e.g. payroll.
+        #[arg(long)]
+        tag: Option<String>,
+    },
+    /// Add an entry to the address book or replace the entry with the same label.
+    AddressBookAdd {
+        /// Unique label of the entry, e.g. alice.
+        label: String,
+        /// Address, alias ID or NFT ID the label refers to, e.g.
+        /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
+        target: String,
+        /// Kind of the target, either `address`, `alias` or `nft`.
+        #[arg(long, default_value = "address")]
+        kind: String,
+        /// Tags of the entry, e.g. --tag payroll --tag monthly.
+        #[arg(long)]
+        tag: Vec<String>,
+    },
+    /// Remove an entry from the address book.
+    AddressBookRemove {
+        /// Label of the entry to remove.
+        label: String,
+    },
     /// List the account addresses.
     Addresses,
     /// Print the account balance.
     Balance {
-        /// Addresses to compute the balance for.
-        addresses: Option<Vec<Bech32Address>>,
+        /// Addresses or address book labels to compute the balance for.
+        addresses: Option<Vec<String>>,
     },
     /// Burn an amount of native token.
     BurnNativeToken {
@@ -96,6 +129,15 @@ pub enum AccountCommand {
         #[arg(long, group = "foundry_metadata")]
         foundry_metadata_file: Option<String>,
     },
+    /// Decode packed bytes to JSON, e.g. decode 0x0140420f... --kind output.
+    Decode {
+        /// Hex encoded packed bytes, with or without 0x prefix.
+        hex: String,
+        /// Kind of the packed object, either `block`, `output`, `payload`, `essence` or `unlock`. Auto-detected if
+        /// omitted.
+        #[arg(long)]
+        kind: Option<String>,
+    },
     /// Destroy an alias.
     DestroyAlias {
         /// Alias ID to be destroyed, e.g. 0xed5a90106ae5d402ebaecb9ba36f32658872df789f7a29b9f6d695b912ec6a1e.
@@ -107,8 +149,30 @@ pub enum AccountCommand {
         /// 0x08cb54928954c3eb7ece1bf1cc0c68eb179dc1c4634ae5d23df1c70643d0911c3d0200000000.
         foundry_id: String,
     },
+    /// Encode JSON to packed bytes, e.g. encode '{"type":1,"reference":0}' --kind unlock.
+    Encode {
+        /// JSON representation of the object.
+        json: String,
+        /// Kind of the object, either `block`, `output`, `payload`, `essence` or `unlock`. Auto-detected if omitted.
+        #[arg(long)]
+        kind: Option<String>,
+    },
     /// Exit the CLI wallet.
     Exit,
+    /// Export the transaction history to a file, one row per movement.
+    ExportHistory {
+        /// Path of the file to write the history to, e.g. ./history.csv.
+        path: String,
+        /// Format of the export.
+        #[arg(long, value_enum, default_value_t = HistoryFormat::Csv)]
+        format: HistoryFormat,
+        /// Only export transactions from this UTC date on, e.g. 2024-01-01.
+        #[arg(long)]
+        from: Option<String>,
+        /// Only export transactions before this UTC date, e.g. 2024-02-01.
+        #[arg(long)]
+        to: Option<String>,
+    },
     /// Request funds from the faucet.
     Faucet {
         /// Address the faucet sends the funds to, defaults to the latest address.
@@ -169,17 +233,20 @@ pub enum AccountCommand {
     },
     /// List all outputs.
     Outputs,
+    /// Send the native tokens of basic outputs to as few outputs as possible, to reclaim storage deposits.
+    ReclaimDeposits,
     /// Send an amount.
     Send {
-        /// Address to send funds to, e.g. rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
-        address: Bech32Address,
+        /// Address or address book label to send funds to, e.g.
+        /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
+        address: String,
         /// Amount to send, e.g. 1000000.
         amount: u64,
         /// Bech32 encoded return address, to which the storage deposit will be returned if one is necessary
         /// given the provided amount. If a storage deposit is needed and a return address is not provided, it will
         /// default to the first address of the account.
         #[arg(long)]
-        return_address: Option<Bech32Address>,
+        return_address: Option<String>,
         /// Expiration in seconds, after which the output will be available for the sender again, if not spent by the
         /// receiver already. The expiration will only be used if one is necessary given the provided amount. If an
         /// expiration is needed but not provided, it will default to one day.
@@ -193,8 +260,9 @@ pub enum AccountCommand {
     /// Send native tokens.
     /// This will create an output with an expiration and storage deposit return unlock condition.
     SendNativeToken {
-        /// Address to send the native tokens to, e.g. rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
-        address: Bech32Address,
+        /// Address or address book label to send the native tokens to, e.g.
+        /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
+        address: String,
         /// Token ID to be sent, e.g. 0x087d205988b733d97fb145ae340e27a8b19554d1ceee64574d7e5ff66c45f69e7a0100000000.
         token_id: String,
         /// Amount to send, e.g. 1000000.
@@ -205,9 +273,11 @@ pub enum AccountCommand {
     },
     /// Send an NFT.
     SendNft {
-        /// Address to send the NFT to, e.g. rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
-        address: Bech32Address,
-        /// NFT ID to be sent, e.g. 0xecadf10e6545aa82da4df2dfd2a496b457c8850d2cab49b7464cb273d3dffb07.
+        /// Address or address book label to send the NFT to, e.g.
+        /// rms1qztwng6cty8cfm42nzvq099ev7udhrnk0rw8jt8vttf9kpqnxhpsx869vr3.
+        address: String,
+        /// NFT ID or address book label of the NFT to be sent, e.g.
+        /// 0xecadf10e6545aa82da4df2dfd2a496b457c8850d2cab49b7464cb273d3dffb07.
         nft_id: String,
     },
     /// Switch to a different account.
@@ -326,6 +396,24 @@ impl FromStr for AddressSelector {
     }
 }
 
+/// Format of an exported history
+#[derive(Debug, Copy, Clone, ValueEnum)]
+pub enum HistoryFormat {
+    /// Comma separated values
+    Csv,
+    /// JSON Lines, one JSON object per line
+    Jsonl,
+}
+
+impl From<HistoryFormat> for HistoryExportFormat {
+    fn from(format: HistoryFormat) -> Self {
+        match format {
+            HistoryFormat::Csv => Self::Csv,
+            HistoryFormat::Jsonl => Self::JsonLines,
+        }
+    }
+}
+
 /// `address` command
 pub async fn address_command(account: &Account, selector: AddressSelector) -> Result<(), Error> {
     match selector {
@@ -345,6 +433,75 @@ pub async fn address_command(account: &Account, selector: AddressSelector) -> Re
     Ok(())
 }
 
+// `address-book` command
+pub async fn address_book_command(wallet: &Wallet, tag: Option<String>) -> Result<(), Error> {
+    let address_book = wallet.address_book().await;
+    let entries = match &tag {
+        Some(tag) => address_book.entries_with_tag(tag).collect::<Vec<_>>(),
+        None => address_book.entries().collect::<Vec<_>>(),
+    };
+
+    if entries.is_empty() {
+        println_log_info!("No address book entries found");
+        return Ok(());
+    }
+
+    for entry in entries {
+        let target = match entry.target() {
+            AddressBookTarget::Address(address) => format!("Address {address}"),
+            AddressBookTarget::Alias(alias_id) => format!("Alias {alias_id}"),
+            AddressBookTarget::Nft(nft_id) => format!("NFT {nft_id}"),
+        };
+        let tags = entry.tags().iter().cloned().collect::<Vec<_>>().join(", ");
+        println_log_info!("{:<20}{target}\t{tags}", entry.label());
+    }
+
+    Ok(())
+}
+
+// `address-book-add` command
+pub async fn address_book_add_command(
+    wallet: &Wallet,
+    label: String,
+    target: String,
+    kind: String,
+    tags: Vec<String>,
+) -> Result<(), Error> {
+    let target = match kind.as_str() {
+        "address" => AddressBookTarget::Address(Bech32Address::try_from_str(&target)?),
+        "alias" => AddressBookTarget::Alias(AliasId::from_str(&target)?),
+        "nft" => AddressBookTarget::Nft(NftId::from_str(&target)?),
+        _ => {
+            return Err(Error::Miscellaneous(format!(
+                "unknown address book entry kind {kind}, expected address, alias or nft"
+            )));
+        }
+    };
+
+    let replaced = wallet
+        .set_address_book_entry(AddressBookEntry::new(label.clone(), target).with_tags(tags))
+        .await?;
+
+    if replaced.is_some() {
+        println_log_info!("Replaced address book entry {label}.");
+    } else {
+        println_log_info!("Added address book entry {label}.");
+    }
+
+    Ok(())
+}
+
+// `address-book-remove` command
+pub async fn address_book_remove_command(wallet: &Wallet, label: String) -> Result<(), Error> {
+    if wallet.remove_address_book_entry(&label).await?.is_some() {
+        println_log_info!("Removed address book entry {label}.");
+    } else {
+        println_log_info!("No address book entry {label} found.");
+    }
+
+    Ok(())
+}
+
 /// `addresses` command
 pub async fn addresses_command(account: &Account) -> Result<(), Error> {
     print_addresses(get_addresses_sorted(account).await?)?;
@@ -353,14 +510,30 @@ pub async fn addresses_command(account: &Account) -> Result<(), Error> {
 }
 
 // `balance` command
-pub async fn balance_command(account: &Account, addresses: Option<Vec<Bech32Address>>) -> Result<(), Error> {
+pub async fn balance_command(wallet: &Wallet, account: &Account, addresses: Option<Vec<String>>) -> Result<(), Error> {
     let balance = if let Some(addresses) = addresses {
-        account.addresses_balance(addresses).await?
+        let mut resolved_addresses = Vec::with_capacity(addresses.len());
+        for address in addresses {
+            resolved_addresses.push(account.resolve_address(&address).await?);
+        }
+        account.addresses_balance(resolved_addresses).await?
     } else {
         account.balance().await?
     };
     println_log_info!("{balance:#?}");
 
+    let address_book = wallet.address_book().await;
+    for alias_id in balance.aliases() {
+        if let Some(label) = address_book.label_of_alias(alias_id) {
+            println_log_info!("Alias {alias_id}: {label}");
+        }
+    }
+    for nft_id in balance.nfts() {
+        if let Some(label) = address_book.label_of_nft(nft_id) {
+            println_log_info!("NFT {nft_id}: {label}");
+        }
+    }
+
     Ok(())
 }
 
@@ -501,6 +674,21 @@ pub async fn consolidate_command(account: &Account) -> Result<(), Error> {
     Ok(())
 }
 
+// `reclaim-deposits` command
+pub async fn reclaim_deposits_command(account: &Account) -> Result<(), Error> {
+    println_log_info!("Reclaiming storage deposits.");
+
+    let transaction = account.reclaim_deposits(ReclaimDepositsParams::new()).await?;
+
+    println_log_info!(
+        "Reclaim transaction sent:\n{:?}\n{:?}",
+        transaction.transaction_id,
+        transaction.block_id
+    );
+
+    Ok(())
+}
+
 // `create-alias-output` command
 pub async fn create_alias_outputs_command(account: &Account) -> Result<(), Error> {
     println_log_info!("Creating alias output.");
@@ -556,6 +744,21 @@ pub async fn create_native_token_command(
     Ok(())
 }
 
+// `decode` command
+pub async fn decode_command(account: &Account, hex: String, kind: Option<String>) -> Result<(), Error> {
+    let kind = parse_packable_kind(kind)?;
+    let protocol_parameters = account.client().get_protocol_parameters().await?;
+    let packed = Packed::from_hex(&hex, kind, &protocol_parameters).map_err(|e| Error::Miscellaneous(e.to_string()))?;
+
+    println_log_info!(
+        "Decoded {}:\n{}",
+        packed.kind(),
+        serde_json::to_string_pretty(&packed.to_json())?
+    );
+
+    Ok(())
+}
+
 // `destroy-alias` command
 pub async fn destroy_alias_command(account: &Account, alias_id: String) -> Result<(), Error> {
     println_log_info!("Destroying alias {alias_id}.");
@@ -586,6 +789,45 @@ pub async fn destroy_foundry_command(account: &Account, foundry_id: String) -> R
     Ok(())
 }
 
+// `encode` command
+pub async fn encode_command(account: &Account, json: String, kind: Option<String>) -> Result<(), Error> {
+    let kind = parse_packable_kind(kind)?;
+    let protocol_parameters = account.client().get_protocol_parameters().await?;
+    let packed = Packed::from_json(serde_json::from_str(&json)?, kind, &protocol_parameters)
+        .map_err(|e| Error::Miscellaneous(e.to_string()))?;
+
+    println_log_info!("Encoded {}:\n{}", packed.kind(), packed.to_hex());
+
+    Ok(())
+}
+
+fn parse_packable_kind(kind: Option<String>) -> Result<Option<PackableKind>, Error> {
+    kind.map(|kind| kind.parse::<PackableKind>())
+        .transpose()
+        .map_err(|e| Error::Miscellaneous(e.to_string()))
+}
+
+// `export-history` command
+pub async fn export_history_command(
+    account: &Account,
+    path: String,
+    format: HistoryFormat,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<(), Error> {
+    let options = HistoryOptions::default().with_range(
+        from.as_deref().map(utc_date_to_millis).transpose()?,
+        to.as_deref().map(utc_date_to_millis).transpose()?,
+    );
+
+    let history = account.export_history(format.into(), options).await?;
+    tokio::fs::write(&path, history).await?;
+
+    println_log_info!("Exported history to {path}.");
+
+    Ok(())
+}
+
 // `faucet` command
 pub async fn faucet_command(
     account: &Account,
@@ -711,7 +953,13 @@ pub async fn output_command(account: &Account, selector: OutputSelector) -> Resu
     };
 
     if let Some(output) = output {
-        println_log_info!("{output:#?}");
+        let protocol_parameters = account.client().get_protocol_parameters().await?;
+        let node = RenderNode::with_value("Output id", output.output_id)
+            .add_child(RenderNode::with_value("Spent", output.is_spent))
+            .add_child(RenderNode::with_value("Remainder", output.remainder))
+            .add_child(Renderer::from(&protocol_parameters).output(&output.output));
+
+        println_log_info!("{}", node.render(RenderFormat::Text));
     } else {
         println_log_info!("Output not found");
     }
@@ -727,9 +975,9 @@ pub async fn outputs_command(account: &Account) -> Result<(), Error> {
 // `send` command
 pub async fn send_command(
     account: &Account,
-    address: impl ConvertTo<Bech32Address>,
+    address: impl ConvertTo<AddressOrLabel>,
     amount: u64,
-    return_address: Option<impl ConvertTo<Bech32Address>>,
+    return_address: Option<impl ConvertTo<AddressOrLabel>>,
     expiration: Option<u32>,
     allow_micro_amount: bool,
 ) -> Result<(), Error> {
@@ -804,10 +1052,10 @@ pub async fn send_native_token_command(
 // `send-nft` command
 pub async fn send_nft_command(
     account: &Account,
-    address: impl ConvertTo<Bech32Address>,
-    nft_id: String,
+    address: impl ConvertTo<AddressOrLabel>,
+    nft_id: impl ConvertTo<NftId>,
 ) -> Result<(), Error> {
-    let outputs = [SendNftParams::new(address.convert()?, &nft_id)?];
+    let outputs = [SendNftParams::new(address, nft_id)?];
     let transaction = account.send_nft(outputs, None).await?;
 
     println_log_info!(
@@ -845,7 +1093,27 @@ pub async fn transaction_command(account: &Account, selector: TransactionSelecto
     };
 
     if let Some(tx) = transaction {
-        println_log_info!("{:#?}", tx);
+        let protocol_parameters = account.client().get_protocol_parameters().await?;
+        let transaction_time = to_utc_date_time(tx.timestamp)?;
+        let mut node = RenderNode::with_value("Transaction", tx.transaction_id)
+            .add_child(RenderNode::with_value(
+                "Inclusion state",
+                format!("{:?}", tx.inclusion_state),
+            ))
+            .add_child(RenderNode::with_value(
+                "Timestamp",
+                transaction_time.format("%Y-%m-%d %H:%M:%S UTC"),
+            ))
+            .add_child(RenderNode::with_value("Incoming", tx.incoming));
+        if let Some(block_id) = tx.block_id {
+            node = node.add_child(RenderNode::with_value("Block id", block_id));
+        }
+        if let Some(note) = &tx.note {
+            node = node.add_child(RenderNode::with_value("Note", note));
+        }
+        node = node.add_child(Renderer::from(&protocol_parameters).transaction_payload(&tx.payload));
+
+        println_log_info!("{}", node.render(RenderFormat::Text));
     } else {
         println_log_info!("No transaction found");
     }
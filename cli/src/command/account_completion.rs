@@ -14,6 +14,9 @@ pub struct AccountCompleter;
 const ACCOUNT_COMMANDS: &[&str] = &[
     "accounts",
     "address",
+    "address-book",
+    "address-book-add",
+    "address-book-remove",
     "addresses",
     "balance",
     "burn-native-token",
@@ -27,6 +30,7 @@ const ACCOUNT_COMMANDS: &[&str] = &[
     "destroy-alias",
     "destroy-foundry",
     "exit",
+    "export-history",
     "faucet",
     "melt-native-token",
     "mint-native-token",
@@ -35,6 +39,7 @@ const ACCOUNT_COMMANDS: &[&str] = &[
     "node-info",
     "output",
     "outputs",
+    "reclaim-deposits",
     "send",
     "send-native-token",
     "send-nft",
@@ -3,7 +3,7 @@
 
 use std::path::Path;
 
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use dialoguer::{console::Term, theme::ColorfulTheme, Input, Select};
 use iota_sdk::{
     client::{utils::Password, verify_mnemonic},
@@ -329,6 +329,18 @@ pub fn to_utc_date_time(ts_millis: u128) -> Result<DateTime<Utc>, Error> {
     Ok(naive_time.and_utc())
 }
 
+pub fn utc_date_to_millis(date: &str) -> Result<u128, Error> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| Error::Miscellaneous(format!("Failed to parse date {date}: {e}")))?;
+    let millis = date
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+        .timestamp_millis();
+
+    u128::try_from(millis).map_err(|e| Error::Miscellaneous(format!("Failed to convert date to timestamp: {e}")))
+}
+
 pub async fn check_file_exists(path: &Path) -> Result<(), Error> {
     if !fs::try_exists(path).await.map_err(|e| {
         Error::Miscellaneous(format!(
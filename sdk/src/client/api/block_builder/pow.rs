@@ -30,12 +30,34 @@ impl ClientInner {
     }
 
     /// Calls the appropriate PoW function depending whether the compilation is for wasm or not.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "pow", skip_all, fields(block_id = tracing::field::Empty))
+    )]
     pub async fn finish_pow(&self, parents: Option<Parents>, payload: Option<Payload>) -> Result<Block> {
+        #[cfg(feature = "metrics")]
+        let start_time = instant::Instant::now();
         #[cfg(not(target_family = "wasm"))]
         let block = self.finish_multi_threaded_pow(parents, payload).await?;
         #[cfg(target_family = "wasm")]
         let block = self.finish_single_threaded_pow(parents, payload).await?;
 
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("block_id", tracing::field::display(block.id()));
+        #[cfg(feature = "metrics")]
+        {
+            use packable::PackableExt;
+
+            use crate::client::metrics::{metrics, POW_DURATION_SECONDS, POW_SCORE};
+
+            metrics().observe(&POW_DURATION_SECONDS, &[], start_time.elapsed().as_secs_f64());
+            metrics().observe(
+                &POW_SCORE,
+                &[],
+                crate::pow::score::PowScorer::new().score(&block.pack_to_vec()),
+            );
+        }
+
         Ok(block)
     }
 
@@ -7,8 +7,10 @@ mod address;
 mod block_builder;
 mod consolidation;
 mod high_level;
+mod rent_optimizer;
+mod swap;
 mod types;
 
-pub use self::{address::*, block_builder::*, types::*};
+pub use self::{address::*, block_builder::*, rent_optimizer::*, swap::*, types::*};
 
 const ADDRESS_GAP_RANGE: u32 = 20;
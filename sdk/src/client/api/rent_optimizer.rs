@@ -0,0 +1,291 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::Result,
+    types::block::{
+        output::{
+            AliasOutputBuilder, BasicOutput, BasicOutputBuilder, FoundryOutputBuilder, NativeTokens,
+            NativeTokensBuilder, NftOutputBuilder, Output, Rent, RentStructure,
+        },
+        protocol::ProtocolParameters,
+    },
+};
+
+/// A change to planned outputs which reduces the storage deposit they lock.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RentSuggestion {
+    /// The basic outputs have the same unlock conditions and features, so they can be merged into one output.
+    #[serde(rename_all = "camelCase")]
+    MergeOutputs {
+        /// The indexes of the planned outputs.
+        outputs: Vec<usize>,
+        #[serde(with = "crate::utils::serde::string")]
+        saved_deposit: u64,
+    },
+    /// The basic outputs have the same unlock conditions but different features, so their native tokens can be
+    /// moved into the first of them.
+    #[serde(rename_all = "camelCase")]
+    MoveNativeTokens {
+        /// The indexes of the planned outputs, the native tokens are moved into the first one.
+        outputs: Vec<usize>,
+        #[serde(with = "crate::utils::serde::string")]
+        saved_deposit: u64,
+    },
+    /// The output would need a lower storage deposit without its features. Unlike the other suggestions this changes
+    /// what the recipient gets, so it's never applied by [`RentOptimizer::optimize()`].
+    #[serde(rename_all = "camelCase")]
+    DropFeatures {
+        /// The index of the planned output.
+        output: usize,
+        /// The kinds of the features.
+        features: Vec<u8>,
+        #[serde(with = "crate::utils::serde::string")]
+        saved_deposit: u64,
+    },
+}
+
+impl RentSuggestion {
+    /// Returns by how much the sum of the storage deposits of the outputs is reduced.
+    pub fn saved_deposit(&self) -> u64 {
+        match self {
+            Self::MergeOutputs { saved_deposit, .. }
+            | Self::MoveNativeTokens { saved_deposit, .. }
+            | Self::DropFeatures { saved_deposit, .. } => *saved_deposit,
+        }
+    }
+}
+
+/// Suggests and applies changes to planned outputs which reduce the storage deposit they lock.
+///
+/// Only basic outputs without a storage deposit return unlock condition are merged or get native tokens moved, chain
+/// outputs and outputs lending their storage deposit are left as they are. The amounts of the planned outputs are
+/// kept, an optimized output is only given more if it needs a higher storage deposit. Reclaiming deposits which aren't
+/// needed anymore is up to the wallet's `Account::reclaim_deposits()`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RentOptimizer {
+    rent_structure: RentStructure,
+    token_supply: u64,
+}
+
+impl From<&ProtocolParameters> for RentOptimizer {
+    fn from(protocol_parameters: &ProtocolParameters) -> Self {
+        Self::new(
+            *protocol_parameters.rent_structure(),
+            protocol_parameters.token_supply(),
+        )
+    }
+}
+
+// An optimized output with the indexes of the planned outputs it was built from.
+struct OptimizedOutput {
+    output: Output,
+    sources: Vec<usize>,
+}
+
+impl RentOptimizer {
+    /// Creates a new [`RentOptimizer`].
+    pub fn new(rent_structure: RentStructure, token_supply: u64) -> Self {
+        Self {
+            rent_structure,
+            token_supply,
+        }
+    }
+
+    /// Returns the sum of the storage deposits the outputs lock.
+    pub fn storage_deposit(&self, outputs: &[Output]) -> u64 {
+        outputs
+            .iter()
+            .map(|output| output.rent_cost(&self.rent_structure))
+            .sum()
+    }
+
+    /// Returns the suggestions to reduce the storage deposits of the planned outputs, with indexes into `outputs`.
+    pub fn suggest(&self, outputs: &[Output]) -> Result<Vec<RentSuggestion>> {
+        let (_, mut suggestions) = self.plan(outputs)?;
+
+        for (index, output) in outputs.iter().enumerate() {
+            let Some(features) = output.features().filter(|features| !features.is_empty()) else {
+                continue;
+            };
+            let without_features = match output {
+                Output::Basic(output) => BasicOutputBuilder::from(output)
+                    .clear_features()
+                    .finish_output(self.token_supply)?,
+                Output::Alias(output) => AliasOutputBuilder::from(output)
+                    .clear_features()
+                    .finish_output(self.token_supply)?,
+                Output::Foundry(output) => FoundryOutputBuilder::from(output)
+                    .clear_features()
+                    .finish_output(self.token_supply)?,
+                Output::Nft(output) => NftOutputBuilder::from(output)
+                    .clear_features()
+                    .finish_output(self.token_supply)?,
+                Output::Treasury(_) => continue,
+            };
+
+            suggestions.push(RentSuggestion::DropFeatures {
+                output: index,
+                features: features.iter().map(|feature| feature.kind()).collect(),
+                saved_deposit: output.rent_cost(&self.rent_structure)
+                    - without_features.rent_cost(&self.rent_structure),
+            });
+        }
+
+        Ok(suggestions)
+    }
+
+    /// Merges the planned outputs and moves their native tokens into fewer outputs, as suggested by
+    /// [`RentOptimizer::suggest()`]. The order of the outputs is kept, merged outputs take the place of the first one.
+    pub fn optimize(&self, outputs: impl Into<Vec<Output>>) -> Result<Vec<Output>> {
+        let (optimized, _) = self.plan(&outputs.into())?;
+
+        Ok(optimized.into_iter().map(|optimized| optimized.output).collect())
+    }
+
+    fn plan(&self, outputs: &[Output]) -> Result<(Vec<OptimizedOutput>, Vec<RentSuggestion>)> {
+        let mut optimized = outputs
+            .iter()
+            .enumerate()
+            .map(|(index, output)| OptimizedOutput {
+                output: output.clone(),
+                sources: vec![index],
+            })
+            .collect::<Vec<_>>();
+        let mut suggestions = Vec::new();
+
+        // Merge outputs with the same unlock conditions and features into the first of them.
+        let mut index = 0;
+        while index < optimized.len() {
+            let Some(output) = optimizable(&optimized[index].output) else {
+                index += 1;
+                continue;
+            };
+            let mut native_tokens = NativeTokensBuilder::new();
+            native_tokens.add_native_tokens(output.native_tokens().clone())?;
+            let mut members = Vec::new();
+
+            for (other_index, other) in optimized.iter().enumerate().skip(index + 1) {
+                let Some(other) = optimizable(&other.output) else {
+                    continue;
+                };
+                if other.unlock_conditions() != output.unlock_conditions() || other.features() != output.features() {
+                    continue;
+                }
+                let mut merged_native_tokens = native_tokens.clone();
+                merged_native_tokens.add_native_tokens(other.native_tokens().clone())?;
+                if merged_native_tokens.len() <= NativeTokens::COUNT_MAX.into() {
+                    native_tokens = merged_native_tokens;
+                    members.push(other_index);
+                }
+            }
+
+            if !members.is_empty() {
+                let builder = BasicOutputBuilder::from(output).with_native_tokens(native_tokens.finish()?);
+                let mut group = vec![optimized[index].output.clone()];
+                let mut sources = optimized[index].sources.clone();
+                // Remove the merged outputs from the back, so the indexes stay valid.
+                for member in members.into_iter().rev() {
+                    let other = optimized.remove(member);
+                    group.push(other.output);
+                    sources.extend(other.sources);
+                }
+                sources.sort_unstable();
+
+                let merged = self.finish(builder, &group)?;
+                suggestions.push(RentSuggestion::MergeOutputs {
+                    saved_deposit: self.storage_deposit(&group) - merged.rent_cost(&self.rent_structure),
+                    outputs: sources.clone(),
+                });
+                optimized[index] = OptimizedOutput {
+                    output: merged,
+                    sources,
+                };
+            }
+            index += 1;
+        }
+
+        // Move the native tokens of outputs with the same unlock conditions into the first of them.
+        for index in 0..optimized.len() {
+            let mut sources = optimized[index].sources.clone();
+            let mut saved_deposit = 0;
+
+            for other_index in index + 1..optimized.len() {
+                let (Some(target), Some(donor)) = (
+                    optimizable(&optimized[index].output),
+                    optimizable(&optimized[other_index].output),
+                ) else {
+                    continue;
+                };
+                if target.native_tokens().is_empty()
+                    || donor.native_tokens().is_empty()
+                    || target.unlock_conditions() != donor.unlock_conditions()
+                {
+                    continue;
+                }
+                let mut native_tokens = NativeTokensBuilder::new();
+                native_tokens.add_native_tokens(target.native_tokens().clone())?;
+                native_tokens.add_native_tokens(donor.native_tokens().clone())?;
+                if native_tokens.len() > NativeTokens::COUNT_MAX.into() {
+                    continue;
+                }
+
+                let before = [optimized[index].output.clone(), optimized[other_index].output.clone()];
+                let after = [
+                    self.finish(
+                        BasicOutputBuilder::from(target).with_native_tokens(native_tokens.finish()?),
+                        &before[..1],
+                    )?,
+                    self.finish(BasicOutputBuilder::from(donor).with_native_tokens([]), &before[1..])?,
+                ];
+                let (deposit_before, deposit_after) = (self.storage_deposit(&before), self.storage_deposit(&after));
+                if deposit_after >= deposit_before {
+                    continue;
+                }
+
+                saved_deposit += deposit_before - deposit_after;
+                sources.extend(&optimized[other_index].sources);
+                let [target, donor] = after;
+                optimized[index].output = target;
+                optimized[other_index].output = donor;
+            }
+
+            if saved_deposit > 0 {
+                suggestions.push(RentSuggestion::MoveNativeTokens {
+                    outputs: sources,
+                    saved_deposit,
+                });
+            }
+        }
+
+        Ok((optimized, suggestions))
+    }
+
+    // Finishes an output replacing a group of outputs. It gets the sum of their amounts, raised to its storage deposit
+    // if needed.
+    fn finish(&self, builder: BasicOutputBuilder, group: &[Output]) -> Result<Output> {
+        let amount = group.iter().map(Output::amount).sum::<u64>();
+        let output = builder
+            .with_minimum_storage_deposit(self.rent_structure)
+            .finish_output(self.token_supply)?;
+
+        Ok(if amount > output.amount() {
+            BasicOutputBuilder::from(output.as_basic())
+                .with_amount(amount)
+                .finish_output(self.token_supply)?
+        } else {
+            output
+        })
+    }
+}
+
+// Returns the output if it's a basic output which can be merged or get native tokens moved.
+fn optimizable(output: &Output) -> Option<&BasicOutput> {
+    match output {
+        Output::Basic(output) if output.unlock_conditions().storage_deposit_return().is_none() => Some(output),
+        _ => None,
+    }
+}
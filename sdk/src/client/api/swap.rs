@@ -0,0 +1,479 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Atomic swaps of assets between two parties in a single transaction.
+//!
+//! The maker creates a [`SwapOffer`] with its inputs and the outputs it wants to own after the swap, the taker
+//! [accepts](SwapOffer::accept) it by adding its own inputs and outputs. The taker
+//! [signs](SwapTransaction::sign_as_taker) the transaction it accepted, the maker
+//! [signs](SwapTransaction::sign_as_maker) it if it completes its offer and either of them submits the transaction.
+//! Since the signatures cover the whole essence, a transaction changed by one party after the other one signed it
+//! can't be submitted. Offers and swap transactions are exchanged out-of-band as [`SwapOfferDto`] and
+//! [`SwapTransactionDto`].
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{
+        api::{
+            input_selection::is_alias_transition,
+            transaction::{validate_regular_transaction_essence_length, validate_transaction_payload_length},
+            verify_semantic, PreparedTransactionData, PreparedTransactionDataDto,
+        },
+        secret::{
+            types::{InputSigningData, InputSigningDataDto},
+            SecretManage,
+        },
+        Error, Result,
+    },
+    types::{
+        block::{
+            address::Address,
+            input::{Input, UtxoInput},
+            output::{InputsCommitment, NativeTokensBuilder, Output},
+            payload::transaction::{
+                dto::TransactionEssenceDto, RegularTransactionEssence, TransactionEssence, TransactionPayload,
+            },
+            protocol::ProtocolParameters,
+            semantic::ConflictReason,
+            unlock::{dto::UnlockDto, AliasUnlock, NftUnlock, ReferenceUnlock, Unlock, Unlocks},
+        },
+        TryFromDto, ValidationParams,
+    },
+};
+
+/// A party of a swap.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SwapParty {
+    /// The party which created the offer, its inputs and outputs come first.
+    Maker,
+    /// The party which accepted the offer.
+    Taker,
+}
+
+/// An offer to swap assets in a single transaction.
+///
+/// The essence is a skeleton with the inputs of the maker, an [`InputsCommitment`] to them and the outputs the maker
+/// wants to own after the swap. It isn't balanced, the difference between the inputs and the outputs is what the
+/// maker offers or asks for.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapOffer {
+    /// The essence skeleton
+    pub essence: TransactionEssence,
+    /// The inputs of the maker
+    pub inputs_data: Vec<InputSigningData>,
+}
+
+impl SwapOffer {
+    /// Creates a new [`SwapOffer`] from the inputs of the maker and the outputs it wants to own after the swap.
+    pub fn new(
+        inputs_data: Vec<InputSigningData>,
+        outputs: impl Into<Vec<Output>>,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self> {
+        let essence = build_essence(
+            protocol_parameters.network_id(),
+            &inputs_data,
+            outputs.into(),
+            protocol_parameters,
+        )?;
+
+        Ok(Self { essence, inputs_data })
+    }
+
+    /// Returns the outputs the maker wants to own after the swap.
+    pub fn outputs(&self) -> &[Output] {
+        let TransactionEssence::Regular(essence) = &self.essence;
+        essence.outputs()
+    }
+
+    /// Accepts the offer by adding the inputs and outputs of the taker, which have to balance the offer. The inputs
+    /// commitment is computed again over the inputs of both parties.
+    pub fn accept(
+        &self,
+        inputs_data: Vec<InputSigningData>,
+        outputs: impl IntoIterator<Item = Output>,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<SwapTransaction> {
+        let TransactionEssence::Regular(offer_essence) = &self.essence;
+        if offer_essence.network_id() != protocol_parameters.network_id() {
+            return Err(Error::InvalidSwap("the offer was made for another network"));
+        }
+
+        let maker_inputs = self.inputs_data.len();
+        let maker_outputs = offer_essence.outputs().len();
+        let inputs_data = self.inputs_data.iter().cloned().chain(inputs_data).collect::<Vec<_>>();
+        let outputs = offer_essence.outputs().iter().cloned().chain(outputs).collect();
+        let essence = build_essence(
+            protocol_parameters.network_id(),
+            &inputs_data,
+            outputs,
+            protocol_parameters,
+        )?;
+        let TransactionEssence::Regular(regular) = &essence;
+        validate_regular_transaction_essence_length(regular)?;
+
+        let swap_transaction = SwapTransaction {
+            unlocks: vec![None; inputs_data.len()],
+            prepared_transaction_data: PreparedTransactionData {
+                essence,
+                inputs_data,
+                remainder: None,
+            },
+            maker_inputs,
+            maker_outputs,
+        };
+        swap_transaction.verify(self)?;
+
+        Ok(swap_transaction)
+    }
+}
+
+/// A swap transaction completing a [`SwapOffer`], with the unlocks of the parties which signed it so far.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapTransaction {
+    /// The balanced transaction, the inputs and outputs of the maker come first.
+    pub prepared_transaction_data: PreparedTransactionData,
+    /// The number of inputs of the maker.
+    pub maker_inputs: usize,
+    /// The number of outputs of the maker.
+    pub maker_outputs: usize,
+    /// The unlocks of the inputs, `None` for the inputs that aren't signed yet.
+    pub unlocks: Vec<Option<Unlock>>,
+}
+
+impl SwapTransaction {
+    /// Returns the offer the transaction completes.
+    pub fn offer(&self) -> Result<SwapOffer> {
+        let TransactionEssence::Regular(essence) = &self.prepared_transaction_data.essence;
+        if self.maker_inputs > self.prepared_transaction_data.inputs_data.len()
+            || self.maker_outputs > essence.outputs().len()
+        {
+            return Err(Error::InvalidSwap(
+                "the transaction has less inputs or outputs than the offer",
+            ));
+        }
+        let inputs_data = self.prepared_transaction_data.inputs_data[..self.maker_inputs].to_vec();
+        let outputs = essence.outputs()[..self.maker_outputs].to_vec();
+
+        Ok(SwapOffer {
+            essence: build_essence(essence.network_id(), &inputs_data, outputs, ValidationParams::default())?,
+            inputs_data,
+        })
+    }
+
+    /// Returns the indexes of the inputs of a party.
+    pub fn inputs_of(&self, party: SwapParty) -> std::ops::Range<usize> {
+        match party {
+            SwapParty::Maker => 0..self.maker_inputs,
+            SwapParty::Taker => self.maker_inputs..self.prepared_transaction_data.inputs_data.len(),
+        }
+    }
+
+    /// Returns whether all inputs are unlocked.
+    pub fn is_signed(&self) -> bool {
+        self.unlocks.iter().all(Option::is_some)
+    }
+
+    /// Verifies that the transaction completes the offer, commits to its inputs and balances the amounts and native
+    /// tokens. The unlocks are verified by the semantic validation once the transaction is signed by both parties.
+    pub fn verify(&self, offer: &SwapOffer) -> Result<()> {
+        if self.offer()? != *offer {
+            return Err(Error::InvalidSwap("the transaction doesn't complete the offer"));
+        }
+
+        let PreparedTransactionData {
+            essence: TransactionEssence::Regular(essence),
+            inputs_data,
+            ..
+        } = &self.prepared_transaction_data;
+        if self.unlocks.len() != inputs_data.len()
+            || essence.inputs().len() != inputs_data.len()
+            || essence
+                .inputs()
+                .iter()
+                .zip(inputs_data)
+                .any(|(input, input_data)| input != &Input::Utxo(UtxoInput::from(*input_data.output_id())))
+        {
+            return Err(Error::InvalidSwap("the inputs data don't match the inputs"));
+        }
+        if essence.inputs_commitment() != &InputsCommitment::new(inputs_data.iter().map(|input| &input.output)) {
+            return Err(Error::TransactionSemantic(ConflictReason::InputsCommitmentsMismatch));
+        }
+
+        let input_amount = inputs_data.iter().map(|input| input.output.amount()).sum::<u64>();
+        let output_amount = essence.outputs().iter().map(Output::amount).sum::<u64>();
+        if input_amount != output_amount {
+            return Err(Error::TransactionSemantic(
+                ConflictReason::CreatedConsumedAmountMismatch,
+            ));
+        }
+
+        // Unlike the semantic validation, burning native tokens isn't allowed, a swap only moves them.
+        if *native_tokens_sum(inputs_data.iter().map(|input| &input.output))?
+            != *native_tokens_sum(essence.outputs().iter())?
+        {
+            return Err(Error::TransactionSemantic(ConflictReason::InvalidNativeTokens));
+        }
+
+        Ok(())
+    }
+
+    /// Verifies that the transaction completes the offer and signs the inputs of the maker. Once both parties signed,
+    /// the transaction is also semantically validated and the unlocks are only kept if it's valid.
+    pub async fn sign_as_maker<M: SecretManage>(
+        &mut self,
+        secret_manager: &M,
+        offer: &SwapOffer,
+        time: u32,
+    ) -> Result<()>
+    where
+        Error: From<M::Error>,
+    {
+        self.verify(offer)?;
+
+        self.sign_inputs(secret_manager, SwapParty::Maker, time).await
+    }
+
+    /// Verifies that the transaction is the one the taker accepted, with the same essence and inputs, and signs the
+    /// inputs of the taker. The outputs of the taker aren't part of the offer, so they're only protected by comparing
+    /// the transaction to the accepted one. Once both parties signed, the transaction is also semantically validated
+    /// and the unlocks are only kept if it's valid.
+    pub async fn sign_as_taker<M: SecretManage>(&mut self, secret_manager: &M, accepted: &Self, time: u32) -> Result<()>
+    where
+        Error: From<M::Error>,
+    {
+        if self.prepared_transaction_data.essence != accepted.prepared_transaction_data.essence
+            || self.prepared_transaction_data.inputs_data != accepted.prepared_transaction_data.inputs_data
+            || self.maker_inputs != accepted.maker_inputs
+            || self.maker_outputs != accepted.maker_outputs
+        {
+            return Err(Error::InvalidSwap("the transaction isn't the accepted one"));
+        }
+        self.verify(&accepted.offer()?)?;
+
+        self.sign_inputs(secret_manager, SwapParty::Taker, time).await
+    }
+
+    // Signs the inputs of the party and keeps the unlocks of the other one.
+    async fn sign_inputs<M: SecretManage>(&mut self, secret_manager: &M, party: SwapParty, time: u32) -> Result<()>
+    where
+        Error: From<M::Error>,
+    {
+        let PreparedTransactionData {
+            essence, inputs_data, ..
+        } = &self.prepared_transaction_data;
+        let TransactionEssence::Regular(regular) = essence;
+        let hashed_essence = essence.hash();
+        let mut unlocks = self.unlocks.clone();
+        let mut unlock_indexes = HashMap::<Address, usize>::new();
+
+        for index in self.inputs_of(party) {
+            let input = &inputs_data[index];
+            let alias_transition = is_alias_transition(&input.output, *input.output_id(), regular.outputs(), None);
+            let (input_address, _) =
+                input
+                    .output
+                    .required_and_unlocked_address(time, input.output_id(), alias_transition)?;
+
+            // Inputs of a party can only reference unlocks of the same party.
+            unlocks[index] = Some(match unlock_indexes.get(&input_address) {
+                Some(unlock_index) => match input_address {
+                    Address::Alias(_) => Unlock::Alias(AliasUnlock::new(*unlock_index as u16)?),
+                    Address::Ed25519(_) => Unlock::Reference(ReferenceUnlock::new(*unlock_index as u16)?),
+                    Address::Nft(_) => Unlock::Nft(NftUnlock::new(*unlock_index as u16)?),
+                },
+                None => {
+                    if !input_address.is_ed25519() {
+                        return Err(Error::InvalidSwap(
+                            "an input is unlocked by an input of the other party",
+                        ));
+                    }
+                    let chain = input.chain.ok_or(Error::MissingBip32Chain)?;
+                    unlock_indexes.insert(input_address, index);
+
+                    secret_manager.signature_unlock(&hashed_essence, chain).await?
+                }
+            });
+
+            match &input.output {
+                Output::Alias(alias_output) => {
+                    unlock_indexes.insert(Address::Alias(alias_output.alias_address(input.output_id())), index);
+                }
+                Output::Nft(nft_output) => {
+                    unlock_indexes.insert(Address::Nft(nft_output.nft_address(input.output_id())), index);
+                }
+                _ => {}
+            }
+        }
+
+        if unlocks.iter().all(Option::is_some) {
+            signed_payload(essence, inputs_data, &unlocks, time)?;
+        }
+        self.unlocks = unlocks;
+
+        Ok(())
+    }
+
+    /// Finishes the transaction signed by both parties into a semantically valid [`TransactionPayload`].
+    pub fn finish(self, time: u32) -> Result<TransactionPayload> {
+        let PreparedTransactionData {
+            essence, inputs_data, ..
+        } = &self.prepared_transaction_data;
+
+        signed_payload(essence, inputs_data, &self.unlocks, time)
+    }
+}
+
+fn build_essence<'a>(
+    network_id: u64,
+    inputs_data: &[InputSigningData],
+    outputs: Vec<Output>,
+    params: impl Into<ValidationParams<'a>> + Send,
+) -> Result<TransactionEssence> {
+    let inputs_commitment = InputsCommitment::new(inputs_data.iter().map(|input| &input.output));
+
+    Ok(TransactionEssence::Regular(
+        RegularTransactionEssence::builder(network_id, inputs_commitment)
+            .with_inputs(
+                inputs_data
+                    .iter()
+                    .map(|input| Input::Utxo(UtxoInput::from(*input.output_id())))
+                    .collect::<Vec<_>>(),
+            )
+            .with_outputs(outputs)
+            .finish_with_params(params)?,
+    ))
+}
+
+// Returns the sum of the native tokens of the outputs.
+pub(crate) fn native_tokens_sum<'a>(outputs: impl Iterator<Item = &'a Output>) -> Result<NativeTokensBuilder> {
+    let mut native_tokens = NativeTokensBuilder::new();
+
+    for output_native_tokens in outputs.filter_map(Output::native_tokens) {
+        native_tokens.add_native_tokens(output_native_tokens.clone())?;
+    }
+
+    Ok(native_tokens)
+}
+
+fn signed_payload(
+    essence: &TransactionEssence,
+    inputs_data: &[InputSigningData],
+    unlocks: &[Option<Unlock>],
+    time: u32,
+) -> Result<TransactionPayload> {
+    let unlocks = unlocks
+        .iter()
+        .cloned()
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::InvalidSwap("the transaction isn't signed by both parties"))?;
+    let transaction_payload = TransactionPayload::new(essence.clone(), Unlocks::new(unlocks)?)?;
+
+    validate_transaction_payload_length(&transaction_payload)?;
+
+    let conflict = verify_semantic(inputs_data, &transaction_payload, time)?;
+
+    if conflict != ConflictReason::None {
+        log::debug!("[SWAP] conflict: {conflict:?} for {:#?}", transaction_payload);
+        return Err(Error::TransactionSemantic(conflict));
+    }
+
+    Ok(transaction_payload)
+}
+
+/// SwapOffer Dto
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapOfferDto {
+    /// The essence skeleton
+    pub essence: TransactionEssenceDto,
+    /// The inputs of the maker
+    pub inputs_data: Vec<InputSigningDataDto>,
+}
+
+impl From<&SwapOffer> for SwapOfferDto {
+    fn from(value: &SwapOffer) -> Self {
+        Self {
+            essence: TransactionEssenceDto::from(&value.essence),
+            inputs_data: value.inputs_data.iter().map(InputSigningDataDto::from).collect(),
+        }
+    }
+}
+
+impl TryFromDto for SwapOffer {
+    type Dto = SwapOfferDto;
+    type Error = crate::types::block::Error;
+
+    fn try_from_dto_with_params_inner(
+        dto: Self::Dto,
+        params: ValidationParams<'_>,
+    ) -> core::result::Result<Self, Self::Error> {
+        Ok(Self {
+            essence: TransactionEssence::try_from_dto_with_params(dto.essence, &params)
+                .map_err(|_| crate::types::block::Error::InvalidField("essence"))?,
+            inputs_data: dto
+                .inputs_data
+                .into_iter()
+                .map(|i| InputSigningData::try_from_dto_with_params(i, &params))
+                .collect::<Result<Vec<InputSigningData>>>()
+                .map_err(|_| crate::types::block::Error::InvalidField("input_data"))?,
+        })
+    }
+}
+
+/// SwapTransaction Dto
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapTransactionDto {
+    /// The balanced transaction
+    pub prepared_transaction_data: PreparedTransactionDataDto,
+    /// The number of inputs of the maker
+    pub maker_inputs: usize,
+    /// The number of outputs of the maker
+    pub maker_outputs: usize,
+    /// The unlocks of the inputs
+    pub unlocks: Vec<Option<UnlockDto>>,
+}
+
+impl From<&SwapTransaction> for SwapTransactionDto {
+    fn from(value: &SwapTransaction) -> Self {
+        Self {
+            prepared_transaction_data: PreparedTransactionDataDto::from(&value.prepared_transaction_data),
+            maker_inputs: value.maker_inputs,
+            maker_outputs: value.maker_outputs,
+            unlocks: value
+                .unlocks
+                .iter()
+                .map(|unlock| unlock.as_ref().map(UnlockDto::from))
+                .collect(),
+        }
+    }
+}
+
+impl TryFromDto for SwapTransaction {
+    type Dto = SwapTransactionDto;
+    type Error = crate::types::block::Error;
+
+    fn try_from_dto_with_params_inner(
+        dto: Self::Dto,
+        params: ValidationParams<'_>,
+    ) -> core::result::Result<Self, Self::Error> {
+        Ok(Self {
+            prepared_transaction_data: PreparedTransactionData::try_from_dto_with_params(
+                dto.prepared_transaction_data,
+                &params,
+            )?,
+            maker_inputs: dto.maker_inputs,
+            maker_outputs: dto.maker_outputs,
+            unlocks: dto
+                .unlocks
+                .into_iter()
+                .map(|unlock| unlock.map(Unlock::try_from).transpose())
+                .collect::<core::result::Result<_, _>>()?,
+        })
+    }
+}
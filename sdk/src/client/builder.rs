@@ -19,7 +19,10 @@ use crate::{
         },
         Client,
     },
-    types::block::protocol::ProtocolParameters,
+    types::{
+        api::core::response::InfoResponse,
+        block::{payload::milestone::ParametersMilestoneOption, protocol::ProtocolParameters, Error as BlockError},
+    },
 };
 
 /// Builder to construct client instance with sensible default values
@@ -76,6 +79,8 @@ impl Default for NetworkInfo {
             fallback_to_local_pow: true,
             tips_interval: DEFAULT_TIPS_INTERVAL,
             latest_milestone_timestamp: None,
+            latest_milestone_index: None,
+            protocol_parameters_upgrades: Vec::new(),
         }
     }
 }
@@ -371,6 +376,12 @@ pub struct NetworkInfo {
     /// The latest cached milestone timestamp.
     #[serde(skip)]
     pub latest_milestone_timestamp: Option<u32>,
+    /// The latest cached milestone index.
+    #[serde(skip)]
+    pub latest_milestone_index: Option<u32>,
+    /// Announced protocol parameters upgrades which aren't active yet, ordered by target milestone index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protocol_parameters_upgrades: Vec<ProtocolParametersUpgrade>,
 }
 
 impl NetworkInfo {
@@ -398,6 +409,97 @@ impl NetworkInfo {
         self.latest_milestone_timestamp = latest_milestone_timestamp.into();
         self
     }
+
+    /// Schedules a protocol parameters upgrade, replacing an upgrade with the same target milestone index. If the
+    /// target milestone index is already reached, the protocol parameters are switched right away.
+    pub fn schedule_protocol_parameters_upgrade(&mut self, upgrade: ProtocolParametersUpgrade) {
+        match self
+            .protocol_parameters_upgrades
+            .binary_search_by_key(&upgrade.target_milestone_index, |upgrade| {
+                upgrade.target_milestone_index
+            }) {
+            Ok(position) => self.protocol_parameters_upgrades[position] = upgrade,
+            Err(position) => self.protocol_parameters_upgrades.insert(position, upgrade),
+        }
+        self.apply_protocol_parameters_upgrades();
+    }
+
+    /// Sets the latest milestone index and switches to the protocol parameters of the upgrades it reached. Returns
+    /// whether the protocol parameters changed.
+    pub fn update_latest_milestone_index(&mut self, latest_milestone_index: u32) -> bool {
+        if self
+            .latest_milestone_index
+            .is_some_and(|index| index > latest_milestone_index)
+        {
+            return false;
+        }
+        self.latest_milestone_index.replace(latest_milestone_index);
+        self.apply_protocol_parameters_upgrades()
+    }
+
+    // Switches to the protocol parameters of the latest upgrade whose target milestone index is reached and removes
+    // the reached upgrades. Returns whether the protocol parameters changed.
+    fn apply_protocol_parameters_upgrades(&mut self) -> bool {
+        let Some(latest_milestone_index) = self.latest_milestone_index else {
+            return false;
+        };
+        let reached = self
+            .protocol_parameters_upgrades
+            .partition_point(|upgrade| upgrade.target_milestone_index <= latest_milestone_index);
+
+        match self.protocol_parameters_upgrades.drain(..reached).next_back() {
+            Some(upgrade) if upgrade.protocol_parameters != self.protocol_parameters => {
+                log::debug!(
+                    "[NetworkInfo] switching to protocol version {} at milestone {}",
+                    upgrade.protocol_parameters.protocol_version(),
+                    upgrade.target_milestone_index
+                );
+                self.protocol_parameters = upgrade.protocol_parameters;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Updates the protocol parameters, the latest milestone and the announced upgrades from the info of a node.
+    pub(crate) fn update_from_node_info(&mut self, info: &InfoResponse) {
+        self.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
+        self.protocol_parameters = info.protocol.clone();
+        // The node switches to the upgrades it reached, the ones known from earlier milestones are dropped.
+        self.latest_milestone_index.replace(info.status.latest_milestone.index);
+        self.protocol_parameters_upgrades
+            .retain(|upgrade| upgrade.target_milestone_index > info.status.latest_milestone.index);
+
+        for pending in &info.pending_protocol_parameters {
+            match ParametersMilestoneOption::try_from(pending)
+                .and_then(|option| ProtocolParametersUpgrade::try_from(&option))
+            {
+                Ok(upgrade) => self.schedule_protocol_parameters_upgrade(upgrade),
+                Err(err) => log::warn!("Couldn't decode pending protocol parameters: {err}"),
+            }
+        }
+    }
+}
+
+/// Protocol parameters announced by a milestone to become active at a target milestone index.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolParametersUpgrade {
+    /// The milestone index at which the protocol parameters become active.
+    pub target_milestone_index: u32,
+    /// The protocol parameters.
+    pub protocol_parameters: ProtocolParameters,
+}
+
+impl TryFrom<&ParametersMilestoneOption> for ProtocolParametersUpgrade {
+    type Error = BlockError;
+
+    fn try_from(value: &ParametersMilestoneOption) -> core::result::Result<Self, Self::Error> {
+        Ok(Self {
+            target_milestone_index: *value.target_milestone_index(),
+            protocol_parameters: value.protocol_parameters()?,
+        })
+    }
 }
 
 fn default_local_pow() -> bool {
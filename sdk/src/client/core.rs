@@ -19,12 +19,14 @@ use super::request_pool::RequestPool;
 use crate::client::constants::CACHE_NETWORK_INFO_TIMEOUT_IN_SECONDS;
 use crate::{
     client::{
-        builder::{ClientBuilder, NetworkInfo},
+        builder::{ClientBuilder, NetworkInfo, ProtocolParametersUpgrade},
         error::Result,
         node_manager::NodeManager,
         Error,
     },
-    types::block::{address::Hrp, output::RentStructure, protocol::ProtocolParameters},
+    types::block::{
+        address::Hrp, output::RentStructure, payload::milestone::MilestonePayload, protocol::ProtocolParameters,
+    },
 };
 
 /// An IOTA node client.
@@ -120,8 +122,7 @@ impl ClientInner {
                 }
             }
             let info = self.get_info().await?.node_info;
-            let mut client_network_info = self.network_info.write().await;
-            client_network_info.protocol_parameters = info.protocol.clone();
+            self.network_info.write().await.update_from_node_info(&info);
             *self.last_sync.lock().await = Some(current_time + CACHE_NETWORK_INFO_TIMEOUT_IN_SECONDS);
         }
 
@@ -133,6 +134,35 @@ impl ClientInner {
         Ok(self.get_network_info().await?.protocol_parameters)
     }
 
+    /// Gets the announced protocol parameters upgrades which aren't active yet, ordered by target milestone index.
+    pub async fn get_protocol_parameters_upgrades(&self) -> Vec<ProtocolParametersUpgrade> {
+        self.network_info.read().await.protocol_parameters_upgrades.clone()
+    }
+
+    /// Schedules the protocol parameters upgrade announced by a milestone and switches to the protocol parameters of
+    /// the upgrades its index reached, without waiting for the next node sync. Returns the new protocol parameters if
+    /// they changed. Milestones received through MQTT are processed automatically, milestones older than the latest
+    /// known one are ignored.
+    pub async fn process_milestone(&self, milestone: &MilestonePayload) -> Result<Option<ProtocolParameters>> {
+        let mut network_info = self.network_info.write().await;
+        // The upgrades of older milestones are already known and may have been replaced since
+        if network_info
+            .latest_milestone_index
+            .is_some_and(|index| index > *milestone.essence().index())
+        {
+            return Ok(None);
+        }
+        let previous_protocol_parameters = network_info.protocol_parameters.clone();
+
+        if let Some(parameters) = milestone.essence().options().parameters() {
+            network_info.schedule_protocol_parameters_upgrade(ProtocolParametersUpgrade::try_from(parameters)?);
+        }
+        network_info.update_latest_milestone_index(*milestone.essence().index());
+
+        Ok((network_info.protocol_parameters != previous_protocol_parameters)
+            .then(|| network_info.protocol_parameters.clone()))
+    }
+
     /// Gets the protocol version of the node we're connecting to.
     pub async fn get_protocol_version(&self) -> Result<u8> {
         Ok(self.get_network_info().await?.protocol_parameters.protocol_version())
@@ -45,6 +45,9 @@ pub enum Error {
     /// Crypto.rs error
     #[error("{0}")]
     Crypto(#[from] crypto::Error),
+    /// The history API of archive nodes isn't provided by the node
+    #[error("history API not available, a permanode is required: {0}")]
+    HistoryNotAvailable(String),
     /// Address not found
     #[error("address: {address} not found in range: {range}")]
     InputAddressNotFound {
@@ -56,6 +59,9 @@ pub enum Error {
     /// Invalid amount in API response
     #[error("invalid amount in API response: {0}")]
     InvalidAmount(String),
+    /// Invalid pagination cursor
+    #[error("invalid cursor: {0}")]
+    InvalidCursor(String),
     /// Invalid mnemonic error
     #[error("invalid mnemonic {0}")]
     InvalidMnemonic(String),
@@ -67,6 +73,9 @@ pub enum Error {
         /// The max supported length.
         max_length: usize,
     },
+    /// Invalid swap error
+    #[error("invalid swap: {0}")]
+    InvalidSwap(&'static str),
     /// The transaction payload is too large
     #[error("the transaction payload is too large. Its length is {length}, max length is {max_length}")]
     InvalidTransactionPayloadLength {
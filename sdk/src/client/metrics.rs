@@ -0,0 +1,453 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Metrics of client and wallet operations.
+//!
+//! The metrics can be collected programmatically with [`Metrics::snapshot()`] or exported in the Prometheus text
+//! format with [`Metrics::encode_prometheus()`] and [`serve_metrics()`].
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Mutex, OnceLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Number of HTTP requests sent to nodes, by node, route, method and status code.
+pub const HTTP_REQUESTS_TOTAL: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_http_requests_total",
+    help: "Number of HTTP requests sent to nodes.",
+    buckets: &[],
+};
+/// Duration of HTTP requests sent to nodes, by node, route and method.
+pub const HTTP_REQUEST_DURATION_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_http_request_duration_seconds",
+    help: "Duration of HTTP requests sent to nodes in seconds.",
+    buckets: DURATION_BUCKETS,
+};
+/// Time requests waited for a slot in the request pool.
+pub const REQUEST_POOL_WAIT_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_request_pool_wait_seconds",
+    help: "Time requests waited for a slot in the request pool in seconds.",
+    buckets: DURATION_BUCKETS,
+};
+/// Duration of the local proof of work of blocks.
+pub const POW_DURATION_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_pow_duration_seconds",
+    help: "Duration of the local proof of work of blocks in seconds.",
+    buckets: &[0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0],
+};
+/// Score of the local proof of work of blocks.
+pub const POW_SCORE: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_pow_score",
+    help: "Score of the local proof of work of blocks.",
+    buckets: &[500.0, 1000.0, 1500.0, 2000.0, 4000.0, 8000.0, 16000.0, 32000.0],
+};
+/// Duration of account syncs, by account index.
+pub const SYNC_DURATION_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_wallet_sync_duration_seconds",
+    help: "Duration of account syncs in seconds.",
+    buckets: DURATION_BUCKETS,
+};
+/// Duration of input selections.
+pub const INPUT_SELECTION_DURATION_SECONDS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_input_selection_duration_seconds",
+    help: "Duration of input selections in seconds.",
+    buckets: DURATION_BUCKETS,
+};
+/// Number of inputs selected for transactions.
+pub const INPUT_SELECTION_INPUTS: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_input_selection_inputs",
+    help: "Number of inputs selected for transactions.",
+    buckets: &[1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0],
+};
+/// Number of sent transactions by inclusion state, counted when they're submitted and when their inclusion state
+/// changes.
+pub const TRANSACTIONS_TOTAL: MetricDescriptor = MetricDescriptor {
+    name: "iota_sdk_wallet_transactions_total",
+    help: "Number of sent transactions by inclusion state.",
+    buckets: &[],
+};
+
+const DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+const DESCRIPTORS: [MetricDescriptor; 9] = [
+    HTTP_REQUESTS_TOTAL,
+    HTTP_REQUEST_DURATION_SECONDS,
+    REQUEST_POOL_WAIT_SECONDS,
+    POW_DURATION_SECONDS,
+    POW_SCORE,
+    SYNC_DURATION_SECONDS,
+    INPUT_SELECTION_DURATION_SECONDS,
+    INPUT_SELECTION_INPUTS,
+    TRANSACTIONS_TOTAL,
+];
+
+/// Describes a metric. Counters have no buckets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricDescriptor {
+    /// The name of the metric.
+    pub name: &'static str,
+    /// The description of the metric.
+    pub help: &'static str,
+    /// The upper bounds of the histogram buckets.
+    pub buckets: &'static [f64],
+}
+
+type Labels = Vec<(&'static str, String)>;
+
+/// The metrics recorded by the SDK.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    counters: Mutex<BTreeMap<(&'static str, Labels), u64>>,
+    histograms: Mutex<BTreeMap<(&'static str, Labels), Histogram>>,
+}
+
+#[derive(Debug)]
+struct Histogram {
+    bucket_counts: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+/// Returns the metrics recorded by the SDK.
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::default)
+}
+
+impl Metrics {
+    /// Increments a counter.
+    pub fn increment_counter(&self, descriptor: &MetricDescriptor, labels: &[(&'static str, &str)]) {
+        let key = (descriptor.name, owned_labels(labels));
+        *self
+            .counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(key)
+            .or_default() += 1;
+    }
+
+    /// Records a value in a histogram.
+    pub fn observe(&self, descriptor: &MetricDescriptor, labels: &[(&'static str, &str)], value: f64) {
+        let key = (descriptor.name, owned_labels(labels));
+        let mut histograms = self.histograms.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let histogram = histograms.entry(key).or_insert_with(|| Histogram {
+            bucket_counts: vec![0; descriptor.buckets.len()],
+            count: 0,
+            sum: 0.0,
+        });
+        for (bucket_count, upper_bound) in histogram.bucket_counts.iter_mut().zip(descriptor.buckets) {
+            if value <= *upper_bound {
+                *bucket_count += 1;
+            }
+        }
+        histogram.count += 1;
+        histogram.sum += value;
+    }
+
+    /// Removes all recorded values.
+    pub fn reset(&self) {
+        self.counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+        self.histograms
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clear();
+    }
+
+    /// Returns the recorded values.
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let counters = self
+            .counters
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|((name, labels), value)| CounterSample {
+                name: name.to_string(),
+                labels: sample_labels(labels),
+                value: *value,
+            })
+            .collect();
+        let histograms = self
+            .histograms
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|((name, labels), histogram)| HistogramSample {
+                name: name.to_string(),
+                labels: sample_labels(labels),
+                buckets: descriptor(name)
+                    .buckets
+                    .iter()
+                    .copied()
+                    .zip(histogram.bucket_counts.iter().copied())
+                    .collect(),
+                count: histogram.count,
+                sum: histogram.sum,
+            })
+            .collect();
+
+        MetricsSnapshot { counters, histograms }
+    }
+
+    /// Returns the recorded values in the Prometheus text format.
+    pub fn encode_prometheus(&self) -> String {
+        let snapshot = self.snapshot();
+        let mut text = String::new();
+
+        for descriptor in DESCRIPTORS {
+            let counters = snapshot
+                .counters
+                .iter()
+                .filter(|sample| sample.name == descriptor.name)
+                .collect::<Vec<_>>();
+            let histograms = snapshot
+                .histograms
+                .iter()
+                .filter(|sample| sample.name == descriptor.name)
+                .collect::<Vec<_>>();
+            if counters.is_empty() && histograms.is_empty() {
+                continue;
+            }
+
+            let kind = if descriptor.buckets.is_empty() {
+                "counter"
+            } else {
+                "histogram"
+            };
+            // Writing to a String can't fail
+            writeln!(text, "# HELP {} {}", descriptor.name, descriptor.help).ok();
+            writeln!(text, "# TYPE {} {kind}", descriptor.name).ok();
+
+            for sample in counters {
+                writeln!(
+                    text,
+                    "{}{} {}",
+                    sample.name,
+                    encode_labels(&sample.labels, None),
+                    sample.value
+                )
+                .ok();
+            }
+            for sample in histograms {
+                for (upper_bound, count) in &sample.buckets {
+                    let le = upper_bound.to_string();
+                    let labels = encode_labels(&sample.labels, Some(&le));
+                    writeln!(text, "{}_bucket{labels} {count}", sample.name).ok();
+                }
+                let labels = encode_labels(&sample.labels, Some("+Inf"));
+                writeln!(text, "{}_bucket{labels} {}", sample.name, sample.count).ok();
+                let labels = encode_labels(&sample.labels, None);
+                writeln!(text, "{}_sum{labels} {}", sample.name, sample.sum).ok();
+                writeln!(text, "{}_count{labels} {}", sample.name, sample.count).ok();
+            }
+        }
+
+        text
+    }
+}
+
+/// The recorded values of all metrics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsSnapshot {
+    /// The values of the counters.
+    pub counters: Vec<CounterSample>,
+    /// The values of the histograms.
+    pub histograms: Vec<HistogramSample>,
+}
+
+/// The value of a counter for a set of labels.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterSample {
+    /// The name of the metric.
+    pub name: String,
+    /// The labels of the value.
+    pub labels: BTreeMap<String, String>,
+    /// The count.
+    pub value: u64,
+}
+
+/// The values of a histogram for a set of labels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistogramSample {
+    /// The name of the metric.
+    pub name: String,
+    /// The labels of the values.
+    pub labels: BTreeMap<String, String>,
+    /// The cumulative count of values for every bucket upper bound.
+    pub buckets: Vec<(f64, u64)>,
+    /// The number of values.
+    pub count: u64,
+    /// The sum of the values.
+    pub sum: f64,
+}
+
+fn descriptor(name: &str) -> MetricDescriptor {
+    // Only names of the descriptors are recorded
+    DESCRIPTORS
+        .into_iter()
+        .find(|descriptor| descriptor.name == name)
+        .expect("unknown metric")
+}
+
+fn owned_labels(labels: &[(&'static str, &str)]) -> Labels {
+    labels.iter().map(|(name, value)| (*name, value.to_string())).collect()
+}
+
+fn sample_labels(labels: &Labels) -> BTreeMap<String, String> {
+    labels
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+fn encode_labels(labels: &BTreeMap<String, String>, le: Option<&str>) -> String {
+    let mut encoded = labels
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .chain(le.map(|le| ("le", le)))
+        .map(|(name, value)| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    if !encoded.is_empty() {
+        encoded = format!("{{{encoded}}}");
+    }
+    encoded
+}
+
+/// A local HTTP server exporting the metrics in the Prometheus text format at `/metrics`.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Debug)]
+pub struct MetricsServer {
+    local_addr: std::net::SocketAddr,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl MetricsServer {
+    /// Returns the address the server listens on.
+    pub fn local_addr(&self) -> std::net::SocketAddr {
+        self.local_addr
+    }
+
+    /// Stops the server.
+    pub fn shutdown(self) {
+        self.handle.abort();
+    }
+}
+
+/// Starts a local HTTP server exporting the metrics in the Prometheus text format at `/metrics`.
+#[cfg(not(target_family = "wasm"))]
+pub async fn serve_metrics(address: impl tokio::net::ToSocketAddrs + Send) -> std::io::Result<MetricsServer> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    let local_addr = listener.local_addr()?;
+    log::debug!("[serve_metrics] listening on {local_addr}");
+
+    let handle = tokio::spawn(async move {
+        loop {
+            let Ok((mut stream, _)) = listener.accept().await else {
+                continue;
+            };
+            tokio::spawn(async move {
+                // Only the request line is needed
+                let mut request = [0; 1024];
+                let Ok(len) = stream.read(&mut request).await else {
+                    return;
+                };
+                let request = String::from_utf8_lossy(&request[..len]);
+                let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+
+                let response = match (request_line.next(), request_line.next()) {
+                    (Some("GET"), Some("/metrics")) => {
+                        let body = metrics().encode_prometheus();
+                        format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                            body.len()
+                        )
+                    }
+                    _ => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+                };
+                stream.write_all(response.as_bytes()).await.ok();
+                stream.shutdown().await.ok();
+            });
+        }
+    });
+
+    Ok(MetricsServer { local_addr, handle })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_prometheus() {
+        let metrics = Metrics::default();
+        let labels = [("node", "http://localhost:14265"), ("route", "/api/core/v2/info")];
+        metrics.increment_counter(&HTTP_REQUESTS_TOTAL, &[("status", "200")]);
+        metrics.increment_counter(&HTTP_REQUESTS_TOTAL, &[("status", "200")]);
+        metrics.observe(&HTTP_REQUEST_DURATION_SECONDS, &labels, 0.02);
+        metrics.observe(&HTTP_REQUEST_DURATION_SECONDS, &labels, 3.0);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.counters[0].value, 2);
+        assert_eq!(snapshot.histograms[0].count, 2);
+        assert_eq!(snapshot.histograms[0].buckets[3], (0.05, 1));
+
+        let text = metrics.encode_prometheus();
+        assert!(text.contains("# TYPE iota_sdk_http_requests_total counter\n"));
+        assert!(text.contains("iota_sdk_http_requests_total{status=\"200\"} 2\n"));
+        assert!(text.contains("# TYPE iota_sdk_http_request_duration_seconds histogram\n"));
+        assert!(text.contains(
+            "iota_sdk_http_request_duration_seconds_bucket{node=\"http://localhost:14265\",route=\"/api/core/v2/info\",le=\"0.05\"} 1\n"
+        ));
+        assert!(text.contains(
+            "iota_sdk_http_request_duration_seconds_bucket{node=\"http://localhost:14265\",route=\"/api/core/v2/info\",le=\"+Inf\"} 2\n"
+        ));
+        assert!(text.contains(
+            "iota_sdk_http_request_duration_seconds_count{node=\"http://localhost:14265\",route=\"/api/core/v2/info\"} 2\n"
+        ));
+        assert!(!text.contains("iota_sdk_pow_score"));
+
+        metrics.reset();
+        assert!(metrics.encode_prometheus().is_empty());
+    }
+
+    #[tokio::test]
+    async fn serve_metrics() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        metrics().increment_counter(&TRANSACTIONS_TOTAL, &[("inclusion_state", "Pending")]);
+        let server = super::serve_metrics("127.0.0.1:0").await.unwrap();
+
+        let mut stream = tokio::net::TcpStream::connect(server.local_addr()).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("iota_sdk_wallet_transactions_total{inclusion_state=\"Pending\"}"));
+
+        let mut stream = tokio::net::TcpStream::connect(server.local_addr()).await.unwrap();
+        stream.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        server.shutdown();
+    }
+}
@@ -40,6 +40,9 @@ pub mod builder;
 pub mod constants;
 pub mod core;
 pub mod error;
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub mod metrics;
 pub mod node_api;
 pub mod node_manager;
 #[cfg(not(target_family = "wasm"))]
@@ -49,12 +52,15 @@ pub mod storage;
 #[cfg(feature = "stronghold")]
 #[cfg_attr(docsrs, doc(cfg(feature = "stronghold")))]
 pub mod stronghold;
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub mod telemetry;
 pub mod utils;
 
 #[cfg(feature = "mqtt")]
 pub use self::node_api::mqtt;
 pub use self::{
-    builder::{ClientBuilder, NetworkInfo},
+    builder::{ClientBuilder, NetworkInfo, ProtocolParametersUpgrade},
     core::*,
     error::*,
     node_api::core::routes::NodeInfoWrapper,
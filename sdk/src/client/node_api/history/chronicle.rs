@@ -0,0 +1,132 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! History provider using the explorer API of Chronicle permanodes.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::{AddressHistoryItem, AddressHistoryPage, HistoryProvider};
+use crate::{
+    client::{node_api::error::Error as NodeApiError, Client, Error, Result},
+    types::block::{
+        address::Bech32Address,
+        output::{OutputId, OutputWithMetadata},
+        payload::transaction::TransactionId,
+        Block,
+    },
+};
+
+/// The default amount of ledger updates requested per page.
+pub const DEFAULT_CHRONICLE_PAGE_SIZE: usize = 1000;
+
+/// Requests the history from the permanodes of a client, which need to provide the Chronicle explorer API.
+///
+/// Outputs and blocks are requested from the core API of the permanodes, falling back to the other nodes. Requesting
+/// the history of an address fails with [`Error::HistoryNotAvailable`] if no node provides the explorer API.
+#[derive(Debug, Clone)]
+pub struct ChronicleHistoryProvider {
+    client: Client,
+    page_size: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LedgerUpdatesByAddressResponse {
+    items: Vec<AddressHistoryItem>,
+    cursor: Option<String>,
+}
+
+impl ChronicleHistoryProvider {
+    /// Creates a new [`ChronicleHistoryProvider`], the client should be built with a permanode.
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            page_size: DEFAULT_CHRONICLE_PAGE_SIZE,
+        }
+    }
+
+    /// Sets the amount of ledger updates requested per page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+}
+
+// Maps not found errors to `None`, as the history of outputs and blocks is incomplete by design.
+fn optional<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(Error::Node(NodeApiError::NotFound(_))) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[async_trait]
+impl HistoryProvider for ChronicleHistoryProvider {
+    /// GET /api/explorer/v2/ledger/updates/by-address/{address}
+    async fn address_history_page(
+        &self,
+        address: &Bech32Address,
+        cursor: Option<String>,
+    ) -> Result<AddressHistoryPage> {
+        let path = &format!("api/explorer/v2/ledger/updates/by-address/{address}");
+        let mut query = format!("pageSize={}&sort=newest", self.page_size);
+        if let Some(cursor) = cursor {
+            query.push_str(&format!("&cursor={cursor}"));
+        }
+
+        // Nodes without the explorer API respond with not found, which must not be mistaken for an empty history.
+        let response = self
+            .client
+            .get_request::<LedgerUpdatesByAddressResponse>(path, Some(&query), false, true)
+            .await
+            .map_err(|e| match e {
+                Error::Node(NodeApiError::NotFound(url)) => Error::HistoryNotAvailable(url),
+                e => e,
+            })?;
+
+        Ok(AddressHistoryPage {
+            items: response.items,
+            cursor: response.cursor,
+        })
+    }
+
+    async fn output(&self, output_id: &OutputId) -> Result<Option<OutputWithMetadata>> {
+        optional(self.client.get_output(output_id).await)
+    }
+
+    async fn included_block(&self, transaction_id: &TransactionId) -> Result<Option<Block>> {
+        optional(self.client.get_included_block(transaction_id).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_updates_by_address_response() {
+        let response = serde_json::from_str::<LedgerUpdatesByAddressResponse>(
+            r#"{
+                "address": "rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy",
+                "items": [
+                    {
+                        "address": "rms1qpllaj0pyveqfkwxmnngz2c488hfdtmfrj3wfkgxtk4gtyrax0jaxzt70zy",
+                        "outputId": "0x1e857d380f813d8035e487b6dfd2ff4740b6775273ba1b576f01381ba2a1a44c0000",
+                        "isSpent": true,
+                        "milestoneIndex": 5,
+                        "milestoneTimestamp": 1690000000
+                    }
+                ],
+                "cursor": "1690000000.0x1e857d380f813d8035e487b6dfd2ff4740b6775273ba1b576f01381ba2a1a44c0000.false.100"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(response.items.len(), 1);
+        assert!(response.items[0].is_spent);
+        assert_eq!(response.items[0].milestone_index, 5);
+        assert!(response.cursor.is_some());
+    }
+}
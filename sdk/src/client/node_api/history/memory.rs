@@ -0,0 +1,222 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-memory history provider, e.g. for tests.
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use super::{AddressHistoryItem, AddressHistoryPage, HistoryProvider};
+use crate::{
+    client::{Error, Result},
+    types::block::{
+        address::Bech32Address,
+        output::{OutputId, OutputWithMetadata},
+        payload::{transaction::TransactionId, Payload},
+        Block,
+    },
+};
+
+/// The default amount of history items returned per page.
+pub const DEFAULT_MEMORY_PAGE_SIZE: usize = 100;
+
+/// Provides a history which is held in memory.
+#[derive(Debug, Clone)]
+pub struct MemoryHistoryProvider {
+    page_size: usize,
+    address_output_ids: HashMap<Bech32Address, Vec<OutputId>>,
+    outputs: HashMap<OutputId, OutputWithMetadata>,
+    blocks: HashMap<TransactionId, Block>,
+}
+
+impl Default for MemoryHistoryProvider {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_MEMORY_PAGE_SIZE,
+            address_output_ids: HashMap::new(),
+            outputs: HashMap::new(),
+            blocks: HashMap::new(),
+        }
+    }
+}
+
+impl MemoryHistoryProvider {
+    /// Creates a new empty [`MemoryHistoryProvider`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the amount of history items returned per page.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Adds an output to the history of an address.
+    pub fn add_output(&mut self, address: Bech32Address, output: OutputWithMetadata) -> &mut Self {
+        let output_id = *output.metadata().output_id();
+        let output_ids = self.address_output_ids.entry(address).or_default();
+        if !output_ids.contains(&output_id) {
+            output_ids.push(output_id);
+        }
+        self.outputs.insert(output_id, output);
+        self
+    }
+
+    /// Adds a block with a transaction payload, blocks without one are ignored.
+    pub fn add_block(&mut self, block: Block) -> &mut Self {
+        if let Some(Payload::Transaction(transaction_payload)) = block.payload() {
+            self.blocks.insert(transaction_payload.id(), block);
+        }
+        self
+    }
+
+    // The creation and, if spent, the spending of the outputs of an address, newest first.
+    fn address_history_items(&self, address: &Bech32Address) -> Vec<AddressHistoryItem> {
+        let mut items = self
+            .address_output_ids
+            .get(address)
+            .into_iter()
+            .flatten()
+            .filter_map(|output_id| self.outputs.get(output_id))
+            .flat_map(|output| {
+                let metadata = output.metadata();
+                let created = AddressHistoryItem {
+                    output_id: *metadata.output_id(),
+                    is_spent: false,
+                    milestone_index: metadata.milestone_index_booked(),
+                    milestone_timestamp: metadata.milestone_timestamp_booked(),
+                };
+                let spent = metadata
+                    .milestone_index_spent()
+                    .zip(metadata.milestone_timestamp_spent())
+                    .map(|(milestone_index, milestone_timestamp)| AddressHistoryItem {
+                        output_id: *metadata.output_id(),
+                        is_spent: true,
+                        milestone_index,
+                        milestone_timestamp,
+                    });
+
+                std::iter::once(created).chain(spent)
+            })
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| {
+            (b.milestone_index, b.is_spent, b.output_id).cmp(&(a.milestone_index, a.is_spent, a.output_id))
+        });
+        items
+    }
+}
+
+#[async_trait]
+impl HistoryProvider for MemoryHistoryProvider {
+    async fn address_history_page(
+        &self,
+        address: &Bech32Address,
+        cursor: Option<String>,
+    ) -> Result<AddressHistoryPage> {
+        // The cursor is the index of the first item of the page.
+        let start = cursor
+            .map(|cursor| cursor.parse::<usize>().map_err(|_| Error::InvalidCursor(cursor)))
+            .transpose()?
+            .unwrap_or_default();
+        let items = self.address_history_items(address);
+        let end = items.len().min(start.saturating_add(self.page_size.max(1)));
+
+        Ok(AddressHistoryPage {
+            items: items.get(start..end).unwrap_or_default().to_vec(),
+            cursor: (end < items.len()).then(|| end.to_string()),
+        })
+    }
+
+    async fn output(&self, output_id: &OutputId) -> Result<Option<OutputWithMetadata>> {
+        Ok(self.outputs.get(output_id).cloned())
+    }
+
+    async fn included_block(&self, transaction_id: &TransactionId) -> Result<Option<Block>> {
+        Ok(self.blocks.get(transaction_id).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::{
+        address::{Address, Ed25519Address, Hrp, ToBech32Ext},
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, OutputMetadata},
+        BlockId,
+    };
+
+    fn output(address: Address, index: u16, spent_transaction_id: Option<TransactionId>) -> OutputWithMetadata {
+        let output_id = OutputId::new(TransactionId::new([1; 32]), index).unwrap();
+
+        OutputWithMetadata::new(
+            BasicOutputBuilder::new_with_amount(1_000_000)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(u64::MAX)
+                .unwrap(),
+            OutputMetadata::new(
+                BlockId::null(),
+                output_id,
+                spent_transaction_id.is_some(),
+                spent_transaction_id.map(|_| 10 + index as u32),
+                spent_transaction_id.map(|_| 1_000 + index as u32),
+                spent_transaction_id,
+                index as u32,
+                index as u32,
+                20,
+            ),
+        )
+    }
+
+    #[tokio::test]
+    async fn memory_history_provider() {
+        let address = Address::from(Ed25519Address::new([1; 32]));
+        let bech32_address = address.to_bech32(Hrp::from_str_unchecked("rms"));
+        let spent_transaction_id = TransactionId::new([2; 32]);
+
+        let mut history_provider = MemoryHistoryProvider::new().with_page_size(2);
+        history_provider
+            .add_output(bech32_address, output(address, 0, Some(spent_transaction_id)))
+            .add_output(bech32_address, output(address, 1, None));
+
+        let page = history_provider
+            .address_history_page(&bech32_address, None)
+            .await
+            .unwrap();
+        // The spending of the first output is the newest item
+        assert!(page.items[0].is_spent && page.items[0].milestone_index == 10);
+        assert_eq!(page.cursor.as_deref(), Some("2"));
+
+        let history = history_provider.address_history(&bech32_address).await.unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(
+            history_provider
+                .address_output_ids(&bech32_address)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let spent_output_id = OutputId::new(TransactionId::new([1; 32]), 0).unwrap();
+        let spent_output = history_provider.spent_output(&spent_output_id).await.unwrap().unwrap();
+        assert_eq!(spent_output.transaction_id, spent_transaction_id);
+        // The block of the spending transaction is unknown
+        assert!(spent_output.transaction.is_none());
+
+        let unspent_output_id = OutputId::new(TransactionId::new([1; 32]), 1).unwrap();
+        assert!(history_provider
+            .spent_output(&unspent_output_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        assert!(matches!(
+            history_provider
+                .address_history_page(&bech32_address, Some("cursor".to_string()))
+                .await,
+            Err(Error::InvalidCursor(_))
+        ));
+    }
+}
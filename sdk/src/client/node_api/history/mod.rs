@@ -0,0 +1,124 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! History API of archive nodes, to request the history of addresses, spent outputs and the transactions which spent
+//! them, after they were pruned by regular nodes.
+
+pub mod chronicle;
+pub mod memory;
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+pub use self::{chronicle::ChronicleHistoryProvider, memory::MemoryHistoryProvider};
+use crate::{
+    client::Result,
+    types::block::{
+        address::Bech32Address,
+        output::{OutputId, OutputWithMetadata},
+        payload::{transaction::TransactionId, Payload, TransactionPayload},
+        Block, BlockId,
+    },
+};
+
+/// An output which was created or spent in the history of an address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressHistoryItem {
+    /// The id of the output
+    pub output_id: OutputId,
+    /// Whether the output was spent or created in this item
+    pub is_spent: bool,
+    /// The index of the milestone which created or spent the output
+    pub milestone_index: u32,
+    /// The timestamp of the milestone which created or spent the output
+    pub milestone_timestamp: u32,
+}
+
+/// A page of the history of an address.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressHistoryPage {
+    /// The items of the page, newest first
+    pub items: Vec<AddressHistoryItem>,
+    /// The cursor to request the next page, `None` if this is the last page
+    pub cursor: Option<String>,
+}
+
+/// A spent output with the transaction which spent it.
+#[derive(Debug, Clone)]
+pub struct SpentOutput {
+    /// The spent output
+    pub output: OutputWithMetadata,
+    /// The id of the transaction which spent the output
+    pub transaction_id: TransactionId,
+    /// The id of the block which included the transaction, if the block is known
+    pub block_id: Option<BlockId>,
+    /// The transaction which spent the output, if the block is known
+    pub transaction: Option<TransactionPayload>,
+}
+
+/// Provides the history of the ledger, which regular nodes prune after some time.
+#[async_trait]
+pub trait HistoryProvider: std::fmt::Debug + Send + Sync {
+    /// Returns a page of the outputs created and spent by an address, newest first. The first page is requested
+    /// without cursor.
+    async fn address_history_page(&self, address: &Bech32Address, cursor: Option<String>)
+        -> Result<AddressHistoryPage>;
+
+    /// Returns an output with its metadata, `None` if the output is unknown.
+    async fn output(&self, output_id: &OutputId) -> Result<Option<OutputWithMetadata>>;
+
+    /// Returns the block which included a transaction, `None` if the transaction is unknown.
+    async fn included_block(&self, transaction_id: &TransactionId) -> Result<Option<Block>>;
+
+    /// Returns the full history of an address, requesting all pages.
+    async fn address_history(&self, address: &Bech32Address) -> Result<Vec<AddressHistoryItem>> {
+        let mut items = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self.address_history_page(address, cursor).await?;
+            items.extend(page.items);
+
+            match page.cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// Returns the ids of all outputs an address ever owned.
+    async fn address_output_ids(&self, address: &Bech32Address) -> Result<HashSet<OutputId>> {
+        Ok(self
+            .address_history(address)
+            .await?
+            .into_iter()
+            .map(|item| item.output_id)
+            .collect())
+    }
+
+    /// Returns a spent output with the transaction which spent it, `None` if the output is unknown or unspent.
+    async fn spent_output(&self, output_id: &OutputId) -> Result<Option<SpentOutput>> {
+        let Some(output) = self.output(output_id).await? else {
+            return Ok(None);
+        };
+        let Some(transaction_id) = output.metadata().transaction_id_spent().copied() else {
+            return Ok(None);
+        };
+
+        let block = self.included_block(&transaction_id).await?;
+
+        Ok(Some(SpentOutput {
+            output,
+            transaction_id,
+            block_id: block.as_ref().map(Block::id),
+            transaction: block.and_then(|block| match block.payload() {
+                Some(Payload::Transaction(transaction_payload)) => Some(*transaction_payload.clone()),
+                _ => None,
+            }),
+        }))
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod query_parameters;
 pub mod routes;
+pub mod stream;
 
 pub(crate) use self::query_parameters::{QueryParameter, QueryParameters};
 use crate::{
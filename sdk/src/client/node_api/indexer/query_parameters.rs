@@ -319,6 +319,233 @@ pub(crate) fn verify_query_parameters_nft_outputs(query_parameters: Vec<QueryPar
     Ok(QueryParameters::new(query_parameters))
 }
 
+/// Query parameters of an indexer output id route, which are typed per output kind so that only the query parameters
+/// supported by the route can be set.
+pub trait OutputQueryParameters: Into<Vec<QueryParameter>> + Send {
+    /// The route of the query.
+    const ROUTE: &'static str;
+}
+
+macro_rules! typed_query_parameters {
+    (
+        $(#[$meta:meta])*
+        $name:ident, $route:literal {
+            $($(#[$method_meta:meta])* $method:ident, $field:ident => $variant:ident($ty:ty)),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase", deny_unknown_fields)]
+        #[must_use]
+        pub struct $name {
+            $(
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                $field: Option<$ty>,
+            )+
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            cursor: Option<String>,
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            page_size: Option<usize>,
+        }
+
+        impl $name {
+            /// Creates new empty query parameters.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            $(
+                $(#[$method_meta])*
+                pub fn $method(mut self, value: impl Into<$ty>) -> Self {
+                    self.$field = Some(value.into());
+                    self
+                }
+            )+
+
+            /// Starts the search from the cursor (confirmationMS+outputId.pageSize).
+            pub fn with_cursor(mut self, cursor: impl Into<String>) -> Self {
+                self.cursor = Some(cursor.into());
+                self
+            }
+
+            /// Sets the maximum amount of items returned in one page.
+            pub fn with_page_size(mut self, page_size: usize) -> Self {
+                self.page_size = Some(page_size);
+                self
+            }
+        }
+
+        impl From<$name> for Vec<QueryParameter> {
+            fn from(query_parameters: $name) -> Self {
+                [
+                    $(query_parameters.$field.map(QueryParameter::$variant),)+
+                    query_parameters.cursor.map(QueryParameter::Cursor),
+                    query_parameters.page_size.map(QueryParameter::PageSize),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            }
+        }
+
+        impl OutputQueryParameters for $name {
+            const ROUTE: &'static str = $route;
+        }
+    };
+}
+
+typed_query_parameters!(
+    /// Query parameters for requests of basic, alias, nft and foundry outputs.
+    AnyOutputQueryParameters, "api/indexer/v1/outputs" {
+        /// Filters outputs based on the presence of native tokens.
+        with_has_native_tokens, has_native_tokens => HasNativeTokens(bool),
+        /// Filters outputs that have at least a certain number of distinct native tokens.
+        with_min_native_token_count, min_native_token_count => MinNativeTokenCount(u32),
+        /// Filters outputs that have at most a certain number of distinct native tokens.
+        with_max_native_token_count, max_native_token_count => MaxNativeTokenCount(u32),
+        /// Returns outputs that were created before a certain Unix timestamp.
+        with_created_before, created_before => CreatedBefore(u32),
+        /// Returns outputs that were created after a certain Unix timestamp.
+        with_created_after, created_after => CreatedAfter(u32),
+        /// Returns outputs that are unlockable by the bech32 address.
+        with_unlockable_by_address, unlockable_by_address => UnlockableByAddress(Bech32Address),
+    }
+);
+
+typed_query_parameters!(
+    /// Query parameters for basic output requests.
+    BasicOutputQueryParameters, "api/indexer/v1/outputs/basic" {
+        /// Bech32-encoded address that should be searched for.
+        with_address, address => Address(Bech32Address),
+        /// Filters outputs based on the presence of native tokens.
+        with_has_native_tokens, has_native_tokens => HasNativeTokens(bool),
+        /// Filters outputs that have at least a certain number of distinct native tokens.
+        with_min_native_token_count, min_native_token_count => MinNativeTokenCount(u32),
+        /// Filters outputs that have at most a certain number of distinct native tokens.
+        with_max_native_token_count, max_native_token_count => MaxNativeTokenCount(u32),
+        /// Filters outputs based on the presence of storage deposit return unlock condition.
+        with_has_storage_deposit_return, has_storage_deposit_return => HasStorageDepositReturn(bool),
+        /// Filters outputs based on the presence of a specific return address in the storage deposit return unlock
+        /// condition.
+        with_storage_deposit_return_address, storage_deposit_return_address => StorageDepositReturnAddress(Bech32Address),
+        /// Filters outputs based on the presence of timelock unlock condition.
+        with_has_timelock, has_timelock => HasTimelock(bool),
+        /// Returns outputs that are timelocked before a certain Unix timestamp.
+        with_timelocked_before, timelocked_before => TimelockedBefore(u32),
+        /// Returns outputs that are timelocked after a certain Unix timestamp.
+        with_timelocked_after, timelocked_after => TimelockedAfter(u32),
+        /// Filters outputs based on the presence of expiration unlock condition.
+        with_has_expiration, has_expiration => HasExpiration(bool),
+        /// Returns outputs that expire before a certain Unix timestamp.
+        with_expires_before, expires_before => ExpiresBefore(u32),
+        /// Returns outputs that expire after a certain Unix timestamp.
+        with_expires_after, expires_after => ExpiresAfter(u32),
+        /// Filters outputs based on the presence of a specific Bech32-encoded return address in the expiration
+        /// unlock condition.
+        with_expiration_return_address, expiration_return_address => ExpirationReturnAddress(Bech32Address),
+        /// Filters outputs based on the presence of validated Sender (bech32 encoded).
+        with_sender, sender => Sender(Bech32Address),
+        /// Filters outputs based on matching Tag Block.
+        with_tag, tag => Tag(String),
+        /// Returns outputs that were created before a certain Unix timestamp.
+        with_created_before, created_before => CreatedBefore(u32),
+        /// Returns outputs that were created after a certain Unix timestamp.
+        with_created_after, created_after => CreatedAfter(u32),
+        /// Returns outputs that are unlockable by the bech32 address.
+        with_unlockable_by_address, unlockable_by_address => UnlockableByAddress(Bech32Address),
+    }
+);
+
+typed_query_parameters!(
+    /// Query parameters for alias output requests.
+    AliasOutputQueryParameters, "api/indexer/v1/outputs/alias" {
+        /// Filters outputs based on bech32-encoded state controller address.
+        with_state_controller, state_controller => StateController(Bech32Address),
+        /// Filters outputs based on bech32-encoded governor (governance controller) address.
+        with_governor, governor => Governor(Bech32Address),
+        /// Filters outputs based on bech32-encoded issuer address.
+        with_issuer, issuer => Issuer(Bech32Address),
+        /// Filters outputs based on the presence of validated Sender (bech32 encoded).
+        with_sender, sender => Sender(Bech32Address),
+        /// Filters outputs based on the presence of native tokens.
+        with_has_native_tokens, has_native_tokens => HasNativeTokens(bool),
+        /// Filters outputs that have at least a certain number of distinct native tokens.
+        with_min_native_token_count, min_native_token_count => MinNativeTokenCount(u32),
+        /// Filters outputs that have at most a certain number of distinct native tokens.
+        with_max_native_token_count, max_native_token_count => MaxNativeTokenCount(u32),
+        /// Returns outputs that were created before a certain Unix timestamp.
+        with_created_before, created_before => CreatedBefore(u32),
+        /// Returns outputs that were created after a certain Unix timestamp.
+        with_created_after, created_after => CreatedAfter(u32),
+        /// Returns outputs that are unlockable by the bech32 address.
+        with_unlockable_by_address, unlockable_by_address => UnlockableByAddress(Bech32Address),
+    }
+);
+
+typed_query_parameters!(
+    /// Query parameters for foundry output requests.
+    FoundryOutputQueryParameters, "api/indexer/v1/outputs/foundry" {
+        /// Filter foundry outputs based on bech32-encoded address of the controlling alias.
+        with_alias_address, alias_address => AliasAddress(Bech32Address),
+        /// Filters outputs based on the presence of native tokens.
+        with_has_native_tokens, has_native_tokens => HasNativeTokens(bool),
+        /// Filters outputs that have at least a certain number of distinct native tokens.
+        with_min_native_token_count, min_native_token_count => MinNativeTokenCount(u32),
+        /// Filters outputs that have at most a certain number of distinct native tokens.
+        with_max_native_token_count, max_native_token_count => MaxNativeTokenCount(u32),
+        /// Returns outputs that were created before a certain Unix timestamp.
+        with_created_before, created_before => CreatedBefore(u32),
+        /// Returns outputs that were created after a certain Unix timestamp.
+        with_created_after, created_after => CreatedAfter(u32),
+    }
+);
+
+typed_query_parameters!(
+    /// Query parameters for nft output requests.
+    NftOutputQueryParameters, "api/indexer/v1/outputs/nft" {
+        /// Bech32-encoded address that should be searched for.
+        with_address, address => Address(Bech32Address),
+        /// Filters outputs based on the presence of native tokens.
+        with_has_native_tokens, has_native_tokens => HasNativeTokens(bool),
+        /// Filters outputs that have at least a certain number of distinct native tokens.
+        with_min_native_token_count, min_native_token_count => MinNativeTokenCount(u32),
+        /// Filters outputs that have at most a certain number of distinct native tokens.
+        with_max_native_token_count, max_native_token_count => MaxNativeTokenCount(u32),
+        /// Filters outputs based on the presence of storage deposit return unlock condition.
+        with_has_storage_deposit_return, has_storage_deposit_return => HasStorageDepositReturn(bool),
+        /// Filters outputs based on the presence of a specific return address in the storage deposit return unlock
+        /// condition.
+        with_storage_deposit_return_address, storage_deposit_return_address => StorageDepositReturnAddress(Bech32Address),
+        /// Filters outputs based on the presence of timelock unlock condition.
+        with_has_timelock, has_timelock => HasTimelock(bool),
+        /// Returns outputs that are timelocked before a certain Unix timestamp.
+        with_timelocked_before, timelocked_before => TimelockedBefore(u32),
+        /// Returns outputs that are timelocked after a certain Unix timestamp.
+        with_timelocked_after, timelocked_after => TimelockedAfter(u32),
+        /// Filters outputs based on the presence of expiration unlock condition.
+        with_has_expiration, has_expiration => HasExpiration(bool),
+        /// Returns outputs that expire before a certain Unix timestamp.
+        with_expires_before, expires_before => ExpiresBefore(u32),
+        /// Returns outputs that expire after a certain Unix timestamp.
+        with_expires_after, expires_after => ExpiresAfter(u32),
+        /// Filters outputs based on the presence of a specific Bech32-encoded return address in the expiration
+        /// unlock condition.
+        with_expiration_return_address, expiration_return_address => ExpirationReturnAddress(Bech32Address),
+        /// Filters outputs based on bech32-encoded issuer address.
+        with_issuer, issuer => Issuer(Bech32Address),
+        /// Filters outputs based on the presence of validated Sender (bech32 encoded).
+        with_sender, sender => Sender(Bech32Address),
+        /// Filters outputs based on matching Tag Block.
+        with_tag, tag => Tag(String),
+        /// Returns outputs that were created before a certain Unix timestamp.
+        with_created_before, created_before => CreatedBefore(u32),
+        /// Returns outputs that were created after a certain Unix timestamp.
+        with_created_after, created_after => CreatedAfter(u32),
+        /// Returns outputs that are unlockable by the bech32 address.
+        with_unlockable_by_address, unlockable_by_address => UnlockableByAddress(Bech32Address),
+    }
+);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,4 +576,52 @@ mod tests {
         // Contains no cursor query parameter
         assert!(!query_parameters.any(|param| matches!(param, QueryParameter::Cursor(_))));
     }
+
+    #[test]
+    fn typed_query_parameters() {
+        let address =
+            Bech32Address::try_from_str("atoi1qzt0nhsf38nh6rs4p6zs5knqp6psgha9wsv74uajqgjmwc75ugupx3y7x0r").unwrap();
+
+        let query_parameters = BasicOutputQueryParameters::new()
+            .with_address(address)
+            .with_has_expiration(false)
+            .with_tag("0x01")
+            .with_page_size(10)
+            .with_tag("0x02");
+        let query_parameters = QueryParameters::new(query_parameters);
+        assert_eq!(
+            query_parameters.to_query_string().unwrap(),
+            format!("address={address}&hasExpiration=false&pageSize=10&tag=0x02")
+        );
+        // The typed query parameters only contain query parameters supported by the route
+        assert!(verify_query_parameters_basic_outputs(query_parameters.0).is_ok());
+
+        let query_parameters: Vec<QueryParameter> = AliasOutputQueryParameters::new()
+            .with_state_controller(address)
+            .with_governor(address)
+            .with_cursor("cursor")
+            .into();
+        assert!(verify_query_parameters_alias_outputs(query_parameters).is_ok());
+
+        let query_parameters =
+            serde_json::from_str::<BasicOutputQueryParameters>(&format!(r#"{{"address":"{address}","pageSize":10}}"#))
+                .unwrap();
+        assert_eq!(
+            query_parameters,
+            BasicOutputQueryParameters::new()
+                .with_address(address)
+                .with_page_size(10)
+        );
+        // Query parameters not supported by the route can't be deserialized either
+        assert!(
+            serde_json::from_str::<BasicOutputQueryParameters>(&format!(r#"{{"stateController":"{address}"}}"#))
+                .is_err()
+        );
+
+        assert_eq!(
+            QueryParameters::new(FoundryOutputQueryParameters::new()).to_query_string(),
+            None
+        );
+        assert_eq!(NftOutputQueryParameters::ROUTE, "api/indexer/v1/outputs/nft");
+    }
 }
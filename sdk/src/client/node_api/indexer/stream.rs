@@ -0,0 +1,121 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Paginated streams over indexer queries.
+
+use futures::{Stream, TryStreamExt};
+
+use crate::{
+    client::{
+        node_api::indexer::query_parameters::{
+            AliasOutputQueryParameters, BasicOutputQueryParameters, FoundryOutputQueryParameters,
+            NftOutputQueryParameters, OutputQueryParameters, QueryParameter, QueryParameters,
+        },
+        Client, Result,
+    },
+    types::{api::plugins::indexer::OutputIdsResponse, block::output::OutputWithMetadata},
+};
+
+/// A page of outputs returned by an indexer query.
+#[derive(Debug, Clone)]
+pub struct OutputsPage {
+    /// The ledger index at which the page was queried.
+    pub ledger_index: u32,
+    /// The cursor to resume the query after this page, `None` if this is the last page.
+    pub cursor: Option<String>,
+    /// The outputs of the page.
+    pub items: Vec<OutputWithMetadata>,
+}
+
+impl Client {
+    /// Returns a stream of the pages of output ids matching the query parameters.
+    /// A page is only requested when the stream is polled, so a slow consumer doesn't buffer all pages in memory.
+    /// The query can be resumed by setting the cursor of the last received page on the query parameters.
+    pub fn output_id_pages<Q: OutputQueryParameters>(
+        &self,
+        query_parameters: Q,
+    ) -> impl Stream<Item = Result<OutputIdsResponse>> + '_ {
+        futures::stream::try_unfold(
+            Some(QueryParameters::new(query_parameters)),
+            move |query_parameters| async move {
+                let Some(mut query_parameters) = query_parameters else {
+                    return Ok(None);
+                };
+
+                let output_ids_response = self
+                    .get_request::<OutputIdsResponse>(
+                        Q::ROUTE,
+                        query_parameters.to_query_string().as_deref(),
+                        true,
+                        false,
+                    )
+                    .await?;
+
+                let next_query_parameters = output_ids_response.cursor.clone().map(|cursor| {
+                    query_parameters.replace(QueryParameter::Cursor(cursor));
+                    query_parameters
+                });
+
+                Ok(Some((output_ids_response, next_query_parameters)))
+            },
+        )
+    }
+
+    /// Returns a stream of the pages of outputs matching the query parameters, the size of the pages can be set with
+    /// the `PageSize` query parameter.
+    pub fn output_pages<Q: OutputQueryParameters>(
+        &self,
+        query_parameters: Q,
+    ) -> impl Stream<Item = Result<OutputsPage>> + '_ {
+        self.output_id_pages(query_parameters)
+            .and_then(move |output_ids_response| async move {
+                Ok(OutputsPage {
+                    ledger_index: output_ids_response.ledger_index,
+                    items: self.get_outputs(&output_ids_response.items).await?,
+                    cursor: output_ids_response.cursor,
+                })
+            })
+    }
+
+    /// Returns a stream of the outputs matching the query parameters, which are requested page by page.
+    pub fn outputs_stream<Q: OutputQueryParameters>(
+        &self,
+        query_parameters: Q,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + '_ {
+        self.output_pages(query_parameters)
+            .map_ok(|page| futures::stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Returns a stream of the basic outputs matching the query parameters.
+    pub fn basic_outputs_stream(
+        &self,
+        query_parameters: BasicOutputQueryParameters,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + '_ {
+        self.outputs_stream(query_parameters)
+    }
+
+    /// Returns a stream of the alias outputs matching the query parameters.
+    pub fn alias_outputs_stream(
+        &self,
+        query_parameters: AliasOutputQueryParameters,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + '_ {
+        self.outputs_stream(query_parameters)
+    }
+
+    /// Returns a stream of the foundry outputs matching the query parameters.
+    pub fn foundry_outputs_stream(
+        &self,
+        query_parameters: FoundryOutputQueryParameters,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + '_ {
+        self.outputs_stream(query_parameters)
+    }
+
+    /// Returns a stream of the nft outputs matching the query parameters.
+    pub fn nft_outputs_stream(
+        &self,
+        query_parameters: NftOutputQueryParameters,
+    ) -> impl Stream<Item = Result<OutputWithMetadata>> + '_ {
+        self.outputs_stream(query_parameters)
+    }
+}
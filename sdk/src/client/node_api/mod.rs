@@ -5,6 +5,7 @@
 
 pub mod core;
 pub mod error;
+pub mod history;
 pub mod indexer;
 #[cfg(feature = "mqtt")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mqtt")))]
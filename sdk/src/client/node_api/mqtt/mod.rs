@@ -210,13 +210,21 @@ fn poll_mqtt(client: &Client, mut event_loop: EventLoop) {
                                         }
                                     } else if p.topic.contains("milestones") {
                                         let payload = &*p.payload;
-                                        let protocol_parameters = &client.network_info.read().await.protocol_parameters;
-
-                                        match Payload::unpack_verified(payload, protocol_parameters) {
-                                            Ok(Payload::Milestone(milestone)) => Ok(TopicEvent {
-                                                topic: p.topic.clone(),
-                                                payload: MqttPayload::MilestonePayload(milestone.as_ref().into()),
-                                            }),
+                                        let protocol_parameters =
+                                            client.network_info.read().await.protocol_parameters.clone();
+
+                                        match Payload::unpack_verified(payload, &protocol_parameters) {
+                                            Ok(Payload::Milestone(milestone)) => {
+                                                // Keep track of the protocol parameters upgrades the milestone
+                                                // announces or reaches
+                                                if let Err(e) = client.process_milestone(&milestone).await {
+                                                    warn!("Processing milestone failed: {:?}", e);
+                                                }
+                                                Ok(TopicEvent {
+                                                    topic: p.topic.clone(),
+                                                    payload: MqttPayload::MilestonePayload(milestone.as_ref().into()),
+                                                })
+                                            }
                                             Ok(p) => {
                                                 warn!(
                                                     "'milestone' topic returned non-milestone payload, kind: {:?}",
@@ -88,11 +88,56 @@ impl HttpClient {
         request_builder
     }
 
+    // Sends the request and records the metrics and the tracing span of it
+    #[allow(unused_variables)]
+    async fn send(request_builder: RequestBuilder, method: &'static str, url: &url::Url) -> Result<reqwest::Response> {
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        let (node, route) = node_and_route(url);
+        #[cfg(feature = "metrics")]
+        let start_time = instant::Instant::now();
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!("node_request", node, route, method, status = tracing::field::Empty);
+
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(request_builder.send(), span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        let result = request_builder.send().await;
+
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        let status = result.as_ref().map_or_else(
+            |_| "error".to_string(),
+            |response| response.status().as_u16().to_string(),
+        );
+        #[cfg(feature = "tracing")]
+        span.record("status", status.as_str());
+        #[cfg(feature = "metrics")]
+        {
+            use crate::client::metrics::{metrics, HTTP_REQUESTS_TOTAL, HTTP_REQUEST_DURATION_SECONDS};
+
+            metrics().increment_counter(
+                &HTTP_REQUESTS_TOTAL,
+                &[
+                    ("node", &node),
+                    ("route", &route),
+                    ("method", method),
+                    ("status", &status),
+                ],
+            );
+            metrics().observe(
+                &HTTP_REQUEST_DURATION_SECONDS,
+                &[("node", &node), ("route", &route), ("method", method)],
+                start_time.elapsed().as_secs_f64(),
+            );
+        }
+
+        Ok(result?)
+    }
+
     pub(crate) async fn get(&self, node: Node, timeout: Duration) -> Result<Response> {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         let start_time = instant::Instant::now();
-        let resp = request_builder.send().await?;
+        let resp = Self::send(request_builder, "GET", &node.url).await?;
         log::debug!(
             "GET: {:?} ms for {} {}",
             start_time.elapsed().as_millis(),
@@ -107,20 +152,73 @@ impl HttpClient {
         let mut request_builder = self.client.get(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("accept", "application/vnd.iota.serializer-v1");
-        let resp = request_builder.send().await?;
+        let resp = Self::send(request_builder, "GET", &node.url).await?;
         Self::parse_response(resp, &node.url).await
     }
 
     pub(crate) async fn post_json(&self, node: Node, timeout: Duration, json: Value) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
-        Self::parse_response(request_builder.json(&json).send().await?, &node.url).await
+        Self::parse_response(
+            Self::send(request_builder.json(&json), "POST", &node.url).await?,
+            &node.url,
+        )
+        .await
     }
 
     pub(crate) async fn post_bytes(&self, node: Node, timeout: Duration, body: &[u8]) -> Result<Response> {
         let mut request_builder = self.client.post(node.url.clone());
         request_builder = self.build_request(request_builder, &node, timeout);
         request_builder = request_builder.header("Content-Type", "application/vnd.iota.serializer-v1");
-        Self::parse_response(request_builder.body(body.to_vec()).send().await?, &node.url).await
+        Self::parse_response(
+            Self::send(request_builder.body(body.to_vec()), "POST", &node.url).await?,
+            &node.url,
+        )
+        .await
+    }
+}
+
+// Returns the node and the route of a request URL, with IDs and indexes in the path replaced, so the number of label
+// values stays small.
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+fn node_and_route(url: &url::Url) -> (String, String) {
+    let route = url
+        .path_segments()
+        .map(|segments| {
+            segments
+                .map(|segment| {
+                    if segment.starts_with("0x") || (!segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()))
+                    {
+                        ":id"
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default();
+
+    (url.origin().ascii_serialization(), format!("/{route}"))
+}
+
+#[cfg(all(test, any(feature = "metrics", feature = "tracing")))]
+mod tests {
+    #[test]
+    fn node_and_route() {
+        let url = url::Url::parse(
+            "https://api.testnet.shimmer.network/api/core/v2/outputs/0x0000000000000000000000000000000000000000000000000000000000000000?x=1",
+        )
+        .unwrap();
+        assert_eq!(
+            super::node_and_route(&url),
+            (
+                "https://api.testnet.shimmer.network".to_string(),
+                "/api/core/v2/outputs/:id".to_string()
+            )
+        );
+
+        let url = url::Url::parse("http://localhost:14265/api/core/v2/milestones/by-index/42").unwrap();
+        assert_eq!(super::node_and_route(&url).1, "/api/core/v2/milestones/by-index/:id");
     }
 }
@@ -103,10 +103,7 @@ impl ClientInner {
 
         if let Some(nodes) = network_nodes.get(most_nodes.0) {
             if let Some((info, _node_url)) = nodes.first() {
-                let mut network_info = self.network_info.write().await;
-
-                network_info.latest_milestone_timestamp = info.status.latest_milestone.timestamp;
-                network_info.protocol_parameters = info.protocol.clone();
+                self.network_info.write().await.update_from_node_info(info);
             }
 
             for (info, node_url) in nodes {
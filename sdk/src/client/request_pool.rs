@@ -35,11 +35,19 @@ impl RequestPool {
     }
 
     pub(crate) async fn borrow(&self) -> Requester {
+        #[cfg(feature = "metrics")]
+        let start_time = instant::Instant::now();
         // Get permission to request
         let mut lock = self.write().await;
         lock.recv.recv().await;
         let sender = lock.sender.clone();
         drop(lock);
+        #[cfg(feature = "metrics")]
+        crate::client::metrics::metrics().observe(
+            &crate::client::metrics::REQUEST_POOL_WAIT_SECONDS,
+            &[],
+            start_time.elapsed().as_secs_f64(),
+        );
         Requester { sender }
     }
 
@@ -0,0 +1,234 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streamed clear signing.
+//!
+//! The essence is sent to the device in sections, the essence header, every output, the essence trailer with the
+//! payload and the signing info with the bip32 indices of the inputs and the remainder. Sections are split into chunks
+//! of at most [`DATA_BLOCK_SIZE`] bytes, so the app can show every output with its native tokens and unlock
+//! conditions, also for essences that don't fit into the buffer on the device.
+//!
+//! The released IOTA and Shimmer apps don't implement the stream, so its instruction and the app versions supporting it
+//! are configured with [`LedgerStreamedSigning`].
+
+use iota_ledger_nano::{
+    api::{
+        constants::{APDUInstructions, APDUCLASS, DATA_BLOCK_SIZE},
+        errors::APIError,
+    },
+    get_app_config, APDUCommand, LedgerBIP32Index, LedgerTransport, Packable as LedgerNanoPackable, Transport,
+    TransportTypes,
+};
+use packable::PackableExt;
+
+use super::Error;
+use crate::{
+    client::secret::{
+        types::{LedgerCapabilities, LedgerStreamedSigning},
+        PreparedTransactionData,
+    },
+    types::block::{
+        output::{Output, UnlockCondition},
+        payload::transaction::TransactionEssence,
+    },
+};
+
+/// P2 flag of a chunk that is followed by more chunks of the same section.
+const P2_MORE_CHUNKS: u8 = 0x80;
+
+/// A section of the streamed essence, sent as P1 of its chunks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub(crate) enum StreamSection {
+    /// The essence kind, network id, inputs, inputs commitment and output count.
+    Header = 0,
+    /// One output.
+    Output = 1,
+    /// The optional payload.
+    Trailer = 2,
+    /// The bip32 indices of the inputs and the remainder.
+    SigningInfo = 3,
+}
+
+/// A chunk of the streamed essence, sent with one APDU.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct StreamChunk {
+    pub(crate) section: StreamSection,
+    pub(crate) more: bool,
+    pub(crate) data: Vec<u8>,
+}
+
+/// Checks if the contents of a transaction can be shown by an app with the capabilities, so it can be signed with
+/// streamed clear signing instead of blind signing.
+pub fn supports_streamed_signing(
+    prepared_transaction: &PreparedTransactionData,
+    capabilities: &LedgerCapabilities,
+) -> bool {
+    let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
+
+    capabilities.streamed_signing
+        && essence.outputs().iter().all(|output| {
+            let output_supported = match output {
+                Output::Basic(_) => true,
+                Output::Alias(_) => capabilities.alias_outputs,
+                Output::Nft(_) => capabilities.nft_outputs,
+                // Token schemes and foundry metadata can't be shown
+                Output::Foundry(_) | Output::Treasury(_) => false,
+            };
+            let native_tokens_supported = capabilities.native_tokens
+                || output
+                    .native_tokens()
+                    .into_iter()
+                    .all(|native_tokens| native_tokens.is_empty());
+            let unlock_conditions_supported = capabilities.unlock_conditions
+                || output
+                    .unlock_conditions()
+                    .into_iter()
+                    .flat_map(|unlock_conditions| unlock_conditions.iter())
+                    .all(|unlock_condition| {
+                        !matches!(
+                            unlock_condition,
+                            UnlockCondition::StorageDepositReturn(_)
+                                | UnlockCondition::Timelock(_)
+                                | UnlockCondition::Expiration(_)
+                        )
+                    });
+
+            output_supported && native_tokens_supported && unlock_conditions_supported
+        })
+}
+
+/// Negotiates the capabilities of the opened app, fails if the IOTA or Shimmer app isn't opened.
+pub(crate) fn negotiate_capabilities(
+    transport_type: &TransportTypes,
+    streamed_signing: Option<&LedgerStreamedSigning>,
+) -> Result<LedgerCapabilities, APIError> {
+    let config = get_app_config(transport_type)?;
+
+    Ok(LedgerCapabilities::from_app_version(
+        config.app_version_major,
+        config.app_version_minor,
+        config.app_version_patch,
+        streamed_signing,
+    ))
+}
+
+/// Splits the essence and the signing info into the chunks of the stream.
+pub(crate) fn essence_stream(
+    prepared_transaction: &PreparedTransactionData,
+    input_bip32_indices: &[LedgerBIP32Index],
+    remainder: Option<(u16, LedgerBIP32Index)>,
+) -> Result<Vec<StreamChunk>, Error> {
+    let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
+
+    let essence_bytes = prepared_transaction.essence.pack_to_vec();
+    let outputs = essence
+        .outputs()
+        .iter()
+        .map(PackableExt::pack_to_vec)
+        .collect::<Vec<_>>();
+    // The payload is prefixed with its length, or is only a zero length if there is none
+    let trailer_len = core::mem::size_of::<u32>() + essence.payload().map_or(0, PackableExt::packed_len);
+    let header_len = essence_bytes.len() - outputs.iter().map(Vec::len).sum::<usize>() - trailer_len;
+
+    let mut signing_info = Vec::new();
+    let mut pack_signing_info = || -> Result<(), iota_ledger_nano::PackableError> {
+        (input_bip32_indices.len() as u16).pack(&mut signing_info)?;
+        for bip32_index in input_bip32_indices {
+            bip32_index.pack(&mut signing_info)?;
+        }
+        let (remainder_index, remainder_bip32) = remainder.unwrap_or_default();
+        u8::from(remainder.is_some()).pack(&mut signing_info)?;
+        remainder_index.pack(&mut signing_info)?;
+        remainder_bip32.pack(&mut signing_info)
+    };
+    pack_signing_info().map_err(|_| Error::MiscError)?;
+
+    let mut chunks = Vec::new();
+    let mut push_section = |section, data: &[u8]| {
+        let count = data.chunks(DATA_BLOCK_SIZE).count();
+        chunks.extend(
+            data.chunks(DATA_BLOCK_SIZE)
+                .enumerate()
+                .map(|(index, chunk)| StreamChunk {
+                    section,
+                    more: index + 1 < count,
+                    data: chunk.to_vec(),
+                }),
+        );
+    };
+
+    push_section(StreamSection::Header, &essence_bytes[..header_len]);
+    for output in &outputs {
+        push_section(StreamSection::Output, output);
+    }
+    push_section(
+        StreamSection::Trailer,
+        &essence_bytes[essence_bytes.len() - trailer_len..],
+    );
+    push_section(StreamSection::SigningInfo, &signing_info);
+
+    Ok(chunks)
+}
+
+/// Streams the essence to the device, waits for the user to confirm it and returns the signatures of the inputs.
+///
+/// The account must have been set on the device before, the stream replaces the data buffer of the app.
+pub(crate) fn sign_streamed(
+    transport_type: &TransportTypes,
+    streamed_signing: &LedgerStreamedSigning,
+    chunks: Vec<StreamChunk>,
+    input_len: usize,
+) -> Result<Vec<u8>, APIError> {
+    let transport = iota_ledger_nano::transport::create_transport(transport_type, None)?;
+
+    log::debug!("[LEDGER] streaming {} chunks", chunks.len());
+    for chunk in chunks {
+        exchange(
+            &transport,
+            streamed_signing.instruction,
+            chunk.section as u8,
+            if chunk.more { P2_MORE_CHUNKS } else { 0 },
+            chunk.data,
+        )?;
+    }
+
+    // show essence to user
+    // if denied by user, it returns with `ConditionsOfUseNotSatisfied`
+    log::debug!("[LEDGER] await user confirmation");
+    exchange(&transport, APDUInstructions::UserConfirm as u8, 0, 0, Vec::new())?;
+
+    let mut signatures = Vec::new();
+    for signature_index in 0..input_len as u8 {
+        signatures.extend(exchange(
+            &transport,
+            APDUInstructions::SignSingle as u8,
+            signature_index,
+            0,
+            Vec::new(),
+        )?);
+    }
+
+    Ok(signatures)
+}
+
+// Sends a command of the app and returns the data of the answer
+fn exchange(transport: &Transport, ins: u8, p1: u8, p2: u8, data: Vec<u8>) -> Result<Vec<u8>, APIError> {
+    let command = APDUCommand {
+        cla: APDUCLASS,
+        ins,
+        p1,
+        p2,
+        data,
+    };
+
+    let answer = match &transport.transport {
+        LedgerTransport::TCP(tcp) => tcp.exchange(&command).map_err(|_| APIError::TransportError)?,
+        LedgerTransport::NativeHID(hid) => hid.exchange(&command).map_err(|_| APIError::TransportError)?,
+    };
+
+    match answer.retcode() {
+        0x9000 => Ok(answer.data().to_vec()),
+        retcode => Err(APIError::get_error(retcode)),
+    }
+}
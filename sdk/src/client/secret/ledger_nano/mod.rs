@@ -5,6 +5,8 @@
 //!
 //! Ledger status codes: <https://github.com/iotaledger/ledger-iota-app/blob/53c1f96d15f8b014ba8ba31a85f0401bb4d33e18/src/iota_io.h#L54>.
 
+mod clear_signing;
+
 use std::{collections::HashMap, ops::Range};
 
 use async_trait::async_trait;
@@ -19,11 +21,12 @@ use iota_ledger_nano::{
 use packable::{error::UnexpectedEOF, unpacker::SliceUnpacker, Packable, PackableExt};
 use tokio::sync::Mutex;
 
+pub use self::clear_signing::supports_streamed_signing;
 use super::{GenerateAddressOptions, SecretManage, SecretManagerConfig};
 use crate::{
     client::secret::{
         is_alias_transition,
-        types::{LedgerApp, LedgerDeviceType},
+        types::{LedgerApp, LedgerCapabilities, LedgerDeviceType, LedgerStreamedSigning},
         LedgerNanoStatus, PreparedTransactionData,
     },
     types::block::{
@@ -110,8 +113,10 @@ pub struct LedgerSecretManager {
     pub is_simulator: bool,
     /// Specifies whether the wallet should be in non-interactive mode.
     pub non_interactive: bool,
-    /// Mutex to prevent multiple simultaneous requests to a ledger.
-    mutex: Mutex<()>,
+    /// The streamed signing flow of the app, streamed signing isn't used without it.
+    pub streamed_signing: Option<LedgerStreamedSigning>,
+    /// Mutex to prevent multiple simultaneous requests to a ledger, holds the capabilities negotiated with the app.
+    mutex: Mutex<Option<LedgerCapabilities>>,
 }
 
 impl TryFrom<u8> for LedgerDeviceType {
@@ -269,108 +274,33 @@ impl SecretManage for LedgerSecretManager {
 
         let bip32_account = account_index.harden().into();
 
-        // pack essence and hash into vec
-        let essence_bytes = prepared_transaction.essence.pack_to_vec();
-        let essence_hash = prepared_transaction.essence.hash().to_vec();
-
         // lock the mutex to prevent multiple simultaneous requests to a ledger
-        let lock = self.mutex.lock().await;
-
-        let ledger = get_ledger(coin_type, bip32_account, self.is_simulator).map_err(Error::from)?;
-        if ledger.is_debug_app() {
-            ledger
-                .set_non_interactive_mode(self.non_interactive)
-                .map_err(Error::from)?;
-        }
-        let blind_signing = needs_blind_signing(prepared_transaction, ledger.get_buffer_size());
+        let mut lock = self.mutex.lock().await;
 
-        // if essence + bip32 input indices are larger than the buffer size or the essence contains
-        // features / types that are not supported blind signing will be needed
-        if blind_signing {
-            // prepare signing
-            log::debug!("[LEDGER] prepare_blind_signing");
-            log::debug!("[LEDGER] {:?} {:?}", input_bip32_indices, essence_hash);
-            ledger
-                .prepare_blind_signing(input_bip32_indices, essence_hash)
-                .map_err(Error::from)?;
-        } else {
-            // figure out the remainder output and bip32 index (if there is one)
-            #[allow(clippy::option_if_let_else)]
-            let (remainder_output, remainder_bip32) = match &prepared_transaction.remainder {
-                Some(remainder) => {
-                    if let Some(chain) = remainder.chain {
-                        (
-                            Some(&remainder.output),
-                            LedgerBIP32Index {
-                                bip32_change: chain.change.harden().into(),
-                                bip32_index: chain.address_index.harden().into(),
-                            },
-                        )
-                    } else {
-                        (None, LedgerBIP32Index::default())
-                    }
-                }
-                None => (None, LedgerBIP32Index::default()),
-            };
-
-            let mut remainder_index = 0u16;
-            if let Some(remainder_output) = remainder_output {
-                match &prepared_transaction.essence {
-                    TransactionEssence::Regular(essence) => {
-                        // Find the index of the remainder in the transaction because it is not always the last output.
-                        // The index within the transaction and the bip32 index will be validated by the hardware
-                        // wallet.
-                        for output in essence.outputs().iter() {
-                            if !output.is_basic() {
-                                log::debug!("[LEDGER] unsupported output");
-                                return Err(Error::MiscError.into());
-                            }
-
-                            if remainder_output == output {
-                                break;
-                            }
-
-                            remainder_index += 1;
-                        }
-
-                        // Was index found?
-                        if remainder_index as usize == essence.outputs().len() {
-                            log::debug!("[LEDGER] remainder_index not found");
-                            return Err(Error::MiscError.into());
-                        }
-                    }
-                }
+        // negotiate the capabilities once per connection, before the ledger keeps the transport until it's dropped
+        let capabilities = match *lock {
+            Some(capabilities) => capabilities,
+            None => {
+                let capabilities =
+                    clear_signing::negotiate_capabilities(&self.transport_type(), self.streamed_signing.as_ref())
+                        .map_err(Error::from)?;
+                *lock = Some(capabilities);
+                capabilities
             }
+        };
 
-            // prepare signing
-            log::debug!("[LEDGER] prepare signing");
-            log::debug!(
-                "[LEDGER] {:?} {:02x?} {} {} {:?}",
-                input_bip32_indices,
-                essence_bytes,
-                remainder_output.is_some(),
-                remainder_index,
-                remainder_bip32
-            );
-            ledger
-                .prepare_signing(
-                    input_bip32_indices,
-                    essence_bytes,
-                    remainder_output.is_some(),
-                    remainder_index,
-                    remainder_bip32,
-                )
-                .map_err(Error::from)?;
+        let signed = self.sign_with_ledger(
+            prepared_transaction,
+            input_bip32_indices,
+            coin_type,
+            bip32_account,
+            &capabilities,
+        );
+        if signed.is_err() {
+            // the device may have been disconnected or another app opened, so negotiate the capabilities again
+            *lock = None;
         }
-
-        // show essence to user
-        // if denied by user, it returns with `DeniedByUser` Error
-        log::debug!("[LEDGER] await user confirmation");
-        ledger.user_confirm().map_err(Error::from)?;
-
-        // sign
-        let signature_bytes = ledger.sign(input_len as u16).map_err(Error::from)?;
-        drop(ledger);
+        let (signing_mode, signature_bytes) = signed?;
         drop(lock);
         let mut unpacker = SliceUnpacker::new(&signature_bytes);
 
@@ -390,9 +320,9 @@ impl SecretManage for LedgerSecretManager {
             }
         }
 
-        // With blind signing the ledger only returns SignatureUnlocks, so we might have to merge them with
-        // Alias/Nft/Reference unlocks
-        if blind_signing {
+        // With blind and streamed signing the ledger only returns SignatureUnlocks, so we might have to merge them
+        // with Alias/Nft/Reference unlocks
+        if signing_mode != SigningMode::Clear {
             unlocks = merge_unlocks(prepared_transaction, unlocks.into_iter(), time)?;
         }
 
@@ -419,10 +349,23 @@ impl SecretManagerConfig for LedgerSecretManager {
     }
 }
 
+// How the essence is presented to the user before signing
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SigningMode {
+    // The essence is uploaded to the buffer of the device and shown
+    Clear,
+    // The essence is streamed to the device in chunks and shown
+    Streamed,
+    // Only the essence hash is shown
+    Blind,
+}
+
 /// the Ledger Nano S(+)/X app can present the user a detailed view of the transaction before it
 /// is signed but only with BasicOutputs, without extra-features and if the Essence is not too large.
 /// If criteria are not met, blind signing is needed.
 /// This method finds out if we have to switch to blind signing mode.
+///
+/// Apps that support streamed signing can also show other transactions, see [`supports_streamed_signing`].
 pub fn needs_blind_signing(prepared_transaction: &PreparedTransactionData, buffer_size: usize) -> bool {
     let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
 
@@ -440,6 +383,36 @@ pub fn needs_blind_signing(prepared_transaction: &PreparedTransactionData, buffe
     total_size > buffer_size
 }
 
+// Find the index of the remainder in the transaction and its bip32 index, the hardware wallet validates both
+fn find_remainder(prepared_transaction: &PreparedTransactionData) -> Result<Option<(u16, LedgerBIP32Index)>, Error> {
+    let Some((remainder_output, chain)) = prepared_transaction
+        .remainder
+        .as_ref()
+        .and_then(|remainder| Some((&remainder.output, remainder.chain?)))
+    else {
+        return Ok(None);
+    };
+
+    let TransactionEssence::Regular(essence) = &prepared_transaction.essence;
+    // The remainder is not always the last output
+    let remainder_index = essence
+        .outputs()
+        .iter()
+        .position(|output| output == remainder_output)
+        .ok_or_else(|| {
+            log::debug!("[LEDGER] remainder_index not found");
+            Error::MiscError
+        })?;
+
+    Ok(Some((
+        remainder_index as u16,
+        LedgerBIP32Index {
+            bip32_change: chain.change.harden().into(),
+            bip32_index: chain.address_index.harden().into(),
+        },
+    )))
+}
+
 impl LedgerSecretManager {
     /// Creates a [`LedgerSecretManager`].
     ///
@@ -448,20 +421,101 @@ impl LedgerSecretManager {
         Self {
             is_simulator,
             non_interactive: false,
-            mutex: Mutex::new(()),
+            streamed_signing: None,
+            mutex: Mutex::new(None),
         }
     }
 
+    // Signs the essence with the opened app, using the mode the app and the transaction support
+    fn sign_with_ledger(
+        &self,
+        prepared_transaction: &PreparedTransactionData,
+        input_bip32_indices: Vec<LedgerBIP32Index>,
+        coin_type: u32,
+        bip32_account: u32,
+        capabilities: &LedgerCapabilities,
+    ) -> Result<(SigningMode, Vec<u8>), Error> {
+        let input_len = prepared_transaction.inputs_data.len();
+
+        // pack essence and hash into vec
+        let essence_bytes = prepared_transaction.essence.pack_to_vec();
+        let essence_hash = prepared_transaction.essence.hash().to_vec();
+
+        let ledger = get_ledger(coin_type, bip32_account, self.is_simulator)?;
+        if ledger.is_debug_app() {
+            ledger.set_non_interactive_mode(self.non_interactive)?;
+        }
+
+        // if essence + bip32 input indices are larger than the buffer size or the essence contains
+        // features / types that are not supported, the essence is streamed if the app can show it,
+        // otherwise blind signing will be needed
+        let signing_mode = if !needs_blind_signing(prepared_transaction, ledger.get_buffer_size()) {
+            SigningMode::Clear
+        } else if self.streamed_signing.is_some() && supports_streamed_signing(prepared_transaction, capabilities) {
+            SigningMode::Streamed
+        } else {
+            SigningMode::Blind
+        };
+        log::debug!("[LEDGER] signing mode {signing_mode:?}");
+
+        let signature_bytes =
+            if let (SigningMode::Streamed, Some(streamed_signing)) = (signing_mode, &self.streamed_signing) {
+                let remainder = find_remainder(prepared_transaction)?;
+                let chunks = clear_signing::essence_stream(prepared_transaction, &input_bip32_indices, remainder)?;
+
+                // the account is set already, release the transport of the ledger to stream the essence
+                drop(ledger);
+                clear_signing::sign_streamed(&self.transport_type(), streamed_signing, chunks, input_len)?
+            } else {
+                if signing_mode == SigningMode::Blind {
+                    // prepare signing
+                    log::debug!("[LEDGER] prepare_blind_signing");
+                    log::debug!("[LEDGER] {:?} {:?}", input_bip32_indices, essence_hash);
+                    ledger.prepare_blind_signing(input_bip32_indices, essence_hash)?;
+                } else {
+                    // figure out the remainder output and bip32 index (if there is one)
+                    let remainder = find_remainder(prepared_transaction)?;
+                    let (remainder_index, remainder_bip32) = remainder.unwrap_or_default();
+
+                    // prepare signing
+                    log::debug!("[LEDGER] prepare signing");
+                    log::debug!(
+                        "[LEDGER] {:?} {:02x?} {} {} {:?}",
+                        input_bip32_indices,
+                        essence_bytes,
+                        remainder.is_some(),
+                        remainder_index,
+                        remainder_bip32
+                    );
+                    ledger.prepare_signing(
+                        input_bip32_indices,
+                        essence_bytes,
+                        remainder.is_some(),
+                        remainder_index,
+                        remainder_bip32,
+                    )?;
+                }
+
+                // show essence to user
+                // if denied by user, it returns with `DeniedByUser` Error
+                log::debug!("[LEDGER] await user confirmation");
+                ledger.user_confirm()?;
+
+                // sign
+                let signature_bytes = ledger.sign(input_len as u16)?;
+                drop(ledger);
+                signature_bytes
+            };
+
+        Ok((signing_mode, signature_bytes))
+    }
+
     /// Get Ledger hardware status.
     pub async fn get_ledger_nano_status(&self) -> LedgerNanoStatus {
         log::debug!("get_ledger_nano_status");
         // lock the mutex
-        let _lock = self.mutex.lock().await;
-        let transport_type = if self.is_simulator {
-            TransportTypes::TCP
-        } else {
-            TransportTypes::NativeHID
-        };
+        let mut lock = self.mutex.lock().await;
+        let transport_type = self.transport_type();
 
         log::debug!("get_opened_app");
         let app = match get_opened_app(&transport_type) {
@@ -473,8 +527,8 @@ impl LedgerSecretManager {
         // if IOTA or Shimmer app is opened, the call will always succeed, returning information like
         // device, debug-flag, version number, lock-state but here we only are interested in a
         // successful call and the locked-flag
-        let (connected_, locked, blind_signing_enabled, device) =
-            get_app_config(&transport_type).map_or((false, None, false, None), |config| {
+        let (connected_, locked, blind_signing_enabled, device, capabilities) =
+            get_app_config(&transport_type).map_or((false, None, false, None, None), |config| {
                 (
                     true,
                     // locked flag
@@ -482,6 +536,12 @@ impl LedgerSecretManager {
                     // blind signing enabled flag
                     config.flags & (1 << 1) != 0,
                     LedgerDeviceType::try_from(config.device).ok(),
+                    Some(LedgerCapabilities::from_app_version(
+                        config.app_version_major,
+                        config.app_version_minor,
+                        config.app_version_patch,
+                        self.streamed_signing.as_ref(),
+                    )),
                 )
             });
 
@@ -489,6 +549,9 @@ impl LedgerSecretManager {
         // get buffer size of connected device
         let buffer_size = get_buffer_size(&transport_type).ok();
 
+        // refresh the capabilities that are used for signing, the opened app may have changed
+        *lock = capabilities;
+
         // We get the app info also if not the iota app is open, but another one
         // connected_ is in this case false, even tough the ledger is connected, that's why we always return true if we
         // got the app
@@ -500,6 +563,15 @@ impl LedgerSecretManager {
             app,
             device,
             buffer_size,
+            capabilities,
+        }
+    }
+
+    fn transport_type(&self) -> TransportTypes {
+        if self.is_simulator {
+            TransportTypes::TCP
+        } else {
+            TransportTypes::NativeHID
         }
     }
 }
@@ -585,14 +657,67 @@ fn merge_unlocks(
 
 #[cfg(test)]
 mod tests {
+    use iota_ledger_nano::api::constants::DATA_BLOCK_SIZE;
     use pretty_assertions::assert_eq;
 
-    use super::*;
+    use super::{
+        clear_signing::{essence_stream, StreamSection},
+        *,
+    };
     use crate::{
         client::{api::GetAddressesOptions, constants::IOTA_COIN_TYPE, secret::SecretManager},
-        types::block::address::ToBech32Ext,
+        types::block::{
+            address::ToBech32Ext,
+            input::{Input, UtxoInput},
+            output::{unlock_condition::AddressUnlockCondition, BasicOutput, InputsCommitment, NftId, NftOutput},
+            payload::{
+                transaction::{RegularTransactionEssence, TransactionId},
+                TaggedDataPayload,
+            },
+            protocol::ProtocolParameters,
+        },
+    };
+
+    const STREAMED_SIGNING: LedgerStreamedSigning = LedgerStreamedSigning {
+        instruction: 0xf0,
+        min_app_version: (1, 1, 0),
     };
 
+    fn prepared_transaction(outputs: Vec<Output>, payload: Option<TaggedDataPayload>) -> PreparedTransactionData {
+        let protocol_parameters = ProtocolParameters::default();
+        let mut essence =
+            RegularTransactionEssence::builder(protocol_parameters.network_id(), InputsCommitment::from([0; 32]))
+                .with_inputs([Input::Utxo(UtxoInput::new(TransactionId::null(), 0).unwrap())])
+                .with_outputs(outputs);
+        if let Some(payload) = payload {
+            essence = essence.with_payload(payload);
+        }
+
+        PreparedTransactionData {
+            essence: TransactionEssence::Regular(essence.finish_with_params(protocol_parameters).unwrap()),
+            inputs_data: Vec::new(),
+            remainder: None,
+        }
+    }
+
+    fn address() -> Address {
+        Address::Ed25519(Ed25519Address::new([1; 32]))
+    }
+
+    fn nft_output() -> Output {
+        NftOutput::build_with_amount(1_000_000, NftId::null())
+            .add_unlock_condition(AddressUnlockCondition::new(address()))
+            .finish_output(ProtocolParameters::default().token_supply())
+            .unwrap()
+    }
+
+    fn basic_output() -> Output {
+        BasicOutput::build_with_amount(1_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(address()))
+            .finish_output(ProtocolParameters::default().token_supply())
+            .unwrap()
+    }
+
     #[tokio::test]
     #[ignore = "requires ledger nano instance"]
     async fn ed25519_address() {
@@ -614,4 +739,115 @@ mod tests {
             "atoi1qqdnv60ryxynaeyu8paq3lp9rkll7d7d92vpumz88fdj4l0pn5mru50gvd8"
         );
     }
+
+    #[test]
+    fn capabilities_from_app_version() {
+        assert_eq!(
+            LedgerCapabilities::from_app_version(1, 0, 9, Some(&STREAMED_SIGNING)),
+            LedgerCapabilities::default()
+        );
+
+        let capabilities = LedgerCapabilities::from_app_version(1, 1, 0, Some(&STREAMED_SIGNING));
+        assert!(capabilities.streamed_signing());
+        assert!(capabilities.native_tokens());
+        assert!(capabilities.nft_outputs());
+        assert!(capabilities.alias_outputs());
+        assert!(capabilities.unlock_conditions());
+        assert_eq!(
+            LedgerCapabilities::from_app_version(2, 0, 0, Some(&STREAMED_SIGNING)),
+            capabilities
+        );
+
+        // Without a streamed signing flow the app can't show more than with clear signing
+        assert_eq!(
+            LedgerCapabilities::from_app_version(2, 0, 0, None),
+            LedgerCapabilities::default()
+        );
+    }
+
+    #[test]
+    fn signing_mode_of_nft_transaction() {
+        let prepared_transaction = prepared_transaction(vec![nft_output()], None);
+
+        assert!(needs_blind_signing(&prepared_transaction, usize::MAX));
+        assert!(!supports_streamed_signing(
+            &prepared_transaction,
+            &LedgerCapabilities::from_app_version(1, 0, 9, Some(&STREAMED_SIGNING))
+        ));
+        assert!(supports_streamed_signing(
+            &prepared_transaction,
+            &LedgerCapabilities::from_app_version(1, 1, 0, Some(&STREAMED_SIGNING))
+        ));
+
+        let capabilities = LedgerCapabilities {
+            nft_outputs: false,
+            ..LedgerCapabilities::from_app_version(1, 1, 0, Some(&STREAMED_SIGNING))
+        };
+        assert!(!supports_streamed_signing(&prepared_transaction, &capabilities));
+    }
+
+    #[test]
+    fn essence_stream_chunks() {
+        let payload = TaggedDataPayload::new(b"tag".to_vec(), vec![42; 600]).unwrap();
+        let prepared_transaction = prepared_transaction(vec![basic_output(), nft_output()], Some(payload));
+        let bip32_index = LedgerBIP32Index {
+            bip32_change: 0u32.harden().into(),
+            bip32_index: 1u32.harden().into(),
+        };
+
+        let chunks = essence_stream(&prepared_transaction, &[bip32_index], Some((1, bip32_index))).unwrap();
+
+        let sections = chunks
+            .iter()
+            .map(|chunk| (chunk.section, chunk.more))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sections,
+            [
+                (StreamSection::Header, false),
+                (StreamSection::Output, false),
+                (StreamSection::Output, false),
+                (StreamSection::Trailer, true),
+                (StreamSection::Trailer, true),
+                (StreamSection::Trailer, false),
+                (StreamSection::SigningInfo, false),
+            ]
+        );
+        assert!(chunks.iter().all(|chunk| chunk.data.len() <= DATA_BLOCK_SIZE));
+
+        // The essence sections add up to the packed essence
+        let essence_bytes = chunks
+            .iter()
+            .filter(|chunk| chunk.section != StreamSection::SigningInfo)
+            .flat_map(|chunk| chunk.data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(essence_bytes, prepared_transaction.essence.pack_to_vec());
+        assert_eq!(
+            chunks[1].data,
+            prepared_transaction.essence.as_regular().outputs()[0].pack_to_vec()
+        );
+
+        // Input count, input bip32 index, remainder flag, remainder index and remainder bip32 index
+        assert_eq!(chunks[6].data.len(), 2 + 8 + 1 + 2 + 8);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires ledger nano instance"]
+    async fn app_capabilities() {
+        let status = LedgerSecretManager::new(true).get_ledger_nano_status().await;
+
+        let version = status
+            .app()
+            .unwrap()
+            .version()
+            .split('.')
+            .map(|part| part.parse::<u8>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            status.capabilities(),
+            Some(LedgerCapabilities::from_app_version(
+                version[0], version[1], version[2], None
+            ))
+        );
+    }
 }
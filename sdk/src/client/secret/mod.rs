@@ -39,7 +39,7 @@ use self::mnemonic::MnemonicSecretManager;
 use self::private_key::PrivateKeySecretManager;
 #[cfg(feature = "stronghold")]
 use self::stronghold::StrongholdSecretManager;
-pub use self::types::{GenerateAddressOptions, LedgerNanoStatus};
+pub use self::types::{GenerateAddressOptions, LedgerCapabilities, LedgerNanoStatus, LedgerStreamedSigning};
 #[cfg(feature = "stronghold")]
 use crate::client::secret::types::StrongholdDto;
 use crate::{
@@ -121,6 +121,8 @@ pub struct LedgerNanoStatus {
     pub(crate) device: Option<LedgerDeviceType>,
     /// Buffer size on device
     pub(crate) buffer_size: Option<usize>,
+    /// Transaction contents the opened app can show for clear signing
+    pub(crate) capabilities: Option<LedgerCapabilities>,
 }
 
 impl LedgerNanoStatus {
@@ -148,6 +150,78 @@ impl LedgerNanoStatus {
     pub fn buffer_size(&self) -> Option<usize> {
         self.buffer_size
     }
+    /// Transaction contents the opened app can show for clear signing
+    pub fn capabilities(&self) -> Option<LedgerCapabilities> {
+        self.capabilities
+    }
+}
+
+/// The streamed clear signing flow of a Ledger app.
+///
+/// The released IOTA and Shimmer apps don't implement streamed signing, so it's only negotiated for apps that are
+/// configured with it, e.g. development builds of the app.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerStreamedSigning {
+    /// The instruction of the APDUs that send the chunks of the essence.
+    pub instruction: u8,
+    /// The first app version that implements the instruction.
+    pub min_app_version: (u8, u8, u8),
+}
+
+/// The transaction contents a Ledger app can show to the user for clear signing.
+///
+/// Transactions with contents the app can't show need blind signing, so the user has to approve the essence hash.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerCapabilities {
+    /// The essence is streamed to the device in chunks, so it can be larger than the buffer on the device.
+    pub(crate) streamed_signing: bool,
+    /// Native tokens of outputs are shown.
+    pub(crate) native_tokens: bool,
+    /// NFT outputs are shown.
+    pub(crate) nft_outputs: bool,
+    /// Alias outputs are shown.
+    pub(crate) alias_outputs: bool,
+    /// Unlock conditions other than the address unlock condition are shown.
+    pub(crate) unlock_conditions: bool,
+}
+
+impl LedgerCapabilities {
+    /// Negotiates the capabilities of an IOTA or Shimmer app with the given version, the app can only show the contents
+    /// of transactions that are streamed to it.
+    pub fn from_app_version(major: u8, minor: u8, patch: u8, streamed_signing: Option<&LedgerStreamedSigning>) -> Self {
+        let streamed_signing =
+            streamed_signing.is_some_and(|streamed_signing| (major, minor, patch) >= streamed_signing.min_app_version);
+
+        Self {
+            streamed_signing,
+            native_tokens: streamed_signing,
+            nft_outputs: streamed_signing,
+            alias_outputs: streamed_signing,
+            unlock_conditions: streamed_signing,
+        }
+    }
+    /// The essence is streamed to the device in chunks, so it can be larger than the buffer on the device.
+    pub fn streamed_signing(&self) -> bool {
+        self.streamed_signing
+    }
+    /// Native tokens of outputs are shown.
+    pub fn native_tokens(&self) -> bool {
+        self.native_tokens
+    }
+    /// NFT outputs are shown.
+    pub fn nft_outputs(&self) -> bool {
+        self.nft_outputs
+    }
+    /// Alias outputs are shown.
+    pub fn alias_outputs(&self) -> bool {
+        self.alias_outputs
+    }
+    /// Unlock conditions other than the address unlock condition are shown.
+    pub fn unlock_conditions(&self) -> bool {
+        self.unlock_conditions
+    }
 }
 
 /// Data for transaction inputs for signing and ordering of unlock blocks
@@ -0,0 +1,106 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracing spans of client and wallet operations.
+//!
+//! With the `tracing` feature, the SDK records these spans:
+//! - `account_sync`, with the account index, the number of synced addresses and of unspent outputs;
+//! - `send_transaction`, covering the preparation, signing and submission of a transaction, with the account index and
+//!   the transaction id once the transaction is signed;
+//! - `prepare_transaction`, with the account index and the number of outputs and selected inputs;
+//! - `sign_transaction`, with the account index and the number of inputs;
+//! - `submit_transaction`, with the transaction id and the block id;
+//! - `pow`, with the block id;
+//! - `node_request`, with the node, the route, the method and the status code.
+//!
+//! Syncing emits a `transaction inclusion state changed` event with the transaction id, so a transaction can be
+//! followed to its inclusion. With the `opentelemetry` feature, the spans can be exported with an OpenTelemetry tracer.
+
+#[cfg(feature = "opentelemetry")]
+pub use tracing_opentelemetry::OpenTelemetryLayer;
+#[cfg(feature = "opentelemetry")]
+use {
+    opentelemetry::trace::Tracer,
+    tracing::Subscriber,
+    tracing_opentelemetry::PreSampledTracer,
+    tracing_subscriber::{layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt},
+};
+
+/// Returns a layer exporting the spans with the OpenTelemetry tracer, to add it to an existing subscriber.
+#[cfg(feature = "opentelemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub fn opentelemetry_layer<S, T>(tracer: T) -> OpenTelemetryLayer<S, T>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    T: Tracer + PreSampledTracer + 'static,
+{
+    tracing_opentelemetry::layer().with_tracer(tracer)
+}
+
+/// Sets a global subscriber exporting the spans with the OpenTelemetry tracer, e.g. the tracer of an OTLP pipeline.
+#[cfg(feature = "opentelemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub fn init_opentelemetry<T>(tracer: T) -> Result<(), tracing_subscriber::util::TryInitError>
+where
+    T: Tracer + PreSampledTracer + Send + Sync + 'static,
+{
+    tracing_subscriber::registry()
+        .with(opentelemetry_layer(tracer))
+        .try_init()
+}
+
+#[cfg(all(test, feature = "opentelemetry"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+    };
+    use tracing_subscriber::{layer::Context, Layer};
+
+    use super::*;
+    use crate::client::Client;
+
+    // Collects the names of the new spans and the recorded status codes
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Collector {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "status" {
+                self.0.lock().unwrap().push(format!("status={value:?}"));
+            }
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "status" {
+                self.0.lock().unwrap().push(format!("status={value}"));
+            }
+        }
+    }
+
+    impl<S: Subscriber + for<'span> LookupSpan<'span>> Layer<S> for Collector {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            self.0.lock().unwrap().push(attrs.metadata().name().to_string());
+        }
+
+        fn on_record(&self, _id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+            values.record(&mut self.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn node_request_span() {
+        let collector = Collector::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(collector.clone())
+            .with(opentelemetry_layer(opentelemetry::trace::noop::NoopTracer::new()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // Nothing listens on the port, so the request fails
+        Client::get_node_info("http://127.0.0.1:1", None).await.unwrap_err();
+
+        assert_eq!(*collector.0.lock().unwrap(), ["node_request", "status=error"]);
+    }
+}
@@ -194,3 +194,9 @@ impl Password {
         self.0.as_bytes()
     }
 }
+
+impl core::fmt::Debug for Password {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("<omitted>")
+    }
+}
@@ -7,9 +7,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::types::block::{
     output::{dto::OutputDto, OutputId, OutputMetadata, OutputWithMetadata},
-    payload::milestone::{option::dto::ReceiptMilestoneOptionDto, MilestoneId},
+    payload::milestone::{option::dto::ReceiptMilestoneOptionDto, MilestoneId, ParametersMilestoneOption},
     protocol::ProtocolParameters,
-    BlockId,
+    BlockId, Error as BlockError,
 };
 
 /// Response of GET /api/core/v2/info.
@@ -81,6 +81,18 @@ pub struct PendingProtocolParameter {
     pub params: String,
 }
 
+impl TryFrom<&PendingProtocolParameter> for ParametersMilestoneOption {
+    type Error = BlockError;
+
+    fn try_from(value: &PendingProtocolParameter) -> Result<Self, Self::Error> {
+        Self::new(
+            value.target_milestone_index.into(),
+            value.protocol_version,
+            prefix_hex::decode::<Vec<u8>>(&value.params).map_err(BlockError::Hex)?,
+        )
+    }
+}
+
 /// Returned in [`InfoResponse`].
 /// Information about the base token.
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,335 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use packable::{
+    error::UnpackError,
+    unpacker::{CounterUnpacker, SliceUnpacker},
+    Packable, PackableExt,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::types::{
+    block::{
+        output::{dto::OutputDto, Output},
+        payload::{
+            dto::PayloadDto,
+            transaction::{dto::TransactionEssenceDto, TransactionEssence},
+            Payload,
+        },
+        protocol::ProtocolParameters,
+        unlock::{dto::UnlockDto, Unlock},
+        Block, BlockDto, Error as BlockError,
+    },
+    TryFromDto,
+};
+
+/// The kinds of objects that can be converted between their packed bytes and JSON.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PackableKind {
+    /// A [`Block`].
+    Block,
+    /// An [`Output`].
+    Output,
+    /// A [`Payload`].
+    Payload,
+    /// A [`TransactionEssence`].
+    Essence,
+    /// An [`Unlock`].
+    Unlock,
+}
+
+impl PackableKind {
+    /// All kinds, in the order they are tried when the kind is auto-detected.
+    pub const ALL: [Self; 5] = [Self::Block, Self::Payload, Self::Output, Self::Essence, Self::Unlock];
+}
+
+impl FromStr for PackableKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "block" => Self::Block,
+            "output" => Self::Output,
+            "payload" => Self::Payload,
+            "essence" => Self::Essence,
+            "unlock" => Self::Unlock,
+            _ => return Err(Error::UnknownKind(s.to_string())),
+        })
+    }
+}
+
+impl fmt::Display for PackableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Block => "block",
+            Self::Output => "output",
+            Self::Payload => "payload",
+            Self::Essence => "essence",
+            Self::Unlock => "unlock",
+        })
+    }
+}
+
+/// Errors that can occur when converting between packed bytes and JSON.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The hex string could not be decoded to bytes.
+    Hex(String),
+    /// The JSON is not a valid representation of the kind, or of any kind if it was auto-detected.
+    Json {
+        kind: Option<PackableKind>,
+        message: String,
+    },
+    /// Unpacking failed after `offset` bytes were read.
+    Unpack {
+        kind: PackableKind,
+        offset: usize,
+        message: String,
+    },
+    /// The object was unpacked from the first `offset` bytes but `remaining` bytes were left.
+    TrailingBytes {
+        kind: PackableKind,
+        offset: usize,
+        remaining: usize,
+    },
+    /// The kind name is not known.
+    UnknownKind(String),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hex(message) => write!(f, "invalid hex: {message}"),
+            Self::Json {
+                kind: Some(kind),
+                message,
+            } => write!(f, "invalid {kind} JSON: {message}"),
+            Self::Json { kind: None, message } => write!(f, "invalid JSON: {message}"),
+            Self::Unpack { kind, offset, message } => {
+                write!(f, "failed to unpack {kind} at byte offset {offset}: {message}")
+            }
+            Self::TrailingBytes {
+                kind,
+                offset,
+                remaining,
+            } => write!(
+                f,
+                "{remaining} trailing bytes after the {kind} ending at byte offset {offset}"
+            ),
+            Self::UnknownKind(kind) => write!(
+                f,
+                "unknown kind \"{kind}\", expected one of block, output, payload, essence or unlock"
+            ),
+        }
+    }
+}
+
+impl Error {
+    // The offset an unpack error was detected at, used to report the most promising auto-detection attempt.
+    fn offset(&self) -> usize {
+        match self {
+            Self::Unpack { offset, .. } | Self::TrailingBytes { offset, .. } => *offset,
+            _ => 0,
+        }
+    }
+}
+
+/// An object that can be converted between its packed bytes and JSON.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Packed {
+    /// A [`Block`].
+    Block(Block),
+    /// An [`Output`].
+    Output(Output),
+    /// A [`Payload`].
+    Payload(Payload),
+    /// A [`TransactionEssence`].
+    Essence(TransactionEssence),
+    /// An [`Unlock`].
+    Unlock(Unlock),
+}
+
+impl Packed {
+    /// Returns the kind of the object.
+    pub fn kind(&self) -> PackableKind {
+        match self {
+            Self::Block(_) => PackableKind::Block,
+            Self::Output(_) => PackableKind::Output,
+            Self::Payload(_) => PackableKind::Payload,
+            Self::Essence(_) => PackableKind::Essence,
+            Self::Unlock(_) => PackableKind::Unlock,
+        }
+    }
+
+    /// Unpacks an object of the given kind from bytes, validating it against the protocol parameters.
+    ///
+    /// If no kind is given, each of [`PackableKind::ALL`] is tried and the first one consuming all bytes is returned.
+    /// If none does, the error of the attempt that read the most bytes is returned.
+    pub fn unpack(
+        bytes: &[u8],
+        kind: Option<PackableKind>,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self, Error> {
+        let Some(kind) = kind else {
+            let mut furthest: Option<Error> = None;
+
+            for kind in PackableKind::ALL {
+                match Self::unpack(bytes, Some(kind), protocol_parameters) {
+                    Ok(packed) => return Ok(packed),
+                    Err(error) => match &furthest {
+                        Some(furthest) if furthest.offset() >= error.offset() => {}
+                        _ => furthest = Some(error),
+                    },
+                }
+            }
+
+            // ALL is not empty so there is at least one error.
+            return Err(furthest.unwrap());
+        };
+
+        Ok(match kind {
+            PackableKind::Block => Self::Block(unpack_exact(bytes, kind, protocol_parameters)?),
+            PackableKind::Output => Self::Output(unpack_exact(bytes, kind, protocol_parameters)?),
+            PackableKind::Payload => Self::Payload(unpack_exact(bytes, kind, protocol_parameters)?),
+            PackableKind::Essence => Self::Essence(unpack_exact(bytes, kind, protocol_parameters)?),
+            PackableKind::Unlock => Self::Unlock(unpack_exact(bytes, kind, &())?),
+        })
+    }
+
+    /// Unpacks an object from a hex string, with or without `0x` prefix. See [`Packed::unpack`].
+    pub fn from_hex(
+        hex: &str,
+        kind: Option<PackableKind>,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self, Error> {
+        let hex = hex.trim();
+        let bytes: Vec<u8> = if hex.starts_with("0x") {
+            prefix_hex::decode(hex)
+        } else {
+            prefix_hex::decode(format!("0x{hex}"))
+        }
+        .map_err(|e| Error::Hex(e.to_string()))?;
+
+        Self::unpack(&bytes, kind, protocol_parameters)
+    }
+
+    /// Converts the JSON representation of an object of the given kind, validating it against the protocol
+    /// parameters.
+    ///
+    /// If no kind is given, each of [`PackableKind::ALL`] is tried and the first valid one is returned.
+    pub fn from_json(
+        json: serde_json::Value,
+        kind: Option<PackableKind>,
+        protocol_parameters: &ProtocolParameters,
+    ) -> Result<Self, Error> {
+        let Some(kind) = kind else {
+            return PackableKind::ALL
+                .into_iter()
+                .find_map(|kind| Self::from_json(json.clone(), Some(kind), protocol_parameters).ok())
+                .ok_or_else(|| Error::Json {
+                    kind: None,
+                    message: "not a valid block, payload, output, essence or unlock".to_string(),
+                });
+        };
+
+        Ok(match kind {
+            PackableKind::Block => Self::Block(from_dto::<Block>(json, kind, protocol_parameters)?),
+            PackableKind::Output => Self::Output(from_dto::<Output>(json, kind, protocol_parameters)?),
+            PackableKind::Payload => Self::Payload(from_dto::<Payload>(json, kind, protocol_parameters)?),
+            PackableKind::Essence => Self::Essence(from_dto::<TransactionEssence>(json, kind, protocol_parameters)?),
+            PackableKind::Unlock => {
+                Self::Unlock(Unlock::try_from(deserialize::<UnlockDto>(json, kind)?).map_err(|e| json_error(kind, e))?)
+            }
+        })
+    }
+
+    /// Returns the JSON representation of the object.
+    pub fn to_json(&self) -> serde_json::Value {
+        // Serializing DTOs to a JSON value can't fail.
+        match self {
+            Self::Block(block) => serde_json::to_value(BlockDto::from(block)),
+            Self::Output(output) => serde_json::to_value(OutputDto::from(output)),
+            Self::Payload(payload) => serde_json::to_value(PayloadDto::from(payload)),
+            Self::Essence(essence) => serde_json::to_value(TransactionEssenceDto::from(essence)),
+            Self::Unlock(unlock) => serde_json::to_value(UnlockDto::from(unlock)),
+        }
+        .unwrap()
+    }
+
+    /// Returns the packed bytes of the object.
+    pub fn pack_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Block(block) => block.pack_to_vec(),
+            Self::Output(output) => output.pack_to_vec(),
+            Self::Payload(payload) => payload.pack_to_vec(),
+            Self::Essence(essence) => essence.pack_to_vec(),
+            Self::Unlock(unlock) => unlock.pack_to_vec(),
+        }
+    }
+
+    /// Returns the packed bytes of the object as a `0x` prefixed hex string.
+    pub fn to_hex(&self) -> String {
+        prefix_hex::encode(self.pack_to_vec())
+    }
+}
+
+fn unpack_exact<P: Packable<UnpackError = BlockError>>(
+    bytes: &[u8],
+    kind: PackableKind,
+    visitor: &P::UnpackVisitor,
+) -> Result<P, Error> {
+    let mut unpacker = CounterUnpacker::new(SliceUnpacker::new(bytes));
+    let unpacked = P::unpack::<_, true>(&mut unpacker, visitor).map_err(|error| Error::Unpack {
+        kind,
+        offset: unpacker.counter(),
+        message: match error {
+            UnpackError::Packable(error) => error.to_string(),
+            UnpackError::Unpacker(_) => "unexpected end of bytes".to_string(),
+        },
+    })?;
+    let offset = unpacker.counter();
+
+    if offset != bytes.len() {
+        return Err(Error::TrailingBytes {
+            kind,
+            offset,
+            remaining: bytes.len() - offset,
+        });
+    }
+
+    Ok(unpacked)
+}
+
+fn deserialize<T: DeserializeOwned>(json: serde_json::Value, kind: PackableKind) -> Result<T, Error> {
+    serde_json::from_value(json).map_err(|e| json_error(kind, e))
+}
+
+fn from_dto<T: TryFromDto<Error = BlockError>>(
+    json: serde_json::Value,
+    kind: PackableKind,
+    protocol_parameters: &ProtocolParameters,
+) -> Result<T, Error>
+where
+    T::Dto: DeserializeOwned,
+{
+    T::try_from_dto_with_params(deserialize::<T::Dto>(json, kind)?, protocol_parameters)
+        .map_err(|e| json_error(kind, e))
+}
+
+fn json_error(kind: PackableKind, error: impl fmt::Display) -> Error {
+    Error::Json {
+        kind: Some(kind),
+        message: error.to_string(),
+    }
+}
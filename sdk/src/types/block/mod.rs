@@ -11,6 +11,9 @@ mod error;
 
 /// A module that provides types and syntactic validations of addresses.
 pub mod address;
+/// A module that provides conversion of blocks and their parts between packed bytes and JSON.
+#[cfg(feature = "serde")]
+pub mod codec;
 /// A module that provides types and syntactic validations of blocks.
 pub mod core;
 /// A module that contains helper functions and types.
@@ -28,6 +31,8 @@ pub mod protocol;
 /// A module that provides utilities for random generation of types.
 #[cfg(feature = "rand")]
 pub mod rand;
+/// A module that provides human-readable rendering of blocks and payloads.
+pub mod render;
 /// A module that provides types and rules for semantic validation.
 pub mod semantic;
 /// A module that provides types and syntactic validations of signatures.
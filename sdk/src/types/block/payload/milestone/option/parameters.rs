@@ -6,9 +6,9 @@
 use alloc::boxed::Box;
 use core::ops::RangeInclusive;
 
-use packable::{bounded::BoundedU16, prefix::BoxedSlicePrefix, Packable};
+use packable::{bounded::BoundedU16, error::UnpackError, prefix::BoxedSlicePrefix, Packable, PackableExt};
 
-use crate::types::block::{payload::milestone::MilestoneIndex, Error};
+use crate::types::block::{payload::milestone::MilestoneIndex, protocol::ProtocolParameters, Error};
 
 pub(crate) type BinaryParametersLength = BoundedU16<
     { *ParametersMilestoneOption::BINARY_PARAMETERS_LENGTH_RANGE.start() },
@@ -64,6 +64,25 @@ impl ParametersMilestoneOption {
     pub fn binary_parameters(&self) -> &[u8] {
         &self.binary_parameters
     }
+
+    /// Unpacks the binary parameters of a [`ParametersMilestoneOption`] to the [`ProtocolParameters`] that become
+    /// active at its target milestone index.
+    pub fn protocol_parameters(&self) -> Result<ProtocolParameters, Error> {
+        let protocol_parameters =
+            ProtocolParameters::unpack_verified(self.binary_parameters(), &()).map_err(|error| match error {
+                UnpackError::Packable(error) => error,
+                UnpackError::Unpacker(_) => Error::InvalidField("binary parameters"),
+            })?;
+
+        if protocol_parameters.protocol_version() != self.protocol_version {
+            return Err(Error::ProtocolVersionMismatch {
+                expected: self.protocol_version,
+                actual: protocol_parameters.protocol_version(),
+            });
+        }
+
+        Ok(protocol_parameters)
+    }
 }
 
 #[cfg(feature = "serde")]
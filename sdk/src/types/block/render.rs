@@ -0,0 +1,547 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::block::{
+    address::{Address, Hrp, ToBech32Ext},
+    input::Input,
+    output::{
+        feature::{Feature, Features},
+        unlock_condition::{UnlockCondition, UnlockConditions},
+        NativeTokens, Output, Rent, RentStructure, TokenScheme,
+    },
+    payload::{
+        milestone::MilestoneOption, transaction::TransactionEssence, MilestonePayload, Payload, TaggedDataPayload,
+        TransactionPayload, TreasuryTransactionPayload,
+    },
+    protocol::ProtocolParameters,
+    signature::Signature,
+    unlock::Unlock,
+    Block,
+};
+
+/// The formats a [`RenderNode`] can be rendered to.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RenderFormat {
+    /// An indented plain text tree.
+    #[default]
+    Text,
+    /// A JSON object with a label, an optional value and children.
+    Json,
+    /// A nested markdown list.
+    Markdown,
+}
+
+/// A node of the annotated tree describing a block, a payload or one of their parts.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct RenderNode {
+    label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<Self>,
+}
+
+impl RenderNode {
+    /// Creates a new [`RenderNode`] without a value.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Creates a new [`RenderNode`] with a value.
+    pub fn with_value(label: impl Into<String>, value: impl ToString) -> Self {
+        Self {
+            value: Some(value.to_string()),
+            ..Self::new(label)
+        }
+    }
+
+    /// Adds a child to the [`RenderNode`].
+    pub fn add_child(mut self, child: Self) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Adds children to the [`RenderNode`].
+    pub fn with_children(mut self, children: impl IntoIterator<Item = Self>) -> Self {
+        self.children.extend(children);
+        self
+    }
+
+    /// Returns the label of the [`RenderNode`].
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the value of the [`RenderNode`], if any.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Returns the children of the [`RenderNode`].
+    pub fn children(&self) -> &[Self] {
+        &self.children
+    }
+
+    /// Returns the first child with the given label, if any.
+    pub fn child(&self, label: &str) -> Option<&Self> {
+        self.children.iter().find(|child| child.label == label)
+    }
+
+    /// Renders the tree to a string in the given format.
+    pub fn render(&self, format: RenderFormat) -> String {
+        let mut rendered = String::new();
+
+        match format {
+            RenderFormat::Text => self.write_lines(&mut rendered, 0, ""),
+            RenderFormat::Markdown => self.write_lines(&mut rendered, 0, "- "),
+            // PANIC: serializing a tree of strings can't fail.
+            RenderFormat::Json => rendered = serde_json::to_string_pretty(self).unwrap(),
+        }
+
+        rendered
+    }
+
+    fn write_lines(&self, rendered: &mut String, depth: usize, bullet: &str) {
+        let (label, separator) = if bullet.is_empty() {
+            (self.label.clone(), "")
+        } else {
+            (format!("**{}**", self.label), "`")
+        };
+        // PANIC: writing to a string can't fail.
+        match &self.value {
+            Some(value) => writeln!(
+                rendered,
+                "{:indent$}{bullet}{label}: {separator}{value}{separator}",
+                "",
+                indent = 2 * depth
+            ),
+            None => writeln!(rendered, "{:indent$}{bullet}{label}", "", indent = 2 * depth),
+        }
+        .unwrap();
+
+        for child in &self.children {
+            child.write_lines(rendered, depth + 1, bullet);
+        }
+    }
+}
+
+impl core::fmt::Display for RenderNode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.render(RenderFormat::Text))
+    }
+}
+
+/// Turns blocks and payloads into annotated [`RenderNode`] trees.
+///
+/// Addresses are shown in Bech32 with the HRP of the network, tagged data and metadata are decoded to UTF-8 when
+/// possible, unlocks are mapped to the inputs they unlock and outputs show their storage deposit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Renderer {
+    bech32_hrp: Hrp,
+    rent_structure: RentStructure,
+}
+
+impl From<&ProtocolParameters> for Renderer {
+    fn from(protocol_parameters: &ProtocolParameters) -> Self {
+        Self::new(*protocol_parameters.bech32_hrp(), *protocol_parameters.rent_structure())
+    }
+}
+
+impl Renderer {
+    /// Creates a new [`Renderer`] for a network.
+    pub fn new(bech32_hrp: Hrp, rent_structure: RentStructure) -> Self {
+        Self {
+            bech32_hrp,
+            rent_structure,
+        }
+    }
+
+    /// Renders a [`Block`].
+    pub fn block(&self, block: &Block) -> RenderNode {
+        let mut node = RenderNode::with_value("Block", block.id())
+            .add_child(RenderNode::with_value("Protocol version", block.protocol_version()))
+            .add_child(
+                RenderNode::new("Parents").with_children(
+                    block
+                        .parents()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, parent)| RenderNode::with_value(format!("Parent {index}"), parent)),
+                ),
+            );
+        if let Some(payload) = block.payload() {
+            node = node.add_child(self.payload(payload));
+        }
+
+        node.add_child(RenderNode::with_value("Nonce", block.nonce()))
+    }
+
+    /// Renders a [`Payload`].
+    pub fn payload(&self, payload: &Payload) -> RenderNode {
+        match payload {
+            Payload::Transaction(payload) => self.transaction_payload(payload),
+            Payload::Milestone(payload) => self.milestone_payload(payload),
+            Payload::TreasuryTransaction(payload) => self.treasury_transaction_payload(payload),
+            Payload::TaggedData(payload) => self.tagged_data_payload(payload),
+        }
+    }
+
+    /// Renders a [`TransactionPayload`], mapping each unlock to the input it unlocks.
+    pub fn transaction_payload(&self, payload: &TransactionPayload) -> RenderNode {
+        let TransactionEssence::Regular(essence) = payload.essence();
+        let input_description = |index: usize| match essence.inputs().get(index) {
+            Some(Input::Utxo(input)) => format!("#{index} {}", input.output_id()),
+            Some(Input::Treasury(input)) => format!("#{index} treasury {}", input.milestone_id()),
+            None => format!("#{index} missing"),
+        };
+
+        let mut node = RenderNode::with_value("Transaction payload", payload.id())
+            .add_child(RenderNode::with_value("Network id", essence.network_id()))
+            .add_child(RenderNode::with_value("Inputs commitment", essence.inputs_commitment()))
+            .add_child(
+                RenderNode::new("Inputs").with_children(essence.inputs().iter().enumerate().map(|(index, input)| {
+                    match input {
+                        Input::Utxo(input) => RenderNode::with_value(format!("Input {index}"), input.output_id()),
+                        Input::Treasury(input) => {
+                            RenderNode::with_value(format!("Treasury input {index}"), input.milestone_id())
+                        }
+                    }
+                })),
+            )
+            .add_child(
+                RenderNode::new("Outputs").with_children(
+                    essence
+                        .outputs()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, output)| self.output_with_label(format!("Output {index}"), output)),
+                ),
+            );
+        if let Some(payload) = essence.payload() {
+            node = node.add_child(self.payload(payload));
+        }
+
+        node.add_child(
+            RenderNode::new("Unlocks").with_children(payload.unlocks().iter().enumerate().map(|(index, unlock)| {
+                let node = RenderNode::with_value(format!("Unlock {index}"), unlock_kind_str(unlock))
+                    .add_child(RenderNode::with_value("Unlocks input", input_description(index)));
+                match unlock {
+                    Unlock::Signature(unlock) => {
+                        let Signature::Ed25519(signature) = unlock.signature();
+                        node.add_child(RenderNode::with_value(
+                            "Public key",
+                            prefix_hex::encode(signature.public_key().as_slice()),
+                        ))
+                    }
+                    Unlock::Reference(unlock) => node.add_child(RenderNode::with_value(
+                        "Same signature as",
+                        format!("unlock {}", unlock.index()),
+                    )),
+                    Unlock::Alias(unlock) => node.add_child(RenderNode::with_value(
+                        "Alias of input",
+                        input_description(unlock.index() as usize),
+                    )),
+                    Unlock::Nft(unlock) => node.add_child(RenderNode::with_value(
+                        "Nft of input",
+                        input_description(unlock.index() as usize),
+                    )),
+                }
+            })),
+        )
+    }
+
+    /// Renders a [`MilestonePayload`].
+    pub fn milestone_payload(&self, payload: &MilestonePayload) -> RenderNode {
+        let essence = payload.essence();
+        let mut options = RenderNode::new("Options");
+        for option in essence.options().iter() {
+            options = options.add_child(match option {
+                MilestoneOption::Receipt(receipt) => RenderNode::new("Receipt")
+                    .add_child(RenderNode::with_value("Migrated at", receipt.migrated_at()))
+                    .add_child(RenderNode::with_value("Last", receipt.last()))
+                    .add_child(
+                        RenderNode::new("Funds").with_children(receipt.funds().iter().map(|entry| {
+                            RenderNode::with_value("Migrated funds", entry.tail_transaction_hash())
+                                .add_child(RenderNode::with_value("Address", self.address(entry.address())))
+                                .add_child(RenderNode::with_value("Amount", entry.amount()))
+                        })),
+                    )
+                    .add_child(self.treasury_transaction_payload(receipt.transaction())),
+                MilestoneOption::Parameters(parameters) => RenderNode::new("Parameters")
+                    .add_child(RenderNode::with_value(
+                        "Target milestone index",
+                        parameters.target_milestone_index(),
+                    ))
+                    .add_child(RenderNode::with_value(
+                        "Protocol version",
+                        parameters.protocol_version(),
+                    ))
+                    .add_child(RenderNode::with_value(
+                        "Binary parameters",
+                        prefix_hex::encode(parameters.binary_parameters()),
+                    )),
+            });
+        }
+
+        RenderNode::with_value("Milestone payload", payload.id())
+            .add_child(RenderNode::with_value("Index", essence.index()))
+            .add_child(RenderNode::with_value("Timestamp", essence.timestamp()))
+            .add_child(RenderNode::with_value("Protocol version", essence.protocol_version()))
+            .add_child(RenderNode::with_value(
+                "Previous milestone id",
+                essence.previous_milestone_id(),
+            ))
+            .add_child(
+                RenderNode::new("Parents").with_children(
+                    essence
+                        .parents()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, parent)| RenderNode::with_value(format!("Parent {index}"), parent)),
+                ),
+            )
+            .add_child(RenderNode::with_value(
+                "Inclusion merkle root",
+                essence.inclusion_merkle_root(),
+            ))
+            .add_child(RenderNode::with_value(
+                "Applied merkle root",
+                essence.applied_merkle_root(),
+            ))
+            .add_child(RenderNode::with_value("Metadata", decode_bytes(essence.metadata())))
+            .add_child(options)
+            .add_child(
+                RenderNode::new("Signatures").with_children(payload.signatures().iter().enumerate().map(
+                    |(index, signature)| {
+                        let Signature::Ed25519(signature) = signature;
+                        RenderNode::with_value(
+                            format!("Signature {index}"),
+                            prefix_hex::encode(signature.public_key().as_slice()),
+                        )
+                    },
+                )),
+            )
+    }
+
+    /// Renders a [`TreasuryTransactionPayload`].
+    pub fn treasury_transaction_payload(&self, payload: &TreasuryTransactionPayload) -> RenderNode {
+        RenderNode::new("Treasury transaction payload")
+            .add_child(RenderNode::with_value("Input", payload.input().milestone_id()))
+            .add_child(self.output_with_label("Output", &Output::Treasury(payload.output().clone())))
+    }
+
+    /// Renders a [`TaggedDataPayload`], decoding its tag and data.
+    pub fn tagged_data_payload(&self, payload: &TaggedDataPayload) -> RenderNode {
+        RenderNode::new("Tagged data payload")
+            .add_child(RenderNode::with_value("Tag", decode_bytes(payload.tag())))
+            .add_child(RenderNode::with_value("Data", decode_bytes(payload.data())))
+    }
+
+    /// Renders an [`Output`] with its storage deposit.
+    pub fn output(&self, output: &Output) -> RenderNode {
+        self.output_with_label("Output", output)
+    }
+
+    fn output_with_label(&self, label: impl Into<String>, output: &Output) -> RenderNode {
+        let mut node = RenderNode::with_value(label, output.kind_str())
+            .add_child(RenderNode::with_value("Amount", output.amount()))
+            .add_child(RenderNode::with_value(
+                "Storage deposit",
+                output.rent_cost(&self.rent_structure),
+            ));
+
+        match output {
+            Output::Alias(output) => {
+                node = node
+                    .add_child(RenderNode::with_value("Alias id", output.alias_id()))
+                    .add_child(RenderNode::with_value("State index", output.state_index()))
+                    .add_child(RenderNode::with_value(
+                        "State metadata",
+                        decode_bytes(output.state_metadata()),
+                    ))
+                    .add_child(RenderNode::with_value("Foundry counter", output.foundry_counter()));
+            }
+            Output::Foundry(output) => {
+                let TokenScheme::Simple(token_scheme) = output.token_scheme();
+                node = node
+                    .add_child(RenderNode::with_value("Foundry id", output.id()))
+                    .add_child(RenderNode::with_value("Serial number", output.serial_number()))
+                    .add_child(
+                        RenderNode::new("Token scheme")
+                            .add_child(RenderNode::with_value("Minted tokens", token_scheme.minted_tokens()))
+                            .add_child(RenderNode::with_value("Melted tokens", token_scheme.melted_tokens()))
+                            .add_child(RenderNode::with_value("Maximum supply", token_scheme.maximum_supply())),
+                    );
+            }
+            Output::Nft(output) => {
+                node = node.add_child(RenderNode::with_value("Nft id", output.nft_id()));
+            }
+            Output::Basic(_) | Output::Treasury(_) => {}
+        }
+
+        if let Some(native_tokens) = output.native_tokens().filter(|native_tokens| !native_tokens.is_empty()) {
+            node = node.add_child(self.native_tokens(native_tokens));
+        }
+        if let Some(unlock_conditions) = output.unlock_conditions() {
+            node = node.add_child(self.unlock_conditions(unlock_conditions));
+        }
+        if let Some(features) = output.features().filter(|features| !features.is_empty()) {
+            node = node.add_child(self.features("Features", features, output));
+        }
+        if let Some(features) = output.immutable_features().filter(|features| !features.is_empty()) {
+            node = node.add_child(self.features("Immutable features", features, output));
+        }
+
+        node
+    }
+
+    fn native_tokens(&self, native_tokens: &NativeTokens) -> RenderNode {
+        RenderNode::new("Native tokens").with_children(
+            native_tokens
+                .iter()
+                .map(|native_token| RenderNode::with_value(native_token.token_id().to_string(), native_token.amount())),
+        )
+    }
+
+    fn unlock_conditions(&self, unlock_conditions: &UnlockConditions) -> RenderNode {
+        RenderNode::new("Unlock conditions").with_children(unlock_conditions.iter().map(|unlock_condition| {
+            match unlock_condition {
+                UnlockCondition::Address(unlock_condition) => {
+                    RenderNode::with_value("Address", self.address(unlock_condition.address()))
+                }
+                UnlockCondition::StorageDepositReturn(unlock_condition) => RenderNode::new("Storage deposit return")
+                    .add_child(RenderNode::with_value(
+                        "Return address",
+                        self.address(unlock_condition.return_address()),
+                    ))
+                    .add_child(RenderNode::with_value("Amount", unlock_condition.amount())),
+                UnlockCondition::Timelock(unlock_condition) => {
+                    RenderNode::with_value("Timelock", unlock_condition.timestamp())
+                }
+                UnlockCondition::Expiration(unlock_condition) => RenderNode::new("Expiration")
+                    .add_child(RenderNode::with_value(
+                        "Return address",
+                        self.address(unlock_condition.return_address()),
+                    ))
+                    .add_child(RenderNode::with_value("Timestamp", unlock_condition.timestamp())),
+                UnlockCondition::StateControllerAddress(unlock_condition) => {
+                    RenderNode::with_value("State controller address", self.address(unlock_condition.address()))
+                }
+                UnlockCondition::GovernorAddress(unlock_condition) => {
+                    RenderNode::with_value("Governor address", self.address(unlock_condition.address()))
+                }
+                UnlockCondition::ImmutableAliasAddress(unlock_condition) => {
+                    RenderNode::with_value("Immutable alias address", self.address(unlock_condition.address()))
+                }
+            }
+        }))
+    }
+
+    fn features(&self, label: &str, features: &Features, output: &Output) -> RenderNode {
+        RenderNode::new(label).with_children(features.iter().map(|feature| match feature {
+            Feature::Sender(feature) => RenderNode::with_value("Sender", self.address(feature.address())),
+            Feature::Issuer(feature) => RenderNode::with_value("Issuer", self.address(feature.address())),
+            Feature::Metadata(feature) => {
+                let node = RenderNode::with_value("Metadata", decode_bytes(feature.data()));
+                match self.standard_metadata(feature.data(), output) {
+                    Some(metadata) => node.add_child(metadata),
+                    None => node,
+                }
+            }
+            Feature::Tag(feature) => RenderNode::with_value("Tag", decode_bytes(feature.tag())),
+        }))
+    }
+
+    // Decodes IRC-27 metadata of NFTs and IRC-30 metadata of foundries.
+    #[allow(unused_variables)]
+    fn standard_metadata(&self, data: &[u8], output: &Output) -> Option<RenderNode> {
+        match output {
+            #[cfg(feature = "irc_27")]
+            Output::Nft(_) => {
+                let metadata =
+                    serde_json::from_slice::<crate::types::block::output::feature::Irc27Metadata>(data).ok()?;
+                let mut node = RenderNode::with_value("IRC-27 metadata", metadata.version())
+                    .add_child(RenderNode::with_value("Name", metadata.name()))
+                    .add_child(RenderNode::with_value("Media type", metadata.media_type()))
+                    .add_child(RenderNode::with_value("URI", metadata.uri()));
+                if let Some(collection_name) = metadata.collection_name() {
+                    node = node.add_child(RenderNode::with_value("Collection name", collection_name));
+                }
+                if let Some(issuer_name) = metadata.issuer_name() {
+                    node = node.add_child(RenderNode::with_value("Issuer name", issuer_name));
+                }
+                if let Some(description) = metadata.description() {
+                    node = node.add_child(RenderNode::with_value("Description", description));
+                }
+                if !metadata.royalties().is_empty() {
+                    node = node.add_child(
+                        RenderNode::new("Royalties").with_children(
+                            metadata
+                                .royalties()
+                                .iter()
+                                .map(|(address, percentage)| RenderNode::with_value(address.to_string(), percentage)),
+                        ),
+                    );
+                }
+                Some(node)
+            }
+            #[cfg(feature = "irc_30")]
+            Output::Foundry(_) => {
+                let metadata =
+                    serde_json::from_slice::<crate::types::block::output::feature::Irc30Metadata>(data).ok()?;
+                let mut node = RenderNode::new("IRC-30 metadata")
+                    .add_child(RenderNode::with_value("Name", metadata.name()))
+                    .add_child(RenderNode::with_value("Symbol", metadata.symbol()))
+                    .add_child(RenderNode::with_value("Decimals", metadata.decimals()));
+                if let Some(description) = metadata.description() {
+                    node = node.add_child(RenderNode::with_value("Description", description));
+                }
+                if let Some(url) = metadata.url() {
+                    node = node.add_child(RenderNode::with_value("URL", url));
+                }
+                if let Some(logo_url) = metadata.logo_url() {
+                    node = node.add_child(RenderNode::with_value("Logo URL", logo_url));
+                }
+                Some(node)
+            }
+            _ => None,
+        }
+    }
+
+    fn address(&self, address: &Address) -> String {
+        address.to_bech32(self.bech32_hrp).to_string()
+    }
+}
+
+fn unlock_kind_str(unlock: &Unlock) -> &'static str {
+    match unlock {
+        Unlock::Signature(_) => "Signature",
+        Unlock::Reference(_) => "Reference",
+        Unlock::Alias(_) => "Alias",
+        Unlock::Nft(_) => "Nft",
+    }
+}
+
+/// Decodes bytes to a quoted UTF-8 string if they're printable text, or to hex otherwise.
+fn decode_bytes(bytes: &[u8]) -> String {
+    match core::str::from_utf8(bytes) {
+        Ok(text) if !text.is_empty() && !text.chars().any(|c| c.is_control() && !c.is_whitespace()) => {
+            format!("{text:?}")
+        }
+        _ => prefix_hex::encode(bytes),
+    }
+}
@@ -129,6 +129,30 @@ pub mod string_prefix {
     }
 }
 
+#[cfg(feature = "client")]
+pub mod evm_address {
+    use alloc::string::String;
+
+    use crypto::signatures::secp256k1_ecdsa::EvmAddress;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &EvmAddress, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&prefix_hex::encode(value.as_ref()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<EvmAddress, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        prefix_hex::decode::<[u8; EvmAddress::LENGTH]>(String::deserialize(deserializer)?)
+            .map(EvmAddress::from)
+            .map_err(de::Error::custom)
+    }
+}
+
 #[cfg(feature = "client")]
 pub mod bip44 {
     use crypto::keys::bip44::Bip44;
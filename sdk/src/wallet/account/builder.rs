@@ -142,6 +142,7 @@ where
             alias: account_alias,
             public_addresses: addresses,
             internal_addresses: Vec::new(),
+            evm_addresses: Vec::new(),
             addresses_with_unspent_outputs: Vec::new(),
             outputs: HashMap::new(),
             locked_outputs: HashSet::new(),
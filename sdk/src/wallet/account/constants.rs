@@ -11,6 +11,10 @@ pub(crate) const DEFAULT_LEDGER_OUTPUT_CONSOLIDATION_THRESHOLD: usize = 15;
 /// Amount of API request that can be sent in parallel during syncing
 pub(crate) const PARALLEL_REQUESTS_AMOUNT: usize = 500;
 
+/// Amount of history provider requests that can be sent in parallel during history backfilling, lower than during
+/// syncing as archive nodes serve far fewer clients
+pub(crate) const PARALLEL_HISTORY_REQUESTS_AMOUNT: usize = 20;
+
 /// ms before an account actually syncs with the network, before it just returns the previous syncing result
 /// this is done to prevent unnecessary simultaneous synchronizations
 pub(crate) const MIN_SYNC_INTERVAL: u128 = 5;
@@ -0,0 +1,476 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, HashMap};
+
+use getset::{CopyGetters, Getters};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::secret::SecretManage,
+    types::{
+        block::{
+            address::{Address, Bech32Address, Hrp, ToBech32Ext},
+            output::{FoundryId, FoundryOutput, Output, TokenId},
+            payload::transaction::{TransactionEssence, TransactionId},
+        },
+        TryFromDto,
+    },
+    wallet::{
+        account::{
+            types::{InclusionState, Transaction},
+            Account,
+        },
+        core::AddressBook,
+    },
+};
+
+/// The direction of a movement in the history of an account.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HistoryDirection {
+    /// Funds received from other addresses
+    Incoming,
+    /// Funds sent to other addresses
+    Outgoing,
+    /// Funds moved between addresses of the account
+    Internal,
+}
+
+/// The amount of a native token moved in a [`HistoryEntry`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryNativeToken {
+    /// The token id
+    #[getset(get = "pub")]
+    token_id: TokenId,
+    /// The symbol of the token, if the account knows its IRC30 metadata
+    #[getset(get = "pub")]
+    symbol: Option<String>,
+    /// The moved amount
+    #[getset(get_copy = "pub")]
+    amount: U256,
+}
+
+/// An address funds were received from or sent to in a [`HistoryEntry`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct HistoryCounterparty {
+    /// The address of the counterparty
+    address: Bech32Address,
+    /// The label of the address in the address book of the wallet
+    label: Option<String>,
+}
+
+/// A single movement of funds in the history of an account, returned from [`Account::history()`].
+///
+/// It doesn't hold a cost basis, which depends on fiat prices and the accounting method and can be derived from the
+/// timestamp and the amounts.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// The id of the transaction
+    #[getset(get = "pub")]
+    transaction_id: TransactionId,
+    /// Unix timestamp in milliseconds of the transaction
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    timestamp: u128,
+    /// The direction of the movement
+    #[getset(get_copy = "pub")]
+    direction: HistoryDirection,
+    /// The addresses funds were received from or sent to
+    #[getset(get = "pub")]
+    counterparties: Vec<HistoryCounterparty>,
+    /// Amount of base coins moved, without storage deposits that need to be returned
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    base_coin_amount: u64,
+    /// Native tokens moved
+    #[getset(get = "pub")]
+    native_tokens: Vec<HistoryNativeToken>,
+    /// Amount of base coins moved as storage deposit that needs to be returned to the sender
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    storage_deposit: u64,
+    /// The note of the transaction
+    #[getset(get = "pub")]
+    note: Option<String>,
+    /// The inclusion state of the transaction
+    #[getset(get_copy = "pub")]
+    inclusion_state: InclusionState,
+}
+
+/// Options to select the transactions of [`Account::history()`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryOptions {
+    /// Only include transactions from this unix timestamp in milliseconds on, inclusive
+    #[serde(default, with = "crate::utils::serde::option_string")]
+    pub from: Option<u128>,
+    /// Only include transactions until this unix timestamp in milliseconds, exclusive
+    #[serde(default, with = "crate::utils::serde::option_string")]
+    pub to: Option<u128>,
+    /// Include incoming transactions
+    #[serde(default = "default_true")]
+    pub incoming: bool,
+    /// Include outgoing and internal transactions
+    #[serde(default = "default_true")]
+    pub outgoing: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            from: None,
+            to: None,
+            incoming: true,
+            outgoing: true,
+        }
+    }
+}
+
+impl HistoryOptions {
+    /// Only include transactions in the given range of unix timestamps in milliseconds.
+    pub fn with_range(mut self, from: impl Into<Option<u128>>, to: impl Into<Option<u128>>) -> Self {
+        self.from = from.into();
+        self.to = to.into();
+        self
+    }
+
+    fn contains(&self, transaction: &Transaction) -> bool {
+        (if transaction.incoming {
+            self.incoming
+        } else {
+            self.outgoing
+        }) && !matches!(self.from, Some(from) if transaction.timestamp < from)
+            && !matches!(self.to, Some(to) if transaction.timestamp >= to)
+    }
+}
+
+/// The format of an exported history.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum HistoryExportFormat {
+    /// Comma separated values with a header row
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl HistoryExportFormat {
+    /// Formats the history entries.
+    pub fn export(&self, entries: &[HistoryEntry]) -> crate::wallet::Result<String> {
+        match self {
+            Self::Csv => Ok(to_csv(entries)),
+            Self::JsonLines => to_json_lines(entries),
+        }
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns one entry per transaction of the account, sorted by timestamp, with the funds it moved from the
+    /// perspective of the account. Outputs of the transactions are decoded against the addresses of the account to
+    /// find the direction and the counterparties, which are annotated with their labels from the address book.
+    pub async fn history(
+        &self,
+        options: impl Into<Option<HistoryOptions>> + Send,
+    ) -> crate::wallet::Result<Vec<HistoryEntry>> {
+        log::debug!("[history]");
+
+        let options = options.into().unwrap_or_default();
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+        let address_book = self.wallet.address_book.read().await;
+
+        let account_details = self.details().await;
+        let account_addresses = account_details
+            .addresses()
+            .into_iter()
+            .map(|address| address.address.inner)
+            .collect::<Vec<_>>();
+
+        let mut entries = account_details
+            .transactions()
+            .values()
+            .chain(account_details.incoming_transactions().values())
+            .filter(|transaction| options.contains(transaction))
+            .map(|transaction| {
+                history_entry(
+                    transaction,
+                    &account_addresses,
+                    account_details.native_token_foundries(),
+                    &address_book,
+                    bech32_hrp,
+                )
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| {
+            a.timestamp
+                .cmp(&b.timestamp)
+                .then_with(|| a.transaction_id.cmp(&b.transaction_id))
+        });
+
+        Ok(entries)
+    }
+
+    /// Exports the history of the account in the given format.
+    /// ```ignore
+    /// let csv = account
+    ///     .export_history(HistoryExportFormat::Csv, HistoryOptions::default().with_range(from, to))
+    ///     .await?;
+    /// std::fs::write("history.csv", csv)?;
+    /// ```
+    pub async fn export_history(
+        &self,
+        format: HistoryExportFormat,
+        options: impl Into<Option<HistoryOptions>> + Send,
+    ) -> crate::wallet::Result<String> {
+        format.export(&self.history(options).await?)
+    }
+}
+
+// Decodes the essence of a transaction against the addresses of the account.
+fn history_entry(
+    transaction: &Transaction,
+    account_addresses: &[Address],
+    native_token_foundries: &HashMap<FoundryId, FoundryOutput>,
+    address_book: &AddressBook,
+    bech32_hrp: Hrp,
+) -> HistoryEntry {
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+
+    let is_own = |address: &Address| account_addresses.contains(address);
+
+    // Incoming transactions move the outputs to the account, outgoing ones the outputs to other addresses.
+    let moved_outputs = essence
+        .outputs()
+        .iter()
+        .filter(|output| matches!(output_owner(output), Some(owner) if is_own(&owner) == transaction.incoming))
+        .collect::<Vec<_>>();
+
+    let mut counterparties = Vec::new();
+    let mut add_counterparty = |address: Address| {
+        if !is_own(&address)
+            && !counterparties
+                .iter()
+                .any(|c: &HistoryCounterparty| *c.address.inner() == address)
+        {
+            counterparties.push(HistoryCounterparty {
+                address: address.to_bech32(bech32_hrp),
+                label: address_book.label_of_address(&address).map(ToOwned::to_owned),
+            });
+        }
+    };
+
+    if transaction.incoming {
+        for input in &transaction.inputs {
+            if let Some(owner) = Output::try_from_dto(input.output.clone())
+                .ok()
+                .as_ref()
+                .and_then(output_owner)
+            {
+                add_counterparty(owner);
+            }
+        }
+        // The inputs may have been pruned already, then the sender feature is the only hint about the sender.
+        for output in &moved_outputs {
+            if let Some(sender) = output.features().and_then(|features| features.sender()) {
+                add_counterparty(*sender.address());
+            }
+        }
+    } else {
+        for output in &moved_outputs {
+            if let Some(owner) = output_owner(output) {
+                add_counterparty(owner);
+            }
+        }
+    }
+
+    let mut base_coin_amount = 0;
+    let mut storage_deposit = 0;
+    let mut native_tokens = BTreeMap::<TokenId, U256>::new();
+    for output in &moved_outputs {
+        let storage_deposit_return = output
+            .unlock_conditions()
+            .and_then(|unlock_conditions| unlock_conditions.storage_deposit_return())
+            .map_or(0, |sdr| sdr.amount());
+        base_coin_amount += output.amount() - storage_deposit_return;
+        storage_deposit += storage_deposit_return;
+        for native_token in output
+            .native_tokens()
+            .iter()
+            .flat_map(|native_tokens| native_tokens.iter())
+        {
+            *native_tokens.entry(*native_token.token_id()).or_default() += native_token.amount();
+        }
+    }
+
+    let direction = if transaction.incoming {
+        HistoryDirection::Incoming
+    } else if moved_outputs.is_empty() {
+        HistoryDirection::Internal
+    } else {
+        HistoryDirection::Outgoing
+    };
+
+    HistoryEntry {
+        transaction_id: transaction.transaction_id,
+        timestamp: transaction.timestamp,
+        direction,
+        counterparties,
+        base_coin_amount,
+        native_tokens: native_tokens
+            .into_iter()
+            .map(|(token_id, amount)| HistoryNativeToken {
+                token_id,
+                symbol: token_symbol(native_token_foundries.get(&FoundryId::from(token_id))),
+                amount,
+            })
+            .collect(),
+        storage_deposit,
+        note: transaction.note.clone(),
+        inclusion_state: transaction.inclusion_state,
+    }
+}
+
+// The address that controls an output.
+fn output_owner(output: &Output) -> Option<Address> {
+    match output {
+        Output::Alias(alias) => Some(*alias.state_controller_address()),
+        Output::Foundry(foundry) => Some(Address::Alias(*foundry.alias_address())),
+        _ => output
+            .unlock_conditions()
+            .and_then(|unlock_conditions| unlock_conditions.address())
+            .map(|address| *address.address()),
+    }
+}
+
+#[cfg(feature = "irc_30")]
+fn token_symbol(foundry: Option<&FoundryOutput>) -> Option<String> {
+    use crate::types::block::output::feature::Irc30Metadata;
+
+    foundry
+        .and_then(|foundry| foundry.immutable_features().metadata())
+        .and_then(|metadata| serde_json::from_slice::<Irc30Metadata>(metadata.data()).ok())
+        .map(|metadata| metadata.symbol().clone())
+}
+
+#[cfg(not(feature = "irc_30"))]
+fn token_symbol(_foundry: Option<&FoundryOutput>) -> Option<String> {
+    None
+}
+
+const CSV_HEADER: &str = "transactionId,timestamp,date,direction,counterparties,baseCoinAmount,nativeTokens,\
+                          storageDeposit,note,inclusionState";
+
+fn to_csv(entries: &[HistoryEntry]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for entry in entries {
+        let counterparties = entry
+            .counterparties
+            .iter()
+            .map(|counterparty| {
+                counterparty.label.as_ref().map_or_else(
+                    || counterparty.address.to_string(),
+                    |label| format!("{label} ({})", counterparty.address),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+        let native_tokens = entry
+            .native_tokens
+            .iter()
+            .map(|native_token| {
+                let token = native_token
+                    .symbol
+                    .clone()
+                    .unwrap_or_else(|| native_token.token_id.to_string());
+                format!("{}:{token}", native_token.amount)
+            })
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let row = [
+            entry.transaction_id.to_string(),
+            entry.timestamp.to_string(),
+            format_utc_date(entry.timestamp),
+            format!("{:?}", entry.direction),
+            counterparties,
+            entry.base_coin_amount.to_string(),
+            native_tokens,
+            entry.storage_deposit.to_string(),
+            entry.note.clone().unwrap_or_default(),
+            format!("{:?}", entry.inclusion_state),
+        ];
+        csv.push_str(&row.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// Quotes a field if it contains characters that have a meaning in CSV.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn to_json_lines(entries: &[HistoryEntry]) -> crate::wallet::Result<String> {
+    let mut json_lines = String::new();
+    for entry in entries {
+        json_lines.push_str(&serde_json::to_string(entry)?);
+        json_lines.push('\n');
+    }
+    Ok(json_lines)
+}
+
+// Formats a unix timestamp in milliseconds as ISO 8601 date and time in UTC, empty if it's out of range.
+fn format_utc_date(timestamp_millis: u128) -> String {
+    i128::try_from(timestamp_millis)
+        .ok()
+        .and_then(|millis| millis.checked_mul(1_000_000))
+        .and_then(|nanos| time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok())
+        .map(|date_time| {
+            format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                date_time.year(),
+                u8::from(date_time.month()),
+                date_time.day(),
+                date_time.hour(),
+                date_time.minute(),
+                date_time.second()
+            )
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utc_date() {
+        assert_eq!(format_utc_date(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc_date(951_782_400_000), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc_date(1_700_000_000_123), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn csv_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -5,6 +5,8 @@
 pub(crate) mod builder;
 /// Constants used for the account and account operations.
 pub(crate) mod constants;
+/// The module for the export of the transaction history
+pub(crate) mod history;
 /// The account operations like address generation, syncing and creating transactions.
 pub(crate) mod operations;
 /// Types used in an account and returned from methods.
@@ -23,15 +25,30 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, RwLock};
 
 #[cfg(feature = "participation")]
-pub use self::operations::participation::{AccountParticipationOverview, ParticipationEventWithNodes};
+pub use self::operations::participation::{
+    manager::{
+        ParticipationManagerOptions, ParticipationWarningEvent, StakingRewardsProjection,
+        DEFAULT_EVENT_ENDING_WARNING_MILESTONES,
+    },
+    AccountParticipationOverview, ParticipationEventWithNodes,
+};
 use self::types::{
-    address::{AccountAddress, AddressWithUnspentOutputs},
+    address::{AccountAddress, AddressWithUnspentOutputs, EvmAccountAddress},
     Balance, OutputData, Transaction, TransactionDto,
 };
 pub use self::{
+    history::{
+        HistoryCounterparty, HistoryDirection, HistoryEntry, HistoryExportFormat, HistoryNativeToken, HistoryOptions,
+    },
     operations::{
+        evm::{EvmSignature, TypedData, TypedDataField},
         output_claiming::OutputsToClaim,
         output_consolidation::ConsolidationParams,
+        output_query::{NativeTokenQuery, OutputQuery, OutputQueryPage, OutputQueryPageDto, OutputSort, OutputSortKey},
+        protocol_upgrade::ProtocolParametersUpgradeEvent,
+        reclaim_deposits::ReclaimDepositsParams,
+        storage_deposit_returns::{StorageDepositLedger, StorageDepositReturn, StorageDepositReturnStatus},
+        swap::SwapOfferParams,
         syncing::{
             options::{AccountSyncOptions, AliasSyncOptions, NftSyncOptions},
             SyncOptions,
@@ -39,6 +56,11 @@ pub use self::{
         transaction::{
             high_level::{
                 create_alias::CreateAliasParams,
+                deposit_to_chain::{
+                    isc::{IscAgentId, IscAssets, IscContractIdentity, IscParameter, IscRequestMetadata},
+                    ChainWithdrawal, DepositToChainParams, DEFAULT_DEPOSIT_GAS_BUDGET, DEFAULT_DEPOSIT_GAS_FEE,
+                },
+                foundry::FoundryInfo,
                 minting::{
                     create_native_token::{
                         CreateNativeTokenParams, CreateNativeTokenTransactionDto,
@@ -46,9 +68,10 @@ pub use self::{
                     },
                     mint_nfts::MintNftParams,
                 },
+                vesting::{VestingInstallment, VestingScheduleParams},
             },
             prepare_output::{Assets, Features, OutputParams, ReturnStrategy, StorageDeposit, Unlocks},
-            RemainderValueStrategy, TransactionOptions, TransactionOptionsDto,
+            RemainderValueStrategy, StorageDepositPolicy, TransactionOptions, TransactionOptionsDto,
         },
     },
     types::OutputDataDto,
@@ -62,15 +85,18 @@ use crate::{
     types::{
         api::core::response::OutputWithMetadataResponse,
         block::{
+            address::Bech32Address,
             output::{dto::FoundryOutputDto, AliasId, FoundryId, FoundryOutput, NftId, Output, OutputId, TokenId},
             payload::{
                 transaction::{TransactionEssence, TransactionId},
                 TransactionPayload,
             },
+            protocol::ProtocolParameters,
+            ConvertTo,
         },
         TryFromDto,
     },
-    wallet::{account::types::InclusionState, Result},
+    wallet::{account::types::InclusionState, AddressOrLabel, Result},
 };
 
 /// Options to filter outputs
@@ -105,6 +131,8 @@ pub struct AccountDetails {
     pub(crate) public_addresses: Vec<AccountAddress>,
     /// Internal addresses
     pub(crate) internal_addresses: Vec<AccountAddress>,
+    /// EVM addresses
+    pub(crate) evm_addresses: Vec<EvmAccountAddress>,
     /// Addresses with unspent outputs
     // used to improve performance for syncing and get balance because it's in most cases only a subset of all
     // addresses
@@ -167,6 +195,12 @@ pub struct AccountInner {
     // again, because sending transactions can change that
     pub(crate) last_synced: Mutex<u128>,
     pub(crate) default_sync_options: Mutex<SyncOptions>,
+    // the protocol parameters of the latest sync, to notice when they change, also across restarts
+    pub(crate) protocol_parameters: Mutex<Option<ProtocolParameters>>,
+    // the journal entries of the transactions which were in progress when the account was loaded, until they're
+    // recovered
+    #[cfg(feature = "storage")]
+    pub(crate) journal_to_recover: Mutex<Vec<crate::wallet::storage::journal::JournalEntry>>,
 }
 
 // impl Deref so we can use `account.details()` instead of `account.details.read()`
@@ -183,17 +217,30 @@ where
     crate::wallet::Error: From<S::Error>,
 {
     /// Create a new Account with an AccountDetails
-    pub(crate) async fn new(details: AccountDetails, wallet: Arc<WalletInner<S>>) -> Result<Self> {
+    #[allow(unused_mut)]
+    pub(crate) async fn new(mut details: AccountDetails, wallet: Arc<WalletInner<S>>) -> Result<Self> {
         #[cfg(feature = "storage")]
-        let default_sync_options = wallet
-            .storage_manager
-            .read()
-            .await
-            .get_default_sync_options(*details.index())
-            .await?
-            .unwrap_or_default();
+        let (default_sync_options, protocol_parameters, journal) = {
+            let storage_manager = wallet.storage_manager.read().await;
+            (
+                storage_manager
+                    .get_default_sync_options(*details.index())
+                    .await?
+                    .unwrap_or_default(),
+                storage_manager.get_protocol_parameters(*details.index()).await?,
+                storage_manager.get_journal(*details.index()).await?,
+            )
+        };
         #[cfg(not(feature = "storage"))]
-        let default_sync_options = Default::default();
+        let (default_sync_options, protocol_parameters) = Default::default();
+
+        // Keep the inputs of transactions which might have been sent locked until the journal is recovered
+        #[cfg(feature = "storage")]
+        for entry in &journal {
+            if entry.step != crate::wallet::storage::journal::JournalStep::InputsLocked {
+                details.locked_outputs.extend(&entry.inputs);
+            }
+        }
 
         Ok(Self {
             wallet,
@@ -201,6 +248,9 @@ where
                 details: RwLock::new(details),
                 last_synced: Default::default(),
                 default_sync_options: Mutex::new(default_sync_options),
+                protocol_parameters: Mutex::new(protocol_parameters),
+                #[cfg(feature = "storage")]
+                journal_to_recover: Mutex::new(journal),
             }),
         })
     }
@@ -210,6 +260,27 @@ where
         &self.wallet.client
     }
 
+    /// Resolves a label of the wallet's address book or a Bech32 encoded address to a [`Bech32Address`]. Transactions
+    /// resolve the [`AddressOrLabel`] of their params with it when they're prepared.
+    pub async fn resolve_address(&self, label_or_address: impl ConvertTo<AddressOrLabel>) -> Result<Bech32Address> {
+        let label = match label_or_address.convert()? {
+            AddressOrLabel::Address(address) => return Ok(address),
+            AddressOrLabel::Label(label) => label,
+        };
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+
+        self.wallet
+            .address_book
+            .read()
+            .await
+            .resolve_address(&label, bech32_hrp)
+    }
+
+    /// Resolves a label of the wallet's address book or a hex encoded NFT id to an [`NftId`].
+    pub async fn resolve_nft_id(&self, label_or_nft_id: &str) -> Result<NftId> {
+        self.wallet.address_book.read().await.resolve_nft_id(label_or_nft_id)
+    }
+
     /// Get the [`Output`] that minted a native token by the token ID. First try to get it
     /// from the account, if it isn't in the account try to get it from the node
     pub async fn get_foundry_output(&self, native_token_id: TokenId) -> Result<Output> {
@@ -488,6 +559,9 @@ pub struct AccountDetailsDto {
     pub public_addresses: Vec<AccountAddress>,
     /// Internal addresses
     pub internal_addresses: Vec<AccountAddress>,
+    /// EVM addresses
+    #[serde(default)]
+    pub evm_addresses: Vec<EvmAccountAddress>,
     /// Addresses with unspent outputs
     pub addresses_with_unspent_outputs: Vec<AddressWithUnspentOutputs>,
     /// Outputs
@@ -521,6 +595,7 @@ impl TryFromDto for AccountDetails {
             alias: dto.alias,
             public_addresses: dto.public_addresses,
             internal_addresses: dto.internal_addresses,
+            evm_addresses: dto.evm_addresses,
             addresses_with_unspent_outputs: dto.addresses_with_unspent_outputs,
             outputs: dto
                 .outputs
@@ -562,6 +637,7 @@ impl From<&AccountDetails> for AccountDetailsDto {
             alias: value.alias().clone(),
             public_addresses: value.public_addresses().clone(),
             internal_addresses: value.internal_addresses().clone(),
+            evm_addresses: value.evm_addresses().clone(),
             addresses_with_unspent_outputs: value.addresses_with_unspent_outputs().clone(),
             outputs: value
                 .outputs()
@@ -685,6 +761,7 @@ mod test {
             alias: "0".to_string(),
             public_addresses: Vec::new(),
             internal_addresses: Vec::new(),
+            evm_addresses: Vec::new(),
             addresses_with_unspent_outputs: Vec::new(),
             outputs: HashMap::new(),
             locked_outputs: HashSet::new(),
@@ -728,6 +805,7 @@ mod test {
                     used: false,
                 }],
                 internal_addresses: Vec::new(),
+                evm_addresses: Vec::new(),
                 addresses_with_unspent_outputs: Vec::new(),
                 outputs: HashMap::new(),
                 locked_outputs: HashSet::new(),
@@ -6,14 +6,17 @@ use primitive_types::U256;
 use crate::{
     client::secret::SecretManage,
     types::block::{
-        address::Bech32Address,
+        address::{Bech32Address, ToBech32Ext},
         output::{unlock_condition::UnlockCondition, FoundryId, NativeTokensBuilder, Output, Rent},
         ConvertTo,
     },
     wallet::{
         account::{
             operations::helpers::time::can_output_be_unlocked_forever_from_now_on,
-            types::{AddressWithUnspentOutputs, Balance, NativeTokensBalance},
+            types::{
+                AddressWithUnspentOutputs, Balance, ExpiringOutput, NativeTokensBalance, TimeLockedBalance,
+                TimeLockedOutput,
+            },
             Account, AccountDetails, OutputsToClaim,
         },
         Error, Result,
@@ -56,6 +59,72 @@ where
             .await
     }
 
+    /// Get the time-locked and expiring funds of the account: outputs that can only be spent after their timelock
+    /// expires and outputs sent with an expiration that return to the account if they aren't claimed in time.
+    pub async fn time_locked_balance(&self) -> Result<TimeLockedBalance> {
+        log::debug!("[BALANCE] time_locked_balance");
+
+        let account_details = self.details().await;
+        let local_time = self.client().get_time_checked().await?;
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+
+        let account_addresses = account_details
+            .addresses()
+            .into_iter()
+            .map(|address| address.address.inner)
+            .collect::<Vec<_>>();
+
+        let mut time_locked_balance = TimeLockedBalance::default();
+
+        for output_data in account_details.unspent_outputs.values() {
+            let (Some(unlock_conditions), Some(native_tokens)) = (
+                output_data.output.unlock_conditions(),
+                output_data.output.native_tokens(),
+            ) else {
+                continue;
+            };
+            let amount = output_data.output.amount();
+
+            // Outgoing outputs that return to the account after their expiration.
+            if let Some(expiration) = unlock_conditions.expiration() {
+                if let Some(address) = unlock_conditions.address() {
+                    if account_addresses.contains(expiration.return_address())
+                        && !account_addresses.contains(address.address())
+                    {
+                        time_locked_balance.expiring.push(ExpiringOutput {
+                            output_id: output_data.output_id,
+                            amount,
+                            native_tokens: native_tokens.clone(),
+                            recipient: address.address().to_bech32(bech32_hrp),
+                            expiration_time: expiration.timestamp(),
+                            reclaimable: unlock_conditions.is_expired(local_time)
+                                && !unlock_conditions.is_time_locked(local_time),
+                        });
+                        continue;
+                    }
+                }
+            }
+
+            // Owned outputs that are still time-locked.
+            if let Some(timelock) = unlock_conditions.timelock() {
+                let owner = output_data
+                    .output
+                    .required_and_unlocked_address(local_time, &output_data.output_id, None)?
+                    .0;
+                if unlock_conditions.is_time_locked(local_time) && account_addresses.contains(&owner) {
+                    time_locked_balance.locked.push(TimeLockedOutput {
+                        output_id: output_data.output_id,
+                        amount,
+                        native_tokens: native_tokens.clone(),
+                        unlock_time: timelock.timestamp(),
+                    });
+                }
+            }
+        }
+
+        Ok(time_locked_balance)
+    }
+
     async fn balance_inner(
         &self,
         addresses_with_unspent_outputs: impl Iterator<Item = &AddressWithUnspentOutputs> + Send,
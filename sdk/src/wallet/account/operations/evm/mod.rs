@@ -0,0 +1,164 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+pub(crate) mod rlp;
+pub(crate) mod transaction;
+pub(crate) mod typed_data;
+
+use crypto::{
+    keys::bip44::Bip44,
+    signatures::secp256k1_ecdsa::{EvmAddress, RecoverableSignature},
+};
+use serde::{Deserialize, Serialize};
+
+use self::transaction::UnsignedTransaction;
+pub use self::typed_data::{TypedData, TypedDataField};
+use crate::{
+    client::{constants::ETHER_COIN_TYPE, secret::SecretManage},
+    utils::serde::prefix_hex_bytes,
+    wallet::account::{types::address::EvmAccountAddress, Account},
+};
+
+/// A secp256k1 signature of an EVM address, in the format of `eth_sign`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmSignature {
+    /// The compressed public key.
+    #[serde(with = "prefix_hex_bytes")]
+    pub public_key: [u8; 33],
+    /// The signature as `r || s || v`, with `v` being 27 or 28.
+    #[serde(with = "prefix_hex_bytes")]
+    pub signature: [u8; RecoverableSignature::LENGTH],
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Generate EVM addresses with the Ether coin type and the index of the account and store them in the account.
+    /// ```ignore
+    /// let evm_addresses = account.generate_evm_addresses(1).await?;
+    /// ```
+    pub async fn generate_evm_addresses(&self, amount: u32) -> crate::wallet::Result<Vec<EvmAccountAddress>> {
+        log::debug!("[EVM] generating {amount} addresses");
+        if amount == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut account_details = self.details_mut().await;
+        let highest_current_index_plus_one = account_details.evm_addresses.len() as u32;
+
+        let addresses = self
+            .wallet
+            .secret_manager
+            .read()
+            .await
+            .generate_evm_addresses(
+                ETHER_COIN_TYPE,
+                account_details.index,
+                highest_current_index_plus_one..highest_current_index_plus_one + amount,
+                None,
+            )
+            .await?;
+
+        let generated_addresses = addresses
+            .into_iter()
+            .enumerate()
+            .map(|(index, address)| EvmAccountAddress {
+                address,
+                key_index: highest_current_index_plus_one + index as u32,
+            })
+            .collect::<Vec<_>>();
+        account_details
+            .evm_addresses
+            .extend(generated_addresses.iter().copied());
+
+        #[cfg(feature = "storage")]
+        {
+            log::debug!("[EVM] storing account {} with new EVM addresses", account_details.index);
+            self.save(Some(&account_details)).await?;
+        }
+
+        Ok(generated_addresses)
+    }
+
+    /// Signs a message with the key of an EVM address of the account, like `personal_sign` (EIP-191).
+    pub async fn sign_evm_message(&self, address: &EvmAddress, message: &[u8]) -> crate::wallet::Result<EvmSignature> {
+        let mut prefixed_message = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        prefixed_message.extend_from_slice(message);
+
+        let (public_key, signature) = self.sign_secp256k1_ecdsa(address, &prefixed_message).await?;
+        let mut signature = signature.to_bytes();
+        signature[64] += 27;
+
+        Ok(EvmSignature { public_key, signature })
+    }
+
+    /// Signs EIP-712 typed data with the key of an EVM address of the account, like `eth_signTypedData_v4`.
+    pub async fn sign_evm_typed_data(
+        &self,
+        address: &EvmAddress,
+        typed_data: &TypedData,
+    ) -> crate::wallet::Result<EvmSignature> {
+        let (public_key, signature) = self
+            .sign_secp256k1_ecdsa(address, &typed_data.signing_payload()?)
+            .await?;
+        let mut signature = signature.to_bytes();
+        signature[64] += 27;
+
+        Ok(EvmSignature { public_key, signature })
+    }
+
+    /// Signs a raw RLP encoded EVM transaction with the key of an EVM address of the account and returns the raw
+    /// signed transaction. Legacy transactions are signed with replay protection for the chain id (EIP-155), EIP-2930
+    /// and EIP-1559 transactions must have the chain id.
+    pub async fn sign_evm_transaction(
+        &self,
+        address: &EvmAddress,
+        transaction: &[u8],
+        chain_id: u64,
+    ) -> crate::wallet::Result<Vec<u8>> {
+        let transaction = UnsignedTransaction::decode(transaction, chain_id)?;
+        let (_, signature) = self
+            .sign_secp256k1_ecdsa(address, &transaction.signing_payload())
+            .await?;
+
+        Ok(transaction.signed(&signature.to_bytes()))
+    }
+
+    // Signs the keccak256 hash of the message with the key of the address
+    async fn sign_secp256k1_ecdsa(
+        &self,
+        address: &EvmAddress,
+        message: &[u8],
+    ) -> crate::wallet::Result<([u8; 33], RecoverableSignature)> {
+        let account_details = self.details().await;
+        let key_index = account_details
+            .evm_addresses
+            .iter()
+            .find(|evm_address| evm_address.address == *address)
+            .map(|evm_address| evm_address.key_index)
+            .ok_or_else(|| crate::wallet::Error::EvmAddressNotFoundInAccount(prefix_hex::encode(address.as_ref())))?;
+        let chain = Bip44::new(ETHER_COIN_TYPE)
+            .with_account(account_details.index)
+            .with_address_index(key_index);
+        drop(account_details);
+
+        let (public_key, signature) = self
+            .wallet
+            .secret_manager
+            .read()
+            .await
+            .sign_secp256k1_ecdsa(message, chain)
+            .await?;
+
+        // The secret manager could have been replaced with one of another seed
+        if public_key.evm_address() != *address {
+            return Err(crate::wallet::Error::EvmAddressNotFoundInAccount(prefix_hex::encode(
+                address.as_ref(),
+            )));
+        }
+
+        Ok((public_key.to_bytes(), signature))
+    }
+}
@@ -0,0 +1,165 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal RLP encoding, as much as is needed to sign EVM transactions.
+
+use crate::wallet::Error;
+
+/// An RLP item, either a byte string or a list of items.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RlpItem {
+    Bytes(Vec<u8>),
+    List(Vec<Self>),
+}
+
+impl RlpItem {
+    /// An unsigned integer, encoded big endian without leading zeros.
+    pub(crate) fn uint(bytes: &[u8]) -> Self {
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        Self::Bytes(bytes[start..].to_vec())
+    }
+
+    /// Returns the value of an unsigned integer that fits into an `u64`.
+    pub(crate) fn as_u64(&self) -> Option<u64> {
+        match self {
+            Self::Bytes(bytes) if bytes.len() <= 8 && bytes.first() != Some(&0) => {
+                Some(bytes.iter().fold(0, |value, byte| value << 8 | *byte as u64))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::Bytes(bytes) if bytes.len() == 1 && bytes[0] < 0x80 => bytes.clone(),
+            Self::Bytes(bytes) => {
+                let mut encoded = encode_length(bytes.len(), 0x80);
+                encoded.extend_from_slice(bytes);
+                encoded
+            }
+            Self::List(items) => {
+                let payload = items.iter().flat_map(Self::encode).collect::<Vec<_>>();
+                let mut encoded = encode_length(payload.len(), 0xc0);
+                encoded.extend(payload);
+                encoded
+            }
+        }
+    }
+
+    /// Decodes a single item, fails if there are bytes left.
+    pub(crate) fn decode(bytes: &[u8]) -> Result<Self, Error> {
+        let (item, consumed) = decode_item(bytes)?;
+        if consumed != bytes.len() {
+            return Err(invalid("trailing bytes"));
+        }
+        Ok(item)
+    }
+}
+
+impl From<u64> for RlpItem {
+    fn from(value: u64) -> Self {
+        Self::uint(&value.to_be_bytes())
+    }
+}
+
+fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len <= 55 {
+        vec![offset + len as u8]
+    } else {
+        let len_bytes = (len as u64).to_be_bytes();
+        let start = len_bytes.iter().position(|b| *b != 0).unwrap_or(len_bytes.len());
+        let mut encoded = vec![offset + 55 + (len_bytes.len() - start) as u8];
+        encoded.extend_from_slice(&len_bytes[start..]);
+        encoded
+    }
+}
+
+// Returns the item and the number of consumed bytes
+fn decode_item(bytes: &[u8]) -> Result<(RlpItem, usize), Error> {
+    let prefix = *bytes.first().ok_or_else(|| invalid("unexpected end"))?;
+
+    let (is_list, header_len, payload_len) = match prefix {
+        0x00..=0x7f => return Ok((RlpItem::Bytes(vec![prefix]), 1)),
+        0x80..=0xb7 => (false, 1, (prefix - 0x80) as usize),
+        0xb8..=0xbf => {
+            let len_of_len = (prefix - 0xb7) as usize;
+            (false, 1 + len_of_len, decode_length(bytes, len_of_len)?)
+        }
+        0xc0..=0xf7 => (true, 1, (prefix - 0xc0) as usize),
+        0xf8..=0xff => {
+            let len_of_len = (prefix - 0xf7) as usize;
+            (true, 1 + len_of_len, decode_length(bytes, len_of_len)?)
+        }
+    };
+
+    let end = header_len
+        .checked_add(payload_len)
+        .filter(|end| *end <= bytes.len())
+        .ok_or_else(|| invalid("unexpected end"))?;
+    let payload = &bytes[header_len..end];
+
+    if is_list {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while offset < payload.len() {
+            let (item, consumed) = decode_item(&payload[offset..])?;
+            items.push(item);
+            offset += consumed;
+        }
+        Ok((RlpItem::List(items), end))
+    } else {
+        if payload.len() == 1 && payload[0] < 0x80 {
+            return Err(invalid("non-canonical single byte"));
+        }
+        Ok((RlpItem::Bytes(payload.to_vec()), end))
+    }
+}
+
+fn decode_length(bytes: &[u8], len_of_len: usize) -> Result<usize, Error> {
+    let len_bytes = bytes.get(1..1 + len_of_len).ok_or_else(|| invalid("unexpected end"))?;
+    if len_of_len > core::mem::size_of::<usize>() || len_bytes[0] == 0 {
+        return Err(invalid("non-canonical length"));
+    }
+    let len = len_bytes.iter().fold(0, |len, byte| len << 8 | *byte as usize);
+    if len <= 55 {
+        return Err(invalid("non-canonical length"));
+    }
+    Ok(len)
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidEvmTransaction(format!("invalid RLP: {reason}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode() {
+        let item = RlpItem::List(vec![
+            RlpItem::Bytes(b"cat".to_vec()),
+            RlpItem::Bytes(b"dog".to_vec()),
+            RlpItem::from(0),
+            RlpItem::from(1024),
+            RlpItem::Bytes(vec![0xaa; 56]),
+            RlpItem::List(Vec::new()),
+        ]);
+        let encoded = item.encode();
+
+        assert_eq!(
+            &encoded[..11],
+            &[0xf8, 0x47, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g', 0x80]
+        );
+        assert_eq!(&encoded[11..16], &[0x82, 0x04, 0x00, 0xb8, 0x38]);
+        assert_eq!(RlpItem::decode(&encoded).unwrap(), item);
+        assert_eq!(RlpItem::from(1024).as_u64(), Some(1024));
+        assert_eq!(RlpItem::from(0).as_u64(), Some(0));
+
+        // Truncated, trailing bytes and non-canonical encodings
+        assert!(RlpItem::decode(&encoded[..encoded.len() - 1]).is_err());
+        assert!(RlpItem::decode(&[0x80, 0x80]).is_err());
+        assert!(RlpItem::decode(&[0x81, 0x01]).is_err());
+        assert!(RlpItem::decode(&[0xb8, 0x01, 0x80]).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::rlp::RlpItem;
+use crate::wallet::Error;
+
+/// Type of EIP-2930 transactions with an access list.
+const ACCESS_LIST_TRANSACTION_TYPE: u8 = 0x01;
+/// Type of EIP-1559 transactions with a priority fee.
+const DYNAMIC_FEE_TRANSACTION_TYPE: u8 = 0x02;
+/// Number of fields of an unsigned legacy transaction, without the EIP-155 chain id fields.
+const LEGACY_TRANSACTION_FIELDS: usize = 6;
+
+/// An unsigned EVM transaction, decoded from its raw RLP encoding.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UnsignedTransaction {
+    // None for legacy transactions
+    transaction_type: Option<u8>,
+    fields: Vec<RlpItem>,
+    chain_id: u64,
+}
+
+impl UnsignedTransaction {
+    /// Decodes a legacy transaction with or without the EIP-155 chain id fields, or an EIP-2930 or EIP-1559
+    /// transaction. The chain id of the transaction must match `chain_id`.
+    pub(crate) fn decode(transaction: &[u8], chain_id: u64) -> Result<Self, Error> {
+        let (transaction_type, rlp) = match transaction.first() {
+            Some(0xc0..=0xff) => (None, transaction),
+            Some(&transaction_type @ (ACCESS_LIST_TRANSACTION_TYPE | DYNAMIC_FEE_TRANSACTION_TYPE)) => {
+                (Some(transaction_type), &transaction[1..])
+            }
+            Some(transaction_type) => {
+                return Err(Error::InvalidEvmTransaction(format!(
+                    "unsupported transaction type {transaction_type}"
+                )));
+            }
+            None => return Err(Error::InvalidEvmTransaction("empty transaction".to_string())),
+        };
+        let RlpItem::List(mut fields) = RlpItem::decode(rlp)? else {
+            return Err(Error::InvalidEvmTransaction("transaction is not a list".to_string()));
+        };
+        // v = chain_id * 2 + 35 + recovery id must not overflow
+        if chain_id > (u64::MAX - 36) / 2 {
+            return Err(Error::InvalidEvmTransaction(format!(
+                "chain id {chain_id} is too large"
+            )));
+        }
+
+        let transaction_chain_id = match transaction_type {
+            None => match fields.len() {
+                LEGACY_TRANSACTION_FIELDS => Some(chain_id),
+                // EIP-155 transactions are signed with [chain_id, 0, 0] in place of [v, r, s]
+                9 => {
+                    let eip155_fields = fields.split_off(LEGACY_TRANSACTION_FIELDS);
+                    if eip155_fields[1..].iter().any(|field| field.as_u64() != Some(0)) {
+                        return Err(Error::InvalidEvmTransaction(
+                            "transaction is already signed".to_string(),
+                        ));
+                    }
+                    eip155_fields[0].as_u64()
+                }
+                len => return Err(invalid_field_count(len)),
+            },
+            Some(transaction_type) => {
+                let expected_len = if transaction_type == ACCESS_LIST_TRANSACTION_TYPE {
+                    8
+                } else {
+                    9
+                };
+                if fields.len() != expected_len {
+                    return Err(invalid_field_count(fields.len()));
+                }
+                fields[0].as_u64()
+            }
+        };
+        if transaction_chain_id != Some(chain_id) {
+            return Err(Error::InvalidEvmTransaction(format!(
+                "transaction isn't for chain id {chain_id}"
+            )));
+        }
+
+        Ok(Self {
+            transaction_type,
+            fields,
+            chain_id,
+        })
+    }
+
+    /// The payload which is hashed with keccak256 and signed.
+    pub(crate) fn signing_payload(&self) -> Vec<u8> {
+        match self.transaction_type {
+            None => self.encode([RlpItem::from(self.chain_id), RlpItem::from(0), RlpItem::from(0)]),
+            Some(_) => self.encode([]),
+        }
+    }
+
+    /// The raw signed transaction, from a signature of the signing payload as `r || s || recovery id`.
+    pub(crate) fn signed(&self, signature: &[u8; 65]) -> Vec<u8> {
+        let recovery_id = signature[64] as u64;
+        let v = match self.transaction_type {
+            // EIP-155
+            None => self.chain_id * 2 + 35 + recovery_id,
+            Some(_) => recovery_id,
+        };
+
+        self.encode([
+            RlpItem::from(v),
+            RlpItem::uint(&signature[..32]),
+            RlpItem::uint(&signature[32..64]),
+        ])
+    }
+
+    fn encode<const N: usize>(&self, additional_fields: [RlpItem; N]) -> Vec<u8> {
+        let mut encoded = self.transaction_type.map(|t| vec![t]).unwrap_or_default();
+        encoded.extend(RlpItem::List(self.fields.iter().cloned().chain(additional_fields).collect()).encode());
+        encoded
+    }
+}
+
+fn invalid_field_count(len: usize) -> Error {
+    Error::InvalidEvmTransaction(format!("unexpected number of fields {len}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crypto::{hashes::keccak::keccak256, signatures::secp256k1_ecdsa::SecretKey};
+
+    use super::*;
+
+    // The example of EIP-155
+    const UNSIGNED_TRANSACTION: &str =
+        "0xec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080";
+    const SIGNED_TRANSACTION: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    #[test]
+    fn eip155_transaction() {
+        let transaction =
+            UnsignedTransaction::decode(&prefix_hex::decode::<Vec<u8>>(UNSIGNED_TRANSACTION).unwrap(), 1).unwrap();
+        let signing_payload = transaction.signing_payload();
+
+        let mut hash = [0; 32];
+        keccak256(&signing_payload, &mut hash);
+        assert_eq!(
+            prefix_hex::encode(hash),
+            "0xdaf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+
+        let secret_key = SecretKey::try_from_bytes(&[0x46; 32]).unwrap();
+        let signature = secret_key.try_sign_keccak256(&signing_payload).unwrap();
+        assert_eq!(
+            prefix_hex::encode(transaction.signed(&signature.to_bytes())),
+            SIGNED_TRANSACTION
+        );
+
+        // The same transaction without the EIP-155 fields is signed for the given chain id
+        let mut legacy_fields = transaction.fields.clone();
+        legacy_fields.truncate(LEGACY_TRANSACTION_FIELDS);
+        let legacy = UnsignedTransaction::decode(&RlpItem::List(legacy_fields).encode(), 1).unwrap();
+        assert_eq!(legacy, transaction);
+    }
+
+    #[test]
+    fn invalid_transactions() {
+        let unsigned = prefix_hex::decode::<Vec<u8>>(UNSIGNED_TRANSACTION).unwrap();
+        // Wrong chain id
+        assert!(UnsignedTransaction::decode(&unsigned, 1072).is_err());
+        // Already signed
+        assert!(UnsignedTransaction::decode(&prefix_hex::decode::<Vec<u8>>(SIGNED_TRANSACTION).unwrap(), 1).is_err());
+        // Unsupported type
+        assert!(UnsignedTransaction::decode(&[0x03, 0xc0], 1).is_err());
+        // Wrong number of fields of an EIP-1559 transaction
+        let mut dynamic_fee = vec![DYNAMIC_FEE_TRANSACTION_TYPE];
+        dynamic_fee.extend(RlpItem::List(vec![RlpItem::from(1); 8]).encode());
+        assert!(UnsignedTransaction::decode(&dynamic_fee, 1).is_err());
+    }
+
+    #[test]
+    fn dynamic_fee_transaction() {
+        let mut fields = vec![RlpItem::from(1072), RlpItem::from(0)];
+        fields.extend([
+            RlpItem::from(1_000_000_000),
+            RlpItem::from(2_000_000_000),
+            RlpItem::from(21000),
+        ]);
+        fields.extend([
+            RlpItem::Bytes(vec![0x35; 20]),
+            RlpItem::from(1),
+            RlpItem::Bytes(Vec::new()),
+            RlpItem::List(Vec::new()),
+        ]);
+        let mut raw = vec![DYNAMIC_FEE_TRANSACTION_TYPE];
+        raw.extend(RlpItem::List(fields.clone()).encode());
+
+        let transaction = UnsignedTransaction::decode(&raw, 1072).unwrap();
+        assert_eq!(transaction.signing_payload(), raw);
+
+        let secret_key = SecretKey::try_from_bytes(&[0x46; 32]).unwrap();
+        let signature = secret_key.try_sign_keccak256(&raw).unwrap();
+        let signed = transaction.signed(&signature.to_bytes());
+
+        assert_eq!(signed[0], DYNAMIC_FEE_TRANSACTION_TYPE);
+        let RlpItem::List(signed_fields) = RlpItem::decode(&signed[1..]).unwrap() else {
+            panic!("signed transaction is not a list");
+        };
+        assert_eq!(signed_fields[..9], fields[..]);
+        assert_eq!(signed_fields[9].as_u64(), Some(signature.to_bytes()[64] as u64));
+        assert_eq!(
+            signature.recover_keccak256(&raw).unwrap().evm_address(),
+            secret_key.public_key().evm_address()
+        );
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crypto::hashes::keccak::keccak256;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::wallet::Error;
+
+/// The name of the type of the domain.
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// EIP-712 typed structured data, in the JSON format of `eth_signTypedData_v4`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// The struct types, must include `EIP712Domain`.
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    /// The type of the message.
+    pub primary_type: String,
+    /// The values of the `EIP712Domain` struct.
+    pub domain: Value,
+    /// The values of the primary type struct.
+    pub message: Value,
+}
+
+/// A member of an EIP-712 struct type.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TypedDataField {
+    /// The name of the member.
+    pub name: String,
+    /// The type of the member, e.g. `address`, `uint256`, `bytes32[]` or the name of a struct type.
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl TypedData {
+    /// Returns the hash of the domain struct.
+    pub fn domain_separator(&self) -> Result<[u8; 32], Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// Returns the hash which is signed, `keccak256(0x19 || 0x01 || domainSeparator || hashStruct(message))`.
+    pub fn signing_hash(&self) -> Result<[u8; 32], Error> {
+        let mut hash = [0; 32];
+        keccak256(&self.signing_payload()?, &mut hash);
+        Ok(hash)
+    }
+
+    /// The payload which is hashed with keccak256 and signed.
+    pub(crate) fn signing_payload(&self) -> Result<Vec<u8>, Error> {
+        let mut payload = vec![0x19, 0x01];
+        payload.extend(self.domain_separator()?);
+        // Only the domain is signed if it's the primary type
+        if self.primary_type != DOMAIN_TYPE {
+            payload.extend(self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(payload)
+    }
+
+    fn struct_fields(&self, kind: &str) -> Result<&[TypedDataField], Error> {
+        self.types
+            .get(kind)
+            .map(Vec::as_slice)
+            .ok_or_else(|| invalid(format!("unknown type {kind}")))
+    }
+
+    // `Name(type1 name1,...)` of the type, followed by the referenced struct types sorted by name
+    fn encode_type(&self, kind: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.find_dependencies(kind, &mut dependencies);
+        dependencies.remove(kind);
+
+        core::iter::once(kind)
+            .chain(dependencies)
+            .map(|name| {
+                let members = self
+                    .struct_fields(name)?
+                    .iter()
+                    .map(|field| format!("{} {}", field.kind, field.name))
+                    .collect::<Vec<_>>();
+                Ok(format!("{name}({})", members.join(",")))
+            })
+            .collect()
+    }
+
+    fn find_dependencies<'a>(&'a self, kind: &'a str, dependencies: &mut BTreeSet<&'a str>) {
+        let base_type = kind.split('[').next().unwrap_or(kind);
+        if let Some(fields) = self.types.get(base_type) {
+            if dependencies.insert(base_type) {
+                for field in fields {
+                    self.find_dependencies(&field.kind, dependencies);
+                }
+            }
+        }
+    }
+
+    fn hash_struct(&self, kind: &str, value: &Value) -> Result<[u8; 32], Error> {
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid(format!("value of {kind} is not an object")))?;
+
+        let mut encoded = keccak(self.encode_type(kind)?.as_bytes()).to_vec();
+        for field in self.struct_fields(kind)? {
+            let value = object
+                .get(&field.name)
+                .ok_or_else(|| invalid(format!("missing member {} of {kind}", field.name)))?;
+            encoded.extend(self.encode_value(&field.kind, value)?);
+        }
+
+        Ok(keccak(&encoded))
+    }
+
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<[u8; 32], Error> {
+        // Arrays are encoded as the hash of their encoded elements
+        if let Some(element_kind) = kind.strip_suffix(']') {
+            let (element_kind, len) = element_kind
+                .rsplit_once('[')
+                .ok_or_else(|| invalid(format!("invalid array type {kind}")))?;
+            let elements = value
+                .as_array()
+                .ok_or_else(|| invalid(format!("value of {kind} is not an array")))?;
+            if !len.is_empty() && len.parse::<usize>().ok() != Some(elements.len()) {
+                return Err(invalid(format!("array of {kind} has {} elements", elements.len())));
+            }
+            let encoded = elements
+                .iter()
+                .map(|element| self.encode_value(element_kind, element))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(keccak(&encoded.concat()));
+        }
+
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        let mut encoded = [0; 32];
+        match kind {
+            "string" => {
+                let string = value
+                    .as_str()
+                    .ok_or_else(|| invalid(format!("value of {kind} is not a string")))?;
+                encoded = keccak(string.as_bytes());
+            }
+            "bytes" => encoded = keccak(&decode_hex(kind, value)?),
+            "bool" => {
+                encoded[31] = value
+                    .as_bool()
+                    .ok_or_else(|| invalid(format!("value of {kind} is not a bool")))?
+                    as u8;
+            }
+            "address" => {
+                let address = decode_hex(kind, value)?;
+                if address.len() != 20 {
+                    return Err(invalid(format!("address {value} doesn't have 20 bytes")));
+                }
+                encoded[12..].copy_from_slice(&address);
+            }
+            _ => {
+                if let Some(len) = kind.strip_prefix("bytes").and_then(|len| len.parse::<usize>().ok()) {
+                    let bytes = decode_hex(kind, value)?;
+                    if !(1..=32).contains(&len) || bytes.len() != len {
+                        return Err(invalid(format!("value {value} is not a {kind}")));
+                    }
+                    encoded[..len].copy_from_slice(&bytes);
+                } else if let Some(bits) = kind.strip_prefix("uint").and_then(parse_bits) {
+                    let (negative, magnitude) = parse_integer(kind, value)?;
+                    if negative || magnitude.bits() > bits {
+                        return Err(invalid(format!("value {value} is not a {kind}")));
+                    }
+                    magnitude.to_big_endian(&mut encoded);
+                } else if let Some(bits) = kind.strip_prefix("int").and_then(parse_bits) {
+                    let (negative, magnitude) = parse_integer(kind, value)?;
+                    // The magnitude of the smallest value is one more than the one of the largest value
+                    let max_magnitude = (U256::one() << (bits - 1)) - U256::from(u8::from(!negative));
+                    if magnitude > max_magnitude {
+                        return Err(invalid(format!("value {value} is not a {kind}")));
+                    }
+                    // Two's complement
+                    let value = if negative {
+                        (!magnitude).overflowing_add(U256::one()).0
+                    } else {
+                        magnitude
+                    };
+                    value.to_big_endian(&mut encoded);
+                } else {
+                    return Err(invalid(format!("unknown type {kind}")));
+                }
+            }
+        }
+
+        Ok(encoded)
+    }
+}
+
+fn keccak(bytes: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    keccak256(bytes, &mut hash);
+    hash
+}
+
+fn parse_bits(bits: &str) -> Option<usize> {
+    bits.parse::<usize>()
+        .ok()
+        .filter(|bits| (8..=256).contains(bits) && bits % 8 == 0)
+}
+
+fn decode_hex(kind: &str, value: &Value) -> Result<Vec<u8>, Error> {
+    value
+        .as_str()
+        .and_then(|hex| prefix_hex::decode::<Vec<u8>>(hex).ok())
+        .ok_or_else(|| invalid(format!("value {value} of {kind} is not hex encoded")))
+}
+
+// Integers are JSON numbers, decimal strings or hex strings, returns the sign and the magnitude
+fn parse_integer(kind: &str, value: &Value) -> Result<(bool, U256), Error> {
+    let error = || invalid(format!("value {value} of {kind} is not an integer"));
+
+    match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => Ok((false, U256::from(number))),
+            (None, Some(number)) => Ok((true, U256::from(number.unsigned_abs()))),
+            _ => Err(error()),
+        },
+        Value::String(string) => {
+            let (negative, digits) = string
+                .strip_prefix('-')
+                .map_or((false, string.as_str()), |digits| (true, digits));
+            let magnitude = digits
+                .strip_prefix("0x")
+                .map_or_else(
+                    || U256::from_dec_str(digits).ok(),
+                    |hex| U256::from_str_radix(hex, 16).ok(),
+                )
+                .ok_or_else(error)?;
+            Ok((negative && !magnitude.is_zero(), magnitude))
+        }
+        _ => Err(error()),
+    }
+}
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidTypedData(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example of EIP-712
+    fn mail() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn mail_signing_hash() {
+        let typed_data = mail();
+
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            prefix_hex::encode(typed_data.domain_separator().unwrap()),
+            "0xf2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            prefix_hex::encode(typed_data.signing_hash().unwrap()),
+            "0xbe609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn integers() {
+        let typed_data = mail();
+
+        let mut minus_one = [0xff; 32];
+        assert_eq!(typed_data.encode_value("int8", &(-1).into()).unwrap(), minus_one);
+        assert_eq!(typed_data.encode_value("int256", &"-0x1".into()).unwrap(), minus_one);
+        minus_one[..31].fill(0);
+        assert_eq!(typed_data.encode_value("uint8", &"255".into()).unwrap(), minus_one);
+
+        assert!(typed_data.encode_value("int8", &(-129).into()).is_err());
+        assert!(typed_data.encode_value("int8", &128.into()).is_err());
+        assert!(typed_data.encode_value("uint8", &256.into()).is_err());
+        assert!(typed_data.encode_value("uint8", &(-1).into()).is_err());
+        assert!(typed_data.encode_value("uint7", &1.into()).is_err());
+    }
+
+    #[test]
+    fn invalid_typed_data() {
+        let mut typed_data = mail();
+        typed_data.message["to"] = serde_json::json!({ "name": "Bob" });
+        assert!(typed_data.signing_hash().is_err());
+
+        let mut typed_data = mail();
+        typed_data.primary_type = "Letter".to_string();
+        assert!(typed_data.signing_hash().is_err());
+    }
+}
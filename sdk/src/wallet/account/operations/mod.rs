@@ -5,6 +5,8 @@
 pub(crate) mod address_generation;
 /// The module to get the accounts balance
 pub(crate) mod balance;
+/// The module for EVM addresses and signing
+pub(crate) mod evm;
 /// Helper functions
 pub(crate) mod helpers;
 /// The module for claiming of outputs with
@@ -15,11 +17,24 @@ pub(crate) mod output_claiming;
 pub(crate) mod output_consolidation;
 /// The module to find additional addresses with unspent outputs
 pub(crate) mod output_finder;
+/// The module for local queries of the outputs
+pub(crate) mod output_query;
 /// The module for participation
 #[cfg(feature = "participation")]
 pub(crate) mod participation;
+/// The module for protocol parameters upgrades
+pub(crate) mod protocol_upgrade;
+/// The module for reclaiming storage deposits
+pub(crate) mod reclaim_deposits;
 /// The module for retrying blocks or transactions
 pub(crate) mod retry;
+/// The module for tracking storage deposits lent to recipients
+pub(crate) mod storage_deposit_returns;
+/// The module for queries of the SQLite storage
+#[cfg(feature = "sqlite")]
+pub(crate) mod storage_query;
+/// The module for atomic swaps with another party
+pub(crate) mod swap;
 /// The module for synchronization of an account
 pub(crate) mod syncing;
 /// The module for transactions
@@ -29,6 +29,9 @@ pub enum OutputsToClaim {
     NativeTokens,
     Nfts,
     Amount,
+    /// Outputs sent by the account with an expiration that returned to the account, because the recipient didn't
+    /// claim them in time
+    Expired,
     All,
 }
 
@@ -107,6 +110,13 @@ impl AccountDetails {
                                     output_ids_to_claim.insert(output_data.output_id);
                                 }
                             }
+                            OutputsToClaim::Expired => {
+                                // Since the output can be unlocked now, an expired output belongs to the account
+                                // through its return address
+                                if unlock_conditions.is_expired(time) {
+                                    output_ids_to_claim.insert(output_data.output_id);
+                                }
+                            }
                             OutputsToClaim::All => {
                                 output_ids_to_claim.insert(output_data.output_id);
                             }
@@ -186,31 +196,34 @@ where
         I::IntoIter: Send,
     {
         log::debug!("[OUTPUT_CLAIMING] claim_outputs");
-        let prepared_transaction = self.prepare_claim_outputs(output_ids_to_claim).await.map_err(|error| {
-            // Map InsufficientStorageDepositAmount error here because it's the result of InsufficientFunds in this
-            // case and then easier to handle
-            match error {
-                crate::wallet::Error::Block(block_error) => match *block_error {
-                    crate::types::block::Error::InsufficientStorageDepositAmount { amount, required } => {
-                        crate::wallet::Error::InsufficientFunds {
-                            available: amount,
-                            required,
+        self.in_transaction_span(async {
+            let prepared_transaction = self.prepare_claim_outputs(output_ids_to_claim).await.map_err(|error| {
+                // Map InsufficientStorageDepositAmount error here because it's the result of InsufficientFunds in this
+                // case and then easier to handle
+                match error {
+                    crate::wallet::Error::Block(block_error) => match *block_error {
+                        crate::types::block::Error::InsufficientStorageDepositAmount { amount, required } => {
+                            crate::wallet::Error::InsufficientFunds {
+                                available: amount,
+                                required,
+                            }
                         }
-                    }
-                    _ => crate::wallet::Error::Block(block_error),
-                },
-                _ => error,
-            }
-        })?;
+                        _ => crate::wallet::Error::Block(block_error),
+                    },
+                    _ => error,
+                }
+            })?;
 
-        let claim_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
+            let claim_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
 
-        log::debug!(
-            "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
-            claim_tx.block_id,
-            claim_tx.transaction_id
-        );
-        Ok(claim_tx)
+            log::debug!(
+                "[OUTPUT_CLAIMING] Claiming transaction created: block_id: {:?} tx_id: {:?}",
+                claim_tx.block_id,
+                claim_tx.transaction_id
+            );
+            Ok(claim_tx)
+        })
+        .await
     }
 
     /// Try to claim basic outputs that have additional unlock conditions to their [AddressUnlockCondition].
@@ -73,7 +73,7 @@ impl<S: 'static + SecretManage> Account<S>
 where
     crate::wallet::Error: From<S::Error>,
 {
-    fn should_consolidate_output(
+    pub(crate) fn should_consolidate_output(
         &self,
         output_data: &OutputData,
         current_time: u32,
@@ -107,16 +107,19 @@ where
     /// is set to `true`, the threshold is ignored. Only consolidates the amount of outputs that fit into a single
     /// transaction.
     pub async fn consolidate_outputs(&self, params: ConsolidationParams) -> Result<Transaction> {
-        let prepared_transaction = self.prepare_consolidate_outputs(params).await?;
-        let consolidation_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
+        self.in_transaction_span(async {
+            let prepared_transaction = self.prepare_consolidate_outputs(params).await?;
+            let consolidation_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
 
-        log::debug!(
-            "[OUTPUT_CONSOLIDATION] consolidation transaction created: block_id: {:?} tx_id: {:?}",
-            consolidation_tx.block_id,
-            consolidation_tx.transaction_id
-        );
+            log::debug!(
+                "[OUTPUT_CONSOLIDATION] consolidation transaction created: block_id: {:?} tx_id: {:?}",
+                consolidation_tx.block_id,
+                consolidation_tx.transaction_id
+            );
 
-        Ok(consolidation_tx)
+            Ok(consolidation_tx)
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -130,6 +133,8 @@ where
         let mut outputs_to_consolidate = Vec::new();
         let account_details = self.details().await;
         let account_addresses = &account_details.addresses_with_unspent_outputs[..];
+        #[cfg(feature = "participation")]
+        let participating_output_ids = account_details.participating_output_ids();
 
         for (output_id, output_data) in account_details.unspent_outputs() {
             #[cfg(feature = "participation")]
@@ -139,6 +144,11 @@ where
                     continue;
                 }
             }
+            // Outputs with participations are kept as well, consolidating them would end their participations.
+            #[cfg(feature = "participation")]
+            if participating_output_ids.contains(output_id) {
+                continue;
+            }
             let is_locked_output = account_details.locked_outputs.contains(output_id);
             let should_consolidate_output =
                 self.should_consolidate_output(output_data, current_time, account_addresses)?;
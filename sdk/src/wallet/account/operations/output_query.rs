@@ -0,0 +1,499 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cmp::Ordering;
+
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::{
+        address::Bech32Address,
+        output::{
+            feature::{IssuerFeature, SenderFeature, TagFeature},
+            unlock_condition::StorageDepositReturnUnlockCondition,
+            TokenId,
+        },
+    },
+    wallet::account::{
+        types::{OutputData, OutputDataDto},
+        Account, AccountDetails,
+    },
+};
+
+/// Query for the outputs of an account, mirroring the query parameters of the indexer. All set conditions need to
+/// match.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputQuery {
+    /// Also return spent outputs
+    pub include_spent: bool,
+    /// Outputs of these types (Basic = 3, Alias = 4, Foundry = 5, NFT = 6)
+    pub output_types: Option<Vec<u8>>,
+    /// Outputs of this account address
+    pub address: Option<Bech32Address>,
+    /// Outputs with at least this amount of base coins
+    #[serde(with = "crate::utils::serde::option_string")]
+    pub min_amount: Option<u64>,
+    /// Outputs with at most this amount of base coins
+    #[serde(with = "crate::utils::serde::option_string")]
+    pub max_amount: Option<u64>,
+    /// Outputs having all of these unlock condition kinds
+    pub unlock_condition_kinds: Option<Vec<u8>>,
+    /// Outputs with or without a storage deposit return unlock condition
+    pub has_storage_deposit_return: Option<bool>,
+    /// Outputs with a storage deposit return unlock condition to this address
+    pub storage_deposit_return_address: Option<Bech32Address>,
+    /// Outputs with or without a timelock unlock condition
+    pub has_timelock: Option<bool>,
+    /// Outputs timelocked before this timestamp
+    pub timelocked_before: Option<u32>,
+    /// Outputs timelocked after this timestamp
+    pub timelocked_after: Option<u32>,
+    /// Outputs with or without an expiration unlock condition
+    pub has_expiration: Option<bool>,
+    /// Outputs expiring before this timestamp
+    pub expires_before: Option<u32>,
+    /// Outputs expiring after this timestamp
+    pub expires_after: Option<u32>,
+    /// Outputs with an expiration unlock condition with this return address
+    pub expiration_return_address: Option<Bech32Address>,
+    /// Outputs with a sender feature with this address
+    pub sender: Option<Bech32Address>,
+    /// Outputs with an issuer feature with this address
+    pub issuer: Option<Bech32Address>,
+    /// Outputs with a tag feature with this tag
+    #[serde(with = "crate::utils::serde::option_prefix_hex_bytes")]
+    pub tag: Option<Vec<u8>>,
+    /// Outputs with or without native tokens
+    pub has_native_tokens: Option<bool>,
+    /// Outputs holding this native token
+    pub native_token: Option<NativeTokenQuery>,
+    /// Outputs booked at or after this timestamp
+    pub created_after: Option<u32>,
+    /// Outputs booked before this timestamp
+    pub created_before: Option<u32>,
+    /// The order of the returned outputs
+    pub sort: OutputSort,
+    /// The number of matching outputs to skip
+    pub offset: usize,
+    /// The maximum number of returned outputs
+    pub limit: Option<usize>,
+}
+
+/// Query for the amount of a native token held by an output.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeTokenQuery {
+    /// The id of the native token
+    pub token_id: TokenId,
+    /// Outputs holding at least this amount of the native token
+    #[serde(default)]
+    pub min_amount: Option<U256>,
+    /// Outputs holding at most this amount of the native token
+    #[serde(default)]
+    pub max_amount: Option<U256>,
+}
+
+/// The order of the outputs returned by an [`OutputQuery`].
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OutputSort {
+    /// The key to sort by
+    pub key: OutputSortKey,
+    /// Sort in descending instead of ascending order
+    pub descending: bool,
+}
+
+/// The key the outputs returned by an [`OutputQuery`] are sorted by, ties are sorted by output id.
+#[derive(Debug, Default, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum OutputSortKey {
+    /// The timestamp of the milestone which booked the output
+    #[default]
+    BookedTimestamp,
+    /// The amount of base coins
+    Amount,
+    /// The output id
+    OutputId,
+}
+
+/// A page of the outputs matching an [`OutputQuery`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OutputQueryPage {
+    /// The number of outputs matching the query, regardless of the offset and limit
+    pub total: usize,
+    /// The matching outputs after the offset, at most the limit
+    pub items: Vec<OutputData>,
+}
+
+/// Dto for a page of the outputs matching an [`OutputQuery`].
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputQueryPageDto {
+    /// The number of outputs matching the query, regardless of the offset and limit
+    pub total: usize,
+    /// The matching outputs after the offset, at most the limit
+    pub items: Vec<OutputDataDto>,
+}
+
+impl From<&OutputQueryPage> for OutputQueryPageDto {
+    fn from(value: &OutputQueryPage) -> Self {
+        Self {
+            total: value.total,
+            items: value.items.iter().map(OutputDataDto::from).collect(),
+        }
+    }
+}
+
+// Returns whether the timestamp is in the window, which is open if no bounds are set.
+// `Option::is_none_or()` would need a newer Rust version.
+#[allow(clippy::unnecessary_map_or)]
+fn in_window(timestamp: Option<u32>, before: Option<u32>, after: Option<u32>) -> bool {
+    if before.is_none() && after.is_none() {
+        return true;
+    }
+
+    timestamp.is_some_and(|timestamp| {
+        before.map_or(true, |before| timestamp < before) && after.map_or(true, |after| timestamp > after)
+    })
+}
+
+impl OutputQuery {
+    /// Returns whether the output matches all set conditions of the query.
+    pub fn matches(&self, output_data: &OutputData) -> bool {
+        let output = &output_data.output;
+        let unlock_conditions = output.unlock_conditions();
+        let features = output.features();
+        let native_tokens = output.native_tokens();
+
+        if output_data.is_spent && !self.include_spent {
+            return false;
+        }
+        if let Some(output_types) = &self.output_types {
+            if !output_types.contains(&output.kind()) {
+                return false;
+            }
+        }
+        if let Some(address) = &self.address {
+            if address.inner() != &output_data.address {
+                return false;
+            }
+        }
+        let amount = output.amount();
+        if self.min_amount.is_some_and(|min| amount < min) || self.max_amount.is_some_and(|max| amount > max) {
+            return false;
+        }
+        if let Some(kinds) = &self.unlock_condition_kinds {
+            if !kinds.iter().all(|kind| {
+                unlock_conditions.is_some_and(|unlock_conditions| {
+                    unlock_conditions
+                        .iter()
+                        .any(|unlock_condition| unlock_condition.kind() == *kind)
+                })
+            }) {
+                return false;
+            }
+        }
+
+        let storage_deposit_return = unlock_conditions.and_then(|u| u.storage_deposit_return());
+        if self
+            .has_storage_deposit_return
+            .is_some_and(|has| has != storage_deposit_return.is_some())
+        {
+            return false;
+        }
+        if let Some(address) = &self.storage_deposit_return_address {
+            if storage_deposit_return.map(StorageDepositReturnUnlockCondition::return_address) != Some(address.inner())
+            {
+                return false;
+            }
+        }
+
+        let timelock = unlock_conditions.and_then(|u| u.timelock());
+        if self.has_timelock.is_some_and(|has| has != timelock.is_some()) {
+            return false;
+        }
+        if !in_window(
+            timelock.map(|timelock| timelock.timestamp()),
+            self.timelocked_before,
+            self.timelocked_after,
+        ) {
+            return false;
+        }
+
+        let expiration = unlock_conditions.and_then(|u| u.expiration());
+        if self.has_expiration.is_some_and(|has| has != expiration.is_some()) {
+            return false;
+        }
+        if !in_window(
+            expiration.map(|expiration| expiration.timestamp()),
+            self.expires_before,
+            self.expires_after,
+        ) {
+            return false;
+        }
+        if let Some(address) = &self.expiration_return_address {
+            if expiration.map(|expiration| expiration.return_address()) != Some(address.inner()) {
+                return false;
+            }
+        }
+
+        if let Some(address) = &self.sender {
+            if features.and_then(|f| f.sender()).map(SenderFeature::address) != Some(address.inner()) {
+                return false;
+            }
+        }
+        if let Some(address) = &self.issuer {
+            if output
+                .immutable_features()
+                .and_then(|f| f.issuer())
+                .map(IssuerFeature::address)
+                != Some(address.inner())
+            {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if features.and_then(|f| f.tag()).map(TagFeature::tag) != Some(tag.as_slice()) {
+                return false;
+            }
+        }
+
+        if self
+            .has_native_tokens
+            .is_some_and(|has| has != native_tokens.is_some_and(|native_tokens| !native_tokens.is_empty()))
+        {
+            return false;
+        }
+        if let Some(native_token_query) = &self.native_token {
+            let Some(amount) = native_tokens.and_then(|native_tokens| {
+                native_tokens
+                    .iter()
+                    .find(|native_token| native_token.token_id() == &native_token_query.token_id)
+                    .map(|native_token| native_token.amount())
+            }) else {
+                return false;
+            };
+            if native_token_query.min_amount.is_some_and(|min| amount < min)
+                || native_token_query.max_amount.is_some_and(|max| amount > max)
+            {
+                return false;
+            }
+        }
+
+        let booked_timestamp = output_data.metadata.milestone_timestamp_booked();
+        if self.created_after.is_some_and(|after| booked_timestamp < after)
+            || self.created_before.is_some_and(|before| booked_timestamp >= before)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl OutputSort {
+    fn compare(&self, a: &OutputData, b: &OutputData) -> Ordering {
+        let ordering = match self.key {
+            OutputSortKey::BookedTimestamp => a
+                .metadata
+                .milestone_timestamp_booked()
+                .cmp(&b.metadata.milestone_timestamp_booked()),
+            OutputSortKey::Amount => a.output.amount().cmp(&b.output.amount()),
+            OutputSortKey::OutputId => Ordering::Equal,
+        }
+        .then_with(|| a.output_id.cmp(&b.output_id));
+
+        if self.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+impl AccountDetails {
+    /// Returns the page of the outputs of the account matching the query, without network requests.
+    pub fn query_outputs(&self, query: &OutputQuery) -> OutputQueryPage {
+        let mut outputs = self
+            .outputs
+            .values()
+            .filter(|output_data| query.matches(output_data))
+            .collect::<Vec<_>>();
+        outputs.sort_unstable_by(|a, b| query.sort.compare(a, b));
+
+        OutputQueryPage {
+            total: outputs.len(),
+            items: outputs
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit.unwrap_or(usize::MAX))
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns the page of the synced outputs of the account matching the query, without network requests.
+    pub async fn query_outputs(&self, query: OutputQuery) -> OutputQueryPage {
+        self.details().await.query_outputs(&query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::{
+        address::{Address, Ed25519Address, Hrp, ToBech32Ext},
+        output::{
+            unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition, TimelockUnlockCondition},
+            BasicOutputBuilder, NativeToken, OutputId, OutputMetadata,
+        },
+        payload::transaction::TransactionId,
+        BlockId,
+    };
+
+    fn output_data(index: u16, builder: BasicOutputBuilder, booked_timestamp: u32, is_spent: bool) -> OutputData {
+        let output_id = OutputId::new(TransactionId::null(), index).unwrap();
+        let address = Address::from(Ed25519Address::new([1; 32]));
+
+        OutputData {
+            output_id,
+            metadata: OutputMetadata::new(
+                BlockId::null(),
+                output_id,
+                is_spent,
+                None,
+                None,
+                None,
+                0,
+                booked_timestamp,
+                0,
+            ),
+            output: builder
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(u64::MAX)
+                .unwrap(),
+            is_spent,
+            address,
+            network_id: 0,
+            remainder: false,
+            chain: None,
+        }
+    }
+
+    fn outputs() -> [OutputData; 3] {
+        let return_address = Address::from(Ed25519Address::new([2; 32]));
+
+        [
+            output_data(
+                0,
+                BasicOutputBuilder::new_with_amount(1_000_000)
+                    .add_feature(TagFeature::new(b"tag".to_vec()).unwrap())
+                    .add_unlock_condition(TimelockUnlockCondition::new(200).unwrap()),
+                10,
+                false,
+            ),
+            output_data(
+                1,
+                BasicOutputBuilder::new_with_amount(2_000_000)
+                    .add_native_token(NativeToken::new(TokenId::new([3; TokenId::LENGTH]), 100).unwrap())
+                    .add_unlock_condition(ExpirationUnlockCondition::new(return_address, 300).unwrap()),
+                30,
+                false,
+            ),
+            output_data(2, BasicOutputBuilder::new_with_amount(3_000_000), 20, true),
+        ]
+    }
+
+    #[test]
+    fn output_query_matches() {
+        let [timelocked, expiring, spent] = outputs();
+        let return_address = Address::from(Ed25519Address::new([2; 32])).to_bech32(Hrp::from_str_unchecked("rms"));
+
+        let query = OutputQuery::default();
+        assert!(query.matches(&timelocked) && query.matches(&expiring) && !query.matches(&spent));
+
+        let query = OutputQuery {
+            include_spent: true,
+            min_amount: Some(2_000_000),
+            ..Default::default()
+        };
+        assert!(!query.matches(&timelocked) && query.matches(&expiring) && query.matches(&spent));
+
+        let query = OutputQuery {
+            tag: Some(b"tag".to_vec()),
+            timelocked_after: Some(100),
+            timelocked_before: Some(300),
+            ..Default::default()
+        };
+        assert!(query.matches(&timelocked) && !query.matches(&expiring));
+
+        let query = OutputQuery {
+            has_timelock: Some(false),
+            expiration_return_address: Some(return_address),
+            native_token: Some(NativeTokenQuery {
+                token_id: TokenId::new([3; TokenId::LENGTH]),
+                min_amount: Some(U256::from(100)),
+                max_amount: None,
+            }),
+            ..Default::default()
+        };
+        assert!(!query.matches(&timelocked) && query.matches(&expiring));
+
+        let query = OutputQuery {
+            expires_before: Some(300),
+            ..Default::default()
+        };
+        assert!(!query.matches(&timelocked) && !query.matches(&expiring));
+    }
+
+    #[test]
+    fn output_query_sort() {
+        let mut outputs = outputs();
+
+        let sort = OutputSort::default();
+        outputs.sort_unstable_by(|a, b| sort.compare(a, b));
+        assert_eq!(
+            outputs.iter().map(|o| o.output.amount()).collect::<Vec<_>>(),
+            [1_000_000, 3_000_000, 2_000_000]
+        );
+
+        let sort = OutputSort {
+            key: OutputSortKey::Amount,
+            descending: true,
+        };
+        outputs.sort_unstable_by(|a, b| sort.compare(a, b));
+        assert_eq!(
+            outputs.iter().map(|o| o.output.amount()).collect::<Vec<_>>(),
+            [3_000_000, 2_000_000, 1_000_000]
+        );
+    }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn query_outputs_pagination() {
+        let mut account = AccountDetails::mock();
+        account.outputs = outputs()
+            .into_iter()
+            .map(|output_data| (output_data.output_id, output_data))
+            .collect();
+
+        let query = OutputQuery {
+            include_spent: true,
+            offset: 1,
+            limit: Some(1),
+            ..Default::default()
+        };
+        let page = account.query_outputs(&query);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].metadata.milestone_timestamp_booked(), 20);
+    }
+}
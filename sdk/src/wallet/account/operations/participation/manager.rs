@@ -0,0 +1,418 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+// The participation manager runs with the background syncing. It re-casts votes which were lost because the voting
+// output was replaced without its participations, e.g. when increasing the voting power without a voting output, and
+// warns about participated events which end soon.
+
+use serde::{Deserialize, Serialize};
+
+use super::{is_valid_participation_output, output_participations};
+#[cfg(feature = "events")]
+use crate::wallet::events::types::WalletEvent;
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::{
+        api::plugins::participation::types::{
+            AddressStakingStatus, ParticipationEventData, ParticipationEventId, ParticipationEventPayload,
+            Participations, PARTICIPATION_TAG,
+        },
+        block::{
+            address::ToBech32Ext,
+            output::{
+                feature::{MetadataFeature, TagFeature},
+                BasicOutputBuilder, Feature, OutputId,
+            },
+            payload::TaggedDataPayload,
+        },
+    },
+    wallet::{
+        account::{types::Transaction, Account, TransactionOptions},
+        Result,
+    },
+};
+
+/// The default number of milestones before the end of an event to warn about it, one day with 10 second milestones.
+pub const DEFAULT_EVENT_ENDING_WARNING_MILESTONES: u32 = 8640;
+
+/// Options of the participation manager.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipationManagerOptions {
+    /// Re-cast votes which are missing in the voting output.
+    pub renew_votes: bool,
+    /// Emit a warning event when a participated event ends within this number of milestones.
+    pub event_ending_warning_milestones: u32,
+}
+
+impl Default for ParticipationManagerOptions {
+    fn default() -> Self {
+        Self {
+            renew_votes: true,
+            event_ending_warning_milestones: DEFAULT_EVENT_ENDING_WARNING_MILESTONES,
+        }
+    }
+}
+
+/// Projected staking rewards of an account for a staking event.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingRewardsProjection {
+    /// The event id.
+    pub event_id: ParticipationEventId,
+    /// The symbol of the rewards.
+    pub symbol: String,
+    /// The amount staked for the event.
+    #[serde(with = "crate::utils::serde::string")]
+    pub staked_amount: u64,
+    /// The rewards until the milestone index.
+    #[serde(with = "crate::utils::serde::string")]
+    pub rewards: u64,
+    /// The rewards at the end of the event if the staked amount doesn't change.
+    #[serde(with = "crate::utils::serde::string")]
+    pub projected_rewards: u64,
+    /// The milestone index the rewards were calculated for.
+    pub milestone_index: u32,
+    /// The milestones until the event ends.
+    pub remaining_milestones: u32,
+    /// If the projected rewards reach the required minimum rewards of the event.
+    pub minimum_reached: bool,
+}
+
+impl StakingRewardsProjection {
+    /// Projects the rewards of a staking event, returns `None` if the event isn't a staking event.
+    /// Every milestone of the event `staked_amount * numerator / denominator` is rewarded.
+    pub fn new(
+        event_id: ParticipationEventId,
+        event: &ParticipationEventData,
+        staked_amount: u64,
+        staking_status: &AddressStakingStatus,
+    ) -> Option<Self> {
+        let ParticipationEventPayload::StakingEventPayload(payload) = event.payload() else {
+            return None;
+        };
+
+        let rewards = staking_status
+            .rewards
+            .get(&event_id.to_string())
+            .map_or(0, |status| status.amount);
+        let remaining_milestones = event
+            .milestone_index_end()
+            .saturating_sub(staking_status.milestone_index.max(*event.milestone_index_start()));
+        let rewards_per_milestone = (staked_amount as u128 * *payload.numerator() as u128)
+            .checked_div(*payload.denominator() as u128)
+            .unwrap_or_default();
+        let projected_rewards =
+            u64::try_from(rewards as u128 + rewards_per_milestone * remaining_milestones as u128).unwrap_or(u64::MAX);
+
+        Some(Self {
+            event_id,
+            symbol: payload.symbol().clone(),
+            staked_amount,
+            rewards,
+            projected_rewards,
+            milestone_index: staking_status.milestone_index,
+            remaining_milestones,
+            minimum_reached: projected_rewards >= *payload.required_minimum_rewards(),
+        })
+    }
+}
+
+/// A warning about the participations of the account.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "warning", rename_all = "camelCase", rename_all_fields = "camelCase")]
+#[non_exhaustive]
+pub enum ParticipationWarningEvent {
+    /// A participation event the account participates in ends soon.
+    EventEnding {
+        event_id: ParticipationEventId,
+        milestone_index_end: u32,
+        /// The milestones until the event ends.
+        milestones_left: u32,
+    },
+    /// A transaction consumes an output without carrying over its participations, which ends them.
+    ParticipationEnding {
+        output_id: OutputId,
+        event_ids: Vec<ParticipationEventId>,
+    },
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Sets the options of the participation manager, which runs with the background syncing. `None` disables it.
+    pub async fn set_participation_manager_options(&self, options: Option<ParticipationManagerOptions>) -> Result<()> {
+        let account_index = self.details().await.index;
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .set_participation_manager_options(account_index, options.as_ref())
+            .await
+    }
+
+    /// Returns the options of the participation manager, `None` if it's disabled.
+    pub async fn participation_manager_options(&self) -> Result<Option<ParticipationManagerOptions>> {
+        let account_index = self.details().await.index;
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .get_participation_manager_options(account_index)
+            .await
+    }
+
+    /// Runs the participation manager once if it's enabled: emits a warning event for each participated event which
+    /// ends soon, once per event, and re-casts missing votes.
+    pub async fn manage_participations(&self) -> Result<Option<Transaction>> {
+        let Some(options) = self.participation_manager_options().await? else {
+            return Ok(None);
+        };
+        log::debug!("[manage_participations]");
+
+        #[cfg(feature = "events")]
+        {
+            let account_index = self.details().await.index;
+            let warnings = self
+                .ending_participation_events(options.event_ending_warning_milestones)
+                .await?;
+            let mut warned_events = self
+                .wallet
+                .storage_manager
+                .read()
+                .await
+                .get_participation_warned_events(account_index)
+                .await?;
+            let warned_events_len = warned_events.len();
+
+            for warning in warnings {
+                if let ParticipationWarningEvent::EventEnding { event_id, .. } = &warning {
+                    if warned_events.insert(*event_id) {
+                        self.emit(account_index, WalletEvent::ParticipationWarning(warning))
+                            .await;
+                    }
+                }
+            }
+
+            if warned_events.len() > warned_events_len {
+                self.wallet
+                    .storage_manager
+                    .read()
+                    .await
+                    .set_participation_warned_events(account_index, &warned_events)
+                    .await?;
+            }
+        }
+
+        if options.renew_votes {
+            self.renew_votes().await
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns a warning for each event the voting output participates in, which ends within the number of
+    /// milestones. Only registered events are checked.
+    pub async fn ending_participation_events(&self, within_milestones: u32) -> Result<Vec<ParticipationWarningEvent>> {
+        let Some(participations) = self
+            .get_voting_output()
+            .await?
+            .and_then(|output_data| output_participations(&output_data.output))
+        else {
+            return Ok(Vec::new());
+        };
+        let events = self.get_participation_events().await?;
+        let latest_milestone_index = self.client().get_info().await?.node_info.status.latest_milestone.index;
+
+        Ok(participations
+            .participations
+            .iter()
+            .filter_map(|participation| {
+                let milestone_index_end = *events.get(&participation.event_id)?.data.milestone_index_end();
+                let milestones_left = milestone_index_end.checked_sub(latest_milestone_index)?;
+                (milestones_left <= within_milestones).then_some(ParticipationWarningEvent::EventEnding {
+                    event_id: participation.event_id,
+                    milestone_index_end,
+                    milestones_left,
+                })
+            })
+            .collect())
+    }
+
+    /// Re-casts the votes of the latest participations of the account which are missing in the voting output, e.g.
+    /// after the voting power was increased without a voting output. Returns `None` if no votes are missing.
+    pub async fn renew_votes(&self) -> Result<Option<Transaction>> {
+        self.in_transaction_span(async {
+            match self.prepare_renew_votes().await? {
+                Some(prepared) => Ok(Some(self.sign_and_submit_transaction(prepared, None).await?)),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    /// Prepares the transaction for [Account::renew_votes()](crate::wallet::Account::renew_votes).
+    pub async fn prepare_renew_votes(&self) -> Result<Option<PreparedTransactionData>> {
+        let Some(voting_output) = self.get_voting_output().await? else {
+            return Ok(None);
+        };
+        let output = voting_output.output.as_basic();
+        let mut participations = output_participations(&voting_output.output).unwrap_or(Participations {
+            participations: Vec::new(),
+        });
+
+        // The participations of the latest output which had participation metadata, this is the voting output itself
+        // if its participations were updated last
+        let latest_participations = self
+            .details()
+            .await
+            .outputs()
+            .values()
+            .filter(|output_data| is_valid_participation_output(&output_data.output))
+            .filter_map(|output_data| {
+                output_participations(&output_data.output).map(|participations| {
+                    (
+                        (output_data.metadata.milestone_timestamp_booked(), !output_data.is_spent),
+                        participations,
+                    )
+                })
+            })
+            .max_by_key(|(booked, _)| *booked)
+            .map(|(_, participations)| participations);
+
+        let mut missing_participations = Participations {
+            participations: latest_participations
+                .map(|latest| latest.participations)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|latest| {
+                    !participations
+                        .participations
+                        .iter()
+                        .any(|participation| participation.event_id == latest.event_id)
+                })
+                .collect(),
+        };
+        if missing_participations.participations.is_empty() {
+            return Ok(None);
+        }
+        self.remove_ended_participation_events(&mut missing_participations)
+            .await?;
+        if missing_participations.participations.is_empty() {
+            return Ok(None);
+        }
+        log::debug!(
+            "[prepare_renew_votes] re-casting {} votes",
+            missing_participations.participations.len()
+        );
+
+        for participation in missing_participations.participations {
+            participations.add_or_replace(participation);
+        }
+        let participation_bytes = participations.to_bytes()?;
+
+        let new_output = BasicOutputBuilder::from(output)
+            .with_features([
+                Feature::Tag(TagFeature::new(PARTICIPATION_TAG)?),
+                Feature::Metadata(MetadataFeature::new(participation_bytes.clone())?),
+            ])
+            .finish_output(self.client().get_token_supply().await?)?;
+
+        self.prepare_transaction(
+            [new_output],
+            Some(TransactionOptions {
+                // Only use previous voting output as input.
+                custom_inputs: Some(vec![voting_output.output_id]),
+                mandatory_inputs: Some(vec![voting_output.output_id]),
+                tagged_data_payload: Some(TaggedDataPayload::new(
+                    PARTICIPATION_TAG.as_bytes().to_vec(),
+                    participation_bytes,
+                )?),
+                ..Default::default()
+            }),
+        )
+        .await
+        .map(Some)
+    }
+
+    /// Projects the staking rewards of the voting output for a staking event, with the rewards of its address until
+    /// now from the node of the event.
+    pub async fn projected_staking_rewards(&self, event_id: ParticipationEventId) -> Result<StakingRewardsProjection> {
+        let event_client = self.get_client_for_event(&event_id).await?;
+        let event = event_client.event(&event_id).await?;
+        let voting_output = self.get_voting_output().await?;
+
+        let staked_amount = voting_output
+            .as_ref()
+            .filter(|output_data| {
+                output_participations(&output_data.output).is_some_and(|participations| {
+                    participations
+                        .participations
+                        .iter()
+                        .any(|participation| participation.event_id == event_id)
+                })
+            })
+            .map_or(0, |output_data| output_data.output.amount());
+        let address = match &voting_output {
+            Some(output_data) => *output_data.output.as_basic().address(),
+            None => {
+                self.public_addresses()
+                    .await
+                    .first()
+                    .expect("account needs to have a public address")
+                    .address
+                    .inner
+            }
+        };
+        let staking_status = event_client
+            .address_staking_status(address.to_bech32(self.client().get_bech32_hrp().await?))
+            .await?;
+
+        StakingRewardsProjection::new(event_id, &event, staked_amount, &staking_status)
+            .ok_or_else(|| crate::wallet::Error::Voting(format!("event {event_id} is not a staking event")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::types::api::plugins::participation::types::StakingStatus;
+
+    #[test]
+    fn staking_rewards_projection() {
+        let event_id = ParticipationEventId::new([42; 32]);
+        // Staking event from milestone 1000 to 9999, rewarding the staked amount every milestone
+        let event = ParticipationEventData::mock();
+        let staking_status = |milestone_index, rewards| AddressStakingStatus {
+            rewards: HashMap::from([(
+                event_id.to_string(),
+                StakingStatus {
+                    amount: rewards,
+                    symbol: ":rocket:".to_string(),
+                    minimum_reached: rewards >= 100,
+                },
+            )]),
+            milestone_index,
+        };
+
+        let projection = StakingRewardsProjection::new(event_id, &event, 10, &staking_status(9000, 50)).unwrap();
+        assert_eq!(projection.remaining_milestones, 999);
+        assert_eq!(projection.projected_rewards, 50 + 9990);
+        assert!(projection.minimum_reached);
+
+        // Before the start only the milestones of the event count
+        let projection = StakingRewardsProjection::new(event_id, &event, 1, &staking_status(0, 0)).unwrap();
+        assert_eq!(projection.remaining_milestones, 8999);
+        assert_eq!(projection.projected_rewards, 8999);
+
+        // After the end nothing is added
+        let projection = StakingRewardsProjection::new(event_id, &event, 0, &staking_status(10_000, 20)).unwrap();
+        assert_eq!(projection.remaining_milestones, 0);
+        assert_eq!(projection.projected_rewards, 20);
+        assert!(!projection.minimum_reached);
+    }
+}
@@ -9,6 +9,7 @@
 // If the user has designated funds to vote with, the resulting output MUST NOT be used for input selection.
 
 pub(crate) mod event;
+pub(crate) mod manager;
 pub(crate) mod voting;
 pub(crate) mod voting_power;
 
@@ -16,6 +17,10 @@ use std::collections::{hash_map::Entry, HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "events")]
+use self::manager::ParticipationWarningEvent;
+#[cfg(feature = "events")]
+use crate::client::secret::types::InputSigningData;
 use crate::{
     client::{node_manager::node::Node, secret::SecretManage, Client},
     types::{
@@ -309,9 +314,22 @@ impl AccountDetails {
             .max_by_key(|output_data| output_data.output.amount())
             .cloned())
     }
+
+    /// Returns the ids of the unspent participation outputs which participate in events.
+    pub(crate) fn participating_output_ids(&self) -> HashSet<OutputId> {
+        self.unspent_outputs
+            .values()
+            .filter(|output_data| {
+                is_valid_participation_output(&output_data.output)
+                    && output_participations(&output_data.output)
+                        .is_some_and(|participations| !participations.participations.is_empty())
+            })
+            .map(|output_data| output_data.output_id)
+            .collect()
+    }
 }
 
-fn is_valid_participation_output(output: &Output) -> bool {
+pub(crate) fn is_valid_participation_output(output: &Output) -> bool {
     // Only basic outputs can be participation outputs.
     if let Output::Basic(basic_output) = &output {
         // Valid participation outputs can only have the AddressUnlockCondition.
@@ -328,6 +346,41 @@ fn is_valid_participation_output(output: &Output) -> bool {
     }
 }
 
+/// Returns a warning for each input with participations which aren't carried over to a participation output.
+#[cfg(feature = "events")]
+pub(crate) fn ended_participations(inputs: &[InputSigningData], outputs: &[Output]) -> Vec<ParticipationWarningEvent> {
+    let output_event_ids = outputs
+        .iter()
+        .filter(|output| is_valid_participation_output(output))
+        .filter_map(output_participations)
+        .flat_map(|participations| participations.participations)
+        .map(|participation| participation.event_id)
+        .collect::<HashSet<_>>();
+
+    inputs
+        .iter()
+        .filter(|input| is_valid_participation_output(&input.output))
+        .filter_map(|input| {
+            let event_ids = output_participations(&input.output)?
+                .participations
+                .into_iter()
+                .map(|participation| participation.event_id)
+                .filter(|event_id| !output_event_ids.contains(event_id))
+                .collect::<Vec<_>>();
+            (!event_ids.is_empty()).then_some(ParticipationWarningEvent::ParticipationEnding {
+                output_id: *input.output_id(),
+                event_ids,
+            })
+        })
+        .collect()
+}
+
+/// Returns the participations of the metadata of an output.
+pub(crate) fn output_participations(output: &Output) -> Option<Participations> {
+    let metadata = output.features()?.metadata()?;
+    Participations::from_bytes(&mut metadata.data()).ok()
+}
+
 #[cfg(test)]
 impl ParticipationEventWithNodes {
     pub fn mock() -> Self {
@@ -41,9 +41,12 @@ where
         event_id: impl Into<Option<ParticipationEventId>> + Send,
         answers: impl Into<Option<Vec<u8>>> + Send,
     ) -> Result<Transaction> {
-        let prepared = self.prepare_vote(event_id, answers).await?;
+        self.in_transaction_span(async {
+            let prepared = self.prepare_vote(event_id, answers).await?;
 
-        self.sign_and_submit_transaction(prepared, None).await
+            self.sign_and_submit_transaction(prepared, None).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -132,9 +135,12 @@ where
     /// milestones in there against latest network milestone).
     /// If NOT already voting for this event, throws an error.
     pub async fn stop_participating(&self, event_id: ParticipationEventId) -> Result<Transaction> {
-        let prepared = self.prepare_stop_participating(event_id).await?;
+        self.in_transaction_span(async {
+            let prepared = self.prepare_stop_participating(event_id).await?;
 
-        self.sign_and_submit_transaction(prepared, None).await
+            self.sign_and_submit_transaction(prepared, None).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
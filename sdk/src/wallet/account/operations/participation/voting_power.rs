@@ -44,9 +44,12 @@ where
     /// Prioritizes consuming outputs that are designated for voting but don't have any metadata (only possible if user
     /// increases voting power then increases again immediately after).
     pub async fn increase_voting_power(&self, amount: u64) -> Result<Transaction> {
-        let prepared = self.prepare_increase_voting_power(amount).await?;
+        self.in_transaction_span(async {
+            let prepared = self.prepare_increase_voting_power(amount).await?;
 
-        self.sign_and_submit_transaction(prepared, None).await
+            self.sign_and_submit_transaction(prepared, None).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -103,9 +106,12 @@ where
     /// Prioritizes consuming outputs that are designated for voting but don't have any metadata (only possible if user
     /// increases voting power then decreases immediately after).
     pub async fn decrease_voting_power(&self, amount: u64) -> Result<Transaction> {
-        let prepared = self.prepare_decrease_voting_power(amount).await?;
+        self.in_transaction_span(async {
+            let prepared = self.prepare_decrease_voting_power(amount).await?;
 
-        self.sign_and_submit_transaction(prepared, None).await
+            self.sign_and_submit_transaction(prepared, None).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -0,0 +1,196 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "events")]
+use crate::wallet::events::types::WalletEvent;
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        output::{
+            AliasOutputBuilder, BasicOutputBuilder, FoundryOutputBuilder, NftOutputBuilder, Output, Rent, RentStructure,
+        },
+        payload::{
+            transaction::{TransactionEssence, TransactionId},
+            Payload,
+        },
+        protocol::ProtocolParameters,
+    },
+    wallet::{
+        account::{operations::transaction::TransactionOptions, Account},
+        Result,
+    },
+};
+
+/// Emitted when the protocol parameters of the network changed since the previous sync of the account, e.g. because
+/// a protocol parameters upgrade announced by a milestone became active.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolParametersUpgradeEvent {
+    pub previous_protocol_parameters: ProtocolParameters,
+    pub protocol_parameters: ProtocolParameters,
+    /// Pending transactions with outputs which don't cover their storage deposit with the new rent structure.
+    pub insufficient_storage_deposit_transactions: Vec<TransactionId>,
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Compares the protocol parameters of the client with the ones of the previous sync, which are persisted with the
+    /// `storage` feature. If they changed, the pending transactions are checked against the new rent structure and,
+    /// with the `events` feature, a [`ProtocolParametersUpgradeEvent`] is emitted.
+    pub(crate) async fn check_protocol_parameters_upgrade(&self) -> Result<()> {
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let mut last_protocol_parameters = self.protocol_parameters.lock().await;
+        if last_protocol_parameters.as_ref() == Some(&protocol_parameters) {
+            return Ok(());
+        }
+        #[cfg(feature = "storage")]
+        {
+            let account_index = *self.details().await.index();
+            self.wallet
+                .storage_manager
+                .read()
+                .await
+                .set_protocol_parameters(account_index, &protocol_parameters)
+                .await?;
+        }
+        let previous_protocol_parameters = last_protocol_parameters.replace(protocol_parameters.clone());
+        drop(last_protocol_parameters);
+        let Some(previous_protocol_parameters) = previous_protocol_parameters else {
+            return Ok(());
+        };
+        log::debug!(
+            "[SYNC] protocol parameters changed from version {} to version {}",
+            previous_protocol_parameters.protocol_version(),
+            protocol_parameters.protocol_version()
+        );
+
+        let account_details = self.details().await;
+        let mut insufficient_storage_deposit_transactions = account_details
+            .pending_transactions()
+            .iter()
+            .filter_map(|transaction_id| account_details.transactions().get(transaction_id))
+            .filter(|transaction| {
+                let TransactionEssence::Regular(essence) = transaction.payload.essence();
+                !covers_storage_deposits(essence.outputs(), &protocol_parameters)
+            })
+            .map(|transaction| transaction.transaction_id)
+            .collect::<Vec<_>>();
+        insufficient_storage_deposit_transactions.sort();
+        #[cfg(feature = "events")]
+        let account_index = account_details.index;
+        drop(account_details);
+
+        for transaction_id in &insufficient_storage_deposit_transactions {
+            log::warn!("[SYNC] pending transaction {transaction_id} doesn't cover the new storage deposits");
+        }
+
+        #[cfg(feature = "events")]
+        self.emit(
+            account_index,
+            WalletEvent::ProtocolParametersUpgrade(Box::new(ProtocolParametersUpgradeEvent {
+                previous_protocol_parameters,
+                protocol_parameters,
+                insufficient_storage_deposit_transactions,
+            })),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Checks a prepared transaction against the current protocol parameters, e.g. after a
+    /// [`ProtocolParametersUpgradeEvent`]. If it's still valid it's returned unchanged, otherwise it's prepared again
+    /// with the same inputs and the amounts of the outputs which don't cover their storage deposit raised to it.
+    ///
+    /// The `options` should be the ones the transaction was prepared with. The remainder is computed again.
+    pub async fn revalidate_prepared_transaction(
+        &self,
+        prepared_transaction_data: PreparedTransactionData,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] revalidate_prepared_transaction");
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+
+        if essence.network_id() == protocol_parameters.network_id()
+            && covers_storage_deposits(essence.outputs(), &protocol_parameters)
+        {
+            return Ok(prepared_transaction_data);
+        }
+
+        let remainder_output = prepared_transaction_data
+            .remainder
+            .as_ref()
+            .map(|remainder| &remainder.output);
+        let outputs = essence
+            .outputs()
+            .iter()
+            .filter(|output| Some(*output) != remainder_output)
+            .map(|output| {
+                with_storage_deposit(
+                    output,
+                    *protocol_parameters.rent_structure(),
+                    protocol_parameters.token_supply(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut options = options.into().unwrap_or_default();
+        // Keep the inputs, so the chain outputs are transitioned the same way.
+        let mandatory_inputs = options.mandatory_inputs.get_or_insert_with(Vec::new);
+        for input in &prepared_transaction_data.inputs_data {
+            if !mandatory_inputs.contains(input.output_id()) {
+                mandatory_inputs.push(*input.output_id());
+            }
+        }
+        if options.tagged_data_payload.is_none() {
+            if let Some(Payload::TaggedData(tagged_data_payload)) = essence.payload() {
+                options.tagged_data_payload = Some(*tagged_data_payload.clone());
+            }
+        }
+
+        // The inputs are locked by the outdated transaction until they're unlocked.
+        self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+        self.prepare_transaction(outputs, options).await
+    }
+}
+
+// Returns whether all outputs cover their storage deposit with the rent structure of the protocol parameters.
+fn covers_storage_deposits(outputs: &[Output], protocol_parameters: &ProtocolParameters) -> bool {
+    outputs.iter().all(|output| {
+        output
+            .verify_storage_deposit(
+                *protocol_parameters.rent_structure(),
+                protocol_parameters.token_supply(),
+            )
+            .is_ok()
+    })
+}
+
+// Raises the amount of an output to its storage deposit if it doesn't cover it.
+fn with_storage_deposit(output: &Output, rent_structure: RentStructure, token_supply: u64) -> Result<Output> {
+    let amount = output.rent_cost(&rent_structure);
+    if output.amount() >= amount {
+        return Ok(output.clone());
+    }
+
+    Ok(match output {
+        Output::Basic(output) => BasicOutputBuilder::from(output)
+            .with_amount(amount)
+            .finish_output(token_supply)?,
+        Output::Alias(output) => AliasOutputBuilder::from(output)
+            .with_amount(amount)
+            .finish_output(token_supply)?,
+        Output::Foundry(output) => FoundryOutputBuilder::from(output)
+            .with_amount(amount)
+            .finish_output(token_supply)?,
+        Output::Nft(output) => NftOutputBuilder::from(output)
+            .with_amount(amount)
+            .finish_output(token_supply)?,
+        Output::Treasury(_) => output.clone(),
+    })
+}
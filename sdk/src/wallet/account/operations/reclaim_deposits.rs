@@ -0,0 +1,207 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{
+        api::{PreparedTransactionData, RentOptimizer, RentSuggestion},
+        secret::SecretManage,
+    },
+    types::block::{
+        address::Bech32Address,
+        input::INPUT_COUNT_MAX,
+        output::{
+            unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeTokens, NativeTokensBuilder, Output,
+            OUTPUT_COUNT_MAX,
+        },
+    },
+    wallet::{
+        account::{
+            operations::output_claiming::get_new_native_token_count,
+            types::{OutputData, Transaction},
+            Account, TransactionOptions,
+        },
+        Result,
+    },
+};
+
+/// The minimum number of outputs holding native tokens that are reclaimed by default.
+const DEFAULT_RECLAIM_DEPOSITS_THRESHOLD: usize = 2;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReclaimDepositsParams {
+    /// Reclaims if the number of outputs holding native tokens is >= the output_threshold.
+    output_threshold: Option<usize>,
+    /// Address to which the native tokens should be sent.
+    target_address: Option<Bech32Address>,
+}
+
+impl ReclaimDepositsParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_output_threshold(mut self, output_threshold: impl Into<Option<usize>>) -> Self {
+        self.output_threshold = output_threshold.into();
+        self
+    }
+
+    pub fn with_target_address(mut self, target_address: impl Into<Option<Bech32Address>>) -> Self {
+        self.target_address = target_address.into();
+        self
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns suggestions to reduce the storage deposits the outputs would lock with the current rent structure, see
+    /// [`RentOptimizer::suggest()`].
+    pub async fn suggest_rent_optimizations(&self, outputs: &[Output]) -> Result<Vec<RentSuggestion>> {
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+
+        Ok(RentOptimizer::from(&protocol_parameters).suggest(outputs)?)
+    }
+
+    /// Reclaims storage deposits by sending the native tokens of basic outputs with only an [AddressUnlockCondition]
+    /// to as few outputs as possible, if the number of such outputs is >= the output_threshold. The storage deposits
+    /// that aren't needed anymore are added to the last output. Only reclaims the amount of outputs that fit into a
+    /// single transaction.
+    pub async fn reclaim_deposits(&self, params: ReclaimDepositsParams) -> Result<Transaction> {
+        self.in_transaction_span(async {
+            let prepared_transaction = self.prepare_reclaim_deposits(params).await?;
+            let reclaim_tx = self.sign_and_submit_transaction(prepared_transaction, None).await?;
+
+            log::debug!(
+                "[RECLAIM_DEPOSITS] reclaim transaction created: block_id: {:?} tx_id: {:?}",
+                reclaim_tx.block_id,
+                reclaim_tx.transaction_id
+            );
+
+            Ok(reclaim_tx)
+        })
+        .await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::reclaim_deposits()](crate::wallet::Account::reclaim_deposits).
+    pub async fn prepare_reclaim_deposits(&self, params: ReclaimDepositsParams) -> Result<PreparedTransactionData> {
+        log::debug!("[RECLAIM_DEPOSITS] prepare reclaiming storage deposits if possible");
+        #[cfg(feature = "participation")]
+        let voting_output = self.get_voting_output().await?;
+        let current_time = self.client().get_time_checked().await?;
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let rent_optimizer = RentOptimizer::from(&protocol_parameters);
+        let output_threshold = params.output_threshold.unwrap_or(DEFAULT_RECLAIM_DEPOSITS_THRESHOLD);
+        let mut outputs_to_reclaim: Vec<OutputData> = Vec::new();
+        let account_details = self.details().await;
+        let account_addresses = &account_details.addresses_with_unspent_outputs[..];
+        #[cfg(feature = "participation")]
+        let participating_output_ids = account_details.participating_output_ids();
+
+        for (output_id, output_data) in account_details.unspent_outputs() {
+            #[cfg(feature = "participation")]
+            if let Some(ref voting_output) = voting_output {
+                // Keep the voting output, its features are needed.
+                if output_data.output_id == voting_output.output_id {
+                    continue;
+                }
+            }
+            // Keep the outputs with participations as well.
+            #[cfg(feature = "participation")]
+            if participating_output_ids.contains(output_id) {
+                continue;
+            }
+            let holds_native_tokens = output_data
+                .output
+                .native_tokens()
+                .is_some_and(|native_tokens| !native_tokens.is_empty());
+            if holds_native_tokens
+                && !account_details.locked_outputs.contains(output_id)
+                && self.should_consolidate_output(output_data, current_time, account_addresses)?
+            {
+                outputs_to_reclaim.push(output_data.clone());
+            }
+        }
+
+        drop(account_details);
+
+        let mut custom_inputs = Vec::new();
+        let mut inputs = Vec::new();
+        let mut total_amount = 0;
+        let mut total_native_tokens = NativeTokensBuilder::new();
+
+        for output_data in outputs_to_reclaim.iter().take(INPUT_COUNT_MAX.into()) {
+            // Checked above, only outputs holding native tokens are reclaimed.
+            let native_tokens = output_data.output.native_tokens().unwrap();
+            // Skip the output if the native tokens wouldn't fit into the outputs of a single transaction.
+            let native_token_count = get_new_native_token_count(&total_native_tokens, native_tokens)?;
+            if native_token_count > usize::from(OUTPUT_COUNT_MAX) * usize::from(NativeTokens::COUNT_MAX) {
+                continue;
+            }
+            total_native_tokens.add_native_tokens(native_tokens.clone())?;
+            total_amount += output_data.output.amount();
+            custom_inputs.push(output_data.output_id);
+            inputs.push(output_data.output.clone());
+        }
+
+        let target_address = params
+            .target_address
+            .map(|bech32| bech32.into_inner())
+            .or_else(|| outputs_to_reclaim.first().map(|output_data| output_data.address));
+        let mut outputs = match target_address {
+            Some(target_address) => total_native_tokens
+                .finish_vec()?
+                .chunks(NativeTokens::COUNT_MAX.into())
+                .map(|native_tokens| {
+                    BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+                        .add_unlock_condition(AddressUnlockCondition::new(target_address))
+                        .with_native_tokens(native_tokens.iter().cloned())
+                        .finish_output(protocol_parameters.token_supply())
+                })
+                .collect::<core::result::Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        let reclaimed_deposit = rent_optimizer
+            .storage_deposit(&inputs)
+            .saturating_sub(rent_optimizer.storage_deposit(&outputs));
+
+        // only reclaim if the outputs are >= output_threshold and it frees deposits
+        if inputs.len() < output_threshold || reclaimed_deposit == 0 {
+            log::debug!(
+                "[RECLAIM_DEPOSITS] no deposits to reclaim, available_outputs: {}, output_threshold: {}",
+                inputs.len(),
+                output_threshold
+            );
+            return Err(crate::wallet::Error::NoOutputsToConsolidate {
+                available_outputs: inputs.len(),
+                consolidation_threshold: output_threshold,
+            });
+        }
+        log::debug!(
+            "[RECLAIM_DEPOSITS] reclaiming {reclaimed_deposit} from {} outputs",
+            inputs.len()
+        );
+
+        // The amount which isn't needed for the storage deposits goes to the last output.
+        let remaining_amount = total_amount - rent_optimizer.storage_deposit(&outputs);
+        if let Some(last_output) = outputs.pop() {
+            outputs.push(
+                BasicOutputBuilder::from(last_output.as_basic())
+                    .with_amount(last_output.amount() + remaining_amount)
+                    .finish_output(protocol_parameters.token_supply())?,
+            );
+        }
+
+        let options = Some(TransactionOptions {
+            custom_inputs: Some(custom_inputs),
+            ..Default::default()
+        });
+
+        self.prepare_transaction(outputs, options).await
+    }
+}
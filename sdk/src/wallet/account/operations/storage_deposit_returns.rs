@@ -0,0 +1,291 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::secret::SecretManage,
+    types::block::{
+        address::{Address, Bech32Address, ToBech32Ext},
+        output::{unlock_condition::ExpirationUnlockCondition, OutputId},
+        payload::transaction::TransactionEssence,
+    },
+    wallet::account::{types::InclusionState, Account},
+};
+
+/// The status of a storage deposit lent to a recipient with a
+/// [`StorageDepositReturnUnlockCondition`](crate::types::block::output::unlock_condition::StorageDepositReturnUnlockCondition).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StorageDepositReturnStatus {
+    /// The recipient didn't claim the output yet, the deposit is returned once it does
+    Outstanding,
+    /// The output expired before the recipient claimed it and can be reclaimed by the account
+    Reclaimable,
+    /// The recipient claimed the output and returned the deposit
+    Returned,
+    /// The output was reclaimed by the account after its expiration
+    Reclaimed,
+    /// The output expired and can only be unlocked by an expiration return address that doesn't belong to the account,
+    /// so the deposit will never come back
+    Lost,
+    /// The output was spent, but the node pruned its metadata, so it's unknown whether the deposit was returned
+    Unknown,
+}
+
+/// A storage deposit lent to a recipient, part of the [`StorageDepositLedger`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDepositReturn {
+    /// The id of the output sent to the recipient
+    #[getset(get = "pub")]
+    output_id: OutputId,
+    /// The recipient of the output
+    #[getset(get = "pub")]
+    recipient: Bech32Address,
+    /// The address the deposit is returned to
+    #[getset(get = "pub")]
+    return_address: Bech32Address,
+    /// The amount of the deposit
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    amount: u64,
+    /// Unix timestamp in seconds after which the recipient can't claim the output anymore
+    #[getset(get_copy = "pub")]
+    expiration_time: Option<u32>,
+    /// The status of the deposit
+    #[getset(get_copy = "pub")]
+    status: StorageDepositReturnStatus,
+}
+
+/// The storage deposits lent by an account, returned from [`Account::storage_deposit_returns()`].
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct StorageDepositLedger {
+    /// The storage deposits of all outputs sent by the account with a storage deposit return to it
+    entries: Vec<StorageDepositReturn>,
+}
+
+impl StorageDepositLedger {
+    /// Returns the sum of the deposits that the account still expects to get back.
+    pub fn outstanding_amount(&self) -> u64 {
+        self.outstanding().map(|entry| entry.amount).sum()
+    }
+
+    /// Returns the sum of the deposits the account still expects to get back, per recipient.
+    pub fn outstanding_by_recipient(&self) -> HashMap<Bech32Address, u64> {
+        self.outstanding().fold(HashMap::new(), |mut by_recipient, entry| {
+            *by_recipient.entry(entry.recipient).or_default() += entry.amount;
+            by_recipient
+        })
+    }
+
+    /// Returns the deposits that will never come back.
+    pub fn lost(&self) -> impl Iterator<Item = &StorageDepositReturn> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == StorageDepositReturnStatus::Lost)
+    }
+
+    /// Returns the sum of the deposits that will never come back.
+    pub fn lost_amount(&self) -> u64 {
+        self.lost().map(|entry| entry.amount).sum()
+    }
+
+    /// Returns the deposits of spent outputs whose metadata was pruned by the node, so their status is unknown.
+    pub fn unknown(&self) -> impl Iterator<Item = &StorageDepositReturn> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == StorageDepositReturnStatus::Unknown)
+    }
+
+    fn outstanding(&self) -> impl Iterator<Item = &StorageDepositReturn> {
+        self.entries.iter().filter(|entry| {
+            matches!(
+                entry.status,
+                StorageDepositReturnStatus::Outstanding | StorageDepositReturnStatus::Reclaimable
+            )
+        })
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns the ledger of storage deposits lent to recipients of outputs the account sent, like native tokens or
+    /// micro amounts. The deposits are taken from the confirmed transactions of the account, their status is
+    /// determined with the synced outputs or requested from the node.
+    pub async fn storage_deposit_returns(&self) -> crate::wallet::Result<StorageDepositLedger> {
+        log::debug!("[storage_deposit_returns]");
+
+        let bech32_hrp = self.client().get_bech32_hrp().await?;
+        let local_time = self.client().get_time_checked().await?;
+
+        let account_details = self.details().await;
+        let account_addresses = account_details
+            .addresses()
+            .into_iter()
+            .map(|address| address.address.inner)
+            .collect::<Vec<_>>();
+
+        // Outputs with a storage deposit return to the account and whether they're spent, with the timestamp of the
+        // milestone that spent them if known.
+        let mut lent_outputs = Vec::new();
+        let mut unknown_output_ids = Vec::new();
+        for transaction in account_details
+            .transactions()
+            .values()
+            .filter(|tx| !tx.incoming && tx.inclusion_state == InclusionState::Confirmed)
+        {
+            let TransactionEssence::Regular(essence) = transaction.payload.essence();
+            for (index, output) in essence.outputs().iter().enumerate() {
+                let Some(unlock_conditions) = output.unlock_conditions() else {
+                    continue;
+                };
+                let (Some(address), Some(sdr)) =
+                    (unlock_conditions.address(), unlock_conditions.storage_deposit_return())
+                else {
+                    continue;
+                };
+                if !account_addresses.contains(sdr.return_address()) || account_addresses.contains(address.address()) {
+                    continue;
+                }
+
+                let output_id = OutputId::new(transaction.transaction_id, index as u16)?;
+                let spent_at = match account_details.outputs().get(&output_id) {
+                    Some(output_data) if !output_data.is_spent => Some(None),
+                    Some(output_data) => output_data.metadata.milestone_timestamp_spent().map(Some),
+                    None => None,
+                };
+                if spent_at.is_none() {
+                    unknown_output_ids.push(output_id);
+                }
+
+                lent_outputs.push((
+                    output_id,
+                    *address.address(),
+                    *sdr.return_address(),
+                    sdr.amount(),
+                    unlock_conditions.expiration().copied(),
+                    spent_at,
+                ));
+            }
+        }
+        drop(account_details);
+
+        let metadata = self
+            .client()
+            .get_outputs_metadata_ignore_errors(&unknown_output_ids)
+            .await?
+            .into_iter()
+            .map(|metadata| (*metadata.output_id(), metadata))
+            .collect::<HashMap<_, _>>();
+
+        let mut entries = Vec::with_capacity(lent_outputs.len());
+        for (output_id, recipient, return_address, amount, expiration, spent_at) in lent_outputs {
+            // Without metadata, the output was pruned by the node, so it was spent a long time ago, before or after
+            // its expiration
+            let spent_at = spent_at.or_else(|| {
+                metadata.get(&output_id).map(|metadata| {
+                    metadata
+                        .is_spent()
+                        .then(|| metadata.milestone_timestamp_spent().unwrap_or(local_time))
+                })
+            });
+            let status = spent_at.map_or_else(
+                || {
+                    log::debug!("[storage_deposit_returns] metadata of output {output_id} not available");
+                    StorageDepositReturnStatus::Unknown
+                },
+                |spent_at| storage_deposit_return_status(expiration.as_ref(), spent_at, local_time, &account_addresses),
+            );
+
+            entries.push(StorageDepositReturn {
+                output_id,
+                recipient: recipient.to_bech32(bech32_hrp),
+                return_address: return_address.to_bech32(bech32_hrp),
+                amount,
+                expiration_time: expiration.as_ref().map(ExpirationUnlockCondition::timestamp),
+                status,
+            });
+        }
+
+        Ok(StorageDepositLedger { entries })
+    }
+}
+
+fn storage_deposit_return_status(
+    expiration: Option<&ExpirationUnlockCondition>,
+    spent_at: Option<u32>,
+    current_time: u32,
+    account_addresses: &[Address],
+) -> StorageDepositReturnStatus {
+    let expired_at = |time: u32| matches!(expiration, Some(expiration) if time >= expiration.timestamp());
+    // After the expiration, only the expiration return address can unlock the output, without returning the deposit
+    let reclaimable_by_account =
+        matches!(expiration, Some(expiration) if account_addresses.contains(expiration.return_address()));
+
+    match spent_at {
+        None if !expired_at(current_time) => StorageDepositReturnStatus::Outstanding,
+        Some(spent_at) if !expired_at(spent_at) => StorageDepositReturnStatus::Returned,
+        None if reclaimable_by_account => StorageDepositReturnStatus::Reclaimable,
+        Some(_) if reclaimable_by_account => StorageDepositReturnStatus::Reclaimed,
+        _ => StorageDepositReturnStatus::Lost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::address::Ed25519Address;
+
+    #[test]
+    fn status() {
+        let own_address = Address::from(Ed25519Address::new([1; Ed25519Address::LENGTH]));
+        let other_address = Address::from(Ed25519Address::new([2; Ed25519Address::LENGTH]));
+        let account_addresses = [own_address];
+
+        let own_expiration = ExpirationUnlockCondition::new(own_address, 100).unwrap();
+        let other_expiration = ExpirationUnlockCondition::new(other_address, 100).unwrap();
+
+        // Unspent
+        assert_eq!(
+            storage_deposit_return_status(None, None, 200, &account_addresses),
+            StorageDepositReturnStatus::Outstanding
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&own_expiration), None, 50, &account_addresses),
+            StorageDepositReturnStatus::Outstanding
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&own_expiration), None, 100, &account_addresses),
+            StorageDepositReturnStatus::Reclaimable
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&other_expiration), None, 100, &account_addresses),
+            StorageDepositReturnStatus::Lost
+        );
+
+        // Spent
+        assert_eq!(
+            storage_deposit_return_status(None, Some(200), 300, &account_addresses),
+            StorageDepositReturnStatus::Returned
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&other_expiration), Some(99), 300, &account_addresses),
+            StorageDepositReturnStatus::Returned
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&own_expiration), Some(150), 300, &account_addresses),
+            StorageDepositReturnStatus::Reclaimed
+        );
+        assert_eq!(
+            storage_deposit_return_status(Some(&other_expiration), Some(150), 300, &account_addresses),
+            StorageDepositReturnStatus::Lost
+        );
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::secret::SecretManage,
+    wallet::{
+        account::{
+            types::{OutputData, Transaction},
+            Account,
+        },
+        storage::{StoredOutputsQuery, StoredTransactionsQuery},
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns the outputs of the account matching the query, looked up in the indexes of the SQLite storage instead
+    /// of iterating over all outputs. Requires the wallet to use [`StorageKind::Sqlite`].
+    ///
+    /// [`StorageKind::Sqlite`]: crate::wallet::storage::StorageKind::Sqlite
+    pub async fn query_stored_outputs(&self, query: StoredOutputsQuery) -> crate::wallet::Result<Vec<OutputData>> {
+        log::debug!("[query_stored_outputs]");
+        let account_index = *self.details().await.index();
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .query_outputs(account_index, &query)
+            .await
+    }
+
+    /// Returns the transactions of the account matching the query, looked up in the indexes of the SQLite storage.
+    /// Requires the wallet to use [`StorageKind::Sqlite`].
+    ///
+    /// [`StorageKind::Sqlite`]: crate::wallet::storage::StorageKind::Sqlite
+    pub async fn query_stored_transactions(
+        &self,
+        query: StoredTransactionsQuery,
+    ) -> crate::wallet::Result<Vec<Transaction>> {
+        log::debug!("[query_stored_transactions]");
+        let account_index = *self.details().await.index();
+        self.wallet
+            .storage_manager
+            .read()
+            .await
+            .query_transactions(account_index, &query)
+            .await
+    }
+}
@@ -0,0 +1,355 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::{
+        api::{native_tokens_sum, SignedTransactionData, SwapOffer, SwapTransaction},
+        secret::SecretManage,
+    },
+    types::block::{
+        address::Address,
+        output::{
+            unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeToken, NftId, NftOutputBuilder, Output,
+        },
+        payload::transaction::TransactionEssence,
+    },
+    wallet::{
+        account::{types::Transaction, Account, TransactionOptions},
+        Error, Result,
+    },
+};
+
+/// The assets offered in a swap and the outputs wanted in return.
+#[derive(Clone, Debug, Default)]
+pub struct SwapOfferParams {
+    /// The amount of base coins offered, it's increased to the storage deposit the offered native tokens need.
+    amount: u64,
+    /// The native tokens offered.
+    native_tokens: Vec<NativeToken>,
+    /// The NFTs offered.
+    nft_ids: Vec<NftId>,
+    /// The outputs the maker wants to own after the swap, funded by the taker.
+    desired_outputs: Vec<Output>,
+}
+
+impl SwapOfferParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn with_native_tokens(mut self, native_tokens: impl IntoIterator<Item = NativeToken>) -> Self {
+        self.native_tokens = native_tokens.into_iter().collect();
+        self
+    }
+
+    pub fn with_nft_ids(mut self, nft_ids: impl IntoIterator<Item = NftId>) -> Self {
+        self.nft_ids = nft_ids.into_iter().collect();
+        self
+    }
+
+    pub fn with_desired_outputs(mut self, desired_outputs: impl IntoIterator<Item = Output>) -> Self {
+        self.desired_outputs = desired_outputs.into_iter().collect();
+        self
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+    crate::client::Error: From<S::Error>,
+{
+    /// Prepares a [`SwapOffer`] with inputs of the account holding the offered assets and the desired outputs. The
+    /// remainder stays with the maker. The inputs are locked until the swap is confirmed, or unlocked again with
+    /// [`Account::cancel_swap_offer()`].
+    pub async fn prepare_swap_offer(
+        &self,
+        params: SwapOfferParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<SwapOffer> {
+        log::debug!("[SWAP] prepare_swap_offer");
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let token_supply = protocol_parameters.token_supply();
+        let address = self.swap_address().await?;
+        let mut offered_outputs = Vec::new();
+
+        if params.amount > 0 || !params.native_tokens.is_empty() {
+            let output = BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .with_native_tokens(params.native_tokens)
+                .finish_output(token_supply)?;
+            offered_outputs.push(if params.amount > output.amount() {
+                BasicOutputBuilder::from(output.as_basic())
+                    .with_amount(params.amount)
+                    .finish_output(token_supply)?
+            } else {
+                output
+            });
+        }
+
+        if !params.nft_ids.is_empty() {
+            let unspent_outputs = self.unspent_outputs(None).await?;
+            for nft_id in params.nft_ids {
+                let nft_output = unspent_outputs
+                    .iter()
+                    .find_map(|output_data| match &output_data.output {
+                        Output::Nft(nft_output) if nft_output.nft_id_non_null(&output_data.output_id) == nft_id => {
+                            Some(nft_output)
+                        }
+                        _ => None,
+                    })
+                    .ok_or(Error::NftNotFoundInUnspentOutputs)?;
+                offered_outputs.push(
+                    NftOutputBuilder::from(nft_output)
+                        .with_nft_id(nft_id)
+                        .with_unlock_conditions([AddressUnlockCondition::new(address)])
+                        .finish_output(token_supply)?,
+                );
+            }
+        }
+
+        if offered_outputs.is_empty() {
+            return Err(crate::client::Error::InvalidSwap("no assets are offered").into());
+        }
+
+        // The offered outputs only select the inputs, the taker creates its own outputs for them.
+        let prepared_transaction_data = self
+            .prepare_transaction(offered_outputs.clone(), swap_options(options))
+            .await?;
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let mut outputs = params.desired_outputs;
+        outputs.extend(without(essence.outputs(), &offered_outputs));
+
+        Ok(SwapOffer::new(
+            prepared_transaction_data.inputs_data,
+            outputs,
+            &protocol_parameters,
+        )?)
+    }
+
+    /// Accepts the offer with a [`SwapTransaction`] signed by the account. The account receives the offered assets and
+    /// its inputs fund the outputs the maker asks for. The transaction is signed right away, so the account never
+    /// signs outputs it didn't create itself, see [`SwapTransaction::sign_as_taker()`]. The inputs are locked until
+    /// the swap is confirmed, or unlocked again with [`Account::cancel_swap_transaction()`].
+    pub async fn accept_swap_offer(
+        &self,
+        offer: &SwapOffer,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<SwapTransaction> {
+        log::debug!("[SWAP] accept_swap_offer");
+        let protocol_parameters = self.client().get_protocol_parameters().await?;
+        let rent_structure = *protocol_parameters.rent_structure();
+        let token_supply = protocol_parameters.token_supply();
+        let address = self.swap_address().await?;
+
+        let input_nft_ids = offer
+            .inputs_data
+            .iter()
+            .filter_map(|input| match &input.output {
+                Output::Nft(nft_output) => Some((nft_output.nft_id_non_null(input.output_id()), nft_output)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let output_nfts = offer
+            .outputs()
+            .iter()
+            .filter_map(|output| match output {
+                Output::Nft(nft_output) if !nft_output.nft_id().is_null() => Some((*nft_output.nft_id(), output)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // The NFTs the maker spends without creating them again are offered, the ones it creates without spending
+        // them are asked for.
+        let mut received_outputs = Vec::new();
+        for (nft_id, nft_output) in &input_nft_ids {
+            if !output_nfts.iter().any(|(output_nft_id, _)| output_nft_id == nft_id) {
+                received_outputs.push(
+                    NftOutputBuilder::from(*nft_output)
+                        .with_nft_id(*nft_id)
+                        .with_unlock_conditions([AddressUnlockCondition::new(address)])
+                        .finish_output(token_supply)?,
+                );
+            }
+        }
+        let mut required_outputs = output_nfts
+            .iter()
+            .filter(|(nft_id, _)| !input_nft_ids.iter().any(|(input_nft_id, _)| input_nft_id == nft_id))
+            .map(|(_, output)| (*output).clone())
+            .collect::<Vec<_>>();
+
+        let input_native_tokens = native_tokens_sum(offer.inputs_data.iter().map(|input| &input.output))?;
+        let output_native_tokens = native_tokens_sum(offer.outputs().iter())?;
+        let mut offered_native_tokens = Vec::new();
+        let mut asked_native_tokens = Vec::new();
+        for (token_id, amount) in input_native_tokens.iter() {
+            let output_amount = output_native_tokens.get(token_id).copied().unwrap_or_default();
+            if *amount > output_amount {
+                offered_native_tokens.push(NativeToken::new(*token_id, *amount - output_amount)?);
+            }
+        }
+        for (token_id, amount) in output_native_tokens.iter() {
+            let input_amount = input_native_tokens.get(token_id).copied().unwrap_or_default();
+            if *amount > input_amount {
+                asked_native_tokens.push(NativeToken::new(*token_id, *amount - input_amount)?);
+            }
+        }
+
+        // The asked native tokens and base coins are requested with an output which is dropped again after the input
+        // selection, the offered ones are received with an output holding at least its storage deposit.
+        let received = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .with_native_tokens(offered_native_tokens)
+            .finish_output(token_supply)?;
+        let requested = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .with_native_tokens(asked_native_tokens)
+            .finish_output(token_supply)?;
+        // The base coins the received output gets on top of the amount of the requested output.
+        let offered_amount = amount_sum(required_outputs.iter())
+            + amount_sum(offer.inputs_data.iter().map(|input| &input.output))
+            - amount_sum(offer.outputs().iter())
+            - amount_sum(received_outputs.iter());
+        let requested_amount = (requested.amount() as i128).max(received.amount() as i128 - offered_amount);
+        required_outputs.push(
+            BasicOutputBuilder::from(requested.as_basic())
+                .with_amount(requested_amount as u64)
+                .finish_output(token_supply)?,
+        );
+        received_outputs.push(
+            BasicOutputBuilder::from(received.as_basic())
+                .with_amount((requested_amount + offered_amount) as u64)
+                .finish_output(token_supply)?,
+        );
+
+        let prepared_transaction_data = self
+            .prepare_transaction(required_outputs.clone(), swap_options(options))
+            .await?;
+        let TransactionEssence::Regular(essence) = &prepared_transaction_data.essence;
+        let mut outputs = without(essence.outputs(), &required_outputs);
+        outputs.extend(received_outputs);
+
+        let result = async {
+            let mut swap_transaction = offer.accept(
+                prepared_transaction_data.inputs_data.clone(),
+                outputs,
+                &protocol_parameters,
+            )?;
+            let accepted = swap_transaction.clone();
+            let current_time = self.client().get_time_checked().await?;
+            swap_transaction
+                .sign_as_taker(&*self.get_secret_manager().read().await, &accepted, current_time)
+                .await?;
+            Ok(swap_transaction)
+        }
+        .await;
+
+        if result.is_err() {
+            self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+        }
+
+        result
+    }
+
+    /// Signs the inputs of the maker in a [`SwapTransaction`], after verifying that it completes the offer, see
+    /// [`SwapTransaction::sign_as_maker()`]. The taker signs when accepting the offer.
+    pub async fn sign_swap_transaction(
+        &self,
+        offer: &SwapOffer,
+        mut swap_transaction: SwapTransaction,
+    ) -> Result<SwapTransaction> {
+        log::debug!("[SWAP] sign_swap_transaction");
+        {
+            let account_details = self.details().await;
+            if !offer
+                .inputs_data
+                .iter()
+                .any(|input| account_details.outputs().contains_key(input.output_id()))
+            {
+                return Err(crate::client::Error::InvalidSwap("the account didn't make the offer").into());
+            }
+        }
+        let current_time = self.client().get_time_checked().await?;
+
+        swap_transaction
+            .sign_as_maker(&*self.get_secret_manager().read().await, offer, current_time)
+            .await?;
+
+        Ok(swap_transaction)
+    }
+
+    /// Submits a [`SwapTransaction`] signed by both parties and stores it in the account.
+    pub async fn submit_swap_transaction(
+        &self,
+        swap_transaction: SwapTransaction,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> Result<Transaction> {
+        log::debug!("[SWAP] submit_swap_transaction");
+        let current_time = self.client().get_time_checked().await?;
+        let inputs_data = swap_transaction.prepared_transaction_data.inputs_data.clone();
+        let transaction_payload = swap_transaction.finish(current_time)?;
+
+        self.submit_and_store_transaction(
+            SignedTransactionData {
+                transaction_payload,
+                inputs_data,
+            },
+            options,
+        )
+        .await
+    }
+
+    /// Cancels an offer made by the account and unlocks its inputs. An offer already accepted and signed by the
+    /// taker could still be completed by signing it, so it's only cancelled for good once the inputs are spent.
+    pub async fn cancel_swap_offer(&self, offer: &SwapOffer) -> Result<()> {
+        log::debug!("[SWAP] cancel_swap_offer");
+        self.unlock_inputs(&offer.inputs_data).await
+    }
+
+    /// Cancels a swap transaction the account is part of and unlocks its inputs.
+    pub async fn cancel_swap_transaction(&self, swap_transaction: &SwapTransaction) -> Result<()> {
+        log::debug!("[SWAP] cancel_swap_transaction");
+        self.unlock_inputs(&swap_transaction.prepared_transaction_data.inputs_data)
+            .await
+    }
+
+    // The address receiving the assets of a swap.
+    async fn swap_address(&self) -> Result<Address> {
+        Ok(*self
+            .addresses()
+            .await?
+            .first()
+            .ok_or(Error::FailedToGetRemainder)?
+            .address()
+            .inner())
+    }
+}
+
+// Swaps must keep the outputs as they are, so they can be found again after the input selection.
+fn swap_options(options: impl Into<Option<TransactionOptions>>) -> Option<TransactionOptions> {
+    options.into().map(|options| TransactionOptions {
+        optimize_storage_deposits: false,
+        ..options
+    })
+}
+
+fn amount_sum<'a>(outputs: impl Iterator<Item = &'a Output>) -> i128 {
+    outputs.map(|output| output.amount() as i128).sum()
+}
+
+// Returns the outputs without the removed ones.
+fn without(outputs: &[Output], removed: &[Output]) -> Vec<Output> {
+    let mut outputs = outputs.to_vec();
+
+    for removed in removed {
+        if let Some(index) = outputs.iter().position(|output| output == removed) {
+            outputs.remove(index);
+        }
+    }
+
+    outputs
+}
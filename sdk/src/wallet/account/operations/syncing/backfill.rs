@@ -0,0 +1,197 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::{hash_map::Entry, HashSet};
+
+use crypto::keys::bip44::Bip44;
+use futures::{StreamExt, TryStreamExt};
+
+use crate::{
+    client::{node_api::history::HistoryProvider, secret::SecretManage},
+    types::{
+        api::core::response::OutputWithMetadataResponse,
+        block::{
+            input::Input,
+            output::OutputId,
+            payload::{
+                transaction::{TransactionEssence, TransactionId},
+                Payload,
+            },
+        },
+    },
+    wallet::account::{
+        build_transaction_from_payload_and_inputs,
+        constants::PARALLEL_HISTORY_REQUESTS_AMOUNT,
+        types::{OutputData, Transaction},
+        Account,
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Backfills the spent outputs of the account addresses and the transactions which created or spent them from a
+    /// history provider, e.g. a permanode, after they were pruned by the regular nodes. Unspent outputs are left to
+    /// [`Account::sync()`]. Returns the ids of the added outputs.
+    pub async fn backfill_history(
+        &self,
+        history_provider: &dyn HistoryProvider,
+    ) -> crate::wallet::Result<Vec<OutputId>> {
+        log::debug!("[SYNC] backfill_history");
+        let network_id = self.client().get_network_id().await?;
+        let (addresses, known_output_ids, coin_type, account_index) = {
+            let account_details = self.details().await;
+            (
+                account_details
+                    .public_addresses
+                    .iter()
+                    .chain(&account_details.internal_addresses)
+                    .cloned()
+                    .collect::<Vec<_>>(),
+                account_details.outputs.keys().copied().collect::<HashSet<_>>(),
+                account_details.coin_type,
+                account_details.index,
+            )
+        };
+
+        let mut new_outputs = Vec::new();
+        for address in addresses {
+            let unknown_output_ids = history_provider
+                .address_output_ids(&address.address)
+                .await?
+                .into_iter()
+                .filter(|output_id| !known_output_ids.contains(output_id))
+                .collect::<Vec<_>>();
+            let outputs = futures::stream::iter(unknown_output_ids)
+                .map(|output_id| async move { history_provider.output(&output_id).await })
+                .buffer_unordered(PARALLEL_HISTORY_REQUESTS_AMOUNT)
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            // Unspent outputs are synced from the regular nodes together with the rest of the account.
+            for output in outputs.into_iter().flatten().filter(|o| o.metadata().is_spent()) {
+                new_outputs.push(OutputData {
+                    output_id: *output.metadata().output_id(),
+                    metadata: *output.metadata(),
+                    output: output.output().clone(),
+                    is_spent: true,
+                    address: address.address.inner,
+                    network_id,
+                    remainder: false,
+                    chain: Some(
+                        Bip44::new(coin_type)
+                            .with_account(account_index)
+                            .with_change(address.internal as _)
+                            .with_address_index(address.key_index),
+                    ),
+                });
+            }
+        }
+
+        // The transactions spending outputs of the account were sent by it, the others only created outputs for it.
+        let spending_transaction_ids = new_outputs
+            .iter()
+            .filter_map(|output_data| output_data.metadata.transaction_id_spent().copied())
+            .collect::<HashSet<_>>();
+        let creating_transaction_ids = new_outputs
+            .iter()
+            .map(|output_data| *output_data.metadata.transaction_id())
+            .filter(|transaction_id| !spending_transaction_ids.contains(transaction_id))
+            .collect::<HashSet<_>>();
+
+        let mut transactions = Vec::new();
+        for transaction_id in spending_transaction_ids.iter().chain(&creating_transaction_ids) {
+            if let Some(transaction) = self.backfill_transaction(history_provider, *transaction_id).await? {
+                transactions.push(transaction);
+            }
+        }
+
+        let mut account_details = self.details_mut().await;
+        let mut new_output_ids = Vec::new();
+        for output_data in new_outputs {
+            if let Entry::Vacant(entry) = account_details.outputs.entry(output_data.output_id) {
+                new_output_ids.push(output_data.output_id);
+                entry.insert(output_data);
+            }
+        }
+        for mut transaction in transactions {
+            let transaction_id = transaction.transaction_id;
+            account_details
+                .inaccessible_incoming_transactions
+                .remove(&transaction_id);
+            if spending_transaction_ids.contains(&transaction_id) {
+                transaction.incoming = false;
+                account_details
+                    .transactions
+                    .entry(transaction_id)
+                    .or_insert(transaction);
+            } else if !account_details.transactions.contains_key(&transaction_id) {
+                account_details
+                    .incoming_transactions
+                    .entry(transaction_id)
+                    .or_insert(transaction);
+            }
+        }
+
+        #[cfg(feature = "storage")]
+        self.save(Some(&account_details)).await?;
+
+        Ok(new_output_ids)
+    }
+
+    // Requests a transaction and its inputs from the history provider, `None` if it's unknown or already in the
+    // account.
+    async fn backfill_transaction(
+        &self,
+        history_provider: &dyn HistoryProvider,
+        transaction_id: TransactionId,
+    ) -> crate::wallet::Result<Option<Transaction>> {
+        {
+            let account_details = self.details().await;
+            if account_details.transactions.contains_key(&transaction_id)
+                || account_details.incoming_transactions.contains_key(&transaction_id)
+            {
+                return Ok(None);
+            }
+        }
+
+        let Some(block) = history_provider.included_block(&transaction_id).await? else {
+            return Ok(None);
+        };
+        let Some(Payload::Transaction(transaction_payload)) = block.payload() else {
+            return Ok(None);
+        };
+
+        let TransactionEssence::Regular(essence) = transaction_payload.essence();
+        // The inputs keep the order of the essence.
+        let input_output_ids = essence
+            .inputs()
+            .iter()
+            .map(|input| match input {
+                Input::Utxo(input) => Some(*input.output_id()),
+                Input::Treasury(_) => None,
+            })
+            .collect::<Vec<_>>();
+        let inputs = futures::stream::iter(input_output_ids)
+            .map(|output_id| async move {
+                let Some(output_id) = output_id else {
+                    return Ok(None);
+                };
+                history_provider.output(&output_id).await
+            })
+            .buffered(PARALLEL_HISTORY_REQUESTS_AMOUNT)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(Some(build_transaction_from_payload_and_inputs(
+            transaction_id,
+            *transaction_payload.clone(),
+            inputs
+                .into_iter()
+                .flatten()
+                .map(OutputWithMetadataResponse::from)
+                .collect(),
+        )?))
+    }
+}
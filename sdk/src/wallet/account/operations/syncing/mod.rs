@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod addresses;
+pub(crate) mod backfill;
 pub(crate) mod foundries;
 pub(crate) mod options;
 pub(crate) mod outputs;
@@ -48,6 +49,14 @@ where
 
     /// Sync the account by fetching new information from the nodes. Will also retry pending transactions
     /// if necessary. A custom default can be set using set_default_sync_options.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "account_sync",
+            skip_all,
+            fields(account_index = tracing::field::Empty, addresses = tracing::field::Empty, outputs = tracing::field::Empty)
+        )
+    )]
     pub async fn sync(&self, options: Option<SyncOptions>) -> crate::wallet::Result<Balance> {
         let options = match options {
             Some(opt) => opt,
@@ -55,6 +64,8 @@ where
         };
 
         log::debug!("[SYNC] start syncing with {:?}", options);
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("account_index", self.details().await.index);
         let syc_start_time = instant::Instant::now();
 
         // Prevent syncing the account multiple times simultaneously
@@ -71,6 +82,13 @@ where
             return self.balance().await;
         }
 
+        // Replay or roll back the transactions that were in progress when the wallet stopped
+        #[cfg(feature = "storage")]
+        self.recover_journal().await?;
+
+        // Check the protocol parameters first, so the pending transactions are checked before they get confirmed
+        self.check_protocol_parameters_upgrade().await?;
+
         self.sync_internal(&options).await?;
 
         // Sync transactions after updating account with outputs, so we can use them to check the transaction
@@ -89,6 +107,14 @@ where
         let time_now = crate::utils::unix_timestamp_now().as_millis();
         *last_synced = time_now;
         log::debug!("[SYNC] finished syncing in {:.2?}", syc_start_time.elapsed());
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("outputs", self.details().await.unspent_outputs().len());
+        #[cfg(feature = "metrics")]
+        crate::client::metrics::metrics().observe(
+            &crate::client::metrics::SYNC_DURATION_SECONDS,
+            &[("account", &self.details().await.index.to_string())],
+            syc_start_time.elapsed().as_secs_f64(),
+        );
         Ok(balance)
     }
 
@@ -97,6 +123,8 @@ where
 
         let addresses_to_sync = self.get_addresses_to_sync(options).await?;
         log::debug!("[SYNC] addresses_to_sync {}", addresses_to_sync.len());
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("addresses", addresses_to_sync.len());
 
         let (spent_or_not_synced_output_ids, addresses_with_unspent_outputs, outputs_data): (
             Vec<OutputId>,
@@ -11,6 +11,7 @@ const DEFAULT_SYNC_INCOMING_TRANSACTIONS: bool = false;
 const DEFAULT_SYNC_ONLY_MOST_BASIC_OUTPUTS: bool = false;
 const DEFAULT_SYNC_PENDING_TRANSACTIONS: bool = true;
 const DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES: bool = false;
+const DEFAULT_RECLAIM_EXPIRED_OUTPUTS: bool = false;
 
 /// The synchronization options
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -56,6 +57,10 @@ pub struct SyncOptions {
     /// Sync native token foundries, so their metadata can be returned in the balance.
     #[serde(default = "default_sync_native_token_foundries")]
     pub sync_native_token_foundries: bool,
+    /// Claim the outputs which returned to the account because they expired before the recipient claimed them. Only
+    /// used by the background syncing, see `Wallet::start_background_syncing()`.
+    #[serde(default = "default_reclaim_expired_outputs")]
+    pub reclaim_expired_outputs: bool,
 }
 
 fn default_address_start_index() -> u32 {
@@ -82,6 +87,10 @@ fn default_sync_native_token_foundries() -> bool {
     DEFAULT_SYNC_NATIVE_TOKEN_FOUNDRIES
 }
 
+fn default_reclaim_expired_outputs() -> bool {
+    DEFAULT_RECLAIM_EXPIRED_OUTPUTS
+}
+
 impl Default for SyncOptions {
     fn default() -> Self {
         Self {
@@ -95,6 +104,7 @@ impl Default for SyncOptions {
             nft: NftSyncOptions::default(),
             sync_only_most_basic_outputs: default_sync_only_most_basic_outputs(),
             sync_native_token_foundries: default_sync_native_token_foundries(),
+            reclaim_expired_outputs: default_reclaim_expired_outputs(),
             force_syncing: default_force_syncing(),
         }
     }
@@ -208,6 +208,23 @@ where
             updated_transactions.push(transaction);
         }
 
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        for transaction in &updated_transactions {
+            if transaction.inclusion_state != InclusionState::Pending {
+                #[cfg(feature = "metrics")]
+                crate::client::metrics::metrics().increment_counter(
+                    &crate::client::metrics::TRANSACTIONS_TOTAL,
+                    &[("inclusion_state", &format!("{:?}", transaction.inclusion_state))],
+                );
+                #[cfg(feature = "tracing")]
+                tracing::info!(
+                    transaction_id = %transaction.transaction_id,
+                    inclusion_state = ?transaction.inclusion_state,
+                    "transaction inclusion state changed"
+                );
+            }
+        }
+
         // updates account with balances, output ids, outputs
         self.update_account_with_transactions(updated_transactions, spent_output_ids, output_ids_to_unlock)
             .await?;
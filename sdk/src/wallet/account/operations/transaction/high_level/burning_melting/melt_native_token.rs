@@ -29,12 +29,15 @@ where
         melt_amount: impl Into<U256> + Send,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
-        let options = options.into();
-        let prepared_transaction = self
-            .prepare_melt_native_token(token_id, melt_amount, options.clone())
-            .await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self
+                .prepare_melt_native_token(token_id, melt_amount, options.clone())
+                .await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -85,7 +88,7 @@ where
     }
 
     /// Find and return unspent `OutputData` for given `alias_id` and `foundry_id`
-    async fn find_alias_and_foundry_output_data(
+    pub(crate) async fn find_alias_and_foundry_output_data(
         &self,
         alias_id: AliasId,
         foundry_id: FoundryId,
@@ -59,10 +59,13 @@ where
         params: Option<CreateAliasParams>,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
-        let options = options.into();
-        let prepared_transaction = self.prepare_create_alias_output(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_create_alias_output(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
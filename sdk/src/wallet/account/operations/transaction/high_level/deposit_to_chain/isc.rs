@@ -0,0 +1,425 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encoding of ISC requests, the metadata of outputs sent to and from smart contract chains.
+//!
+//! Sizes and amounts are encoded as unsigned LEB128, hnames as little endian `u32`, the gas budget is encoded plus one
+//! so that `u64::MAX` is encoded as zero.
+
+use crypto::signatures::secp256k1_ecdsa::EvmAddress;
+use packable::PackableExt;
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::block::{
+        address::{Address, Bech32Address, Hrp},
+        output::{AliasId, NativeToken, NftId, TokenId},
+    },
+    utils::serde::{evm_address, prefix_hex_bytes},
+    wallet::Error,
+};
+
+/// Hname of the `accounts` core contract.
+const ACCOUNTS_CONTRACT_HNAME: u32 = 0x3c4b5e02;
+/// Hname of the `transferAllowanceTo` entry point of the `accounts` contract.
+const TRANSFER_ALLOWANCE_TO_HNAME: u32 = 0x23f4e3a1;
+/// Name of the agent id parameter of `transferAllowanceTo`.
+const AGENT_ID_PARAM: &str = "a";
+
+const HAS_BASE_TOKENS: u8 = 0x80;
+const HAS_NATIVE_TOKENS: u8 = 0x40;
+const HAS_NFTS: u8 = 0x20;
+
+/// An agent on a chain, which can own assets.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum IscAgentId {
+    /// No agent.
+    Nil,
+    /// An address on L1.
+    Address { address: Bech32Address },
+    /// A contract on a chain.
+    #[serde(rename_all = "camelCase")]
+    Contract { chain_id: AliasId, hname: u32 },
+    /// An EVM account on the chain.
+    Ethereum {
+        #[serde(with = "evm_address")]
+        address: EvmAddress,
+    },
+}
+
+/// The contract that sent a request.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum IscContractIdentity {
+    /// An ISC contract, identified by its hname.
+    Hname { hname: u32 },
+    /// An EVM contract.
+    Evm {
+        #[serde(with = "evm_address")]
+        address: EvmAddress,
+    },
+}
+
+/// Assets on a chain, e.g. the allowance of a request.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IscAssets {
+    #[serde(with = "crate::utils::serde::string")]
+    pub base_tokens: u64,
+    #[serde(default)]
+    pub native_tokens: Vec<NativeToken>,
+    #[serde(default)]
+    pub nfts: Vec<NftId>,
+}
+
+/// A parameter of a request.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct IscParameter {
+    pub key: String,
+    #[serde(with = "prefix_hex_bytes")]
+    pub value: Vec<u8>,
+}
+
+/// The metadata of a request, stored in the [`MetadataFeature`](crate::types::block::output::feature::MetadataFeature)
+/// of outputs sent to or from a chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IscRequestMetadata {
+    /// The contract which sent the request, if it was sent by a contract.
+    pub sender_contract: Option<IscContractIdentity>,
+    /// Hname of the called contract.
+    pub target_contract: u32,
+    /// Hname of the called entry point.
+    pub entry_point: u32,
+    /// The parameters, sorted by key.
+    pub params: Vec<IscParameter>,
+    /// The assets the called entry point may take from the sender.
+    pub allowance: IscAssets,
+    /// The maximum gas the request may use.
+    #[serde(with = "crate::utils::serde::string")]
+    pub gas_budget: u64,
+}
+
+impl IscRequestMetadata {
+    /// A request to transfer the allowance from the account of the sender on the chain to the agent.
+    pub fn transfer_allowance_to(agent_id: &IscAgentId, allowance: IscAssets, gas_budget: u64) -> Self {
+        Self {
+            sender_contract: None,
+            target_contract: ACCOUNTS_CONTRACT_HNAME,
+            entry_point: TRANSFER_ALLOWANCE_TO_HNAME,
+            params: vec![IscParameter {
+                key: AGENT_ID_PARAM.to_string(),
+                value: pack_agent_id(agent_id),
+            }],
+            allowance,
+            gas_budget,
+        }
+    }
+
+    /// Encodes the metadata.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        match self.sender_contract {
+            None => bytes.push(0),
+            Some(IscContractIdentity::Hname { hname }) => {
+                bytes.push(1);
+                bytes.extend(hname.to_le_bytes());
+            }
+            Some(IscContractIdentity::Evm { address }) => {
+                bytes.push(2);
+                bytes.extend(address.as_ref());
+            }
+        }
+        bytes.extend(self.target_contract.to_le_bytes());
+        bytes.extend(self.entry_point.to_le_bytes());
+        write_leb128(&mut bytes, self.gas_budget.wrapping_add(1));
+
+        let mut params = self.params.iter().collect::<Vec<_>>();
+        params.sort_by(|a, b| a.key.cmp(&b.key));
+        write_leb128(&mut bytes, params.len() as u64);
+        for param in params {
+            write_sized(&mut bytes, param.key.as_bytes());
+            write_sized(&mut bytes, &param.value);
+        }
+
+        let allowance = &self.allowance;
+        let flags = if allowance.base_tokens != 0 { HAS_BASE_TOKENS } else { 0 }
+            | if allowance.native_tokens.is_empty() {
+                0
+            } else {
+                HAS_NATIVE_TOKENS
+            }
+            | if allowance.nfts.is_empty() { 0 } else { HAS_NFTS };
+        bytes.push(flags);
+        if allowance.base_tokens != 0 {
+            write_leb128(&mut bytes, allowance.base_tokens);
+        }
+        if !allowance.native_tokens.is_empty() {
+            write_leb128(&mut bytes, allowance.native_tokens.len() as u64);
+            for native_token in &allowance.native_tokens {
+                bytes.extend(native_token.token_id().as_ref());
+                let mut amount = [0; 32];
+                native_token.amount().to_big_endian(&mut amount);
+                let start = amount.iter().position(|b| *b != 0).unwrap_or(amount.len());
+                write_sized(&mut bytes, &amount[start..]);
+            }
+        }
+        if !allowance.nfts.is_empty() {
+            write_leb128(&mut bytes, allowance.nfts.len() as u64);
+            for nft_id in &allowance.nfts {
+                bytes.extend(nft_id.as_ref());
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes the metadata.
+    pub fn unpack(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(bytes);
+
+        let sender_contract = match reader.read_u8()? {
+            0 => None,
+            1 => Some(IscContractIdentity::Hname {
+                hname: reader.read_u32()?,
+            }),
+            2 => Some(IscContractIdentity::Evm {
+                address: EvmAddress::from(reader.read_array()?),
+            }),
+            kind => return Err(invalid(format!("invalid contract identity kind {kind}"))),
+        };
+        let target_contract = reader.read_u32()?;
+        let entry_point = reader.read_u32()?;
+        let gas_budget = reader.read_leb128()?.wrapping_sub(1);
+
+        let params = (0..reader.read_leb128()?)
+            .map(|_| {
+                let key = String::from_utf8(reader.read_sized()?.to_vec())
+                    .map_err(|_| invalid("parameter key is not UTF-8".to_string()))?;
+                Ok(IscParameter {
+                    key,
+                    value: reader.read_sized()?.to_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let flags = reader.read_u8()?;
+        let mut allowance = IscAssets::default();
+        if flags & HAS_BASE_TOKENS != 0 {
+            allowance.base_tokens = reader.read_leb128()?;
+        }
+        if flags & HAS_NATIVE_TOKENS != 0 {
+            for _ in 0..reader.read_leb128()? {
+                let token_id = TokenId::new(reader.read_array()?);
+                let amount = reader.read_sized()?;
+                if amount.len() > 32 {
+                    return Err(invalid("native token amount is too large".to_string()));
+                }
+                allowance
+                    .native_tokens
+                    .push(NativeToken::new(token_id, U256::from_big_endian(amount))?);
+            }
+        }
+        if flags & HAS_NFTS != 0 {
+            for _ in 0..reader.read_leb128()? {
+                allowance.nfts.push(NftId::new(reader.read_array()?));
+            }
+        }
+
+        if !reader.0.is_empty() {
+            return Err(invalid("trailing bytes".to_string()));
+        }
+
+        Ok(Self {
+            sender_contract,
+            target_contract,
+            entry_point,
+            params,
+            allowance,
+            gas_budget,
+        })
+    }
+
+    /// Returns the decoded value of the agent id parameter, the receiver of `transferAllowanceTo`.
+    pub fn agent_id(&self, hrp: Hrp) -> Option<IscAgentId> {
+        self.params
+            .iter()
+            .find(|param| param.key == AGENT_ID_PARAM)
+            .and_then(|param| unpack_agent_id(&param.value, hrp).ok())
+    }
+}
+
+fn pack_agent_id(agent_id: &IscAgentId) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    match agent_id {
+        IscAgentId::Nil => bytes.push(0),
+        IscAgentId::Address { address } => {
+            bytes.push(1);
+            bytes.extend(address.inner().pack_to_vec());
+        }
+        IscAgentId::Contract { chain_id, hname } => {
+            bytes.push(2);
+            bytes.extend(chain_id.as_ref());
+            bytes.extend(hname.to_le_bytes());
+        }
+        IscAgentId::Ethereum { address } => {
+            bytes.push(3);
+            bytes.extend(address.as_ref());
+        }
+    }
+    bytes
+}
+
+fn unpack_agent_id(bytes: &[u8], hrp: Hrp) -> Result<IscAgentId, Error> {
+    let mut reader = Reader(bytes);
+    let agent_id = match reader.read_u8()? {
+        0 => IscAgentId::Nil,
+        1 => {
+            // All address kinds have 32 bytes
+            let address = Address::unpack_verified(reader.read_array::<33>()?, &())
+                .map_err(|_| invalid("invalid address".to_string()))?;
+            IscAgentId::Address {
+                address: Bech32Address::new(hrp, address),
+            }
+        }
+        2 => IscAgentId::Contract {
+            chain_id: AliasId::new(reader.read_array()?),
+            hname: reader.read_u32()?,
+        },
+        3 => IscAgentId::Ethereum {
+            address: EvmAddress::from(reader.read_array()?),
+        },
+        kind => return Err(invalid(format!("invalid agent id kind {kind}"))),
+    };
+    if !reader.0.is_empty() {
+        return Err(invalid("trailing bytes".to_string()));
+    }
+    Ok(agent_id)
+}
+
+fn write_leb128(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_sized(bytes: &mut Vec<u8>, data: &[u8]) {
+    write_leb128(bytes, data.len() as u64);
+    bytes.extend_from_slice(data);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(invalid("unexpected end".to_string()));
+        }
+        let (read, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(read)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        Ok(self.read(N)?.try_into().expect("read N bytes"))
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_leb128(&mut self) -> Result<u64, Error> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("integer is too large".to_string()))
+    }
+
+    fn read_sized(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.read_leb128()?;
+        self.read(usize::try_from(len).map_err(|_| invalid("unexpected end".to_string()))?)
+    }
+}
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidIscRequest(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::types::block::address::AliasAddress;
+
+    #[test]
+    fn transfer_allowance_to_ethereum_account() {
+        let agent_id = IscAgentId::Ethereum {
+            address: EvmAddress::from([0x35; 20]),
+        };
+        let metadata = IscRequestMetadata::transfer_allowance_to(
+            &agent_id,
+            IscAssets {
+                base_tokens: 1_000_000,
+                ..Default::default()
+            },
+            10_000_000,
+        );
+        let bytes = metadata.pack();
+
+        let mut expected = vec![0x00, 0x02, 0x5e, 0x4b, 0x3c, 0xa1, 0xe3, 0xf4, 0x23];
+        // Gas budget plus one
+        expected.extend([0x81, 0xad, 0xe2, 0x04]);
+        // One parameter "a" with the agent id
+        expected.extend([0x01, 0x01, b'a', 0x15, 0x03]);
+        expected.extend([0x35; 20]);
+        // Allowance with base tokens
+        expected.extend([0x80, 0xc0, 0x84, 0x3d]);
+        assert_eq!(bytes, expected);
+
+        let hrp = Hrp::from_str_unchecked("smr");
+        let unpacked = IscRequestMetadata::unpack(&bytes).unwrap();
+        assert_eq!(unpacked, metadata);
+        assert_eq!(unpacked.agent_id(hrp), Some(agent_id));
+    }
+
+    #[test]
+    fn pack_unpack_assets() {
+        let hrp = Hrp::from_str_unchecked("rms");
+        let agent_id = IscAgentId::Address {
+            address: Bech32Address::new(hrp, Address::Alias(AliasAddress::new(AliasId::new([1; 32])))),
+        };
+        let mut metadata = IscRequestMetadata::transfer_allowance_to(
+            &agent_id,
+            IscAssets {
+                base_tokens: 0,
+                native_tokens: vec![NativeToken::new(TokenId::new([2; 38]), 300).unwrap()],
+                nfts: vec![NftId::new([3; 32])],
+            },
+            u64::MAX,
+        );
+        metadata.sender_contract = Some(IscContractIdentity::Hname { hname: 0x1234 });
+        let bytes = metadata.pack();
+
+        let unpacked = IscRequestMetadata::unpack(&bytes).unwrap();
+        assert_eq!(unpacked, metadata);
+        assert_eq!(unpacked.agent_id(hrp), Some(agent_id));
+
+        assert!(IscRequestMetadata::unpack(&bytes[..bytes.len() - 1]).is_err());
+        assert!(IscRequestMetadata::unpack(&[bytes.as_slice(), &[0]].concat()).is_err());
+    }
+}
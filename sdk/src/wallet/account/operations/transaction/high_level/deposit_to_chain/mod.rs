@@ -0,0 +1,325 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+pub(crate) mod isc;
+
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+use self::isc::{IscAgentId, IscAssets, IscRequestMetadata};
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        address::{Bech32Address, Hrp, ToBech32Ext},
+        output::{
+            feature::{Feature, MetadataFeature, SenderFeature},
+            unlock_condition::AddressUnlockCondition,
+            BasicOutputBuilder, NativeToken, NftId, NftOutputBuilder, Output, OutputId,
+        },
+        ConvertTo,
+    },
+    wallet::account::{operations::transaction::Transaction, types::OutputData, Account, TransactionOptions},
+};
+
+/// The default gas budget of a deposit request.
+pub const DEFAULT_DEPOSIT_GAS_BUDGET: u64 = 10_000_000;
+/// The default amount of base coins for the gas fee of a deposit request.
+pub const DEFAULT_DEPOSIT_GAS_FEE: u64 = 100_000;
+
+/// Params for `deposit_to_chain()`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct DepositToChainParams {
+    /// Bech32 encoded alias address of the chain
+    chain_address: Bech32Address,
+    /// The agent on the chain receiving the deposit
+    recipient: IscAgentId,
+    /// Amount of base coins credited to the recipient
+    #[serde(with = "crate::utils::serde::string")]
+    amount: u64,
+    /// Native tokens credited to the recipient
+    #[serde(default)]
+    native_tokens: Vec<NativeToken>,
+    /// Nft credited to the recipient, which is sent in the output of the request
+    #[serde(default)]
+    nft_id: Option<NftId>,
+    /// The maximum gas the request may use
+    #[serde(default = "default_gas_budget", with = "crate::utils::serde::string")]
+    gas_budget: u64,
+    /// Amount of base coins added to the output to pay for the gas, the unused part stays in the account of the
+    /// sender on the chain
+    #[serde(default = "default_gas_fee", with = "crate::utils::serde::string")]
+    gas_fee: u64,
+}
+
+fn default_gas_budget() -> u64 {
+    DEFAULT_DEPOSIT_GAS_BUDGET
+}
+
+fn default_gas_fee() -> u64 {
+    DEFAULT_DEPOSIT_GAS_FEE
+}
+
+impl DepositToChainParams {
+    /// Creates a new instance of [`DepositToChainParams`]
+    pub fn new(
+        chain_address: impl ConvertTo<Bech32Address>,
+        recipient: IscAgentId,
+        amount: u64,
+    ) -> Result<Self, crate::wallet::Error> {
+        let chain_address = chain_address.convert()?;
+        if !chain_address.inner().is_alias() {
+            return Err(crate::wallet::Error::InvalidChainAddress(chain_address));
+        }
+
+        Ok(Self {
+            chain_address,
+            recipient,
+            amount,
+            native_tokens: Vec::new(),
+            nft_id: None,
+            gas_budget: DEFAULT_DEPOSIT_GAS_BUDGET,
+            gas_fee: DEFAULT_DEPOSIT_GAS_FEE,
+        })
+    }
+
+    pub fn with_native_tokens(mut self, native_tokens: impl IntoIterator<Item = NativeToken>) -> Self {
+        self.native_tokens = native_tokens.into_iter().collect();
+        self
+    }
+
+    pub fn with_nft_id(mut self, nft_id: impl Into<Option<NftId>>) -> Self {
+        self.nft_id = nft_id.into();
+        self
+    }
+
+    pub fn with_gas_budget(mut self, gas_budget: u64) -> Self {
+        self.gas_budget = gas_budget;
+        self
+    }
+
+    pub fn with_gas_fee(mut self, gas_fee: u64) -> Self {
+        self.gas_fee = gas_fee;
+        self
+    }
+}
+
+/// An output a chain sent to the account, e.g. for a withdrawal from the chain.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainWithdrawal {
+    /// The id of the output
+    pub output_id: OutputId,
+    /// Bech32 encoded alias address of the chain
+    pub chain_address: Bech32Address,
+    /// Amount of base coins
+    #[serde(with = "crate::utils::serde::string")]
+    pub amount: u64,
+    /// Native tokens
+    pub native_tokens: Vec<NativeToken>,
+    /// The nft, if the output is an nft output
+    pub nft_id: Option<NftId>,
+    /// The request metadata, if the output has metadata in the ISC request format
+    pub metadata: Option<IscRequestMetadata>,
+}
+
+impl ChainWithdrawal {
+    /// Decodes an output sent by a chain, returns `None` if the sender of the output isn't an alias address.
+    pub fn from_output_data(output_data: &OutputData, hrp: Hrp) -> Option<Self> {
+        let output = &output_data.output;
+        let features = output.features()?;
+        let chain_address = features
+            .sender()
+            .map(SenderFeature::address)
+            .filter(|address| address.is_alias())?;
+
+        Some(Self {
+            output_id: output_data.output_id,
+            chain_address: chain_address.to_bech32(hrp),
+            amount: output.amount(),
+            native_tokens: output
+                .native_tokens()
+                .map(|native_tokens| native_tokens.to_vec())
+                .unwrap_or_default(),
+            nft_id: match output {
+                Output::Nft(nft_output) => Some(nft_output.nft_id_non_null(&output_data.output_id)),
+                _ => None,
+            },
+            metadata: features
+                .metadata()
+                .and_then(|metadata| IscRequestMetadata::unpack(metadata.data()).ok()),
+        })
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Deposits base coins, native tokens and an nft to an agent on a smart contract chain, e.g. an EVM account.
+    /// Sends an output to the alias address of the chain with an ISC request to transfer the assets from the account
+    /// of the sender on the chain to the recipient. The output holds at least the storage deposit.
+    /// ```ignore
+    /// let params = DepositToChainParams::new(
+    ///     "rms1pr59qm43mjtvhcajfmupqf23x29llam88yecn6pyul80rx099krmv2fnnux",
+    ///     IscAgentId::Ethereum { address: evm_address },
+    ///     1_000_000,
+    /// )?;
+    ///
+    /// let transaction = account.deposit_to_chain(params, None).await?;
+    /// ```
+    pub async fn deposit_to_chain(
+        &self,
+        params: DepositToChainParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_deposit_to_chain(params, options.clone()).await?;
+
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
+    }
+
+    /// Prepares the transaction for [Account::deposit_to_chain()](crate::wallet::Account::deposit_to_chain).
+    pub async fn prepare_deposit_to_chain(
+        &self,
+        params: DepositToChainParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_deposit_to_chain");
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+
+        self.client().bech32_hrp_matches(params.chain_address.hrp()).await?;
+        if let IscAgentId::Address { address } = &params.recipient {
+            self.client().bech32_hrp_matches(address.hrp()).await?;
+        }
+
+        let sender_address = self
+            .public_addresses()
+            .await
+            .first()
+            .expect("first address is generated during account creation")
+            .address
+            .inner;
+        let metadata = IscRequestMetadata::transfer_allowance_to(
+            &params.recipient,
+            IscAssets {
+                base_tokens: params.amount,
+                native_tokens: params.native_tokens.clone(),
+                nfts: params.nft_id.into_iter().collect(),
+            },
+            params.gas_budget,
+        );
+        let features: [Feature; 2] = [
+            SenderFeature::new(sender_address).into(),
+            MetadataFeature::new(metadata.pack())?.into(),
+        ];
+        let unlock_conditions = [AddressUnlockCondition::new(*params.chain_address.inner())];
+        let amount = params.amount + params.gas_fee;
+
+        let output = match params.nft_id {
+            Some(nft_id) => {
+                let nft_output = self
+                    .unspent_outputs(None)
+                    .await?
+                    .into_iter()
+                    .find_map(|output_data| match output_data.output {
+                        Output::Nft(nft_output) if nft_output.nft_id_non_null(&output_data.output_id) == nft_id => {
+                            Some(nft_output)
+                        }
+                        _ => None,
+                    })
+                    .ok_or(crate::wallet::Error::NftNotFoundInUnspentOutputs)?;
+                let builder = NftOutputBuilder::from(&nft_output)
+                    .with_nft_id(nft_id)
+                    .with_native_tokens(params.native_tokens)
+                    .with_unlock_conditions(unlock_conditions)
+                    .with_features(features);
+                let output = builder
+                    .clone()
+                    .with_minimum_storage_deposit(rent_structure)
+                    .finish_output(token_supply)?;
+                if output.amount() < amount {
+                    builder.with_amount(amount).finish_output(token_supply)?
+                } else {
+                    output
+                }
+            }
+            None => {
+                let builder = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+                    .with_native_tokens(params.native_tokens)
+                    .with_unlock_conditions(unlock_conditions)
+                    .with_features(features);
+                let output = builder.clone().finish_output(token_supply)?;
+                if output.amount() < amount {
+                    builder.with_amount(amount).finish_output(token_supply)?
+                } else {
+                    output
+                }
+            }
+        };
+
+        self.prepare_transaction([output], options).await
+    }
+
+    /// Returns the unspent outputs of the account which were sent by a chain, with their decoded request metadata.
+    pub async fn chain_withdrawals(&self) -> crate::wallet::Result<Vec<ChainWithdrawal>> {
+        let hrp = self.client().get_bech32_hrp().await?;
+
+        Ok(self
+            .unspent_outputs(None)
+            .await?
+            .iter()
+            .filter_map(|output_data| ChainWithdrawal::from_output_data(output_data, hrp))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::block::{
+        address::{Address, AliasAddress, Ed25519Address},
+        output::{AliasId, OutputMetadata},
+        payload::transaction::TransactionId,
+        BlockId,
+    };
+
+    #[test]
+    fn chain_withdrawal() {
+        let hrp = Hrp::from_str_unchecked("rms");
+        let chain_address = Address::Alias(AliasAddress::new(AliasId::new([1; 32])));
+        let metadata = IscRequestMetadata::transfer_allowance_to(&IscAgentId::Nil, IscAssets::default(), 0);
+        let output_id = OutputId::new(TransactionId::null(), 0).unwrap();
+        let address = Ed25519Address::new([0; 32]);
+
+        let output_data = |sender: Address| OutputData {
+            output_id,
+            metadata: OutputMetadata::new(BlockId::null(), output_id, false, None, None, None, 0, 0, 0),
+            output: BasicOutputBuilder::new_with_amount(1_000_000)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .add_feature(SenderFeature::new(sender))
+                .add_feature(MetadataFeature::new(metadata.pack()).unwrap())
+                .finish_output(u64::MAX)
+                .unwrap(),
+            is_spent: false,
+            address: address.into(),
+            network_id: 0,
+            remainder: false,
+            chain: None,
+        };
+
+        let withdrawal = ChainWithdrawal::from_output_data(&output_data(chain_address), hrp).unwrap();
+        assert_eq!(withdrawal.chain_address, chain_address.to_bech32(hrp));
+        assert_eq!(withdrawal.amount, 1_000_000);
+        assert_eq!(withdrawal.nft_id, None);
+        assert_eq!(withdrawal.metadata.as_ref(), Some(&metadata));
+
+        // Outputs not sent by a chain aren't withdrawals
+        assert!(ChainWithdrawal::from_output_data(&output_data(address.into()), hrp).is_none());
+    }
+}
@@ -0,0 +1,248 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use getset::{CopyGetters, Getters};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::output::{
+        feature::MetadataFeature, AliasOutputBuilder, FoundryId, FoundryOutputBuilder, Output, TokenId, TokenScheme,
+    },
+    wallet::{
+        account::{types::Transaction, Account, TransactionOptions},
+        Error,
+    },
+};
+
+/// Supply information of a foundry, returned from [`Account::foundry_info()`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct FoundryInfo {
+    /// The foundry id.
+    #[getset(get = "pub")]
+    pub(crate) foundry_id: FoundryId,
+    /// The id of the native token controlled by the foundry.
+    #[getset(get = "pub")]
+    pub(crate) token_id: TokenId,
+    /// Amount of tokens minted by the foundry.
+    #[getset(get_copy = "pub")]
+    pub(crate) minted_tokens: U256,
+    /// Amount of tokens melted by the foundry.
+    #[getset(get_copy = "pub")]
+    pub(crate) melted_tokens: U256,
+    /// Maximum supply of tokens controlled by the foundry.
+    #[getset(get_copy = "pub")]
+    pub(crate) maximum_supply: U256,
+    /// Amount of tokens in circulation, minted minus melted tokens.
+    #[getset(get_copy = "pub")]
+    pub(crate) circulating_supply: U256,
+    /// Amount of circulating tokens held by the account.
+    #[getset(get_copy = "pub")]
+    pub(crate) held_tokens: U256,
+    /// Amount of circulating tokens held by the account that can currently be spent.
+    #[getset(get_copy = "pub")]
+    pub(crate) available_tokens: U256,
+    /// Whether the foundry and its controlling alias output are unspent outputs of the account.
+    #[getset(get_copy = "pub")]
+    pub(crate) controlled: bool,
+}
+
+impl FoundryInfo {
+    /// Returns the amount of tokens that can still be minted before reaching the maximum supply.
+    pub fn mintable_tokens(&self) -> U256 {
+        self.maximum_supply - self.circulating_supply
+    }
+
+    /// Returns whether the foundry can be destroyed by the account, which requires it to control the foundry and to be
+    /// able to spend the whole circulating supply.
+    pub fn is_destroyable(&self) -> bool {
+        self.controlled && self.available_tokens == self.circulating_supply
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Returns the supply information of a foundry. The foundry is first looked up in the account and then requested
+    /// from the node, so foundries controlled by other accounts can be inspected too.
+    pub async fn foundry_info(&self, token_id: TokenId) -> crate::wallet::Result<FoundryInfo> {
+        log::debug!("[foundry_info]");
+
+        let foundry_id = FoundryId::from(token_id);
+        let foundry_output = self.get_foundry_output(token_id).await?;
+        let TokenScheme::Simple(token_scheme) = foundry_output.as_foundry().token_scheme();
+        let alias_id = *foundry_id.alias_address().alias_id();
+
+        let controlled = self.unspent_foundry_output(&foundry_id).await?.is_some()
+            && self.unspent_alias_output(&alias_id).await?.is_some();
+
+        let (held_tokens, available_tokens) = self
+            .balance()
+            .await?
+            .native_tokens()
+            .iter()
+            .find(|native_token| *native_token.token_id() == token_id)
+            .map_or((U256::zero(), U256::zero()), |native_token| {
+                (native_token.total(), native_token.available())
+            });
+
+        Ok(FoundryInfo {
+            foundry_id,
+            token_id,
+            minted_tokens: token_scheme.minted_tokens(),
+            melted_tokens: token_scheme.melted_tokens(),
+            maximum_supply: token_scheme.maximum_supply(),
+            circulating_supply: token_scheme.circulating_supply(),
+            held_tokens,
+            available_tokens,
+            controlled,
+        })
+    }
+
+    /// Replaces the metadata feature of a foundry controlled by the account, or removes it if `None` is provided.
+    ///
+    /// Only the mutable metadata can be updated, the immutable metadata of a foundry is fixed at its creation by the
+    /// protocol.
+    /// ```ignore
+    /// let transaction = account
+    ///     .update_foundry_metadata(token_id, Some(b"new foundry metadata".to_vec()), None)
+    ///     .await?;
+    /// println!("Transaction sent: {}", transaction.transaction_id);
+    /// ```
+    pub async fn update_foundry_metadata(
+        &self,
+        token_id: TokenId,
+        metadata: Option<Vec<u8>>,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self
+                .prepare_update_foundry_metadata(token_id, metadata, options.clone())
+                .await?;
+
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::update_foundry_metadata()](crate::wallet::Account::update_foundry_metadata).
+    pub async fn prepare_update_foundry_metadata(
+        &self,
+        token_id: TokenId,
+        metadata: Option<Vec<u8>>,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_update_foundry_metadata");
+
+        let foundry_id = FoundryId::from(token_id);
+        let alias_id = *foundry_id.alias_address().alias_id();
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+
+        let (existing_alias_output_data, existing_foundry_output_data) = self
+            .find_alias_and_foundry_output_data(alias_id, foundry_id)
+            .await
+            .map_err(|e| {
+                Error::FoundryOperationFailed(format!("foundry {foundry_id} is not controlled by the account: {e}"))
+            })?;
+
+        let (Output::Alias(alias_output), Output::Foundry(foundry_output)) =
+            (existing_alias_output_data.output, existing_foundry_output_data.output)
+        else {
+            unreachable!("We already checked the output kinds")
+        };
+
+        let mut foundry_output_builder = FoundryOutputBuilder::from(&foundry_output).clear_features();
+        if let Some(metadata) = metadata {
+            foundry_output_builder = foundry_output_builder.add_feature(MetadataFeature::new(metadata)?);
+        }
+
+        // The storage deposit may need to increase with the size of the new metadata, but shouldn't decrease.
+        let mut new_foundry_output = foundry_output_builder
+            .clone()
+            .with_minimum_storage_deposit(rent_structure)
+            .finish_output(token_supply)?;
+        if new_foundry_output.amount() < foundry_output.amount() {
+            new_foundry_output = foundry_output_builder
+                .with_amount(foundry_output.amount())
+                .finish_output(token_supply)?;
+        }
+
+        let outputs = [
+            AliasOutputBuilder::from(&alias_output)
+                .with_alias_id(alias_id)
+                .with_state_index(alias_output.state_index() + 1)
+                .finish_output(token_supply)?,
+            new_foundry_output,
+        ];
+
+        self.prepare_transaction(outputs, options).await
+    }
+
+    /// Destroys a foundry controlled by the account.
+    ///
+    /// All circulating tokens of the foundry need to be held by the account. They are swept from all outputs holding
+    /// them and melted as part of the foundry destruction, in a single transaction.
+    /// ```ignore
+    /// let info = account.foundry_info(token_id).await?;
+    /// if info.is_destroyable() {
+    ///     let transaction = account.destroy_foundry(*info.foundry_id(), None).await?;
+    ///     println!("Transaction sent: {}", transaction.transaction_id);
+    /// }
+    /// ```
+    pub async fn destroy_foundry(
+        &self,
+        foundry_id: FoundryId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_destroy_foundry(foundry_id, options.clone()).await?;
+
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::destroy_foundry()](crate::wallet::Account::destroy_foundry).
+    pub async fn prepare_destroy_foundry(
+        &self,
+        foundry_id: FoundryId,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_destroy_foundry");
+
+        let token_id = TokenId::from(foundry_id);
+        let info = self.foundry_info(token_id).await?;
+
+        if !info.controlled {
+            return Err(Error::FoundryOperationFailed(format!(
+                "foundry {foundry_id} is not controlled by the account"
+            )));
+        }
+        if info.available_tokens != info.circulating_supply {
+            return Err(Error::FoundryOperationFailed(format!(
+                "can't destroy foundry {foundry_id}: only {} of {} circulating tokens are available in the account",
+                info.available_tokens, info.circulating_supply
+            )));
+        }
+
+        let mut options: TransactionOptions = options.into().unwrap_or_default();
+        let mut burn = options.burn.take().unwrap_or_default().add_foundry(foundry_id);
+        // The foundry destruction requires the whole circulating supply in the inputs, which melts it.
+        if !info.circulating_supply.is_zero() {
+            burn = burn.add_native_token(token_id, info.circulating_supply);
+        }
+        options.burn = Some(burn);
+
+        // The empty list of outputs is used. Outputs will be generated by
+        // the input selection algorithm based on the content of the [`Burn`] object.
+        self.prepare_transaction([], Some(options)).await
+    }
+}
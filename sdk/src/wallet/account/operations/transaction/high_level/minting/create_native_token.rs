@@ -112,15 +112,18 @@ where
         params: CreateNativeTokenParams,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<CreateNativeTokenTransaction> {
-        let options = options.into();
-        let prepared = self.prepare_create_native_token(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared = self.prepare_create_native_token(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared.transaction, options)
-            .await
-            .map(|transaction| CreateNativeTokenTransaction {
-                token_id: prepared.token_id,
-                transaction,
-            })
+            self.sign_and_submit_transaction(prepared.transaction, options)
+                .await
+                .map(|transaction| CreateNativeTokenTransaction {
+                    token_id: prepared.token_id,
+                    transaction,
+                })
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -37,13 +37,16 @@ where
         mint_amount: impl Into<U256> + Send,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
-        let options = options.into();
-        let prepared = self
-            .prepare_mint_native_token(token_id, mint_amount, options.clone())
-            .await?;
-        let transaction = self.sign_and_submit_transaction(prepared, options).await?;
-
-        Ok(transaction)
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared = self
+                .prepare_mint_native_token(token_id, mint_amount, options.clone())
+                .await?;
+            let transaction = self.sign_and_submit_transaction(prepared, options).await?;
+
+            Ok(transaction)
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -143,10 +143,13 @@ where
     where
         I::IntoIter: Send,
     {
-        let options = options.into();
-        let prepared_transaction = self.prepare_mint_nfts(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_mint_nfts(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
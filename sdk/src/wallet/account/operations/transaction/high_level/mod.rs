@@ -3,7 +3,10 @@
 
 pub(crate) mod burning_melting;
 pub(crate) mod create_alias;
+pub(crate) mod deposit_to_chain;
+pub(crate) mod foundry;
 pub(crate) mod minting;
 pub(crate) mod send;
 pub(crate) mod send_native_tokens;
 pub(crate) mod send_nft;
+pub(crate) mod vesting;
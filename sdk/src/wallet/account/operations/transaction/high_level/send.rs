@@ -7,7 +7,6 @@ use serde::{Deserialize, Serialize};
 use crate::{
     client::{api::PreparedTransactionData, secret::SecretManage},
     types::block::{
-        address::Bech32Address,
         output::{
             unlock_condition::{
                 AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
@@ -20,7 +19,7 @@ use crate::{
         account::{
             constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionOptions,
         },
-        Error,
+        AddressOrLabel, Error,
     },
 };
 
@@ -31,14 +30,14 @@ pub struct SendParams {
     #[serde(with = "crate::utils::serde::string")]
     #[getset(get = "pub")]
     amount: u64,
-    /// Bech32 encoded address
+    /// Bech32 encoded address or label of the wallet's address book
     #[getset(get = "pub")]
-    address: Bech32Address,
-    /// Bech32 encoded return address, to which the storage deposit will be returned if one is necessary
-    /// given the provided amount. If a storage deposit is needed and a return address is not provided, it will
-    /// default to the first address of the account.
+    address: AddressOrLabel,
+    /// Bech32 encoded return address or label of the wallet's address book, to which the storage deposit will be
+    /// returned if one is necessary given the provided amount. If a storage deposit is needed and a return address
+    /// is not provided, it will default to the first address of the account.
     #[getset(get = "pub")]
-    return_address: Option<Bech32Address>,
+    return_address: Option<AddressOrLabel>,
     /// Expiration in seconds, after which the output will be available for the sender again, if not spent by the
     /// receiver already. The expiration will only be used if one is necessary given the provided amount. If an
     /// expiration is needed but not provided, it will default to one day.
@@ -47,7 +46,7 @@ pub struct SendParams {
 }
 
 impl SendParams {
-    pub fn new(amount: u64, address: impl ConvertTo<Bech32Address>) -> Result<Self, crate::wallet::Error> {
+    pub fn new(amount: u64, address: impl ConvertTo<AddressOrLabel>) -> Result<Self, crate::wallet::Error> {
         Ok(Self {
             amount,
             address: address.convert()?,
@@ -58,13 +57,13 @@ impl SendParams {
 
     pub fn try_with_return_address(
         mut self,
-        address: impl ConvertTo<Bech32Address>,
+        address: impl ConvertTo<AddressOrLabel>,
     ) -> Result<Self, crate::wallet::Error> {
         self.return_address = Some(address.convert()?);
         Ok(self)
     }
 
-    pub fn with_return_address(mut self, address: impl Into<Option<Bech32Address>>) -> Self {
+    pub fn with_return_address(mut self, address: impl Into<Option<AddressOrLabel>>) -> Self {
         self.return_address = address.into();
         self
     }
@@ -83,11 +82,11 @@ where
     ///
     /// Calls [Account::send_with_params()](crate::wallet::Account::send_with_params) internally.
     /// The options may define the remainder value strategy or custom inputs.
-    /// The provided Addresses provided with [`SendParams`] need to be bech32-encoded.
+    /// The address can be bech32-encoded or a label of the wallet's address book.
     pub async fn send(
         &self,
         amount: u64,
-        address: impl ConvertTo<Bech32Address>,
+        address: impl ConvertTo<AddressOrLabel>,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
         let params = [SendParams::new(amount, address)?];
@@ -98,7 +97,7 @@ where
     ///
     /// Calls [Account::send_outputs()](crate::wallet::Account::send_outputs) internally.
     /// The options may define the remainder value strategy or custom inputs.
-    /// Addresses provided with [`SendParams`] need to be bech32-encoded.
+    /// Addresses provided with [`SendParams`] can be bech32-encoded or labels of the wallet's address book.
     /// ```ignore
     /// let params = [SendParams::new(
     ///     "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
@@ -119,10 +118,13 @@ where
     where
         I::IntoIter: Send,
     {
-        let options = options.into();
-        let prepared_transaction = self.prepare_send(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_send(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -145,6 +147,13 @@ where
 
         let local_time = self.client().get_time_checked().await?;
 
+        let storage_deposit_policy = options
+            .as_ref()
+            .and_then(|options| options.storage_deposit_policy)
+            .unwrap_or_default();
+        let allow_micro_amount = options.as_ref().map(|o| o.allow_micro_amount).unwrap_or_default();
+        let mut gifted_storage_deposit = 0;
+
         let mut outputs = Vec::new();
         for SendParams {
             address,
@@ -153,7 +162,12 @@ where
             expiration,
         } in params
         {
+            let address = self.resolve_address(address).await?;
             self.client().bech32_hrp_matches(address.hrp()).await?;
+            let return_address = match return_address {
+                Some(return_address) => Some(self.resolve_address(return_address).await?),
+                None => None,
+            };
             let return_address = return_address
                 .map(|return_address| {
                     if return_address.hrp() != address.hrp() {
@@ -178,6 +192,17 @@ where
                         .with_amount(amount)
                         .finish_output(token_supply)?,
                 )
+            } else if storage_deposit_policy.gifts(output.amount() - amount, gifted_storage_deposit) {
+                if !allow_micro_amount {
+                    return Err(Error::InsufficientFunds {
+                        available: amount,
+                        required: output.amount(),
+                    });
+                }
+
+                // The missing storage deposit is gifted to the recipient, so it doesn't need to be returned
+                gifted_storage_deposit += output.amount() - amount;
+                outputs.push(output);
             } else {
                 let expiration_time = expiration.map_or(local_time + DEFAULT_EXPIRATION_TIME, |expiration_time| {
                     local_time + expiration_time
@@ -189,7 +214,7 @@ where
                     .with_expiration()?
                     .finish()?;
 
-                if !options.as_ref().map(|o| o.allow_micro_amount).unwrap_or_default() {
+                if !allow_micro_amount {
                     return Err(Error::InsufficientFunds {
                         available: amount,
                         required: amount + storage_deposit_amount,
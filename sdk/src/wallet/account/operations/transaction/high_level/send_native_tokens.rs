@@ -8,7 +8,6 @@ use serde::{Deserialize, Serialize};
 use crate::{
     client::{api::PreparedTransactionData, secret::SecretManage},
     types::block::{
-        address::Bech32Address,
         output::{
             unlock_condition::{
                 AddressUnlockCondition, ExpirationUnlockCondition, StorageDepositReturnUnlockCondition,
@@ -21,7 +20,7 @@ use crate::{
         account::{
             constants::DEFAULT_EXPIRATION_TIME, operations::transaction::Transaction, Account, TransactionOptions,
         },
-        Error, Result,
+        AddressOrLabel, Error, Result,
     },
 };
 
@@ -29,16 +28,16 @@ use crate::{
 #[derive(Debug, Clone, Serialize, Deserialize, Getters)]
 #[serde(rename_all = "camelCase")]
 pub struct SendNativeTokensParams {
-    /// Bech32 encoded address
+    /// Bech32 encoded address or label of the wallet's address book
     #[getset(get = "pub")]
-    address: Bech32Address,
+    address: AddressOrLabel,
     /// Native tokens
     #[getset(get = "pub")]
     native_tokens: Vec<(TokenId, U256)>,
-    /// Bech32 encoded address return address, to which the storage deposit will be returned. Default will use the
-    /// first address of the account
+    /// Bech32 encoded return address or label of the wallet's address book, to which the storage deposit will be
+    /// returned. Default will use the first address of the account
     #[getset(get = "pub")]
-    return_address: Option<Bech32Address>,
+    return_address: Option<AddressOrLabel>,
     /// Expiration in seconds, after which the output will be available for the sender again, if not spent by the
     /// receiver before. Default is 1 day
     #[getset(get = "pub")]
@@ -48,7 +47,7 @@ pub struct SendNativeTokensParams {
 impl SendNativeTokensParams {
     /// Creates a new instance of [`SendNativeTokensParams`]
     pub fn new(
-        address: impl ConvertTo<Bech32Address>,
+        address: impl ConvertTo<AddressOrLabel>,
         native_tokens: impl IntoIterator<Item = (TokenId, U256)>,
     ) -> Result<Self> {
         Ok(Self {
@@ -59,14 +58,14 @@ impl SendNativeTokensParams {
         })
     }
 
-    /// Set the return address and try convert to [`Bech32Address`]
-    pub fn try_with_return_address(mut self, return_address: impl ConvertTo<Bech32Address>) -> Result<Self> {
+    /// Set the return address and try convert to [`AddressOrLabel`]
+    pub fn try_with_return_address(mut self, return_address: impl ConvertTo<AddressOrLabel>) -> Result<Self> {
         self.return_address = Some(return_address.convert()?);
         Ok(self)
     }
 
     /// Set the return address
-    pub fn with_return_address(mut self, return_address: impl Into<Option<Bech32Address>>) -> Self {
+    pub fn with_return_address(mut self, return_address: impl Into<Option<AddressOrLabel>>) -> Self {
         self.return_address = return_address.into();
         self
     }
@@ -84,9 +83,12 @@ where
 {
     /// Sends native tokens in basic outputs with a [`StorageDepositReturnUnlockCondition`] and an
     /// [`ExpirationUnlockCondition`], so that the storage deposit is returned to the sender and the sender gets access
-    /// to the output again after a predefined time (default 1 day).
+    /// to the output again after a predefined time (default 1 day). If the
+    /// [`StorageDepositPolicy`](crate::wallet::account::StorageDepositPolicy) of the options gifts the storage
+    /// deposit, only the [`ExpirationUnlockCondition`] is added.
     /// Calls [Account::send_outputs()](crate::wallet::Account::send_outputs) internally. The options may define the
-    /// remainder value strategy or custom inputs. Note that the address needs to be bech32-encoded.
+    /// remainder value strategy or custom inputs. Note that the address needs to be bech32-encoded or a label of the
+    /// wallet's address book.
     /// ```ignore
     /// let params = [SendNativeTokensParams {
     ///     address: "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu".to_string(),
@@ -111,10 +113,13 @@ where
     where
         I::IntoIter: Send,
     {
-        let options = options.into();
-        let prepared_transaction = self.prepare_send_native_tokens(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_send_native_tokens(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -128,6 +133,7 @@ where
         I::IntoIter: Send,
     {
         log::debug!("[TRANSACTION] prepare_send_native_tokens");
+        let options = options.into();
         let rent_structure = self.client().get_rent_structure().await?;
         let token_supply = self.client().get_token_supply().await?;
 
@@ -136,6 +142,12 @@ where
 
         let local_time = self.client().get_time_checked().await?;
 
+        let storage_deposit_policy = options
+            .as_ref()
+            .and_then(|options| options.storage_deposit_policy)
+            .unwrap_or_default();
+        let mut gifted_storage_deposit = 0;
+
         let mut outputs = Vec::new();
         for SendNativeTokensParams {
             address,
@@ -144,7 +156,12 @@ where
             expiration,
         } in params
         {
+            let address = self.resolve_address(address).await?;
             self.client().bech32_hrp_matches(address.hrp()).await?;
+            let return_address = match return_address {
+                Some(return_address) => Some(self.resolve_address(return_address).await?),
+                None => None,
+            };
             let return_address = return_address
                 .map(|addr| {
                     if address.hrp() != addr.hrp() {
@@ -167,6 +184,28 @@ where
                     .collect::<Result<Vec<NativeToken>>>()?,
             )?;
 
+            let expiration_time = expiration.map_or(local_time + DEFAULT_EXPIRATION_TIME, |expiration_time| {
+                local_time + expiration_time
+            });
+
+            // If the storage deposit is gifted, nothing needs to be returned, but the native tokens still go back to
+            // the sender if the recipient doesn't claim them before the expiration
+            let gifted_storage_deposit_amount = MinimumStorageDepositBasicOutput::new(rent_structure, token_supply)
+                .with_native_tokens(native_tokens.clone())
+                .with_expiration()?
+                .finish()?;
+            if storage_deposit_policy.gifts(gifted_storage_deposit_amount, gifted_storage_deposit) {
+                gifted_storage_deposit += gifted_storage_deposit_amount;
+                outputs.push(
+                    BasicOutputBuilder::new_with_amount(gifted_storage_deposit_amount)
+                        .with_native_tokens(native_tokens)
+                        .add_unlock_condition(AddressUnlockCondition::new(address))
+                        .add_unlock_condition(ExpirationUnlockCondition::new(return_address, expiration_time)?)
+                        .finish_output(token_supply)?,
+                );
+                continue;
+            }
+
             // get minimum required amount for such an output, so we don't lock more than required
             // We have to check it for every output individually, because different address types and amount of
             // different native tokens require a different storage deposit
@@ -176,10 +215,6 @@ where
                 .with_expiration()?
                 .finish()?;
 
-            let expiration_time = expiration.map_or(local_time + DEFAULT_EXPIRATION_TIME, |expiration_time| {
-                local_time + expiration_time
-            });
-
             outputs.push(
                 BasicOutputBuilder::new_with_amount(storage_deposit_amount)
                     .with_native_tokens(native_tokens)
@@ -7,20 +7,22 @@ use serde::{Deserialize, Serialize};
 use crate::{
     client::{api::PreparedTransactionData, secret::SecretManage},
     types::block::{
-        address::Bech32Address,
         output::{unlock_condition::AddressUnlockCondition, NftId, NftOutputBuilder, Output},
         ConvertTo,
     },
-    wallet::account::{operations::transaction::Transaction, Account, TransactionOptions},
+    wallet::{
+        account::{operations::transaction::Transaction, Account, TransactionOptions},
+        AddressOrLabel,
+    },
 };
 
 /// Params for `send_nft()`
 #[derive(Debug, Clone, Serialize, Deserialize, Getters)]
 #[serde(rename_all = "camelCase")]
 pub struct SendNftParams {
-    /// Bech32 encoded address
+    /// Bech32 encoded address or label of the wallet's address book
     #[getset(get = "pub")]
-    address: Bech32Address,
+    address: AddressOrLabel,
     /// Nft id
     #[getset(get = "pub")]
     nft_id: NftId,
@@ -29,7 +31,7 @@ pub struct SendNftParams {
 impl SendNftParams {
     /// Creates a new instance of [`SendNftParams`]
     pub fn new(
-        address: impl ConvertTo<Bech32Address>,
+        address: impl ConvertTo<AddressOrLabel>,
         nft_id: impl ConvertTo<NftId>,
     ) -> Result<Self, crate::wallet::Error> {
         Ok(Self {
@@ -50,7 +52,7 @@ where
     /// predefined time (default 1 day).
     /// Calls [Account::send_outputs()](crate::wallet::account::Account::send_outputs) internally. The options may
     /// define the remainder value strategy. Note that custom inputs will be replaced with the required nft inputs
-    /// and addresses need to be bech32-encoded.
+    /// and addresses need to be bech32-encoded or labels of the wallet's address book.
     /// ```ignore
     /// let params = [SendNftParams::new(
     ///     "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
@@ -73,10 +75,13 @@ where
     where
         I::IntoIter: Send,
     {
-        let options = options.into();
-        let prepared_transaction = self.prepare_send_nft(params, options.clone()).await?;
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_send_nft(params, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction, options).await
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
     }
 
     /// Prepares the transaction for
@@ -97,6 +102,7 @@ where
         let mut outputs = Vec::new();
 
         for SendNftParams { address, nft_id } in params {
+            let address = self.resolve_address(address).await?;
             self.client().bech32_hrp_matches(address.hrp()).await?;
 
             // Find nft output from the inputs
@@ -0,0 +1,190 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    client::{api::PreparedTransactionData, secret::SecretManage},
+    types::block::{
+        address::Bech32Address,
+        output::{
+            unlock_condition::{AddressUnlockCondition, TimelockUnlockCondition},
+            BasicOutputBuilder,
+        },
+        ConvertTo,
+    },
+    wallet::{
+        account::{operations::transaction::Transaction, Account, TransactionOptions},
+        Error,
+    },
+};
+
+/// A single installment of a vesting schedule.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingInstallment {
+    /// Amount of base coins of the installment
+    #[serde(with = "crate::utils::serde::string")]
+    pub amount: u64,
+    /// Unix timestamp in seconds after which the recipient can spend the installment
+    pub unlock_time: u32,
+}
+
+/// Parameters for `create_vesting_schedule()`
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct VestingScheduleParams {
+    /// Bech32 encoded address of the recipient
+    address: Bech32Address,
+    /// Installments of the schedule, each one results in a time-locked output
+    installments: Vec<VestingInstallment>,
+}
+
+impl VestingScheduleParams {
+    pub fn new(address: impl ConvertTo<Bech32Address>) -> Result<Self, crate::wallet::Error> {
+        Ok(Self {
+            address: address.convert()?,
+            installments: Vec::new(),
+        })
+    }
+
+    /// Creates a schedule that splits `total_amount` into `count` equal installments, the first one unlocking at
+    /// `start_time` and the following ones every `interval` seconds. The rounding remainder is added to the last
+    /// installment.
+    pub fn with_equal_installments(
+        address: impl ConvertTo<Bech32Address>,
+        total_amount: u64,
+        count: u16,
+        start_time: u32,
+        interval: u32,
+    ) -> Result<Self, crate::wallet::Error> {
+        if count == 0 {
+            return Err(Error::VestingScheduleInvalid(
+                "a vesting schedule needs at least one installment".to_string(),
+            ));
+        }
+
+        let installment_amount = total_amount / count as u64;
+        let remainder = total_amount % count as u64;
+
+        let mut params = Self::new(address)?;
+        for i in 0..count as u32 {
+            let unlock_time = interval
+                .checked_mul(i)
+                .and_then(|offset| start_time.checked_add(offset))
+                .ok_or_else(|| Error::VestingScheduleInvalid("unlock time overflows".to_string()))?;
+            let amount = if i == count as u32 - 1 {
+                installment_amount + remainder
+            } else {
+                installment_amount
+            };
+            params = params.add_installment(amount, unlock_time);
+        }
+
+        Ok(params)
+    }
+
+    pub fn add_installment(mut self, amount: u64, unlock_time: u32) -> Self {
+        self.installments.push(VestingInstallment { amount, unlock_time });
+        self
+    }
+
+    pub fn with_installments(mut self, installments: impl IntoIterator<Item = VestingInstallment>) -> Self {
+        self.installments = installments.into_iter().collect();
+        self
+    }
+
+    /// Returns the sum of the amounts of all installments.
+    pub fn total_amount(&self) -> u64 {
+        self.installments.iter().map(|installment| installment.amount).sum()
+    }
+}
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Creates a vesting schedule, sending one time-locked output per installment to the recipient in a single
+    /// transaction. Each installment can only be spent by the recipient after its unlock time.
+    /// ```ignore
+    /// let params = VestingScheduleParams::with_equal_installments(
+    ///     "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
+    ///     12_000_000,
+    ///     12,
+    ///     start_time,
+    ///     30 * 24 * 60 * 60,
+    /// )?;
+    ///
+    /// let transaction = account.create_vesting_schedule(params, None).await?;
+    /// println!("Transaction sent: {}", transaction.transaction_id);
+    /// ```
+    pub async fn create_vesting_schedule(
+        &self,
+        params: VestingScheduleParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Transaction> {
+        self.in_transaction_span(async {
+            let options = options.into();
+            let prepared_transaction = self.prepare_create_vesting_schedule(params, options.clone()).await?;
+
+            self.sign_and_submit_transaction(prepared_transaction, options).await
+        })
+        .await
+    }
+
+    /// Prepares the transaction for
+    /// [Account::create_vesting_schedule()](crate::wallet::Account::create_vesting_schedule).
+    pub async fn prepare_create_vesting_schedule(
+        &self,
+        params: VestingScheduleParams,
+        options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<PreparedTransactionData> {
+        log::debug!("[TRANSACTION] prepare_create_vesting_schedule");
+
+        let VestingScheduleParams { address, installments } = params;
+
+        if installments.is_empty() {
+            return Err(Error::VestingScheduleInvalid(
+                "a vesting schedule needs at least one installment".to_string(),
+            ));
+        }
+        self.client().bech32_hrp_matches(address.hrp()).await?;
+
+        let rent_structure = self.client().get_rent_structure().await?;
+        let token_supply = self.client().get_token_supply().await?;
+        let local_time = self.client().get_time_checked().await?;
+
+        let mut outputs = Vec::with_capacity(installments.len());
+        for VestingInstallment { amount, unlock_time } in installments {
+            if unlock_time <= local_time {
+                return Err(Error::VestingScheduleInvalid(format!(
+                    "unlock time {unlock_time} is not in the future"
+                )));
+            }
+
+            let output = BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .add_unlock_condition(TimelockUnlockCondition::new(unlock_time)?)
+                .finish_output(token_supply)?;
+
+            // Installments are fully transferred to the recipient, so each of them has to cover its own storage
+            // deposit.
+            if amount < output.amount() {
+                return Err(Error::InsufficientFunds {
+                    available: amount,
+                    required: output.amount(),
+                });
+            }
+
+            outputs.push(
+                BasicOutputBuilder::from(output.as_basic())
+                    .with_amount(amount)
+                    .finish_output(token_supply)?,
+            );
+        }
+
+        self.prepare_transaction(outputs, options).await
+    }
+}
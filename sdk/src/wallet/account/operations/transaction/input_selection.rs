@@ -58,14 +58,19 @@ where
             .map(|address| *address.address.as_ref())
             .collect::<Vec<_>>();
 
-        // Prevent consuming the voting output if not actually wanted
+        // Prevent consuming the voting output and other outputs with participations if not actually wanted
         #[cfg(feature = "participation")]
-        if let Some(voting_output) = &voting_output {
-            let required = mandatory_inputs.as_ref().map_or(false, |mandatory_inputs| {
-                mandatory_inputs.contains(&voting_output.output_id)
-            });
-            if !required {
-                forbidden_inputs.insert(voting_output.output_id);
+        for output_id in voting_output
+            .iter()
+            .map(|voting_output| voting_output.output_id)
+            .chain(account_details.participating_output_ids())
+        {
+            let wanted = custom_inputs
+                .iter()
+                .chain(mandatory_inputs.iter())
+                .any(|inputs| inputs.contains(&output_id));
+            if !wanted {
+                forbidden_inputs.insert(output_id);
             }
         }
 
@@ -117,6 +122,10 @@ where
                 account_details.locked_outputs.insert(*output.output_id());
             }
 
+            #[cfg(all(feature = "events", feature = "participation"))]
+            self.emit_participation_warnings(account_details.index, &selected_transaction_data)
+                .await;
+
             return Ok(selected_transaction_data);
         } else if let Some(mandatory_inputs) = mandatory_inputs {
             // Check that no input got already locked
@@ -157,6 +166,10 @@ where
                 account_details.locked_outputs.insert(*output.output_id());
             }
 
+            #[cfg(all(feature = "events", feature = "participation"))]
+            self.emit_participation_warnings(account_details.index, &selected_transaction_data)
+                .await;
+
             return Ok(selected_transaction_data);
         }
 
@@ -201,6 +214,17 @@ where
 
         Ok(selected_transaction_data)
     }
+
+    // Warns about participations which end, because their outputs are consumed without carrying them over
+    #[cfg(all(feature = "events", feature = "participation"))]
+    async fn emit_participation_warnings(&self, account_index: u32, selected: &Selected) {
+        for warning in
+            crate::wallet::account::operations::participation::ended_participations(&selected.inputs, &selected.outputs)
+        {
+            self.emit(account_index, WalletEvent::ParticipationWarning(warning))
+                .await;
+        }
+    }
 }
 
 /// Filter available outputs to only include outputs that don't have unlock conditions, that could create
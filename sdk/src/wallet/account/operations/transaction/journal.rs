@@ -0,0 +1,128 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::secret::SecretManage,
+    types::TryFromDto,
+    wallet::{
+        account::{types::Transaction, Account},
+        storage::journal::{JournalEntry, JournalStep},
+    },
+};
+
+impl<S: 'static + SecretManage> Account<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Writes the step a transaction is about to take to the journal.
+    pub(crate) async fn journal(&self, entry: JournalEntry) -> crate::wallet::Result<()> {
+        let account_index = self.details().await.index;
+        self.wallet
+            .storage_manager
+            .write()
+            .await
+            .journal(account_index, entry)
+            .await
+    }
+
+    /// Replays or rolls back the transactions in the journal, which weren't stored in the account because the wallet
+    /// stopped while sending them. It's done on the first sync of the account, only the transactions which were in
+    /// progress when the account was loaded are recovered.
+    ///
+    /// Transactions with a submitted block are stored as pending. Signed transactions are stored as pending and
+    /// submitted again, unless the node reports one of their inputs as spent by another transaction. Transactions that
+    /// weren't signed are dropped, their inputs were already unlocked when the account was loaded.
+    pub async fn recover_journal(&self) -> crate::wallet::Result<()> {
+        let mut journal = self.journal_to_recover.lock().await;
+        if journal.is_empty() {
+            return Ok(());
+        }
+        let account_index = self.details().await.index;
+        log::debug!("[recover_journal] recovering {} transactions", journal.len());
+
+        let mut rolled_back_inputs = Vec::new();
+        let mut replayed_transactions = Vec::new();
+        for entry in journal.iter() {
+            match &entry.step {
+                JournalStep::InputsLocked => {
+                    log::debug!("[recover_journal] dropping unsigned transaction {}", entry.id);
+                }
+                JournalStep::TransactionBuilt { transaction } => {
+                    let mut transaction = Transaction::try_from_dto(transaction.clone())?;
+                    if self.recover_signed_transaction(&mut transaction).await {
+                        replayed_transactions.push(transaction);
+                    } else {
+                        log::debug!(
+                            "[recover_journal] rolling back conflicting transaction {}",
+                            transaction.transaction_id
+                        );
+                        rolled_back_inputs.extend(&entry.inputs);
+                    }
+                }
+                JournalStep::BlockSubmitted { transaction } => {
+                    replayed_transactions.push(Transaction::try_from_dto(transaction.clone())?);
+                }
+            }
+        }
+
+        let mut account_details = self.details_mut().await;
+        for output_id in &rolled_back_inputs {
+            account_details.locked_outputs.remove(output_id);
+        }
+        for transaction in replayed_transactions {
+            // Already stored before the journal entry could be removed
+            if account_details.transactions.contains_key(&transaction.transaction_id) {
+                continue;
+            }
+            log::debug!("[recover_journal] replaying transaction {}", transaction.transaction_id);
+            for input in &transaction.inputs {
+                account_details.locked_outputs.insert(*input.metadata.output_id());
+            }
+            account_details.pending_transactions.insert(transaction.transaction_id);
+            account_details
+                .transactions
+                .insert(transaction.transaction_id, transaction);
+        }
+        self.save(Some(&account_details)).await?;
+        drop(account_details);
+
+        let ids = journal.iter().map(|entry| entry.id.clone()).collect::<Vec<_>>();
+        self.wallet
+            .storage_manager
+            .write()
+            .await
+            .remove_journal_entry_ids(account_index, &ids)
+            .await?;
+        journal.clear();
+
+        Ok(())
+    }
+
+    // Returns whether a signed transaction should be replayed. If the node doesn't know it, it's submitted again,
+    // failed submissions are retried while syncing the pending transactions.
+    async fn recover_signed_transaction(&self, transaction: &mut Transaction) -> bool {
+        let transaction_id = transaction.transaction_id;
+        if self.details().await.transactions().contains_key(&transaction_id) {
+            return true;
+        }
+
+        if let Ok(block_metadata) = self.client().get_included_block_metadata(&transaction_id).await {
+            transaction.block_id = Some(block_metadata.block_id);
+            return true;
+        }
+
+        for input in &transaction.inputs {
+            if let Ok(output_metadata) = self.client().get_output_metadata(input.metadata.output_id()).await {
+                if matches!(output_metadata.transaction_id_spent(), Some(spent_by) if *spent_by != transaction_id) {
+                    return false;
+                }
+            }
+        }
+
+        match self.submit_transaction_payload(transaction.payload.clone()).await {
+            Ok(block_id) => transaction.block_id = Some(block_id),
+            Err(err) => log::error!("[recover_journal] failed to submit transaction {transaction_id}: {err}"),
+        }
+        true
+    }
+}
@@ -4,13 +4,22 @@
 mod build_transaction;
 pub(crate) mod high_level;
 mod input_selection;
+#[cfg(feature = "storage")]
+mod journal;
 mod options;
 pub(crate) mod prepare_output;
 mod prepare_transaction;
 mod sign_transaction;
 pub(crate) mod submit_transaction;
 
-pub use self::options::{RemainderValueStrategy, TransactionOptions, TransactionOptionsDto};
+use std::future::Future;
+
+pub use self::options::{RemainderValueStrategy, StorageDepositPolicy, TransactionOptions, TransactionOptionsDto};
+#[cfg(feature = "storage")]
+use crate::wallet::{
+    account::types::TransactionDto,
+    storage::journal::{JournalEntry, JournalStep},
+};
 use crate::{
     client::{
         api::{verify_semantic, PreparedTransactionData, SignedTransactionData},
@@ -90,36 +99,100 @@ where
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
         log::debug!("[TRANSACTION] finish_transaction");
-        let options = options.into();
+        self.in_transaction_span(async {
+            let options = options.into();
 
-        let prepared_transaction_data = self.prepare_transaction(outputs, options.clone()).await?;
+            let prepared_transaction_data = self.prepare_transaction(outputs, options.clone()).await?;
 
-        self.sign_and_submit_transaction(prepared_transaction_data, options)
-            .await
+            self.sign_and_submit_transaction(prepared_transaction_data, options)
+                .await
+        })
+        .await
     }
 
-    /// Signs a transaction, submit it to a node and store it in the account
+    /// Signs a transaction, submit it to a node and store it in the account. If the protocol parameters changed since
+    /// the transaction was prepared and its outputs don't cover their storage deposits anymore, it's prepared again
+    /// with [`Account::revalidate_prepared_transaction()`] first.
     pub async fn sign_and_submit_transaction(
         &self,
         prepared_transaction_data: PreparedTransactionData,
         options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Transaction> {
         log::debug!("[TRANSACTION] sign_and_submit_transaction");
+        self.in_transaction_span(async {
+            let options = options.into();
 
-        let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
-            Ok(res) => res,
-            Err(err) => {
-                // unlock outputs so they are available for a new transaction
-                self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
-                return Err(err);
-            }
-        };
+            let inputs_data = prepared_transaction_data.inputs_data.clone();
+            let prepared_transaction_data = match self
+                .revalidate_prepared_transaction(prepared_transaction_data, options.clone())
+                .await
+            {
+                Ok(res) => res,
+                Err(err) => {
+                    // unlock outputs so they are available for a new transaction
+                    self.unlock_inputs(&inputs_data).await?;
+                    return Err(err);
+                }
+            };
 
-        self.submit_and_store_transaction(signed_transaction_data, options)
-            .await
+            let signed_transaction_data = match self.sign_transaction_essence(&prepared_transaction_data).await {
+                Ok(res) => res,
+                Err(err) => {
+                    // unlock outputs so they are available for a new transaction
+                    self.unlock_inputs(&prepared_transaction_data.inputs_data).await?;
+                    return Err(err);
+                }
+            };
+
+            // Key the transaction span by the transaction id
+            #[cfg(feature = "tracing")]
+            tracing::Span::current().record(
+                "transaction_id",
+                tracing::field::display(signed_transaction_data.transaction_payload.id()),
+            );
+
+            self.submit_and_store_transaction(signed_transaction_data, options)
+                .await
+        })
+        .await
+    }
+
+    /// Runs the preparation, signing and submission of a transaction in a `send_transaction` span, so that the spans
+    /// of its steps are correlated. Calls nested in a high-level method, like the signing of the transaction it
+    /// prepared, run in the span of that method.
+    pub(crate) async fn in_transaction_span<T>(
+        &self,
+        transaction: impl Future<Output = crate::wallet::Result<T>> + Send,
+    ) -> crate::wallet::Result<T> {
+        #[cfg(feature = "tracing")]
+        let in_transaction_span = tracing::Span::current()
+            .metadata()
+            .is_some_and(|metadata| metadata.name() == "send_transaction");
+        #[cfg(feature = "tracing")]
+        if !in_transaction_span {
+            let span = tracing::info_span!(
+                "send_transaction",
+                account_index = self.details().await.index,
+                transaction_id = tracing::field::Empty
+            );
+            return tracing::Instrument::instrument(transaction, span).await;
+        }
+
+        transaction.await
     }
 
     /// Validates the transaction, submit it to a node and store it in the account
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "submit_transaction",
+            skip_all,
+            fields(
+                transaction_id = %signed_transaction_data.transaction_payload.id(),
+                block_id = tracing::field::Empty
+            )
+        )
+    )]
     pub async fn submit_and_store_transaction(
         &self,
         signed_transaction_data: SignedTransactionData,
@@ -150,36 +223,33 @@ where
             return Err(Error::TransactionSemantic(conflict).into());
         }
 
-        // Ignore errors from sending, we will try to send it again during [`sync_pending_transactions`]
-        let block_id = match self
-            .submit_transaction_payload(signed_transaction_data.transaction_payload.clone())
-            .await
-        {
-            Ok(block_id) => Some(block_id),
-            Err(err) => {
-                log::error!("Failed to submit_transaction_payload {}", err);
-                None
-            }
-        };
-
         let transaction_id = signed_transaction_data.transaction_payload.id();
 
         // store transaction payload to account (with db feature also store the account to the db)
         let network_id = self.client().get_network_id().await?;
 
+        #[cfg(feature = "storage")]
+        let input_ids = signed_transaction_data
+            .inputs_data
+            .iter()
+            .map(|input| *input.output_id())
+            .collect::<Vec<_>>();
+        #[cfg(feature = "storage")]
+        let journal_id = prefix_hex::encode(signed_transaction_data.transaction_payload.essence().hash());
+
         let inputs = signed_transaction_data
             .inputs_data
-            .into_iter()
+            .iter()
             .map(|input| OutputWithMetadataResponse {
                 metadata: input.output_metadata,
                 output: OutputDto::from(&input.output),
             })
             .collect();
 
-        let transaction = Transaction {
+        let mut transaction = Transaction {
             transaction_id,
             payload: signed_transaction_data.transaction_payload,
-            block_id,
+            block_id: None,
             network_id,
             timestamp: crate::utils::unix_timestamp_now().as_millis(),
             inclusion_state: InclusionState::Pending,
@@ -188,6 +258,55 @@ where
             inputs,
         };
 
+        // Journal the signed transaction, so it can be replayed if the wallet stops before it's stored
+        #[cfg(feature = "storage")]
+        if let Err(err) = self
+            .journal(JournalEntry {
+                id: journal_id.clone(),
+                inputs: input_ids.clone(),
+                step: JournalStep::TransactionBuilt {
+                    transaction: TransactionDto::from(&transaction),
+                },
+            })
+            .await
+        {
+            // unlock outputs so they are available for a new transaction
+            self.unlock_inputs(&signed_transaction_data.inputs_data).await?;
+            return Err(err);
+        }
+
+        // Ignore errors from sending, we will try to send it again during [`sync_pending_transactions`]
+        match self.submit_transaction_payload(transaction.payload.clone()).await {
+            Ok(block_id) => {
+                transaction.block_id = Some(block_id);
+                #[cfg(feature = "tracing")]
+                tracing::Span::current().record("block_id", tracing::field::display(block_id));
+                // The block is already sent, so failing to journal it must not fail the transaction
+                #[cfg(feature = "storage")]
+                if let Err(err) = self
+                    .journal(JournalEntry {
+                        id: journal_id,
+                        inputs: input_ids.clone(),
+                        step: JournalStep::BlockSubmitted {
+                            transaction: TransactionDto::from(&transaction),
+                        },
+                    })
+                    .await
+                {
+                    log::error!("Failed to journal submitted block {}", err);
+                }
+            }
+            Err(err) => {
+                log::error!("Failed to submit_transaction_payload {}", err);
+            }
+        }
+
+        #[cfg(feature = "metrics")]
+        crate::client::metrics::metrics().increment_counter(
+            &crate::client::metrics::TRANSACTIONS_TOTAL,
+            &[("inclusion_state", &format!("{:?}", transaction.inclusion_state))],
+        );
+
         let mut account_details = self.details_mut().await;
 
         account_details.transactions.insert(transaction_id, transaction.clone());
@@ -196,13 +315,19 @@ where
         {
             log::debug!("[TRANSACTION] storing account {}", account_details.index());
             self.save(Some(&account_details)).await?;
+            self.wallet
+                .storage_manager
+                .write()
+                .await
+                .remove_journal_entries(account_details.index, &input_ids)
+                .await?;
         }
 
         Ok(transaction)
     }
 
     // unlock outputs
-    async fn unlock_inputs(&self, inputs: &[InputSigningData]) -> crate::wallet::Result<()> {
+    pub(crate) async fn unlock_inputs(&self, inputs: &[InputSigningData]) -> crate::wallet::Result<()> {
         let mut account_details = self.details_mut().await;
         for input_signing_data in inputs {
             let output_id = input_signing_data.output_id();
@@ -212,6 +337,16 @@ where
                 output_id
             );
         }
+        #[cfg(feature = "storage")]
+        {
+            let output_ids = inputs.iter().map(|input| *input.output_id()).collect::<Vec<_>>();
+            self.wallet
+                .storage_manager
+                .write()
+                .await
+                .remove_journal_entries(account_details.index, &output_ids)
+                .await?;
+        }
         Ok(())
     }
 }
@@ -25,6 +25,13 @@ pub struct TransactionOptions {
     pub burn: Option<Burn>,
     pub note: Option<String>,
     pub allow_micro_amount: bool,
+    /// How storage deposits required by outputs sending assets to other addresses are handled. If not set, they are
+    /// returned, unless the [`ReturnStrategy`](super::prepare_output::ReturnStrategy) of an output says otherwise.
+    pub storage_deposit_policy: Option<StorageDepositPolicy>,
+    /// Merges outputs and moves native tokens into fewer outputs with a
+    /// [`RentOptimizer`](crate::client::api::RentOptimizer) before the inputs are selected, to need lower storage
+    /// deposits. The amounts of the outputs are kept.
+    pub optimize_storage_deposits: bool,
 }
 
 impl TransactionOptions {
@@ -38,6 +45,8 @@ impl TransactionOptions {
             burn: value.burn.map(Burn::try_from).transpose()?,
             note: value.note,
             allow_micro_amount: value.allow_micro_amount,
+            storage_deposit_policy: value.storage_deposit_policy,
+            optimize_storage_deposits: value.optimize_storage_deposits,
         })
     }
 }
@@ -60,6 +69,10 @@ pub struct TransactionOptionsDto {
     pub note: Option<String>,
     #[serde(default)]
     pub allow_micro_amount: bool,
+    #[serde(default)]
+    pub storage_deposit_policy: Option<StorageDepositPolicy>,
+    #[serde(default)]
+    pub optimize_storage_deposits: bool,
 }
 
 #[allow(clippy::enum_variant_names)]
@@ -80,3 +93,30 @@ impl Default for RemainderValueStrategy {
         Self::ReuseAddress
     }
 }
+
+/// The policy for storage deposits that are required to send assets or micro amounts to another address.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "policy", content = "value")]
+pub enum StorageDepositPolicy {
+    /// The storage deposit is lent to the recipient and returned to the sender with a
+    /// [`StorageDepositReturnUnlockCondition`](crate::types::block::output::unlock_condition::StorageDepositReturnUnlockCondition).
+    #[default]
+    Return,
+    /// The storage deposit is gifted to the recipient.
+    Gift,
+    /// Storage deposits are gifted as long as their sum in the transaction doesn't exceed the limit, the ones above
+    /// it are returned.
+    GiftUpToLimit(#[serde(with = "crate::utils::serde::string")] u64),
+}
+
+impl StorageDepositPolicy {
+    /// Returns whether a storage deposit of `amount` is gifted, given the amount that has already been gifted in the
+    /// same transaction.
+    pub(crate) fn gifts(&self, amount: u64, already_gifted: u64) -> bool {
+        match self {
+            Self::Return => false,
+            Self::Gift => true,
+            Self::GiftUpToLimit(limit) => already_gifted.saturating_add(amount) <= *limit,
+        }
+    }
+}
@@ -30,7 +30,9 @@ where
     /// Prepare a basic or NFT output for sending
     /// If the amount is below the minimum required storage deposit, by default the remaining amount will automatically
     /// be added with a StorageDepositReturn UnlockCondition, when setting the ReturnStrategy to `gift`, the full
-    /// minimum required storage deposit will be sent to the recipient.
+    /// minimum required storage deposit will be sent to the recipient. Without a ReturnStrategy, the
+    /// [`StorageDepositPolicy`](super::StorageDepositPolicy) of the transaction options decides, a limit only applies
+    /// to the storage deposit of this output, use [`Account::prepare_outputs()`] to apply it to several outputs.
     /// When the assets contain an nft_id, the data from the existing nft output will be used, just with the address
     /// unlock conditions replaced
     pub async fn prepare_output(
@@ -38,8 +40,41 @@ where
         params: OutputParams,
         transaction_options: impl Into<Option<TransactionOptions>> + Send,
     ) -> crate::wallet::Result<Output> {
-        log::debug!("[OUTPUT] prepare_output {params:?}");
+        self.prepare_output_internal(params, transaction_options.into(), &mut 0)
+            .await
+    }
+
+    /// Prepare several outputs for sending in the same transaction, like [`Account::prepare_output()`]. The storage
+    /// deposits gifted by a [`StorageDepositPolicy::GiftUpToLimit`](super::StorageDepositPolicy::GiftUpToLimit) are
+    /// summed over all outputs.
+    pub async fn prepare_outputs<I: IntoIterator<Item = OutputParams> + Send>(
+        &self,
+        params: I,
+        transaction_options: impl Into<Option<TransactionOptions>> + Send,
+    ) -> crate::wallet::Result<Vec<Output>>
+    where
+        I::IntoIter: Send,
+    {
         let transaction_options = transaction_options.into();
+        let mut gifted_storage_deposit = 0;
+        let mut outputs = Vec::new();
+        for params in params {
+            outputs.push(
+                self.prepare_output_internal(params, transaction_options.clone(), &mut gifted_storage_deposit)
+                    .await?,
+            );
+        }
+        Ok(outputs)
+    }
+
+    // Prepare an output, adding the storage deposit it gifts to `gifted_storage_deposit`
+    async fn prepare_output_internal(
+        &self,
+        params: OutputParams,
+        transaction_options: Option<TransactionOptions>,
+        gifted_storage_deposit: &mut u64,
+    ) -> crate::wallet::Result<Output> {
+        log::debug!("[OUTPUT] prepare_output {params:?}");
         let token_supply = self.client().get_token_supply().await?;
 
         self.client().bech32_hrp_matches(params.recipient_address.hrp()).await?;
@@ -116,16 +151,31 @@ where
             second_output_builder = second_output_builder.with_amount(params.amount);
         }
 
+        // An explicit return strategy of the output takes precedence over the storage deposit policy of the transaction
         let return_strategy = params
             .storage_deposit
             .clone()
             .unwrap_or_default()
             .return_strategy
-            .unwrap_or_default();
+            .unwrap_or_else(|| {
+                let storage_deposit_policy = transaction_options
+                    .as_ref()
+                    .and_then(|options| options.storage_deposit_policy)
+                    .unwrap_or_default();
+                if storage_deposit_policy.gifts(
+                    min_required_storage_deposit.saturating_sub(params.amount),
+                    *gifted_storage_deposit,
+                ) {
+                    ReturnStrategy::Gift
+                } else {
+                    ReturnStrategy::Return
+                }
+            });
         let remainder_address = self.get_remainder_address(transaction_options).await?;
         if params.amount < min_required_storage_deposit {
             if return_strategy == ReturnStrategy::Gift {
                 second_output_builder = second_output_builder.with_amount(min_required_storage_deposit);
+                *gifted_storage_deposit += min_required_storage_deposit - params.amount;
             }
             if return_strategy == ReturnStrategy::Return {
                 second_output_builder =
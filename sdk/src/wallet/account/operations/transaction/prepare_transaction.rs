@@ -9,7 +9,10 @@ use packable::bounded::TryIntoBoundedU16Error;
 #[cfg(feature = "events")]
 use crate::wallet::events::types::{AddressData, TransactionProgressEvent, WalletEvent};
 use crate::{
-    client::{api::PreparedTransactionData, secret::SecretManage},
+    client::{
+        api::{PreparedTransactionData, RentOptimizer},
+        secret::SecretManage,
+    },
     types::block::{
         input::INPUT_COUNT_RANGE,
         output::{Output, OUTPUT_COUNT_RANGE},
@@ -25,6 +28,14 @@ where
     crate::wallet::Error: From<S::Error>,
 {
     /// Get inputs and build the transaction essence
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "prepare_transaction",
+            skip_all,
+            fields(account_index = tracing::field::Empty, outputs = tracing::field::Empty, inputs = tracing::field::Empty)
+        )
+    )]
     pub async fn prepare_transaction(
         &self,
         outputs: impl Into<Vec<Output>> + Send,
@@ -32,8 +43,12 @@ where
     ) -> crate::wallet::Result<PreparedTransactionData> {
         log::debug!("[TRANSACTION] prepare_transaction");
         let options = options.into();
-        let outputs = outputs.into();
+        let mut outputs = outputs.into();
         let prepare_transaction_start_time = Instant::now();
+        #[cfg(feature = "tracing")]
+        tracing::Span::current()
+            .record("account_index", self.details().await.index)
+            .record("outputs", outputs.len());
         let rent_structure = self.client().get_rent_structure().await?;
         let token_supply = self.client().get_token_supply().await?;
 
@@ -42,6 +57,18 @@ where
             output.verify_storage_deposit(rent_structure, token_supply)?;
         }
 
+        if options
+            .as_ref()
+            .is_some_and(|options| options.optimize_storage_deposits)
+        {
+            let optimized_outputs = RentOptimizer::new(rent_structure, token_supply).optimize(outputs)?;
+            log::debug!(
+                "[TRANSACTION] optimized storage deposits to {} outputs",
+                optimized_outputs.len()
+            );
+            outputs = optimized_outputs;
+        }
+
         let is_burn_present = options.as_ref().map(|options| options.burn.is_some()).unwrap_or(false);
 
         // Validate the number of outputs. The validation shouldn't be performed if [`Burn`] is present.
@@ -100,6 +127,8 @@ where
             None => None,
         };
 
+        #[cfg(feature = "metrics")]
+        let input_selection_start_time = instant::Instant::now();
         let selected_transaction_data = self
             .select_inputs(
                 outputs,
@@ -115,6 +144,23 @@ where
                 options.as_ref().and_then(|options| options.burn.as_ref()),
             )
             .await?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("inputs", selected_transaction_data.inputs.len());
+        #[cfg(feature = "metrics")]
+        {
+            use crate::client::metrics::{metrics, INPUT_SELECTION_DURATION_SECONDS, INPUT_SELECTION_INPUTS};
+
+            metrics().observe(
+                &INPUT_SELECTION_DURATION_SECONDS,
+                &[],
+                input_selection_start_time.elapsed().as_secs_f64(),
+            );
+            metrics().observe(
+                &INPUT_SELECTION_INPUTS,
+                &[],
+                selected_transaction_data.inputs.len() as f64,
+            );
+        }
 
         let prepared_transaction_data = match self
             .build_transaction_essence(selected_transaction_data.clone(), options)
@@ -128,6 +174,18 @@ where
             }
         };
 
+        // Journal the locked inputs, so they can be unlocked if the wallet stops before the transaction is stored
+        #[cfg(feature = "storage")]
+        if let Err(err) = self
+            .journal(crate::wallet::storage::journal::JournalEntry::inputs_locked(
+                &prepared_transaction_data,
+            ))
+            .await
+        {
+            self.unlock_inputs(&selected_transaction_data.inputs).await?;
+            return Err(err);
+        }
+
         log::debug!(
             "[TRANSACTION] finished prepare_transaction in {:.2?}",
             prepare_transaction_start_time.elapsed()
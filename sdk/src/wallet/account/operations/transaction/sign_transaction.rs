@@ -25,11 +25,21 @@ where
     crate::wallet::Error: From<S::Error>,
 {
     /// Signs a transaction essence.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "sign_transaction",
+            skip_all,
+            fields(account_index = tracing::field::Empty, inputs = prepared_transaction_data.inputs_data.len())
+        )
+    )]
     pub async fn sign_transaction_essence(
         &self,
         prepared_transaction_data: &PreparedTransactionData,
     ) -> crate::wallet::Result<SignedTransactionData> {
         log::debug!("[TRANSACTION] sign_transaction_essence");
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("account_index", self.details().await.index);
         log::debug!("[TRANSACTION] prepared_transaction_data {prepared_transaction_data:?}");
         #[cfg(feature = "events")]
         self.emit(
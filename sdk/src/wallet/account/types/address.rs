@@ -3,12 +3,16 @@
 
 use std::hash::Hash;
 
+use crypto::signatures::secp256k1_ecdsa::EvmAddress;
 use getset::{Getters, Setters};
 use serde::{Deserialize, Serialize};
 
-use crate::types::{
-    self,
-    block::{address::Bech32Address, output::OutputId, ConvertTo},
+use crate::{
+    types::{
+        self,
+        block::{address::Bech32Address, output::OutputId, ConvertTo},
+    },
+    utils::serde::evm_address,
 };
 
 /// An account address.
@@ -67,3 +71,15 @@ impl AddressWithUnspentOutputs {
         self.address
     }
 }
+
+/// An EVM address of an account, derived with the Ether coin type.
+#[derive(Debug, Getters, Clone, Copy, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct EvmAccountAddress {
+    /// The address.
+    #[serde(with = "evm_address")]
+    pub(crate) address: EvmAddress,
+    /// The address key index.
+    pub(crate) key_index: u32,
+}
@@ -7,7 +7,10 @@ use getset::{CopyGetters, Getters};
 use primitive_types::U256;
 use serde::{Deserialize, Serialize};
 
-use crate::types::block::output::{feature::MetadataFeature, AliasId, FoundryId, NftId, OutputId, TokenId};
+use crate::types::block::{
+    address::Bech32Address,
+    output::{feature::MetadataFeature, AliasId, FoundryId, NativeTokens, NftId, OutputId, TokenId},
+};
 
 /// The balance of an account, returned from [`crate::wallet::account::Account::sync()`] and
 /// [`crate::wallet::account::Account::balance()`].
@@ -147,6 +150,98 @@ impl std::ops::AddAssign for NativeTokensBalance {
     }
 }
 
+/// Time-locked and expiring funds of an account, returned from
+/// [`crate::wallet::account::Account::time_locked_balance()`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct TimeLockedBalance {
+    /// Outputs owned by the account which can't be spent before their timelock expires
+    pub(crate) locked: Vec<TimeLockedOutput>,
+    /// Outputs sent by the account with an expiration, which return to the account if they aren't claimed by the
+    /// recipient in time
+    pub(crate) expiring: Vec<ExpiringOutput>,
+}
+
+impl TimeLockedBalance {
+    /// Returns the amount of base coins that is currently time-locked.
+    pub fn locked_amount(&self) -> u64 {
+        self.locked.iter().map(|output| output.amount).sum()
+    }
+
+    /// Returns the amount of base coins that is still time-locked at the given unix timestamp in seconds.
+    pub fn locked_amount_at(&self, timestamp: u32) -> u64 {
+        self.locked
+            .iter()
+            .filter(|output| output.unlock_time > timestamp)
+            .map(|output| output.amount)
+            .sum()
+    }
+
+    /// Returns the amount of base coins of expired outputs, which can be reclaimed by the account now.
+    pub fn reclaimable_amount(&self) -> u64 {
+        self.expiring
+            .iter()
+            .filter(|output| output.reclaimable)
+            .map(|output| output.amount)
+            .sum()
+    }
+
+    /// Returns the amount of base coins of outputs that become reclaimable by the account if they are still unclaimed
+    /// at the given unix timestamp in seconds.
+    pub fn reclaimable_amount_at(&self, timestamp: u32) -> u64 {
+        self.expiring
+            .iter()
+            .filter(|output| output.expiration_time <= timestamp)
+            .map(|output| output.amount)
+            .sum()
+    }
+}
+
+/// An output owned by the account with a timelock, part of [`TimeLockedBalance`]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeLockedOutput {
+    /// The output id
+    #[getset(get = "pub")]
+    pub(crate) output_id: OutputId,
+    /// Amount of base coins
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    pub(crate) amount: u64,
+    /// Native tokens
+    #[getset(get = "pub")]
+    pub(crate) native_tokens: NativeTokens,
+    /// Unix timestamp in seconds until which the output is locked
+    #[getset(get_copy = "pub")]
+    pub(crate) unlock_time: u32,
+}
+
+/// An output sent by the account with an expiration, part of [`TimeLockedBalance`]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Getters, CopyGetters)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringOutput {
+    /// The output id
+    #[getset(get = "pub")]
+    pub(crate) output_id: OutputId,
+    /// Amount of base coins
+    #[serde(with = "crate::utils::serde::string")]
+    #[getset(get_copy = "pub")]
+    pub(crate) amount: u64,
+    /// Native tokens
+    #[getset(get = "pub")]
+    pub(crate) native_tokens: NativeTokens,
+    /// The recipient which can claim the output until it expires
+    #[getset(get = "pub")]
+    pub(crate) recipient: Bech32Address,
+    /// Unix timestamp in seconds after which the output returns to the account
+    #[getset(get_copy = "pub")]
+    pub(crate) expiration_time: u32,
+    /// Whether the output is expired and can be reclaimed by the account
+    #[getset(get_copy = "pub")]
+    pub(crate) reclaimable: bool,
+}
+
 #[cfg(feature = "rand")]
 impl Balance {
     pub fn rand_mock() -> Self {
@@ -13,8 +13,11 @@ use crypto::keys::bip44::Bip44;
 use serde::{Deserialize, Deserializer, Serialize};
 
 pub use self::{
-    address::{AccountAddress, AddressWithUnspentOutputs},
-    balance::{Balance, BaseCoinBalance, NativeTokensBalance, RequiredStorageDeposit},
+    address::{AccountAddress, AddressWithUnspentOutputs, EvmAccountAddress},
+    balance::{
+        Balance, BaseCoinBalance, ExpiringOutput, NativeTokensBalance, RequiredStorageDeposit, TimeLockedBalance,
+        TimeLockedOutput,
+    },
 };
 use crate::{
     client::secret::types::InputSigningData,
@@ -0,0 +1,266 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    types::block::{
+        address::{Address, AliasAddress, Bech32Address, Hrp, NftAddress, ToBech32Ext},
+        output::{AliasId, NftId},
+        ConvertTo,
+    },
+    wallet::Error,
+};
+
+/// What an [`AddressBookEntry`] refers to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum AddressBookTarget {
+    /// A Bech32 encoded address
+    Address(Bech32Address),
+    /// The id of an alias
+    Alias(AliasId),
+    /// The id of an NFT
+    Nft(NftId),
+}
+
+impl AddressBookTarget {
+    /// Returns the address of the target, alias and NFT ids are converted to their address.
+    pub fn to_address(&self) -> Address {
+        match self {
+            Self::Address(address) => *address.inner(),
+            Self::Alias(alias_id) => AliasAddress::new(*alias_id).into(),
+            Self::Nft(nft_id) => NftAddress::new(*nft_id).into(),
+        }
+    }
+}
+
+/// A Bech32 encoded address or a label of the wallet's [`AddressBook`], resolved when a transaction is prepared.
+///
+/// Strings that are valid Bech32 addresses are taken as address, all other strings as label.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AddressOrLabel {
+    /// A Bech32 encoded address
+    Address(Bech32Address),
+    /// A label of the address book
+    Label(String),
+}
+
+impl From<Bech32Address> for AddressOrLabel {
+    fn from(address: Bech32Address) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl<T: AsRef<str> + Send> ConvertTo<AddressOrLabel> for T {
+    fn convert(self) -> Result<AddressOrLabel, crate::types::block::Error> {
+        let label_or_address = self.as_ref();
+
+        Ok(Bech32Address::try_from_str(label_or_address).map_or_else(
+            |_| AddressOrLabel::Label(label_or_address.to_owned()),
+            AddressOrLabel::Address,
+        ))
+    }
+}
+
+impl ConvertTo<AddressOrLabel> for Bech32Address {
+    fn convert(self) -> Result<AddressOrLabel, crate::types::block::Error> {
+        Ok(self.into())
+    }
+}
+
+impl ConvertTo<AddressOrLabel> for &Bech32Address {
+    fn convert(self) -> Result<AddressOrLabel, crate::types::block::Error> {
+        Ok((*self).into())
+    }
+}
+
+/// A labelled entry of the [`AddressBook`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[serde(rename_all = "camelCase")]
+#[getset(get = "pub")]
+pub struct AddressBookEntry {
+    /// The unique label of the entry
+    label: String,
+    /// The address, alias id or NFT id the label refers to
+    target: AddressBookTarget,
+    /// Tags to group entries, e.g. `payroll` or `exchange`
+    #[serde(default)]
+    tags: BTreeSet<String>,
+}
+
+impl AddressBookEntry {
+    /// Creates a new [`AddressBookEntry`].
+    pub fn new(label: impl Into<String>, target: AddressBookTarget) -> Self {
+        Self {
+            label: label.into(),
+            target,
+            tags: BTreeSet::new(),
+        }
+    }
+
+    /// Adds a tag to the entry.
+    pub fn add_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.insert(tag.into());
+        self
+    }
+
+    /// Sets the tags of the entry.
+    pub fn with_tags(mut self, tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tags = tags.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// The address book of a wallet, holding labelled addresses, alias ids and NFT ids shared by all accounts.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AddressBook {
+    entries: BTreeMap<String, AddressBookEntry>,
+}
+
+impl AddressBook {
+    /// Returns the entry with the given label.
+    pub fn get(&self, label: &str) -> Option<&AddressBookEntry> {
+        self.entries.get(label)
+    }
+
+    /// Returns all entries, ordered by label.
+    pub fn entries(&self) -> impl Iterator<Item = &AddressBookEntry> {
+        self.entries.values()
+    }
+
+    /// Returns the entries with the given tag, ordered by label.
+    pub fn entries_with_tag<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a AddressBookEntry> {
+        self.entries.values().filter(move |entry| entry.tags.contains(tag))
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the address book is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Inserts an entry, replacing and returning an existing entry with the same label.
+    pub fn insert(&mut self, entry: AddressBookEntry) -> Option<AddressBookEntry> {
+        self.entries.insert(entry.label.clone(), entry)
+    }
+
+    /// Removes and returns the entry with the given label.
+    pub fn remove(&mut self, label: &str) -> Option<AddressBookEntry> {
+        self.entries.remove(label)
+    }
+
+    /// Returns the label of an address. Alias and NFT addresses also match entries of their alias or NFT id, the
+    /// network of Bech32 addresses is ignored.
+    pub fn label_of_address(&self, address: &Address) -> Option<&str> {
+        self.entries
+            .values()
+            .find(|entry| entry.target.to_address() == *address)
+            .map(|entry| entry.label.as_str())
+    }
+
+    /// Returns the label of an alias id.
+    pub fn label_of_alias(&self, alias_id: &AliasId) -> Option<&str> {
+        self.label_of_address(&AliasAddress::new(*alias_id).into())
+    }
+
+    /// Returns the label of an NFT id.
+    pub fn label_of_nft(&self, nft_id: &NftId) -> Option<&str> {
+        self.label_of_address(&NftAddress::new(*nft_id).into())
+    }
+
+    /// Resolves a label or a Bech32 encoded address to a [`Bech32Address`]. Labels of alias and NFT ids resolve to
+    /// their alias and NFT address, encoded with the given Human Readable Part.
+    pub fn resolve_address(&self, label_or_address: &str, bech32_hrp: Hrp) -> crate::wallet::Result<Bech32Address> {
+        match self.entries.get(label_or_address) {
+            Some(AddressBookEntry {
+                target: AddressBookTarget::Address(address),
+                ..
+            }) => Ok(*address),
+            Some(entry) => Ok(entry.target.to_address().to_bech32(bech32_hrp)),
+            None => Bech32Address::try_from_str(label_or_address).map_err(|_| {
+                Error::AddressBookResolutionFailed(format!(
+                    "`{label_or_address}` is neither a label nor a Bech32 address"
+                ))
+            }),
+        }
+    }
+
+    /// Resolves a label or a hex encoded NFT id to an [`NftId`].
+    pub fn resolve_nft_id(&self, label_or_nft_id: &str) -> crate::wallet::Result<NftId> {
+        match self.entries.get(label_or_nft_id) {
+            Some(AddressBookEntry {
+                target: AddressBookTarget::Nft(nft_id),
+                ..
+            }) => Ok(*nft_id),
+            Some(_) => Err(Error::AddressBookResolutionFailed(format!(
+                "`{label_or_nft_id}` doesn't refer to an NFT"
+            ))),
+            None => NftId::from_str(label_or_nft_id).map_err(|_| {
+                Error::AddressBookResolutionFailed(format!("`{label_or_nft_id}` is neither a label nor an NFT id"))
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::types::block::address::Ed25519Address;
+
+    #[test]
+    fn resolve() {
+        let hrp = Hrp::from_str_unchecked("rms");
+        let address = Ed25519Address::new([1; Ed25519Address::LENGTH]).to_bech32(hrp);
+        let nft_id = NftId::new([2; NftId::LENGTH]);
+
+        let mut address_book = AddressBook::default();
+        address_book.insert(AddressBookEntry::new("alice", AddressBookTarget::Address(address)).add_tag("payroll"));
+        address_book.insert(AddressBookEntry::new("artwork", AddressBookTarget::Nft(nft_id)));
+
+        assert_eq!(address_book.resolve_address("alice", hrp).unwrap(), address);
+        assert_eq!(
+            address_book.resolve_address(&address.to_string(), hrp).unwrap(),
+            address
+        );
+        assert_eq!(
+            address_book.resolve_address("artwork", hrp).unwrap(),
+            NftAddress::new(nft_id).to_bech32(hrp)
+        );
+        assert!(address_book.resolve_address("bob", hrp).is_err());
+
+        let label: AddressOrLabel = "alice".convert().unwrap();
+        assert_eq!(label, AddressOrLabel::Label("alice".to_owned()));
+        let bech32_address: AddressOrLabel = address.to_string().convert().unwrap();
+        assert_eq!(bech32_address, AddressOrLabel::Address(address));
+        assert_eq!(
+            serde_json::from_value::<AddressOrLabel>(serde_json::json!(address.to_string())).unwrap(),
+            bech32_address
+        );
+        assert_eq!(
+            serde_json::from_value::<AddressOrLabel>(serde_json::json!("alice")).unwrap(),
+            label
+        );
+
+        assert_eq!(address_book.resolve_nft_id("artwork").unwrap(), nft_id);
+        assert_eq!(address_book.resolve_nft_id(&nft_id.to_string()).unwrap(), nft_id);
+        assert!(address_book.resolve_nft_id("alice").is_err());
+
+        assert_eq!(address_book.label_of_address(address.inner()), Some("alice"));
+        assert_eq!(address_book.label_of_nft(&nft_id), Some("artwork"));
+        assert_eq!(address_book.entries_with_tag("payroll").count(), 1);
+    }
+}
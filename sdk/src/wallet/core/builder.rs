@@ -23,7 +23,10 @@ use crate::wallet::{
     storage::{StorageManager, StorageOptions},
 };
 use crate::{
-    client::secret::{SecretManage, SecretManager},
+    client::{
+        secret::{SecretManage, SecretManager},
+        Client,
+    },
     wallet::{core::WalletInner, Account, ClientOptions, Wallet},
 };
 
@@ -37,6 +40,8 @@ pub struct WalletBuilder<S: SecretManage = SecretManager> {
     pub(crate) storage_options: Option<StorageOptions>,
     #[serde(skip)]
     pub(crate) secret_manager: Option<Arc<RwLock<S>>>,
+    #[serde(skip)]
+    pub(crate) client: Option<Client>,
 }
 
 impl<S: SecretManage> Default for WalletBuilder<S> {
@@ -47,6 +52,7 @@ impl<S: SecretManage> Default for WalletBuilder<S> {
             #[cfg(feature = "storage")]
             storage_options: Default::default(),
             secret_manager: Default::default(),
+            client: Default::default(),
         }
     }
 }
@@ -69,6 +75,14 @@ where
         self
     }
 
+    /// Set a client to be used instead of building one from the client options, so it can be shared with other
+    /// wallets. Its options are stored as the client options of the wallet. Note that updating the client options of
+    /// one of the wallets updates the client of all of them.
+    pub fn with_client(mut self, client: impl Into<Option<Client>>) -> Self {
+        self.client = client.into();
+        self
+    }
+
     /// Set the coin type for the wallet. Registered coin types can be found at <https://github.com/satoshilabs/slips/blob/master/slip-0044.md>.
     pub fn with_coin_type(mut self, coin_type: impl Into<Option<u32>>) -> Self {
         self.coin_type = coin_type.into();
@@ -117,6 +131,10 @@ where
     pub async fn finish(mut self) -> crate::wallet::Result<Wallet<S>> {
         log::debug!("[WalletBuilder]");
 
+        if let Some(client) = &self.client {
+            self.client_options = Some(ClientOptions::from_client(client).await);
+        }
+
         #[cfg(feature = "storage")]
         let storage_options = self.storage_options.clone().unwrap_or_default();
         // Check if the db exists and if not, return an error if one parameter is missing, because otherwise the db
@@ -134,14 +152,8 @@ where
             }
         }
 
-        #[cfg(all(feature = "rocksdb", feature = "storage"))]
-        let storage =
-            crate::wallet::storage::adapter::rocksdb::RocksdbStorageAdapter::new(storage_options.path.clone())?;
-        #[cfg(all(not(feature = "rocksdb"), feature = "storage"))]
-        let storage = Memory::default();
-
         #[cfg(feature = "storage")]
-        let mut storage_manager = StorageManager::new(storage, storage_options.encryption_key.clone()).await?;
+        let mut storage_manager = storage_manager(&storage_options).await?;
 
         #[cfg(feature = "storage")]
         let read_manager_builder = Self::load(&storage_manager).await?;
@@ -181,6 +193,10 @@ where
 
         #[cfg(feature = "storage")]
         let mut accounts = storage_manager.get_accounts().await?;
+        #[cfg(feature = "storage")]
+        let address_book = storage_manager.get_address_book().await?;
+        #[cfg(not(feature = "storage"))]
+        let address_book = super::AddressBook::default();
 
         // Check against potential account coin type before saving the wallet data
         #[cfg(feature = "storage")]
@@ -208,20 +224,25 @@ where
         let accounts = Vec::new();
         let wallet_inner = Arc::new(WalletInner {
             background_syncing_status: AtomicUsize::new(0),
-            client: self
-                .client_options
-                .clone()
-                .ok_or(crate::wallet::Error::MissingParameter("client_options"))?
-                .finish()
-                .await?,
+            client: match self.client.take() {
+                Some(client) => client,
+                None => {
+                    self.client_options
+                        .clone()
+                        .ok_or(crate::wallet::Error::MissingParameter("client_options"))?
+                        .finish()
+                        .await?
+                }
+            },
             coin_type: AtomicU32::new(coin_type),
             secret_manager: self
                 .secret_manager
                 .ok_or(crate::wallet::Error::MissingParameter("secret_manager"))?,
+            address_book: RwLock::new(address_book),
             #[cfg(feature = "events")]
             event_emitter,
             #[cfg(feature = "storage")]
-            storage_options,
+            storage_options: tokio::sync::RwLock::new(storage_options),
             #[cfg(feature = "storage")]
             storage_manager: tokio::sync::RwLock::new(storage_manager),
         });
@@ -253,12 +274,30 @@ where
         Self {
             client_options: Some(wallet.client_options().await),
             coin_type: Some(wallet.coin_type.load(Ordering::Relaxed)),
-            storage_options: Some(wallet.storage_options.clone()),
+            storage_options: Some(wallet.storage_options.read().await.clone()),
             secret_manager: Some(wallet.secret_manager.clone()),
+            client: None,
         }
     }
 }
 
+// Opens the storage with the adapter of the storage kind
+#[cfg(feature = "storage")]
+async fn storage_manager(storage_options: &StorageOptions) -> crate::wallet::Result<StorageManager> {
+    #[cfg(feature = "sqlite")]
+    if matches!(storage_options.kind, crate::wallet::storage::StorageKind::Sqlite) {
+        let storage = crate::wallet::storage::adapter::sqlite::SqliteStorageAdapter::new(&storage_options.path)?;
+        return StorageManager::new(storage, storage_options.storage_key()).await;
+    }
+
+    #[cfg(feature = "rocksdb")]
+    let storage = crate::wallet::storage::adapter::rocksdb::RocksdbStorageAdapter::new(storage_options.path.clone())?;
+    #[cfg(not(feature = "rocksdb"))]
+    let storage = Memory::default();
+
+    StorageManager::new(storage, storage_options.storage_key()).await
+}
+
 // Check if any of the locked inputs is not used in a transaction and unlock them, so they get available for new
 // transactions
 #[cfg(feature = "storage")]
@@ -312,6 +351,7 @@ pub(crate) mod dto {
                 #[cfg(feature = "storage")]
                 storage_options: value.storage_options,
                 secret_manager: None,
+                client: None,
             }
         }
     }
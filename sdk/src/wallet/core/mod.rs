@@ -1,6 +1,7 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod address_book;
 pub(crate) mod builder;
 pub(crate) mod operations;
 
@@ -12,7 +13,10 @@ use std::sync::{
 use crypto::keys::bip39::{Mnemonic, MnemonicRef};
 use tokio::sync::RwLock;
 
-pub use self::builder::WalletBuilder;
+pub use self::{
+    address_book::{AddressBook, AddressBookEntry, AddressBookTarget, AddressOrLabel},
+    builder::WalletBuilder,
+};
 #[cfg(feature = "events")]
 use crate::wallet::events::{
     types::{Event, WalletEventType},
@@ -77,10 +81,11 @@ pub struct WalletInner<S: SecretManage = SecretManager> {
     pub(crate) client: Client,
     pub(crate) coin_type: AtomicU32,
     pub(crate) secret_manager: Arc<RwLock<S>>,
+    pub(crate) address_book: RwLock<AddressBook>,
     #[cfg(feature = "events")]
     pub(crate) event_emitter: tokio::sync::RwLock<EventEmitter>,
     #[cfg(feature = "storage")]
-    pub(crate) storage_options: StorageOptions,
+    pub(crate) storage_options: tokio::sync::RwLock<StorageOptions>,
     #[cfg(feature = "storage")]
     pub(crate) storage_manager: tokio::sync::RwLock<StorageManager>,
 }
@@ -0,0 +1,71 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::secret::SecretManage,
+    wallet::core::{
+        address_book::{AddressBook, AddressBookEntry},
+        WalletInner,
+    },
+};
+
+impl<S: 'static + SecretManage> WalletInner<S> {
+    /// Returns the address book of the wallet.
+    pub async fn address_book(&self) -> AddressBook {
+        self.address_book.read().await.clone()
+    }
+
+    /// Inserts an entry into the address book, replacing and returning an existing entry with the same label.
+    /// ```ignore
+    /// wallet
+    ///     .set_address_book_entry(
+    ///         AddressBookEntry::new("alice", AddressBookTarget::Address(address)).add_tag("payroll"),
+    ///     )
+    ///     .await?;
+    ///
+    /// let params = [SendParams::new(1_000_000, "alice")?];
+    /// ```
+    pub async fn set_address_book_entry(
+        &self,
+        entry: AddressBookEntry,
+    ) -> crate::wallet::Result<Option<AddressBookEntry>> {
+        log::debug!("[set_address_book_entry] {}", entry.label());
+
+        let mut address_book = self.address_book.write().await;
+        // Change a copy, so the address book stays unchanged if it can't be saved
+        let mut updated_address_book = address_book.clone();
+        let replaced = updated_address_book.insert(entry);
+
+        #[cfg(feature = "storage")]
+        self.storage_manager
+            .read()
+            .await
+            .save_address_book(&updated_address_book)
+            .await?;
+
+        *address_book = updated_address_book;
+        Ok(replaced)
+    }
+
+    /// Removes and returns the address book entry with the given label.
+    pub async fn remove_address_book_entry(&self, label: &str) -> crate::wallet::Result<Option<AddressBookEntry>> {
+        log::debug!("[remove_address_book_entry] {label}");
+
+        let mut address_book = self.address_book.write().await;
+        // Change a copy, so the address book stays unchanged if it can't be saved
+        let mut updated_address_book = address_book.clone();
+        let removed = updated_address_book.remove(label);
+
+        #[cfg(feature = "storage")]
+        if removed.is_some() {
+            self.storage_manager
+                .read()
+                .await
+                .save_address_book(&updated_address_book)
+                .await?;
+        }
+
+        *address_book = updated_address_book;
+        Ok(removed)
+    }
+}
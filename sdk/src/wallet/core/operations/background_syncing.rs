@@ -1,13 +1,16 @@
 // Copyright 2021 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{sync::atomic::Ordering, time::Duration};
+use std::{future::Future, sync::atomic::Ordering, time::Duration};
 
 use tokio::time::sleep;
 
 use crate::{
     client::secret::SecretManage,
-    wallet::{account::operations::syncing::SyncOptions, Wallet},
+    wallet::{
+        account::{operations::syncing::SyncOptions, Account, OutputsToClaim},
+        Wallet,
+    },
 };
 
 /// The default interval for background syncing
@@ -17,7 +20,12 @@ impl<S: 'static + SecretManage> Wallet<S>
 where
     crate::wallet::Error: From<S::Error>,
 {
-    /// Start the background syncing process for all accounts, default interval is 7 seconds
+    /// Start the background syncing process for all accounts, default interval is 7 seconds. Outputs sent with an
+    /// expiration that returned to an account, because they weren't claimed in time, are reclaimed automatically if
+    /// `SyncOptions::reclaim_expired_outputs` is set, in the provided options or else in the default sync options of
+    /// the account.
+    /// With the `participation` feature, the participation manager of the accounts runs after syncing, see
+    /// `Account::set_participation_manager_options()`.
     pub async fn start_background_syncing(
         &self,
         options: Option<SyncOptions>,
@@ -35,46 +43,49 @@ where
 
         self.background_syncing_status.store(1, Ordering::Relaxed);
         let wallet = self.clone();
-        let _background_syncing = std::thread::spawn(move || {
-            #[cfg(not(target_family = "wasm"))]
-            let runtime = tokio::runtime::Builder::new_multi_thread()
-                .enable_all()
-                .build()
-                .unwrap();
-            #[cfg(target_family = "wasm")]
-            let runtime = tokio::runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .unwrap();
-            runtime.block_on(async {
-                'outer: loop {
-                    log::debug!("[background_syncing]: syncing accounts");
-                    for account in wallet.accounts.read().await.iter() {
-                        // Check if the process should stop before syncing each account so it stops faster
-                        if wallet.background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
-                        }
-                        match account.sync(options.clone()).await {
-                            Ok(_) => {}
-                            Err(err) => log::debug!("[background_syncing] error: {}", err),
-                        };
+        spawn_background_task(move || async move {
+            'outer: loop {
+                log::debug!("[background_syncing]: syncing accounts");
+                for account in wallet.accounts.read().await.iter() {
+                    // Check if the process should stop before syncing each account so it stops faster
+                    if wallet.background_syncing_status.load(Ordering::Relaxed) == 2 {
+                        log::debug!("[background_syncing]: stopping");
+                        break 'outer;
                     }
-                    // split interval syncing to seconds so stopping the process doesn't have to wait long
-                    let seconds = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL).as_secs();
-                    for _ in 0..seconds {
-                        if wallet.background_syncing_status.load(Ordering::Relaxed) == 2 {
-                            log::debug!("[background_syncing]: stopping");
-                            break 'outer;
+                    match account.sync(options.clone()).await {
+                        Ok(_) => {
+                            // Without options, the account was synced with its default sync options
+                            let reclaim = match &options {
+                                Some(options) => options.reclaim_expired_outputs,
+                                None => account.default_sync_options().await.reclaim_expired_outputs,
+                            };
+                            if reclaim {
+                                if let Err(err) = reclaim_expired_outputs(account).await {
+                                    log::debug!("[background_syncing] reclaiming expired outputs error: {}", err)
+                                }
+                            }
+                            #[cfg(feature = "participation")]
+                            if let Err(err) = account.manage_participations().await {
+                                log::debug!("[background_syncing] managing participations error: {}", err)
+                            }
                         }
-                        sleep(Duration::from_secs(1)).await;
+                        Err(err) => log::debug!("[background_syncing] error: {}", err),
+                    };
+                }
+                // split interval syncing to seconds so stopping the process doesn't have to wait long
+                let seconds = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL).as_secs();
+                for _ in 0..seconds {
+                    if wallet.background_syncing_status.load(Ordering::Relaxed) == 2 {
+                        log::debug!("[background_syncing]: stopping");
+                        break 'outer;
                     }
+                    sleep(Duration::from_secs(1)).await;
                 }
-                wallet.background_syncing_status.store(0, Ordering::Relaxed);
-                log::debug!("[background_syncing]: stopped");
-            });
-        });
-        Ok(())
+            }
+            wallet.background_syncing_status.store(0, Ordering::Relaxed);
+            log::debug!("[background_syncing]: stopped");
+        })
+        .inspect_err(|_| self.background_syncing_status.store(0, Ordering::Relaxed))
     }
 
     /// Stop the background syncing of the accounts
@@ -96,3 +107,36 @@ where
         Ok(())
     }
 }
+
+/// Runs a task on its own thread and runtime, so it keeps running independently of the runtime of the caller. Fails if
+/// the runtime can't be created.
+pub(crate) fn spawn_background_task<T, F>(task: T) -> crate::wallet::Result<()>
+where
+    T: FnOnce() -> F + Send + 'static,
+    F: Future<Output = ()>,
+{
+    #[cfg(not(target_family = "wasm"))]
+    let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+    #[cfg(target_family = "wasm")]
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+
+    std::thread::spawn(move || runtime.block_on(task()));
+
+    Ok(())
+}
+
+// Claims the outputs which returned to the account because they expired before the recipient claimed them
+async fn reclaim_expired_outputs<S: 'static + SecretManage>(account: &Account<S>) -> crate::wallet::Result<()>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    let expired_output_ids = account.claimable_outputs(OutputsToClaim::Expired).await?;
+    if !expired_output_ids.is_empty() {
+        log::debug!(
+            "[background_syncing]: reclaiming {} expired outputs",
+            expired_output_ids.len()
+        );
+        account.claim_outputs(expired_output_ids).await?;
+    }
+    Ok(())
+}
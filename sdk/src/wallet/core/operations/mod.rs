@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod account_recovery;
+pub(crate) mod address_book;
 pub(crate) mod address_generation;
 pub(crate) mod background_syncing;
 pub(crate) mod client;
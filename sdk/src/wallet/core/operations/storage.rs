@@ -7,13 +7,16 @@ mod storage_stub {
 
     use crate::{
         client::{
-            secret::{mnemonic::MnemonicSecretManager, SecretManagerConfig},
+            secret::{mnemonic::MnemonicSecretManager, SecretManage, SecretManagerConfig},
             storage::StorageAdapter,
         },
         wallet::{
             core::builder::dto::WalletBuilderDto,
             migration::chrysalis::CHRYSALIS_STORAGE_KEY,
-            storage::constants::{SECRET_MANAGER_KEY, WALLET_INDEXATION_KEY},
+            storage::{
+                constants::{SECRET_MANAGER_KEY, WALLET_INDEXATION_KEY},
+                StorageKey,
+            },
             Wallet, WalletBuilder,
         },
     };
@@ -92,6 +95,38 @@ mod storage_stub {
             self.storage_manager.read().await.get(CHRYSALIS_STORAGE_KEY).await
         }
     }
+
+    impl<S: 'static + SecretManage> Wallet<S>
+    where
+        crate::wallet::Error: From<S::Error>,
+        WalletBuilder<S>: SaveLoadWallet,
+    {
+        /// Re-encrypts all records of the storage with a key derived from the new storage key, or decrypts them if
+        /// `None` is provided. The rotation is atomic: if it gets interrupted, it's finished the next time the wallet
+        /// is built. The new storage key needs to be provided to the
+        /// [`StorageOptions`](crate::wallet::storage::StorageOptions) from then on.
+        pub async fn rotate_storage_key(
+            &self,
+            new_storage_key: impl Into<Option<StorageKey>> + Send,
+        ) -> crate::wallet::Result<()> {
+            log::debug!("[rotate_storage_key]");
+            // Prevent accounts from being added or changed while the records are re-encrypted
+            let _accounts = self.accounts.write().await;
+            let new_storage_key = new_storage_key.into();
+            self.storage_manager
+                .write()
+                .await
+                .rotate_encryption_key(new_storage_key.clone())
+                .await?;
+            self.storage_options.write().await.set_storage_key(new_storage_key);
+
+            // Wallet data saved by previous versions still contains the encryption key
+            WalletBuilder::from_wallet(self)
+                .await
+                .save(&*self.storage_manager.read().await)
+                .await
+        }
+    }
 }
 #[cfg(not(feature = "storage"))]
 mod storage_stub {
@@ -198,6 +198,8 @@ impl Wallet {
                 .with_storage_path(
                     &self
                         .storage_options
+                        .read()
+                        .await
                         .path
                         .clone()
                         .into_os_string()
@@ -371,6 +373,8 @@ impl Wallet<StrongholdSecretManager> {
                 .with_storage_path(
                     &self
                         .storage_options
+                        .read()
+                        .await
                         .path
                         .clone()
                         .into_os_string()
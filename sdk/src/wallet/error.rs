@@ -19,6 +19,9 @@ pub enum Error {
     /// Account not found
     #[error("account {0} not found")]
     AccountNotFound(String),
+    /// Resolving a label or identifier with the address book failed
+    #[error("address book resolution failed: {0}")]
+    AddressBookResolutionFailed(String),
     /// Address not found in account
     #[error("address {0} not found in account")]
     AddressNotFoundInAccount(Bech32Address),
@@ -43,24 +46,42 @@ pub enum Error {
     /// Custom input error
     #[error("custom input error {0}")]
     CustomInput(String),
+    /// EVM address not found in account
+    #[error("EVM address {0} not found in account")]
+    EvmAddressNotFoundInAccount(String),
     /// Failed to get remainder
     #[error("failed to get remainder address")]
     FailedToGetRemainder,
+    /// Updating or destroying a foundry failed
+    #[error("foundry operation failed: {0}")]
+    FoundryOperationFailed(String),
     /// Insufficient funds to send transaction.
     #[error("address owns insufficient funds: {required} base unit required, but {available} base unit available")]
     InsufficientFunds { available: u64, required: u64 },
+    /// Address of a chain isn't an alias address
+    #[error("invalid chain address {0}, must be an alias address")]
+    InvalidChainAddress(Bech32Address),
     /// Invalid coin type, all accounts need to have the same coin type
     #[error("invalid coin type for new account: {new_coin_type}, existing coin type is: {existing_coin_type}")]
     InvalidCoinType {
         new_coin_type: u32,
         existing_coin_type: u32,
     },
+    /// Invalid EVM transaction
+    #[error("invalid EVM transaction: {0}")]
+    InvalidEvmTransaction(String),
+    /// Invalid ISC request metadata
+    #[error("invalid ISC request: {0}")]
+    InvalidIscRequest(String),
     /// Invalid mnemonic error
     #[error("invalid mnemonic: {0}")]
     InvalidMnemonic(String),
     /// Invalid output kind.
     #[error("invalid output kind: {0}")]
     InvalidOutputKind(String),
+    /// Invalid EIP-712 typed data
+    #[error("invalid typed data: {0}")]
+    InvalidTypedData(String),
     /// IO error. (storage, backup, restore)
     #[error("`{0}`")]
     Io(#[from] std::io::Error),
@@ -109,6 +130,15 @@ pub enum Error {
     /// Transaction not found
     #[error("transaction {0} not found")]
     TransactionNotFound(TransactionId),
+    /// Invalid vesting schedule
+    #[error("invalid vesting schedule: {0}")]
+    VestingScheduleInvalid(String),
+    /// A wallet with the id is already hosted
+    #[error("wallet {0} is already hosted")]
+    WalletAlreadyHosted(String),
+    /// Wallet not found in the host
+    #[error("wallet {0} not hosted")]
+    WalletNotHosted(String),
     // TODO more precise error
     /// Voting error
     #[cfg(feature = "participation")]
@@ -189,3 +219,10 @@ impl From<rocksdb::Error> for Error {
         Self::Storage(error.to_string())
     }
 }
+
+#[cfg(feature = "sqlite")]
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Self {
+        Self::Storage(error.to_string())
+    }
+}
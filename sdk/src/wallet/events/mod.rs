@@ -44,6 +44,9 @@ impl EventEmitter {
                 WalletEventType::ConsolidationRequired,
                 #[cfg(feature = "ledger_nano")]
                 WalletEventType::LedgerAddressGeneration,
+                #[cfg(feature = "participation")]
+                WalletEventType::ParticipationWarning,
+                WalletEventType::ProtocolParametersUpgrade,
             ] {
                 self.handlers.entry(event_type).or_default().push(handler.clone());
             }
@@ -77,6 +80,9 @@ impl EventEmitter {
             WalletEvent::ConsolidationRequired => WalletEventType::ConsolidationRequired,
             #[cfg(feature = "ledger_nano")]
             WalletEvent::LedgerAddressGeneration(_) => WalletEventType::LedgerAddressGeneration,
+            #[cfg(feature = "participation")]
+            WalletEvent::ParticipationWarning(_) => WalletEventType::ParticipationWarning,
+            WalletEvent::ProtocolParametersUpgrade(_) => WalletEventType::ProtocolParametersUpgrade,
         };
         let event = Event { account_index, event };
         if let Some(handlers) = self.handlers.get(&event_type) {
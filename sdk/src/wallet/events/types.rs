@@ -4,6 +4,9 @@
 use getset::Getters;
 use serde::{Deserialize, Serialize, Serializer};
 
+#[cfg(feature = "participation")]
+pub use crate::wallet::account::ParticipationWarningEvent;
+pub use crate::wallet::account::ProtocolParametersUpgradeEvent;
 use crate::{
     client::api::PreparedTransactionDataDto,
     types::{
@@ -36,6 +39,10 @@ pub enum WalletEvent {
     SpentOutput(Box<SpentOutputEvent>),
     TransactionInclusion(TransactionInclusionEvent),
     TransactionProgress(TransactionProgressEvent),
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    ParticipationWarning(ParticipationWarningEvent),
+    ProtocolParametersUpgrade(Box<ProtocolParametersUpgradeEvent>),
 }
 
 impl Serialize for WalletEvent {
@@ -58,6 +65,9 @@ impl Serialize for WalletEvent {
             T3(&'a SpentOutputEvent),
             T4(&'a TransactionInclusionEvent),
             T5(TransactionProgressEvent_<'a>),
+            #[cfg(feature = "participation")]
+            T6(&'a ParticipationWarningEvent),
+            T7(&'a ProtocolParametersUpgradeEvent),
         }
         #[derive(Serialize)]
         struct TypedWalletEvent_<'a> {
@@ -92,6 +102,15 @@ impl Serialize for WalletEvent {
                 kind: WalletEventType::TransactionProgress as u8,
                 event: WalletEvent_::T5(TransactionProgressEvent_ { progress: e }),
             },
+            #[cfg(feature = "participation")]
+            Self::ParticipationWarning(e) => TypedWalletEvent_ {
+                kind: WalletEventType::ParticipationWarning as u8,
+                event: WalletEvent_::T6(e),
+            },
+            Self::ProtocolParametersUpgrade(e) => TypedWalletEvent_ {
+                kind: WalletEventType::ProtocolParametersUpgrade as u8,
+                event: WalletEvent_::T7(e),
+            },
         };
         event.serialize(serializer)
     }
@@ -143,6 +162,17 @@ impl<'de> Deserialize<'de> for WalletEvent {
                         })?
                         .progress,
                 ),
+                #[cfg(feature = "participation")]
+                WalletEventType::ParticipationWarning => {
+                    Self::ParticipationWarning(ParticipationWarningEvent::deserialize(value).map_err(|e| {
+                        serde::de::Error::custom(format!("cannot deserialize ParticipationWarning: {e}"))
+                    })?)
+                }
+                WalletEventType::ProtocolParametersUpgrade => Self::ProtocolParametersUpgrade(Box::new(
+                    ProtocolParametersUpgradeEvent::deserialize(value).map_err(|e| {
+                        serde::de::Error::custom(format!("cannot deserialize ProtocolParametersUpgrade: {e}"))
+                    })?,
+                )),
             },
         )
     }
@@ -160,6 +190,10 @@ pub enum WalletEventType {
     SpentOutput = 3,
     TransactionInclusion = 4,
     TransactionProgress = 5,
+    #[cfg(feature = "participation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
+    ParticipationWarning = 6,
+    ProtocolParametersUpgrade = 7,
 }
 
 impl TryFrom<u8> for WalletEventType {
@@ -174,6 +208,9 @@ impl TryFrom<u8> for WalletEventType {
             3 => Self::SpentOutput,
             4 => Self::TransactionInclusion,
             5 => Self::TransactionProgress,
+            #[cfg(feature = "participation")]
+            6 => Self::ParticipationWarning,
+            7 => Self::ProtocolParametersUpgrade,
             _ => return Err(format!("invalid event type {value}")),
         };
         Ok(event_type)
@@ -0,0 +1,305 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::{sync::RwLock, time::sleep};
+
+use crate::{
+    client::{
+        secret::{SecretManage, SecretManager},
+        Client,
+    },
+    wallet::{
+        account::{operations::syncing::SyncOptions, types::Balance},
+        core::operations::{
+            background_syncing::{spawn_background_task, DEFAULT_BACKGROUNDSYNCING_INTERVAL},
+            storage::SaveLoadWallet,
+        },
+        Wallet, WalletBuilder,
+    },
+};
+
+/// The default number of wallets a [`WalletHost`] syncs at the same time.
+pub const DEFAULT_MAX_CONCURRENT_SYNCS: usize = 4;
+
+/// Options for the syncing scheduler of a [`WalletHost`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletHostOptions {
+    /// The maximum number of wallets synced at the same time.
+    #[serde(default = "default_max_concurrent_syncs")]
+    pub max_concurrent_syncs: usize,
+    /// The maximum number of wallets synced in one round. The least recently synced wallets are synced first, so
+    /// every wallet is synced before any wallet is synced again. All wallets are synced in every round if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_wallets_per_round: Option<usize>,
+    /// The options used to sync the accounts of the wallets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_options: Option<SyncOptions>,
+}
+
+fn default_max_concurrent_syncs() -> usize {
+    DEFAULT_MAX_CONCURRENT_SYNCS
+}
+
+impl Default for WalletHostOptions {
+    fn default() -> Self {
+        Self {
+            max_concurrent_syncs: DEFAULT_MAX_CONCURRENT_SYNCS,
+            max_wallets_per_round: None,
+            sync_options: None,
+        }
+    }
+}
+
+/// Hosts many wallets in one process.
+///
+/// Every wallet has its own storage and secret manager, but they share one [`Client`], so node health checks, the
+/// request pool and MQTT subscriptions are shared too. The wallets are synced by one scheduler, instead of a background
+/// syncing task per wallet.
+#[derive(Debug)]
+pub struct WalletHost<S: SecretManage = SecretManager> {
+    inner: Arc<WalletHostInner<S>>,
+}
+
+impl<S: SecretManage> Clone for WalletHost<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct WalletHostInner<S: SecretManage> {
+    client: Client,
+    options: WalletHostOptions,
+    wallets: RwLock<HashMap<String, HostedWallet<S>>>,
+    // Incremented for every finished sync, to order the wallets by when they were synced last
+    sync_counter: AtomicU64,
+    // 0 = not running, 1 = running, 2 = stopping
+    background_syncing_status: AtomicUsize,
+}
+
+#[derive(Debug)]
+struct HostedWallet<S: SecretManage> {
+    wallet: Wallet<S>,
+    // The value of the sync counter when the wallet was synced last, 0 if it was never synced
+    last_synced: u64,
+    syncing: Arc<AtomicBool>,
+}
+
+// Marks a wallet as syncing until it's dropped, so the mark is also cleared if the sync is cancelled
+struct SyncingGuard(Arc<AtomicBool>);
+
+impl Drop for SyncingGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
+impl<S: 'static + SecretManage> WalletHost<S>
+where
+    crate::wallet::Error: From<S::Error>,
+{
+    /// Creates a host for wallets using the client.
+    pub fn new(client: Client, options: impl Into<Option<WalletHostOptions>>) -> Self {
+        Self {
+            inner: Arc::new(WalletHostInner {
+                client,
+                options: options.into().unwrap_or_default(),
+                wallets: RwLock::new(HashMap::new()),
+                sync_counter: AtomicU64::new(0),
+                background_syncing_status: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Get the client shared by the wallets.
+    pub fn client(&self) -> &Client {
+        &self.inner.client
+    }
+
+    /// Get the options of the syncing scheduler.
+    pub fn options(&self) -> &WalletHostOptions {
+        &self.inner.options
+    }
+
+    /// Builds a wallet with the shared client and hosts it with the id.
+    pub async fn add_wallet(
+        &self,
+        id: impl Into<String> + Send,
+        builder: WalletBuilder<S>,
+    ) -> crate::wallet::Result<Wallet<S>>
+    where
+        WalletBuilder<S>: SaveLoadWallet,
+    {
+        let id = id.into();
+        log::debug!("[WalletHost] add_wallet {id}");
+        if self.inner.wallets.read().await.contains_key(&id) {
+            return Err(crate::wallet::Error::WalletAlreadyHosted(id));
+        }
+
+        let wallet = builder.with_client(self.inner.client.clone()).finish().await?;
+
+        let mut wallets = self.inner.wallets.write().await;
+        // Check again, another wallet with the id could have been added while building this one
+        if wallets.contains_key(&id) {
+            return Err(crate::wallet::Error::WalletAlreadyHosted(id));
+        }
+        wallets.insert(
+            id,
+            HostedWallet {
+                wallet: wallet.clone(),
+                last_synced: 0,
+                syncing: Default::default(),
+            },
+        );
+
+        Ok(wallet)
+    }
+
+    /// Removes the wallet with the id from the host and returns it.
+    pub async fn remove_wallet(&self, id: &str) -> crate::wallet::Result<Wallet<S>> {
+        log::debug!("[WalletHost] remove_wallet {id}");
+        self.inner
+            .wallets
+            .write()
+            .await
+            .remove(id)
+            .map(|hosted| hosted.wallet)
+            .ok_or_else(|| crate::wallet::Error::WalletNotHosted(id.to_string()))
+    }
+
+    /// Get the wallet with the id.
+    pub async fn get_wallet(&self, id: &str) -> crate::wallet::Result<Wallet<S>> {
+        self.inner
+            .wallets
+            .read()
+            .await
+            .get(id)
+            .map(|hosted| hosted.wallet.clone())
+            .ok_or_else(|| crate::wallet::Error::WalletNotHosted(id.to_string()))
+    }
+
+    /// Get the ids of the hosted wallets.
+    pub async fn wallet_ids(&self) -> Vec<String> {
+        let mut ids = self.inner.wallets.read().await.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    /// Syncs one round of wallets, at most `max_concurrent_syncs` at the same time. Wallets that weren't synced for
+    /// the longest time are synced first, wallets that are being synced already are skipped. Returns the balance or
+    /// the error of every synced wallet.
+    pub async fn sync_wallets(&self) -> Vec<(String, crate::wallet::Result<Balance>)> {
+        let scheduled = self.schedule_round().await;
+        log::debug!("[WalletHost] syncing {} wallets", scheduled.len());
+
+        stream::iter(scheduled)
+            .map(|(id, wallet, syncing)| async move {
+                let result = wallet.sync(self.inner.options.sync_options.clone()).await;
+                if let Some(hosted) = self.inner.wallets.write().await.get_mut(&id) {
+                    hosted.last_synced = self.inner.sync_counter.fetch_add(1, Ordering::Relaxed) + 1;
+                }
+                drop(syncing);
+                (id, result)
+            })
+            .buffer_unordered(self.inner.options.max_concurrent_syncs.max(1))
+            .collect()
+            .await
+    }
+
+    // Selects the wallets for the next round, least recently synced first, and marks them as syncing
+    async fn schedule_round(&self) -> Vec<(String, Wallet<S>, SyncingGuard)> {
+        // Locked for writing, so that concurrent rounds don't schedule the same wallets
+        let wallets = self.inner.wallets.write().await;
+
+        let mut candidates = wallets
+            .iter()
+            .filter(|(_, hosted)| !hosted.syncing.load(Ordering::Relaxed))
+            .map(|(id, hosted)| (hosted.last_synced, id.clone()))
+            .collect::<Vec<_>>();
+        candidates.sort();
+        if let Some(max_wallets_per_round) = self.inner.options.max_wallets_per_round {
+            candidates.truncate(max_wallets_per_round);
+        }
+
+        candidates
+            .into_iter()
+            .filter_map(|(_, id)| {
+                let hosted = wallets.get(&id)?;
+                hosted.syncing.store(true, Ordering::Relaxed);
+                Some((id, hosted.wallet.clone(), SyncingGuard(hosted.syncing.clone())))
+            })
+            .collect()
+    }
+
+    /// Start syncing the hosted wallets in the background, default interval between rounds is 7 seconds.
+    pub async fn start_background_syncing(&self, interval: Option<Duration>) -> crate::wallet::Result<()> {
+        log::debug!("[WalletHost] start_background_syncing");
+        // stop existing process if running
+        if self.inner.background_syncing_status.load(Ordering::Relaxed) == 1 {
+            self.inner.background_syncing_status.store(2, Ordering::Relaxed);
+        };
+        while self.inner.background_syncing_status.load(Ordering::Relaxed) == 2 {
+            log::debug!("[WalletHost] waiting for the old background syncing process to stop");
+            sleep(Duration::from_secs(1)).await;
+        }
+
+        self.inner.background_syncing_status.store(1, Ordering::Relaxed);
+        let host = self.clone();
+        spawn_background_task(move || async move {
+            'outer: loop {
+                for (id, result) in host.sync_wallets().await {
+                    if let Err(err) = result {
+                        log::debug!("[WalletHost] syncing wallet {id} error: {err}");
+                    }
+                }
+                // split interval syncing to seconds so stopping the process doesn't have to wait long
+                let seconds = interval.unwrap_or(DEFAULT_BACKGROUNDSYNCING_INTERVAL).as_secs();
+                for _ in 0..seconds {
+                    if host.inner.background_syncing_status.load(Ordering::Relaxed) == 2 {
+                        break 'outer;
+                    }
+                    sleep(Duration::from_secs(1)).await;
+                }
+                if host.inner.background_syncing_status.load(Ordering::Relaxed) == 2 {
+                    break 'outer;
+                }
+            }
+            host.inner.background_syncing_status.store(0, Ordering::Relaxed);
+            log::debug!("[WalletHost] background syncing stopped");
+        })
+        .inspect_err(|_| self.inner.background_syncing_status.store(0, Ordering::Relaxed))
+    }
+
+    /// Stop syncing the hosted wallets in the background.
+    pub async fn stop_background_syncing(&self) -> crate::wallet::Result<()> {
+        log::debug!("[WalletHost] stop_background_syncing");
+        // immediately return if not running
+        if self.inner.background_syncing_status.load(Ordering::Relaxed) == 0 {
+            return Ok(());
+        }
+        // send stop request
+        self.inner.background_syncing_status.store(2, Ordering::Relaxed);
+        // wait until it stopped
+        while self.inner.background_syncing_status.load(Ordering::Relaxed) != 0 {
+            #[cfg(target_family = "wasm")]
+            gloo_timers::future::TimeoutFuture::new(10).await;
+            #[cfg(not(target_family = "wasm"))]
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    }
+}
@@ -220,7 +220,7 @@ pub(crate) mod rocksdb {
                 constants::{
                     ACCOUNTS_INDEXATION_KEY, ACCOUNT_INDEXATION_KEY, SECRET_MANAGER_KEY, WALLET_INDEXATION_KEY,
                 },
-                StorageManager,
+                StorageKey, StorageManager,
             },
         },
     };
@@ -263,7 +263,8 @@ pub(crate) mod rocksdb {
 
         let stardust_db = crate::wallet::storage::adapter::rocksdb::RocksdbStorageAdapter::new(storage_path_string)?;
 
-        let stardust_storage = StorageManager::new(stardust_db, new_db_encryption_key).await?;
+        let stardust_storage =
+            StorageManager::new(stardust_db, new_db_encryption_key.into().map(StorageKey::Key)).await?;
 
         // store chrysalis data in a new key
         stardust_storage
@@ -20,6 +20,8 @@ pub mod error;
 #[cfg(feature = "events")]
 #[cfg_attr(docsrs, doc(cfg(feature = "events")))]
 pub mod events;
+/// The wallet host module, for many wallets sharing one client.
+pub mod host;
 /// The storage module.
 #[cfg(feature = "storage")]
 #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
@@ -30,15 +32,18 @@ pub(crate) mod task;
 pub use self::{
     account::{
         operations::transaction::high_level::{
+            deposit_to_chain::DepositToChainParams,
             minting::{create_native_token::CreateNativeTokenParams, mint_nfts::MintNftParams},
             send::SendParams,
             send_native_tokens::SendNativeTokensParams,
             send_nft::SendNftParams,
+            vesting::VestingScheduleParams,
         },
         Account,
     },
-    core::{Wallet, WalletBuilder},
+    core::{AddressBook, AddressBookEntry, AddressBookTarget, AddressOrLabel, Wallet, WalletBuilder},
     error::Error,
+    host::{WalletHost, WalletHostOptions},
 };
 
 /// The wallet Result type.
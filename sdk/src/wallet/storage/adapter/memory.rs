@@ -9,7 +9,7 @@ use tokio::sync::RwLock;
 use crate::client::storage::StorageAdapter;
 
 /// A storage adapter that stores data in memory.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Memory(Arc<RwLock<HashMap<String, Vec<u8>>>>);
 
 #[async_trait::async_trait]
@@ -6,6 +6,10 @@ pub mod memory;
 #[cfg(feature = "rocksdb")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rocksdb")))]
 pub mod rocksdb;
+/// SQLite storage adapter.
+#[cfg(feature = "sqlite")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sqlite")))]
+pub mod sqlite;
 
 use async_trait::async_trait;
 
@@ -19,10 +23,14 @@ pub(crate) trait DynStorageAdapter: std::fmt::Debug + Send + Sync {
 
     /// Removes a record from the storage.
     async fn dyn_delete(&self, key: &str) -> crate::wallet::Result<()>;
+
+    /// Allows to access adapters that store accounts in their own format.
+    #[cfg(feature = "sqlite")]
+    fn as_any(&self) -> &dyn std::any::Any;
 }
 
 #[async_trait]
-impl<T: StorageAdapter> DynStorageAdapter for T
+impl<T: StorageAdapter + 'static> DynStorageAdapter for T
 where
     crate::wallet::Error: From<T::Error>,
 {
@@ -37,6 +45,11 @@ where
     async fn dyn_delete(&self, key: &str) -> crate::wallet::Result<()> {
         Ok(self.delete(key).await?)
     }
+
+    #[cfg(feature = "sqlite")]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 #[async_trait]
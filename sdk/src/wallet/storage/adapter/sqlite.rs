@@ -0,0 +1,926 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::{Arc, Mutex as StdMutex},
+};
+
+use packable::PackableExt;
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension, Transaction as SqlTransaction};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{
+    client::storage::StorageAdapter,
+    types::block::{
+        address::{Address, Bech32Address},
+        output::{OutputId, TokenId},
+        payload::transaction::TransactionId,
+    },
+    wallet::{
+        account::{
+            types::{AccountAddress, OutputDataDto, TransactionDto},
+            AccountDetails, AccountDetailsDto,
+        },
+        storage::encryption::RecordCipher,
+    },
+};
+
+/// The name of the database file in the storage directory.
+pub const SQLITE_FILENAME: &str = "wallet.sqlite";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS records (
+        key TEXT PRIMARY KEY NOT NULL,
+        value BLOB NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS outputs (
+        account_index INTEGER NOT NULL,
+        output_id TEXT NOT NULL,
+        address TEXT NOT NULL,
+        unspent INTEGER NOT NULL,
+        booked_timestamp INTEGER NOT NULL,
+        data BLOB NOT NULL,
+        rotated_data BLOB,
+        PRIMARY KEY (account_index, output_id)
+    );
+    CREATE INDEX IF NOT EXISTS outputs_address ON outputs (account_index, address);
+    CREATE INDEX IF NOT EXISTS outputs_booked_timestamp ON outputs (account_index, booked_timestamp);
+    CREATE TABLE IF NOT EXISTS output_native_tokens (
+        account_index INTEGER NOT NULL,
+        output_id TEXT NOT NULL,
+        token_id TEXT NOT NULL,
+        PRIMARY KEY (account_index, output_id, token_id)
+    );
+    CREATE INDEX IF NOT EXISTS output_native_tokens_token_id ON output_native_tokens (account_index, token_id);
+    CREATE TABLE IF NOT EXISTS transactions (
+        account_index INTEGER NOT NULL,
+        transaction_id TEXT NOT NULL,
+        incoming INTEGER NOT NULL,
+        timestamp INTEGER NOT NULL,
+        data BLOB NOT NULL,
+        rotated_data BLOB,
+        PRIMARY KEY (account_index, transaction_id, incoming)
+    );
+    CREATE INDEX IF NOT EXISTS transactions_timestamp ON transactions (account_index, timestamp);
+    CREATE TABLE IF NOT EXISTS addresses (
+        account_index INTEGER NOT NULL,
+        internal INTEGER NOT NULL,
+        key_index INTEGER NOT NULL,
+        address TEXT NOT NULL,
+        data BLOB NOT NULL,
+        rotated_data BLOB,
+        PRIMARY KEY (account_index, internal, key_index)
+    );
+    CREATE INDEX IF NOT EXISTS addresses_address ON addresses (account_index, address);
+";
+
+// Tables whose `data` column holds an encrypted record.
+const DATA_TABLES: [&str; 3] = ["outputs", "transactions", "addresses"];
+
+/// Storage adapter backed by SQLite.
+///
+/// Besides key value records, the outputs, transactions and addresses of accounts are stored in their own tables.
+/// They're written incrementally and are indexed by address, native token id and timestamp. The database calls run on
+/// blocking threads, so they don't stall the async runtime.
+///
+/// Only the `data` column of these rows is encrypted with the storage key. The indexed columns, i.e. the addresses,
+/// output ids, native token ids, transaction ids, timestamps and whether outputs are unspent, are stored in plain text,
+/// so anyone who can read the database file can link the addresses of the accounts and follow their activity on the
+/// ledger, even if the storage is encrypted.
+#[derive(Clone, Debug)]
+pub struct SqliteStorageAdapter {
+    pub(crate) connection: Arc<StdMutex<Connection>>,
+    // Fingerprints of the rows last written or read per account, so that only changed rows get written
+    written_rows: Arc<Mutex<HashMap<u32, WrittenRows>>>,
+}
+
+#[derive(Debug, Default)]
+struct WrittenRows {
+    outputs: HashMap<OutputId, u64>,
+    transactions: HashMap<(TransactionId, bool), u64>,
+    addresses: HashMap<(bool, u32), u64>,
+}
+
+// The rows of an account to write and delete in a database transaction.
+#[derive(Debug, Default)]
+struct RowChanges {
+    outputs: Vec<OutputRow>,
+    deleted_outputs: Vec<String>,
+    transactions: Vec<TransactionRow>,
+    deleted_transactions: Vec<(String, bool)>,
+    addresses: Vec<AddressRow>,
+    deleted_addresses: Vec<(bool, u32)>,
+}
+
+#[derive(Debug)]
+struct OutputRow {
+    output_id: String,
+    address: String,
+    unspent: bool,
+    booked_timestamp: u32,
+    token_ids: Vec<String>,
+    data: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct TransactionRow {
+    transaction_id: String,
+    incoming: bool,
+    timestamp: i64,
+    data: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct AddressRow {
+    internal: bool,
+    key_index: u32,
+    address: String,
+    data: Vec<u8>,
+}
+
+impl SqliteStorageAdapter {
+    /// Initialises the storage adapter, the database is created in the given directory if it doesn't exist.
+    pub fn new(path: impl AsRef<Path>) -> crate::wallet::Result<Self> {
+        std::fs::create_dir_all(path.as_ref())?;
+        let connection = Connection::open(path.as_ref().join(SQLITE_FILENAME))?;
+        connection.pragma_update(None, "journal_mode", "WAL")?;
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(Self {
+            connection: Arc::new(StdMutex::new(connection)),
+            written_rows: Default::default(),
+        })
+    }
+
+    // Runs database calls on a blocking thread.
+    async fn with_connection<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> crate::wallet::Result<T> + Send + 'static,
+    ) -> crate::wallet::Result<T> {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection
+                .lock()
+                .map_err(|_| crate::wallet::Error::Storage("the SQLite connection is poisoned".to_string()))?;
+            f(&mut connection)
+        })
+        .await?
+    }
+
+    /// Writes the rows of an account that changed since they were last written or read, together with the remaining
+    /// account details under the given key, in a single database transaction.
+    pub(crate) async fn save_account(
+        &self,
+        key: &str,
+        account: &AccountDetails,
+        cipher: &RecordCipher,
+    ) -> crate::wallet::Result<()> {
+        let account_index = *account.index();
+        let details = AccountDetailsDto {
+            index: account_index,
+            coin_type: *account.coin_type(),
+            alias: account.alias().clone(),
+            public_addresses: Vec::new(),
+            internal_addresses: Vec::new(),
+            evm_addresses: account.evm_addresses().clone(),
+            addresses_with_unspent_outputs: account.addresses_with_unspent_outputs().clone(),
+            outputs: HashMap::new(),
+            locked_outputs: account.locked_outputs().clone(),
+            unspent_outputs: HashMap::new(),
+            transactions: HashMap::new(),
+            pending_transactions: account.pending_transactions().clone(),
+            incoming_transactions: HashMap::new(),
+            native_token_foundries: account
+                .native_token_foundries()
+                .iter()
+                .map(|(id, foundry)| (*id, foundry.into()))
+                .collect(),
+        };
+        let key = key.to_string();
+        let record = cipher.encrypt(&serde_json::to_vec(&details)?)?;
+
+        let mut written_rows = self.written_rows.lock().await;
+        // If the state of the rows isn't known, they're all written again
+        let previous = written_rows.remove(&account_index);
+        let rewrite = previous.is_none();
+        let previous = previous.unwrap_or_default();
+        let mut written = WrittenRows::default();
+        let mut changes = RowChanges::default();
+
+        let unspent_only = account
+            .unspent_outputs()
+            .iter()
+            .filter(|(output_id, _)| !account.outputs().contains_key(output_id));
+        for (output_id, output_data) in account.outputs().iter().chain(unspent_only) {
+            let unspent = account.unspent_outputs().contains_key(output_id);
+            let data = serde_json::to_vec(&OutputDataDto::from(output_data))?;
+            let fingerprint = fingerprint(&data, unspent);
+            if previous.outputs.get(output_id) != Some(&fingerprint) {
+                changes.outputs.push(OutputRow {
+                    output_id: output_id.to_string(),
+                    address: address_column(&output_data.address),
+                    unspent,
+                    booked_timestamp: output_data.metadata.milestone_timestamp_booked(),
+                    token_ids: output_data
+                        .output
+                        .native_tokens()
+                        .map(|native_tokens| {
+                            native_tokens
+                                .iter()
+                                .map(|native_token| native_token.token_id().to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    data: cipher.encrypt(&data)?,
+                });
+            }
+            written.outputs.insert(*output_id, fingerprint);
+        }
+        changes.deleted_outputs = previous
+            .outputs
+            .keys()
+            .filter(|id| !written.outputs.contains_key(id))
+            .map(ToString::to_string)
+            .collect();
+
+        // Sent and incoming transactions are kept apart, as the same transaction can be in both
+        let transactions = account
+            .transactions()
+            .values()
+            .map(|transaction| (transaction, false))
+            .chain(
+                account
+                    .incoming_transactions()
+                    .values()
+                    .map(|transaction| (transaction, true)),
+            );
+        for (transaction, incoming) in transactions {
+            let key = (transaction.transaction_id, incoming);
+            let data = serde_json::to_vec(&TransactionDto::from(transaction))?;
+            let fingerprint = fingerprint(&data, incoming);
+            if previous.transactions.get(&key) != Some(&fingerprint) {
+                changes.transactions.push(TransactionRow {
+                    transaction_id: transaction.transaction_id.to_string(),
+                    incoming,
+                    timestamp: i64::try_from(transaction.timestamp).unwrap_or(i64::MAX),
+                    data: cipher.encrypt(&data)?,
+                });
+            }
+            written.transactions.insert(key, fingerprint);
+        }
+        changes.deleted_transactions = previous
+            .transactions
+            .keys()
+            .filter(|key| !written.transactions.contains_key(key))
+            .map(|(transaction_id, incoming)| (transaction_id.to_string(), *incoming))
+            .collect();
+
+        for address in account.public_addresses().iter().chain(account.internal_addresses()) {
+            let key = (address.internal, address.key_index);
+            let data = serde_json::to_vec(address)?;
+            let fingerprint = fingerprint(&data, address.internal);
+            if previous.addresses.get(&key) != Some(&fingerprint) {
+                changes.addresses.push(AddressRow {
+                    internal: address.internal,
+                    key_index: address.key_index,
+                    address: address_column(address.address.inner()),
+                    data: cipher.encrypt(&data)?,
+                });
+            }
+            written.addresses.insert(key, fingerprint);
+        }
+        changes.deleted_addresses = previous
+            .addresses
+            .keys()
+            .filter(|key| !written.addresses.contains_key(key))
+            .copied()
+            .collect();
+
+        self.with_connection(move |connection| {
+            let tx = connection.transaction()?;
+            tx.execute(
+                "INSERT INTO records (key, value) VALUES (?1, ?2) ON CONFLICT (key) DO UPDATE SET value = \
+                 excluded.value",
+                params![key, record],
+            )?;
+            if rewrite {
+                delete_account_rows(&tx, account_index)?;
+            }
+            write_account_rows(&tx, account_index, &changes)?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await?;
+        written_rows.insert(account_index, written);
+
+        Ok(())
+    }
+
+    /// Reads the account details stored under the given key, together with the rows of the account.
+    pub(crate) async fn get_account(
+        &self,
+        key: &str,
+        cipher: &RecordCipher,
+    ) -> crate::wallet::Result<Option<AccountDetailsDto>> {
+        let mut written_rows = self.written_rows.lock().await;
+
+        let key = key.to_string();
+        let Some(record) = self
+            .with_connection(move |connection| {
+                Ok(connection
+                    .query_row("SELECT value FROM records WHERE key = ?1", [key], |row| row.get(0))
+                    .optional()?)
+            })
+            .await?
+        else {
+            return Ok(None);
+        };
+        let mut account = serde_json::from_slice::<AccountDetailsDto>(&cipher.decrypt(record)?)?;
+        let account_index = account.index;
+        let (outputs, transactions, addresses) = self
+            .with_connection(move |connection| {
+                let outputs = query_rows(
+                    connection,
+                    "SELECT data, unspent FROM outputs WHERE account_index = ?1 ORDER BY output_id",
+                    account_index,
+                )?;
+                let transactions = query_rows(
+                    connection,
+                    "SELECT data, incoming FROM transactions WHERE account_index = ?1",
+                    account_index,
+                )?;
+                let addresses = query_rows(
+                    connection,
+                    "SELECT data, internal FROM addresses WHERE account_index = ?1 ORDER BY key_index",
+                    account_index,
+                )?;
+                Ok((outputs, transactions, addresses))
+            })
+            .await?;
+        let mut written = WrittenRows::default();
+
+        for (data, unspent) in outputs {
+            let data = cipher.decrypt(data)?;
+            let output_data = serde_json::from_slice::<OutputDataDto>(&data)?;
+            written
+                .outputs
+                .insert(output_data.output_id, fingerprint(&data, unspent));
+            if unspent {
+                account
+                    .unspent_outputs
+                    .insert(output_data.output_id, output_data.clone());
+            }
+            account.outputs.insert(output_data.output_id, output_data);
+        }
+
+        for (data, incoming) in transactions {
+            let data = cipher.decrypt(data)?;
+            let transaction = serde_json::from_slice::<TransactionDto>(&data)?;
+            written
+                .transactions
+                .insert((transaction.transaction_id, incoming), fingerprint(&data, incoming));
+            if incoming {
+                account
+                    .incoming_transactions
+                    .insert(transaction.transaction_id, transaction);
+            } else {
+                account.transactions.insert(transaction.transaction_id, transaction);
+            }
+        }
+
+        for (data, internal) in addresses {
+            let data = cipher.decrypt(data)?;
+            let address = serde_json::from_slice::<AccountAddress>(&data)?;
+            written
+                .addresses
+                .insert((address.internal, address.key_index), fingerprint(&data, internal));
+            if address.internal {
+                account.internal_addresses.push(address);
+            } else {
+                account.public_addresses.push(address);
+            }
+        }
+
+        written_rows.insert(account.index, written);
+
+        Ok(Some(account))
+    }
+
+    /// Deletes the rows of an account.
+    pub(crate) async fn remove_account(&self, account_index: u32) -> crate::wallet::Result<()> {
+        let mut written_rows = self.written_rows.lock().await;
+        self.with_connection(move |connection| {
+            let tx = connection.transaction()?;
+            delete_account_rows(&tx, account_index)?;
+            tx.commit()?;
+            Ok(())
+        })
+        .await?;
+        written_rows.remove(&account_index);
+
+        Ok(())
+    }
+
+    /// Writes the `data` column of all rows re-encrypted with the new cipher to the `rotated_data` column.
+    pub(crate) async fn prepare_rotation(
+        &self,
+        cipher: &RecordCipher,
+        new_cipher: &RecordCipher,
+    ) -> crate::wallet::Result<()> {
+        let rows = self
+            .with_connection(|connection| {
+                let mut rows = Vec::new();
+                for table in DATA_TABLES {
+                    let mut statement = connection.prepare(&format!("SELECT rowid, data FROM {table}"))?;
+                    for row in
+                        statement.query_map([], |row| Ok((table, row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?)))?
+                    {
+                        rows.push(row?);
+                    }
+                }
+                Ok(rows)
+            })
+            .await?;
+        let rotated_rows = rows
+            .into_iter()
+            .map(|(table, row_id, data)| Ok((table, row_id, new_cipher.encrypt(&cipher.decrypt(data)?)?)))
+            .collect::<crate::wallet::Result<Vec<_>>>()?;
+
+        self.with_connection(move |connection| {
+            let tx = connection.transaction()?;
+            for (table, row_id, rotated_data) in rotated_rows {
+                tx.execute(
+                    &format!("UPDATE {table} SET rotated_data = ?1 WHERE rowid = ?2"),
+                    params![rotated_data, row_id],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Replaces the `data` column of all rows with the re-encrypted `rotated_data` column.
+    pub(crate) async fn finish_rotation(&self) -> crate::wallet::Result<()> {
+        self.with_connection(|connection| {
+            let tx = connection.transaction()?;
+            for table in DATA_TABLES {
+                tx.execute(
+                    &format!(
+                        "UPDATE {table} SET data = rotated_data, rotated_data = NULL WHERE rotated_data IS NOT NULL"
+                    ),
+                    [],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Returns the outputs of an account matching the query.
+    pub(crate) async fn query_outputs(
+        &self,
+        account_index: u32,
+        query: &StoredOutputsQuery,
+        cipher: &RecordCipher,
+    ) -> crate::wallet::Result<Vec<OutputDataDto>> {
+        let mut conditions = Conditions::new("SELECT data FROM outputs WHERE account_index = ?1", account_index);
+        if let Some(address) = &query.address {
+            conditions.push("address =", address_column(address.inner()));
+        }
+        if let Some(token_id) = &query.token_id {
+            conditions.push(
+                "output_id IN (SELECT output_id FROM output_native_tokens WHERE account_index = ?1 AND token_id =",
+                token_id.to_string(),
+            );
+            conditions.sql.push(')');
+        }
+        if let Some(timestamp) = query.lower_bound_booked_timestamp {
+            conditions.push("booked_timestamp >=", timestamp);
+        }
+        if let Some(timestamp) = query.upper_bound_booked_timestamp {
+            conditions.push("booked_timestamp <", timestamp);
+        }
+        if query.unspent_only {
+            conditions.push("unspent =", true);
+        }
+        conditions.sql.push_str(" ORDER BY booked_timestamp, output_id");
+
+        self.query_data(conditions)
+            .await?
+            .into_iter()
+            .map(|data| Ok(serde_json::from_slice(&cipher.decrypt(data)?)?))
+            .collect()
+    }
+
+    /// Returns the transactions of an account matching the query.
+    pub(crate) async fn query_transactions(
+        &self,
+        account_index: u32,
+        query: &StoredTransactionsQuery,
+        cipher: &RecordCipher,
+    ) -> crate::wallet::Result<Vec<TransactionDto>> {
+        let mut conditions = Conditions::new("SELECT data FROM transactions WHERE account_index = ?1", account_index);
+        if let Some(incoming) = query.incoming {
+            conditions.push("incoming =", incoming);
+        }
+        if let Some(timestamp) = query.lower_bound_timestamp {
+            conditions.push("timestamp >=", i64::try_from(timestamp).unwrap_or(i64::MAX));
+        }
+        if let Some(timestamp) = query.upper_bound_timestamp {
+            conditions.push("timestamp <", i64::try_from(timestamp).unwrap_or(i64::MAX));
+        }
+        conditions.sql.push_str(" ORDER BY timestamp, transaction_id");
+
+        self.query_data(conditions)
+            .await?
+            .into_iter()
+            .map(|data| Ok(serde_json::from_slice(&cipher.decrypt(data)?)?))
+            .collect()
+    }
+
+    // Returns the `data` column of the rows matching the conditions.
+    async fn query_data(&self, conditions: Conditions) -> crate::wallet::Result<Vec<Vec<u8>>> {
+        self.with_connection(move |connection| {
+            let mut statement = connection.prepare(&conditions.sql)?;
+            let rows = statement
+                .query_map(params_from_iter(conditions.values), |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(rows)
+        })
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl StorageAdapter for SqliteStorageAdapter {
+    type Error = crate::wallet::Error;
+
+    async fn get_bytes(&self, key: &str) -> crate::wallet::Result<Option<Vec<u8>>> {
+        let key = key.to_string();
+        self.with_connection(move |connection| {
+            Ok(connection
+                .query_row("SELECT value FROM records WHERE key = ?1", [key], |row| row.get(0))
+                .optional()?)
+        })
+        .await
+    }
+
+    async fn set_bytes(&self, key: &str, record: &[u8]) -> crate::wallet::Result<()> {
+        let (key, record) = (key.to_string(), record.to_vec());
+        self.with_connection(move |connection| {
+            connection.execute(
+                "INSERT INTO records (key, value) VALUES (?1, ?2) ON CONFLICT (key) DO UPDATE SET value = \
+                 excluded.value",
+                params![key, record],
+            )?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> crate::wallet::Result<()> {
+        let key = key.to_string();
+        self.with_connection(move |connection| {
+            connection.execute("DELETE FROM records WHERE key = ?1", [key])?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Query for the outputs of an account stored in a [`SqliteStorageAdapter`], all set conditions need to match.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredOutputsQuery {
+    /// Outputs of this account address
+    pub address: Option<Bech32Address>,
+    /// Outputs holding this native token
+    pub token_id: Option<TokenId>,
+    /// Outputs booked at or after this timestamp
+    pub lower_bound_booked_timestamp: Option<u32>,
+    /// Outputs booked before this timestamp
+    pub upper_bound_booked_timestamp: Option<u32>,
+    /// Only unspent outputs
+    #[serde(default)]
+    pub unspent_only: bool,
+}
+
+/// Query for the transactions of an account stored in a [`SqliteStorageAdapter`], all set conditions need to match.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredTransactionsQuery {
+    /// Only incoming or only sent transactions
+    pub incoming: Option<bool>,
+    /// Transactions created at or after this timestamp in milliseconds
+    #[serde(default, with = "crate::utils::serde::option_string")]
+    pub lower_bound_timestamp: Option<u128>,
+    /// Transactions created before this timestamp in milliseconds
+    #[serde(default, with = "crate::utils::serde::option_string")]
+    pub upper_bound_timestamp: Option<u128>,
+}
+
+// A query with the conditions that are set, each bound to the next numbered parameter.
+struct Conditions {
+    sql: String,
+    values: Vec<Value>,
+}
+
+impl Conditions {
+    fn new(select: &str, account_index: u32) -> Self {
+        Self {
+            sql: select.to_string(),
+            values: vec![account_index.into()],
+        }
+    }
+
+    fn push(&mut self, condition: &str, value: impl Into<Value>) {
+        self.values.push(value.into());
+        self.sql.push_str(&format!(" AND {condition} ?{}", self.values.len()));
+    }
+}
+
+// Writes and deletes the changed rows of an account.
+fn write_account_rows(tx: &SqlTransaction<'_>, account_index: u32, changes: &RowChanges) -> crate::wallet::Result<()> {
+    for output in &changes.outputs {
+        tx.execute(
+            "INSERT OR REPLACE INTO outputs (account_index, output_id, address, unspent, booked_timestamp, data) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                account_index,
+                output.output_id,
+                output.address,
+                output.unspent,
+                output.booked_timestamp,
+                output.data
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM output_native_tokens WHERE account_index = ?1 AND output_id = ?2",
+            params![account_index, output.output_id],
+        )?;
+        for token_id in &output.token_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO output_native_tokens (account_index, output_id, token_id) VALUES (?1, ?2, ?3)",
+                params![account_index, output.output_id, token_id],
+            )?;
+        }
+    }
+    for output_id in &changes.deleted_outputs {
+        tx.execute(
+            "DELETE FROM outputs WHERE account_index = ?1 AND output_id = ?2",
+            params![account_index, output_id],
+        )?;
+        tx.execute(
+            "DELETE FROM output_native_tokens WHERE account_index = ?1 AND output_id = ?2",
+            params![account_index, output_id],
+        )?;
+    }
+
+    for transaction in &changes.transactions {
+        tx.execute(
+            "INSERT OR REPLACE INTO transactions (account_index, transaction_id, incoming, timestamp, data) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                account_index,
+                transaction.transaction_id,
+                transaction.incoming,
+                transaction.timestamp,
+                transaction.data
+            ],
+        )?;
+    }
+    for (transaction_id, incoming) in &changes.deleted_transactions {
+        tx.execute(
+            "DELETE FROM transactions WHERE account_index = ?1 AND transaction_id = ?2 AND incoming = ?3",
+            params![account_index, transaction_id, incoming],
+        )?;
+    }
+
+    for address in &changes.addresses {
+        tx.execute(
+            "INSERT OR REPLACE INTO addresses (account_index, internal, key_index, address, data) \
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                account_index,
+                address.internal,
+                address.key_index,
+                address.address,
+                address.data
+            ],
+        )?;
+    }
+    for (internal, key_index) in &changes.deleted_addresses {
+        tx.execute(
+            "DELETE FROM addresses WHERE account_index = ?1 AND internal = ?2 AND key_index = ?3",
+            params![account_index, internal, key_index],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Returns the `data` column of the rows of an account with the flag selected as second column.
+fn query_rows(connection: &Connection, sql: &str, account_index: u32) -> crate::wallet::Result<Vec<(Vec<u8>, bool)>> {
+    let mut statement = connection.prepare(sql)?;
+    let rows = statement
+        .query_map([account_index], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+fn delete_account_rows(tx: &SqlTransaction<'_>, account_index: u32) -> crate::wallet::Result<()> {
+    for table in ["outputs", "output_native_tokens", "transactions", "addresses"] {
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE account_index = ?1"),
+            [account_index],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Addresses are indexed by their packed bytes, so that lookups don't depend on the Human Readable Part.
+fn address_column(address: &Address) -> String {
+    prefix_hex::encode(address.pack_to_vec())
+}
+
+fn fingerprint(data: &[u8], flag: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    flag.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(all(test, feature = "rand"))]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        types::{
+            block::{
+                address::{Ed25519Address, Hrp, ToBech32Ext},
+                output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeToken, OutputMetadata},
+                rand::{address::rand_ed25519_address, block::rand_block_id, output::rand_output_id},
+            },
+            TryFromDto,
+        },
+        wallet::{
+            account::types::OutputData,
+            storage::{StorageKey, StorageManager},
+        },
+    };
+
+    fn output_data(address: Address, booked_timestamp: u32, token_id: Option<TokenId>) -> OutputData {
+        let output_id = rand_output_id();
+        let mut builder =
+            BasicOutputBuilder::new_with_amount(1_000_000).add_unlock_condition(AddressUnlockCondition::new(address));
+        if let Some(token_id) = token_id {
+            builder = builder.add_native_token(NativeToken::new(token_id, 10).unwrap());
+        }
+
+        OutputData {
+            output_id,
+            metadata: OutputMetadata::new(
+                rand_block_id(),
+                output_id,
+                false,
+                None,
+                None,
+                None,
+                0,
+                booked_timestamp,
+                0,
+            ),
+            output: builder.finish_output(1_813_620_509_061_365).unwrap(),
+            is_spent: false,
+            address,
+            network_id: 0,
+            remainder: false,
+            chain: None,
+        }
+    }
+
+    fn account_with_outputs(outputs: &[OutputData]) -> AccountDetails {
+        let mut account = AccountDetailsDto::from(&AccountDetails::mock());
+        for output_data in outputs {
+            account
+                .outputs
+                .insert(output_data.output_id, OutputDataDto::from(output_data));
+            account
+                .unspent_outputs
+                .insert(output_data.output_id, OutputDataDto::from(output_data));
+        }
+        AccountDetails::try_from_dto(account).unwrap()
+    }
+
+    fn output_count(connection: &Connection) -> usize {
+        connection
+            .query_row("SELECT COUNT(*) FROM outputs", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    fn total_changes(connection: &Connection) -> usize {
+        connection
+            .query_row("SELECT total_changes()", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn save_get_query_account() {
+        let storage_path = "test-storage/sqlite_save_get_query_account";
+        std::fs::remove_dir_all(storage_path).ok();
+
+        let address = Address::from(rand_ed25519_address());
+        let token_id = TokenId::new([1; TokenId::LENGTH]);
+        let outputs = [
+            output_data(address, 100, Some(token_id)),
+            output_data(address, 200, None),
+            output_data(Address::from(Ed25519Address::new([2; 32])), 300, None),
+        ];
+        let account = account_with_outputs(&outputs);
+
+        let storage = SqliteStorageAdapter::new(storage_path).unwrap();
+        let mut storage_manager = StorageManager::new(storage.clone(), StorageKey::from([1; 32]))
+            .await
+            .unwrap();
+        storage_manager.save_account(&account).await.unwrap();
+        assert_eq!(output_count(&storage.connection.lock().unwrap()), 3);
+
+        let accounts = StorageManager::new(
+            SqliteStorageAdapter::new(storage_path).unwrap(),
+            StorageKey::from([1; 32]),
+        )
+        .await
+        .unwrap()
+        .get_accounts()
+        .await
+        .unwrap();
+        assert_eq!(accounts, vec![account]);
+
+        let by_address = storage_manager
+            .query_outputs(
+                0,
+                &StoredOutputsQuery {
+                    address: Some(address.to_bech32(Hrp::from_str_unchecked("rms"))),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(by_address.len(), 2);
+        let by_token_id = storage_manager
+            .query_outputs(
+                0,
+                &StoredOutputsQuery {
+                    token_id: Some(token_id),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(by_token_id, vec![outputs[0].clone()]);
+        let by_timestamp = storage_manager
+            .query_outputs(
+                0,
+                &StoredOutputsQuery {
+                    lower_bound_booked_timestamp: Some(150),
+                    upper_bound_booked_timestamp: Some(300),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(by_timestamp, vec![outputs[1].clone()]);
+
+        // Only the removed output is deleted
+        let account = account_with_outputs(&outputs[1..]);
+        storage_manager.save_account(&account).await.unwrap();
+        assert_eq!(output_count(&storage.connection.lock().unwrap()), 2);
+
+        // Without changes, only the records of the account indexes and the account details are written
+        let changes = total_changes(&storage.connection.lock().unwrap());
+        storage_manager.save_account(&account).await.unwrap();
+        assert_eq!(total_changes(&storage.connection.lock().unwrap()), changes + 2);
+
+        // The rows are re-encrypted with the storage
+        storage_manager.rotate_encryption_key(None).await.unwrap();
+        let accounts = StorageManager::new(SqliteStorageAdapter::new(storage_path).unwrap(), None)
+            .await
+            .unwrap()
+            .get_accounts()
+            .await
+            .unwrap();
+        assert_eq!(accounts, vec![account]);
+
+        drop(storage_manager);
+        drop(storage);
+        std::fs::remove_dir_all(storage_path).ok();
+    }
+}
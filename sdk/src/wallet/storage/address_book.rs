@@ -0,0 +1,51 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::manager::StorageManager;
+use crate::{
+    client::storage::StorageAdapter,
+    wallet::{core::address_book::AddressBook, storage::constants::ADDRESS_BOOK_KEY},
+};
+
+impl StorageManager {
+    pub(crate) async fn save_address_book(&self, address_book: &AddressBook) -> crate::wallet::Result<()> {
+        log::debug!("save_address_book");
+
+        self.storage.set(ADDRESS_BOOK_KEY, address_book).await
+    }
+
+    pub(crate) async fn get_address_book(&self) -> crate::wallet::Result<AddressBook> {
+        log::debug!("get_address_book");
+
+        Ok(self.storage.get(ADDRESS_BOOK_KEY).await?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        types::block::output::NftId,
+        wallet::{
+            core::address_book::{AddressBookEntry, AddressBookTarget},
+            storage::adapter::memory::Memory,
+        },
+    };
+
+    #[tokio::test]
+    async fn save_get_address_book() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager.get_address_book().await.unwrap().is_empty());
+
+        let mut address_book = AddressBook::default();
+        address_book.insert(
+            AddressBookEntry::new("artwork", AddressBookTarget::Nft(NftId::new([1; NftId::LENGTH]))).add_tag("art"),
+        );
+
+        storage_manager.save_address_book(&address_book).await.unwrap();
+
+        assert_eq!(storage_manager.get_address_book().await.unwrap(), address_book);
+    }
+}
@@ -23,11 +23,21 @@ pub(crate) const ACCOUNTS_INDEXATION_KEY: &str = "iota-wallet-accounts";
 pub(crate) const ACCOUNT_INDEXATION_KEY: &str = "iota-wallet-account-";
 
 pub(crate) const ACCOUNT_SYNC_OPTIONS: &str = "sync-options";
+pub(crate) const ACCOUNT_JOURNAL: &str = "journal";
+pub(crate) const ACCOUNT_PROTOCOL_PARAMETERS: &str = "protocol-parameters";
 
-pub(crate) const DATABASE_SCHEMA_VERSION: u8 = 1;
+pub(crate) const ADDRESS_BOOK_KEY: &str = "iota-wallet-address-book";
+
+pub(crate) const RECORD_KEYS_KEY: &str = "iota-wallet-record-keys";
+
+pub(crate) const DATABASE_SCHEMA_VERSION: u8 = 2;
 pub(crate) const DATABASE_SCHEMA_VERSION_KEY: &str = "database-schema-version";
 
 #[cfg(feature = "participation")]
 pub(crate) const PARTICIPATION_EVENTS: &str = "participation-events";
 #[cfg(feature = "participation")]
 pub(crate) const PARTICIPATION_CACHED_OUTPUTS: &str = "participation-cached-outputs";
+#[cfg(feature = "participation")]
+pub(crate) const PARTICIPATION_MANAGER_OPTIONS: &str = "participation-manager-options";
+#[cfg(feature = "participation")]
+pub(crate) const PARTICIPATION_WARNED_EVENTS: &str = "participation-warned-events";
@@ -0,0 +1,333 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::num::NonZeroU32;
+
+use crypto::ciphers::chacha;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    client::Password,
+    wallet::{storage::constants::DATABASE_SCHEMA_VERSION, Error},
+};
+
+/// Schema version of databases written before the envelope encryption, which stored a plain `u8` as version and
+/// encrypted records directly with the storage key.
+pub(crate) const LEGACY_DATABASE_SCHEMA_VERSION: u8 = 1;
+
+// Version byte prepended to records encrypted with the data key, so that future schemes can be told apart.
+const ENVELOPE_V1: u8 = 1;
+
+const PBKDF2_ITERATIONS: u32 = 100_000;
+const PBKDF2_SALT_LENGTH: usize = 16;
+
+/// The secret the key-encryption key of the storage is derived from. Records aren't encrypted with it directly, but
+/// with a random data key that is stored wrapped by the key-encryption key.
+#[derive(Clone)]
+pub enum StorageKey {
+    /// A 32 byte key used as key-encryption key, e.g. held in a Stronghold snapshot
+    Key(Zeroizing<[u8; 32]>),
+    /// A password the key-encryption key is derived from with PBKDF2
+    Password(Password),
+}
+
+impl core::fmt::Debug for StorageKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Key(_) => f.write_str("Key(<omitted>)"),
+            Self::Password(_) => f.write_str("Password(<omitted>)"),
+        }
+    }
+}
+
+impl From<[u8; 32]> for StorageKey {
+    fn from(key: [u8; 32]) -> Self {
+        Self::Key(Zeroizing::new(key))
+    }
+}
+
+impl From<Password> for StorageKey {
+    fn from(password: Password) -> Self {
+        Self::Password(password)
+    }
+}
+
+#[cfg(feature = "stronghold")]
+impl StorageKey {
+    const STRONGHOLD_KEY: &'static str = "iota-wallet-storage-key";
+
+    /// Returns the storage key held in the Stronghold snapshot, a random one is generated and written to the snapshot
+    /// if there is none yet. The Stronghold password needs to be set.
+    pub async fn from_stronghold(
+        stronghold: &crate::client::stronghold::StrongholdAdapter,
+    ) -> crate::wallet::Result<Self> {
+        use crate::client::storage::StorageAdapter;
+
+        if let Some(key) = stronghold.get_bytes(Self::STRONGHOLD_KEY).await? {
+            let key = Zeroizing::new(key);
+            return Ok(Self::Key(Zeroizing::new(key.as_slice().try_into().map_err(|_| {
+                Error::Storage("invalid storage key in stronghold".to_string())
+            })?)));
+        }
+
+        let mut key = Zeroizing::new([0; 32]);
+        crypto::utils::rand::fill(key.as_mut())?;
+        stronghold.set_bytes(Self::STRONGHOLD_KEY, key.as_ref()).await?;
+        stronghold.write_stronghold_snapshot(None).await?;
+
+        Ok(Self::Key(key))
+    }
+}
+
+/// The metadata stored under the database schema version key, in plain text so that it can be read before the
+/// records can be decrypted.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DatabaseSchema {
+    pub(crate) version: u8,
+    /// How the records are encrypted, `None` if they're stored in plain text
+    pub(crate) encryption: Option<StorageEncryption>,
+    /// Keys of records whose re-encrypted copy still needs to replace the original after a key rotation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) pending_rotation: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) enum EncryptionScheme {
+    /// Records encrypted with XChaCha20-Poly1305 and a random data key, which is wrapped by the key-encryption key
+    #[serde(rename = "xchacha20poly1305-envelope-v1")]
+    XChaCha20Poly1305EnvelopeV1,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub(crate) enum KeyDerivation {
+    /// The storage key is used as key-encryption key
+    None,
+    /// The key-encryption key is derived from a password
+    #[serde(rename_all = "camelCase")]
+    Pbkdf2HmacSha512 {
+        #[serde(with = "crate::utils::serde::prefix_hex_bytes")]
+        salt: Vec<u8>,
+        iterations: u32,
+    },
+}
+
+/// The encryption of the records of a database.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct StorageEncryption {
+    pub(crate) scheme: EncryptionScheme,
+    pub(crate) key_derivation: KeyDerivation,
+    /// The data key, encrypted with the key-encryption key
+    #[serde(with = "crate::utils::serde::prefix_hex_bytes")]
+    pub(crate) wrapped_data_key: Vec<u8>,
+}
+
+impl StorageEncryption {
+    /// Creates the encryption for a new random data key, wrapped by a key-encryption key derived from the storage key.
+    pub(crate) fn new(storage_key: &StorageKey) -> crate::wallet::Result<(Self, RecordCipher)> {
+        let key_derivation = match storage_key {
+            StorageKey::Key(_) => KeyDerivation::None,
+            StorageKey::Password(_) => {
+                let mut salt = vec![0; PBKDF2_SALT_LENGTH];
+                crypto::utils::rand::fill(&mut salt)?;
+                KeyDerivation::Pbkdf2HmacSha512 {
+                    salt,
+                    iterations: PBKDF2_ITERATIONS,
+                }
+            }
+        };
+
+        let mut data_key = Zeroizing::new([0; 32]);
+        crypto::utils::rand::fill(data_key.as_mut())?;
+
+        let key_encryption_key = key_encryption_key(storage_key, &key_derivation)?;
+        let wrapped_data_key = chacha::aead_encrypt(key_encryption_key.as_ref(), data_key.as_ref())?;
+
+        Ok((
+            Self {
+                scheme: EncryptionScheme::XChaCha20Poly1305EnvelopeV1,
+                key_derivation,
+                wrapped_data_key,
+            },
+            RecordCipher::Envelope(data_key),
+        ))
+    }
+
+    /// Unwraps the data key with the key-encryption key derived from the storage key.
+    pub(crate) fn unlock(&self, storage_key: &StorageKey) -> crate::wallet::Result<RecordCipher> {
+        let key_encryption_key = key_encryption_key(storage_key, &self.key_derivation)?;
+        let data_key = Zeroizing::new(
+            chacha::aead_decrypt(key_encryption_key.as_ref(), &self.wrapped_data_key)
+                .map_err(|_| Error::Storage("invalid storage encryption key".to_string()))?,
+        );
+
+        Ok(RecordCipher::Envelope(Zeroizing::new(
+            data_key
+                .as_slice()
+                .try_into()
+                .map_err(|_| Error::Storage("invalid storage data key length".to_string()))?,
+        )))
+    }
+}
+
+fn key_encryption_key(
+    storage_key: &StorageKey,
+    key_derivation: &KeyDerivation,
+) -> crate::wallet::Result<Zeroizing<[u8; 32]>> {
+    match (storage_key, key_derivation) {
+        (StorageKey::Key(key), KeyDerivation::None) => Ok(key.clone()),
+        (StorageKey::Password(password), KeyDerivation::Pbkdf2HmacSha512 { salt, iterations }) => {
+            let iterations = NonZeroU32::new(*iterations)
+                .ok_or_else(|| Error::Storage("invalid key derivation iterations".to_string()))?;
+            let mut derived_key = Zeroizing::new([0; 64]);
+            crypto::keys::pbkdf::PBKDF2_HMAC_SHA512(password.as_bytes(), salt, iterations, derived_key.as_mut());
+
+            let mut key = Zeroizing::new([0; 32]);
+            key.copy_from_slice(&derived_key[..32]);
+            Ok(key)
+        }
+        (StorageKey::Key(_), _) => Err(Error::Storage(
+            "the storage is encrypted with a password, but a key was provided".to_string(),
+        )),
+        (StorageKey::Password(_), _) => Err(Error::Storage(
+            "the storage is encrypted with a key, but a password was provided".to_string(),
+        )),
+    }
+}
+
+/// How records are encrypted before they're written to the storage adapter.
+#[derive(Default)]
+pub(crate) enum RecordCipher {
+    /// Records are stored in plain text
+    #[default]
+    Plain,
+    /// Records are encrypted directly with the storage key, as done by schema version 1
+    Legacy(Zeroizing<[u8; 32]>),
+    /// Records are encrypted with the data key of the envelope encryption
+    Envelope(Zeroizing<[u8; 32]>),
+}
+
+impl core::fmt::Debug for RecordCipher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Plain => f.write_str("Plain"),
+            Self::Legacy(_) => f.write_str("Legacy(<omitted>)"),
+            Self::Envelope(_) => f.write_str("Envelope(<omitted>)"),
+        }
+    }
+}
+
+impl RecordCipher {
+    pub(crate) fn encrypt(&self, record: &[u8]) -> crate::wallet::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Plain => record.to_vec(),
+            Self::Legacy(key) => chacha::aead_encrypt(key.as_ref(), record)?,
+            Self::Envelope(data_key) => {
+                let mut envelope = vec![ENVELOPE_V1];
+                envelope.extend(chacha::aead_encrypt(data_key.as_ref(), record)?);
+                envelope
+            }
+        })
+    }
+
+    pub(crate) fn decrypt(&self, record: Vec<u8>) -> crate::wallet::Result<Vec<u8>> {
+        Ok(match self {
+            Self::Plain => record,
+            Self::Legacy(key) => chacha::aead_decrypt(key.as_ref(), &record)?,
+            Self::Envelope(data_key) => match record.split_first() {
+                Some((&ENVELOPE_V1, ciphertext)) => chacha::aead_decrypt(data_key.as_ref(), ciphertext)?,
+                _ => return Err(Error::Storage("unknown record encryption scheme".to_string())),
+            },
+        })
+    }
+}
+
+/// Reads the schema of a database and returns the cipher to read its records with. `None` is returned as schema for
+/// databases that still have to be upgraded to the current schema version, including new ones.
+pub(crate) fn open_database(
+    schema_record: Option<Vec<u8>>,
+    storage_key: Option<&StorageKey>,
+) -> crate::wallet::Result<(Option<DatabaseSchema>, RecordCipher)> {
+    let Some(schema_record) = schema_record else {
+        return Ok((None, RecordCipher::Plain));
+    };
+
+    if let Ok(schema) = serde_json::from_slice::<DatabaseSchema>(&schema_record) {
+        if schema.version != DATABASE_SCHEMA_VERSION {
+            return Err(Error::Storage(format!(
+                "unsupported database schema version {}",
+                schema.version
+            )));
+        }
+        let cipher = match (&schema.encryption, storage_key) {
+            (Some(encryption), Some(storage_key)) => encryption.unlock(storage_key)?,
+            (Some(_), None) => {
+                return Err(Error::Storage(
+                    "the storage is encrypted, but no encryption key was provided".to_string(),
+                ));
+            }
+            (None, _) => RecordCipher::Plain,
+        };
+        return Ok((Some(schema), cipher));
+    }
+
+    // Schema version 1 stored the version like any other record, so it's encrypted if the records are.
+    let (version, cipher) = match serde_json::from_slice::<u8>(&schema_record) {
+        Ok(version) => (version, RecordCipher::Plain),
+        Err(_) => {
+            let Some(StorageKey::Key(key)) = storage_key else {
+                return Err(Error::Storage(
+                    "the storage is encrypted, but no encryption key was provided".to_string(),
+                ));
+            };
+            let cipher = RecordCipher::Legacy(key.clone());
+            let version = serde_json::from_slice::<u8>(
+                &cipher
+                    .decrypt(schema_record)
+                    .map_err(|_| Error::Storage("invalid storage encryption key".to_string()))?,
+            )?;
+            (version, cipher)
+        }
+    };
+    if version != LEGACY_DATABASE_SCHEMA_VERSION {
+        return Err(Error::Storage(format!("unsupported database schema version {version}")));
+    }
+
+    Ok((None, cipher))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn envelope_roundtrip() {
+        for storage_key in [
+            StorageKey::from([7; 32]),
+            StorageKey::from(Password::from("correct horse battery staple".to_string())),
+        ] {
+            let (encryption, cipher) = StorageEncryption::new(&storage_key).unwrap();
+
+            let record = cipher.encrypt(b"record").unwrap();
+            assert_eq!(record[0], ENVELOPE_V1);
+
+            let cipher = encryption.unlock(&storage_key).unwrap();
+            assert_eq!(cipher.decrypt(record).unwrap(), b"record");
+        }
+    }
+
+    #[test]
+    fn wrong_storage_key() {
+        let (encryption, _) = StorageEncryption::new(&StorageKey::from([1; 32])).unwrap();
+
+        assert!(encryption.unlock(&StorageKey::from([2; 32])).is_err());
+        assert!(encryption
+            .unlock(&StorageKey::from(Password::from("password".to_string())))
+            .is_err());
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use super::manager::StorageManager;
+use crate::{
+    client::{api::PreparedTransactionData, storage::StorageAdapter},
+    types::block::output::OutputId,
+    wallet::{
+        account::types::TransactionDto,
+        storage::constants::{ACCOUNT_INDEXATION_KEY, ACCOUNT_JOURNAL},
+    },
+};
+
+/// The step a transaction is about to take, written to the journal before it's taken.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum JournalStep {
+    /// The inputs got locked for a prepared transaction, nothing got signed yet
+    InputsLocked,
+    /// The transaction got signed and is about to be submitted
+    TransactionBuilt { transaction: TransactionDto },
+    /// The block with the transaction got submitted and the transaction is about to be stored in the account
+    BlockSubmitted { transaction: TransactionDto },
+}
+
+/// An entry of the journal, for a transaction that isn't stored in the account yet.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct JournalEntry {
+    /// The hash of the transaction essence
+    pub(crate) id: String,
+    /// The inputs locked for the transaction
+    pub(crate) inputs: Vec<OutputId>,
+    pub(crate) step: JournalStep,
+}
+
+impl JournalEntry {
+    pub(crate) fn inputs_locked(prepared_transaction_data: &PreparedTransactionData) -> Self {
+        Self {
+            id: prefix_hex::encode(prepared_transaction_data.essence.hash()),
+            inputs: prepared_transaction_data
+                .inputs_data
+                .iter()
+                .map(|input| *input.output_id())
+                .collect(),
+            step: JournalStep::InputsLocked,
+        }
+    }
+}
+
+pub(crate) fn journal_key(account_index: u32) -> String {
+    format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_JOURNAL}")
+}
+
+impl StorageManager {
+    pub(crate) async fn get_journal(&self, account_index: u32) -> crate::wallet::Result<Vec<JournalEntry>> {
+        Ok(self.storage.get(&journal_key(account_index)).await?.unwrap_or_default())
+    }
+
+    /// Writes an entry to the journal, replacing the entry of the same transaction.
+    pub(crate) async fn journal(&self, account_index: u32, entry: JournalEntry) -> crate::wallet::Result<()> {
+        log::debug!("[journal] {} {:?}", entry.id, entry.step);
+        let mut journal = self.get_journal(account_index).await?;
+
+        match journal.iter_mut().find(|journaled| journaled.id == entry.id) {
+            Some(journaled) => *journaled = entry,
+            None => journal.push(entry),
+        }
+
+        self.storage.set(&journal_key(account_index), &journal).await
+    }
+
+    /// Removes the entries of transactions using any of the inputs, after they got stored or rolled back.
+    pub(crate) async fn remove_journal_entries(
+        &self,
+        account_index: u32,
+        inputs: &[OutputId],
+    ) -> crate::wallet::Result<()> {
+        self.retain_journal_entries(account_index, |entry| {
+            !entry.inputs.iter().any(|input| inputs.contains(input))
+        })
+        .await
+    }
+
+    /// Removes the entries with the ids, after they got recovered.
+    pub(crate) async fn remove_journal_entry_ids(
+        &self,
+        account_index: u32,
+        ids: &[String],
+    ) -> crate::wallet::Result<()> {
+        self.retain_journal_entries(account_index, |entry| !ids.contains(&entry.id))
+            .await
+    }
+
+    async fn retain_journal_entries(
+        &self,
+        account_index: u32,
+        f: impl Fn(&JournalEntry) -> bool + Send,
+    ) -> crate::wallet::Result<()> {
+        let mut journal = self.get_journal(account_index).await?;
+        let journal_len = journal.len();
+        journal.retain(f);
+
+        match journal.len() {
+            len if len == journal_len => Ok(()),
+            0 => self.storage.delete(&journal_key(account_index)).await,
+            _ => self.storage.set(&journal_key(account_index), &journal).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{types::block::payload::transaction::TransactionId, wallet::storage::adapter::memory::Memory};
+
+    #[tokio::test]
+    async fn journal() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager.get_journal(0).await.unwrap().is_empty());
+
+        let output_id = |index| OutputId::new(TransactionId::null(), index).unwrap();
+        let inputs = [output_id(0), output_id(1)];
+        let entry = |id: &str, inputs: &[OutputId]| JournalEntry {
+            id: id.to_string(),
+            inputs: inputs.to_vec(),
+            step: JournalStep::InputsLocked,
+        };
+        storage_manager.journal(0, entry("a", &inputs[..1])).await.unwrap();
+        storage_manager.journal(0, entry("b", &inputs[1..])).await.unwrap();
+        storage_manager.journal(0, entry("b", &inputs[1..])).await.unwrap();
+        assert_eq!(storage_manager.get_journal(0).await.unwrap().len(), 2);
+        assert!(storage_manager.get_journal(1).await.unwrap().is_empty());
+
+        storage_manager
+            .remove_journal_entries(0, &[inputs[0], output_id(2)])
+            .await
+            .unwrap();
+        assert_eq!(
+            storage_manager.get_journal(0).await.unwrap(),
+            vec![entry("b", &inputs[1..])]
+        );
+
+        storage_manager.journal(0, entry("c", &inputs[..1])).await.unwrap();
+        storage_manager
+            .remove_journal_entry_ids(0, &["c".to_string(), "d".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(
+            storage_manager.get_journal(0).await.unwrap(),
+            vec![entry("b", &inputs[1..])]
+        );
+
+        storage_manager.remove_journal_entries(0, &inputs).await.unwrap();
+        assert!(storage_manager.get_journal(0).await.unwrap().is_empty());
+    }
+}
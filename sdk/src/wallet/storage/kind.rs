@@ -10,6 +10,10 @@ pub enum StorageKind {
     /// RocksDB storage.
     #[cfg(feature = "rocksdb")]
     Rocksdb,
+    /// SQLite storage, with outputs, transactions and addresses in their own tables. Their indexed columns aren't
+    /// encrypted, see [`SqliteStorageAdapter`](crate::wallet::storage::adapter::sqlite::SqliteStorageAdapter).
+    #[cfg(feature = "sqlite")]
+    Sqlite,
     /// Storage backed by a Map in memory.
     Memory,
     /// Wasm storage.
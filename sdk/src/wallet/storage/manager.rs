@@ -2,15 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use futures::{StreamExt, TryStreamExt};
-use zeroize::Zeroizing;
 
+#[cfg(feature = "sqlite")]
+use crate::wallet::{
+    account::types::{OutputData, Transaction},
+    storage::{adapter::sqlite::SqliteStorageAdapter, StoredOutputsQuery, StoredTransactionsQuery},
+};
 use crate::{
     client::storage::StorageAdapter,
-    types::TryFromDto,
+    types::{block::protocol::ProtocolParameters, TryFromDto},
     wallet::{
         account::{AccountDetails, AccountDetailsDto, SyncOptions},
-        migration::migrate,
-        storage::{constants::*, DynStorageAdapter, Storage},
+        migration::{chrysalis::CHRYSALIS_STORAGE_KEY, migrate, MIGRATION_VERSION_KEY},
+        storage::{
+            constants::*,
+            encryption::{open_database, DatabaseSchema, RecordCipher, StorageEncryption},
+            DynStorageAdapter, Storage, StorageKey,
+        },
     },
 };
 
@@ -25,35 +33,180 @@ pub(crate) struct StorageManager {
 impl StorageManager {
     pub(crate) async fn new(
         storage: impl DynStorageAdapter + 'static,
-        encryption_key: impl Into<Option<Zeroizing<[u8; 32]>>> + Send,
+        encryption_key: impl Into<Option<StorageKey>> + Send,
     ) -> crate::wallet::Result<Self> {
-        let storage = Storage {
-            inner: Box::new(storage) as _,
-            encryption_key: encryption_key.into(),
+        let encryption_key = encryption_key.into();
+        let inner = Box::new(storage) as Box<dyn DynStorageAdapter>;
+
+        let schema_record = inner.as_ref().get_bytes(DATABASE_SCHEMA_VERSION_KEY).await?;
+        let (schema, cipher) = open_database(schema_record, encryption_key.as_ref())?;
+
+        let mut storage_manager = Self {
+            storage: Storage {
+                inner,
+                cipher,
+                record_keys: Default::default(),
+            },
+            account_indexes: Vec::new(),
         };
-        migrate(&storage).await?;
-
-        // Get the db version or set it
-        if let Some(db_schema_version) = storage.get::<u8>(DATABASE_SCHEMA_VERSION_KEY).await? {
-            if db_schema_version != DATABASE_SCHEMA_VERSION {
-                return Err(crate::wallet::Error::Storage(format!(
-                    "unsupported database schema version {db_schema_version}"
-                )));
+
+        // Finish a key rotation that was interrupted, before any record is read
+        if let Some(schema) = &schema {
+            if !schema.pending_rotation.is_empty() {
+                storage_manager.finish_rotation(schema.clone()).await?;
             }
-        } else {
-            storage
-                .set(DATABASE_SCHEMA_VERSION_KEY, &DATABASE_SCHEMA_VERSION)
-                .await?;
+        }
+
+        // Databases written before the keys of the records were indexed get the index from the known keys
+        if !storage_manager.storage.load_record_keys().await? {
+            storage_manager.index_legacy_record_keys().await?;
+        }
+
+        migrate(&storage_manager.storage).await?;
+
+        storage_manager.account_indexes = storage_manager
+            .storage
+            .get(ACCOUNTS_INDEXATION_KEY)
+            .await?
+            .unwrap_or_default();
+
+        match schema {
+            // New databases and databases of schema version 1 get their records written with the envelope encryption
+            None => storage_manager.rotate_encryption_key(encryption_key).await?,
+            // Encrypt a database that was created without encryption
+            Some(DatabaseSchema { encryption: None, .. }) if encryption_key.is_some() => {
+                storage_manager.rotate_encryption_key(encryption_key).await?
+            }
+            Some(_) => {}
+        }
+
+        Ok(storage_manager)
+    }
+
+    /// Re-encrypts all records with a new data key, wrapped by a key-encryption key derived from the given storage key.
+    /// Without a storage key the records are decrypted. The re-encrypted records are first written next to the
+    /// original ones and only replace them after the new encryption has been recorded, so that an interrupted rotation
+    /// is finished the next time the storage is opened.
+    pub(crate) async fn rotate_encryption_key(&mut self, storage_key: Option<StorageKey>) -> crate::wallet::Result<()> {
+        let (encryption, cipher) = match storage_key {
+            Some(storage_key) => {
+                let (encryption, cipher) = StorageEncryption::new(&storage_key)?;
+                (Some(encryption), cipher)
+            }
+            None => (None, RecordCipher::Plain),
         };
 
-        let account_indexes = storage.get(ACCOUNTS_INDEXATION_KEY).await?.unwrap_or_default();
+        let mut pending_rotation = Vec::new();
+        let mut record_keys = self.storage.record_keys().await;
+        record_keys.push(RECORD_KEYS_KEY.to_owned());
+        for key in record_keys {
+            if let Some(record) = self.storage.get_bytes(&key).await? {
+                self.storage
+                    .inner
+                    .as_ref()
+                    .set_bytes(&rotation_key(&key), &cipher.encrypt(&record)?)
+                    .await?;
+                pending_rotation.push(key);
+            }
+        }
+
+        #[cfg(feature = "sqlite")]
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.prepare_rotation(&self.storage.cipher, &cipher).await?;
+        }
 
-        let storage_manager = Self {
-            storage,
-            account_indexes,
+        let schema = DatabaseSchema {
+            version: DATABASE_SCHEMA_VERSION,
+            encryption,
+            pending_rotation,
         };
+        self.write_schema(&schema).await?;
+        self.storage.cipher = cipher;
 
-        Ok(storage_manager)
+        self.finish_rotation(schema).await
+    }
+
+    async fn finish_rotation(&self, mut schema: DatabaseSchema) -> crate::wallet::Result<()> {
+        let inner = self.storage.inner.as_ref();
+        for key in &schema.pending_rotation {
+            let rotation_key = rotation_key(key);
+            // Already moved if the rotation got interrupted after this record
+            if let Some(record) = inner.get_bytes(&rotation_key).await? {
+                inner.set_bytes(key, &record).await?;
+                inner.delete(&rotation_key).await?;
+            }
+        }
+        #[cfg(feature = "sqlite")]
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.finish_rotation().await?;
+        }
+
+        schema.pending_rotation.clear();
+        self.write_schema(&schema).await
+    }
+
+    /// Returns the outputs of an account matching the query, looked up in the tables of the SQLite storage.
+    #[cfg(feature = "sqlite")]
+    pub(crate) async fn query_outputs(
+        &self,
+        account_index: u32,
+        query: &StoredOutputsQuery,
+    ) -> crate::wallet::Result<Vec<OutputData>> {
+        self.sqlite()
+            .ok_or_else(not_queryable)?
+            .query_outputs(account_index, query, &self.storage.cipher)
+            .await?
+            .into_iter()
+            .map(|dto| Ok(OutputData::try_from_dto(dto)?))
+            .collect()
+    }
+
+    /// Returns the transactions of an account matching the query, looked up in the tables of the SQLite storage.
+    #[cfg(feature = "sqlite")]
+    pub(crate) async fn query_transactions(
+        &self,
+        account_index: u32,
+        query: &StoredTransactionsQuery,
+    ) -> crate::wallet::Result<Vec<Transaction>> {
+        self.sqlite()
+            .ok_or_else(not_queryable)?
+            .query_transactions(account_index, query, &self.storage.cipher)
+            .await?
+            .into_iter()
+            .map(|dto| Ok(Transaction::try_from_dto(dto)?))
+            .collect()
+    }
+
+    /// Returns the SQLite adapter, if the accounts are stored in it.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn sqlite(&self) -> Option<&SqliteStorageAdapter> {
+        self.storage.inner.as_any().downcast_ref()
+    }
+
+    // The schema is stored in plain text, as it's needed to decrypt the other records.
+    async fn write_schema(&self, schema: &DatabaseSchema) -> crate::wallet::Result<()> {
+        self.storage
+            .inner
+            .as_ref()
+            .set_bytes(DATABASE_SCHEMA_VERSION_KEY, &serde_json::to_vec(schema)?)
+            .await
+    }
+
+    async fn index_legacy_record_keys(&self) -> crate::wallet::Result<()> {
+        let account_indexes = self
+            .storage
+            .get::<Vec<u32>>(ACCOUNTS_INDEXATION_KEY)
+            .await?
+            .unwrap_or_default();
+
+        let mut record_keys = Vec::new();
+        for key in legacy_record_keys(&account_indexes) {
+            if self.storage.inner.as_ref().get_bytes(&key).await?.is_some() {
+                record_keys.push(key);
+            }
+        }
+
+        self.storage.register_record_keys(record_keys).await
     }
 
     pub(crate) async fn get_accounts(&mut self) -> crate::wallet::Result<Vec<AccountDetails>> {
@@ -69,6 +222,10 @@ impl StorageManager {
             .filter_map(|account_index| async {
                 let account_index = *account_index;
                 let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}");
+                #[cfg(feature = "sqlite")]
+                if let Some(sqlite) = self.sqlite() {
+                    return sqlite.get_account(&key, &self.storage.cipher).await.transpose();
+                }
                 self.get::<AccountDetailsDto>(&key).await.transpose()
             })
             .map(|res| AccountDetails::try_from_dto(res?))
@@ -83,15 +240,22 @@ impl StorageManager {
         }
 
         self.set(ACCOUNTS_INDEXATION_KEY, &self.account_indexes).await?;
-        self.set(
-            &format!("{ACCOUNT_INDEXATION_KEY}{}", account.index()),
-            &AccountDetailsDto::from(account),
-        )
-        .await
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{}", account.index());
+        #[cfg(feature = "sqlite")]
+        if let Some(sqlite) = self.sqlite() {
+            // The account record is written by the adapter, next to the rows of the account
+            self.storage.register_record_keys([key.clone()]).await?;
+            return sqlite.save_account(&key, account, &self.storage.cipher).await;
+        }
+        self.set(&key, &AccountDetailsDto::from(account)).await
     }
 
     pub(crate) async fn remove_account(&mut self, account_index: u32) -> crate::wallet::Result<()> {
         self.delete(&format!("{ACCOUNT_INDEXATION_KEY}{account_index}")).await?;
+        #[cfg(feature = "sqlite")]
+        if let Some(sqlite) = self.sqlite() {
+            sqlite.remove_account(account_index).await?;
+        }
         self.account_indexes.retain(|a| a != &account_index);
         self.set(ACCOUNTS_INDEXATION_KEY, &self.account_indexes).await
     }
@@ -112,6 +276,66 @@ impl StorageManager {
         let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SYNC_OPTIONS}");
         self.get(&key).await
     }
+
+    pub(crate) async fn set_protocol_parameters(
+        &self,
+        account_index: u32,
+        protocol_parameters: &ProtocolParameters,
+    ) -> crate::wallet::Result<()> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_PROTOCOL_PARAMETERS}");
+        self.set(&key, protocol_parameters).await
+    }
+
+    pub(crate) async fn get_protocol_parameters(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<Option<ProtocolParameters>> {
+        let key = format!("{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_PROTOCOL_PARAMETERS}");
+        self.get(&key).await
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn not_queryable() -> crate::wallet::Error {
+    crate::wallet::Error::Storage("queries are only supported by the SQLite storage".to_string())
+}
+
+/// Returns the keys of the records the wallet wrote before the storage indexed the keys of its records.
+fn legacy_record_keys(account_indexes: &[u32]) -> Vec<String> {
+    let mut keys = [
+        WALLET_INDEXATION_KEY,
+        SECRET_MANAGER_KEY,
+        ACCOUNTS_INDEXATION_KEY,
+        ADDRESS_BOOK_KEY,
+        MIGRATION_VERSION_KEY,
+        CHRYSALIS_STORAGE_KEY,
+    ]
+    .map(String::from)
+    .to_vec();
+
+    for account_index in account_indexes {
+        keys.push(format!("{ACCOUNT_INDEXATION_KEY}{account_index}"));
+        keys.push(format!(
+            "{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_SYNC_OPTIONS}"
+        ));
+        keys.push(format!(
+            "{ACCOUNT_INDEXATION_KEY}{account_index}-{ACCOUNT_PROTOCOL_PARAMETERS}"
+        ));
+        keys.push(crate::wallet::storage::journal::journal_key(*account_index));
+        #[cfg(feature = "participation")]
+        {
+            keys.push(format!("{PARTICIPATION_EVENTS}{account_index}"));
+            keys.push(format!("{PARTICIPATION_CACHED_OUTPUTS}{account_index}"));
+            keys.push(format!("{PARTICIPATION_MANAGER_OPTIONS}{account_index}"));
+            keys.push(format!("{PARTICIPATION_WARNED_EVENTS}{account_index}"));
+        }
+    }
+
+    keys
+}
+
+fn rotation_key(key: &str) -> String {
+    format!("{key}-rotating")
 }
 
 #[async_trait::async_trait]
@@ -179,6 +403,23 @@ mod tests {
         assert!(storage_manager.get_accounts().await.unwrap().is_empty());
     }
 
+    #[tokio::test]
+    async fn save_get_protocol_parameters() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager.get_protocol_parameters(0).await.unwrap().is_none());
+
+        let protocol_parameters = ProtocolParameters::default();
+        storage_manager
+            .set_protocol_parameters(0, &protocol_parameters)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage_manager.get_protocol_parameters(0).await.unwrap(),
+            Some(protocol_parameters)
+        );
+        assert!(storage_manager.get_protocol_parameters(1).await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn save_get_wallet_data() {
         let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
@@ -189,8 +430,17 @@ mod tests {
                 .is_none()
         );
 
-        let wallet_builder = WalletBuilder::<SecretManager>::new();
+        let wallet_builder = WalletBuilder::<SecretManager>::new()
+            .with_storage_options(crate::wallet::storage::StorageOptions::default().with_encryption_key([1; 32]));
         wallet_builder.save(&storage_manager).await.unwrap();
+        // The encryption key isn't written to the storage it encrypts
+        let wallet_data = storage_manager
+            .get::<serde_json::Value>(WALLET_INDEXATION_KEY)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(wallet_data["storageOptions"].get("path").is_some());
+        assert!(wallet_data["storageOptions"].get("encryptionKey").is_none());
 
         assert!(
             WalletBuilder::<SecretManager>::load(&storage_manager)
@@ -199,4 +449,192 @@ mod tests {
                 .is_some()
         );
     }
+
+    #[tokio::test]
+    async fn rotate_encryption_key() {
+        let memory = Memory::default();
+        let key = StorageKey::from([1; 32]);
+        let password = StorageKey::from(crate::client::Password::from("password".to_string()));
+
+        let mut storage_manager = StorageManager::new(memory.clone(), key.clone()).await.unwrap();
+        storage_manager.save_account(&AccountDetails::mock()).await.unwrap();
+        let account_key = format!("{ACCOUNT_INDEXATION_KEY}0");
+        assert!(memory.get::<AccountDetailsDto>(&account_key).await.is_err());
+        let journal_entry = crate::wallet::storage::journal::JournalEntry {
+            id: "0x00".to_string(),
+            inputs: Vec::new(),
+            step: crate::wallet::storage::journal::JournalStep::InputsLocked,
+        };
+        storage_manager.journal(0, journal_entry.clone()).await.unwrap();
+        #[cfg(feature = "participation")]
+        let (participation_manager_options, warned_events) = {
+            let options = crate::wallet::account::ParticipationManagerOptions::default();
+            let warned_events = std::collections::HashSet::from([
+                crate::types::api::plugins::participation::types::ParticipationEventId::new([42; 32]),
+            ]);
+            storage_manager
+                .set_participation_manager_options(0, Some(&options))
+                .await
+                .unwrap();
+            storage_manager
+                .set_participation_warned_events(0, &warned_events)
+                .await
+                .unwrap();
+            (options, warned_events)
+        };
+
+        // Wrong or missing keys can't open the storage
+        assert!(StorageManager::new(memory.clone(), StorageKey::from([2; 32]))
+            .await
+            .is_err());
+        assert!(StorageManager::new(memory.clone(), password.clone()).await.is_err());
+        assert!(StorageManager::new(memory.clone(), None).await.is_err());
+
+        storage_manager
+            .rotate_encryption_key(Some(password.clone()))
+            .await
+            .unwrap();
+        assert!(StorageManager::new(memory.clone(), key).await.is_err());
+        let mut storage_manager = StorageManager::new(memory.clone(), password).await.unwrap();
+        assert_eq!(storage_manager.get_accounts().await.unwrap().len(), 1);
+        assert_eq!(storage_manager.get_journal(0).await.unwrap(), vec![journal_entry]);
+        #[cfg(feature = "participation")]
+        {
+            assert_eq!(
+                storage_manager.get_participation_manager_options(0).await.unwrap(),
+                Some(participation_manager_options)
+            );
+            assert_eq!(
+                storage_manager.get_participation_warned_events(0).await.unwrap(),
+                warned_events
+            );
+        }
+
+        storage_manager.rotate_encryption_key(None).await.unwrap();
+        assert!(memory.get::<AccountDetailsDto>(&account_key).await.unwrap().is_some());
+        let schema = memory
+            .get::<DatabaseSchema>(DATABASE_SCHEMA_VERSION_KEY)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(schema.version, DATABASE_SCHEMA_VERSION);
+        assert!(schema.encryption.is_none());
+        assert!(schema.pending_rotation.is_empty());
+    }
+
+    #[tokio::test]
+    async fn rotate_encryption_key_of_any_record() {
+        let memory = Memory::default();
+        let key = StorageKey::from([1; 32]);
+        let new_key = StorageKey::from([2; 32]);
+
+        let mut storage_manager = StorageManager::new(memory.clone(), key).await.unwrap();
+        storage_manager.set("custom-record", &42u32).await.unwrap();
+
+        storage_manager
+            .rotate_encryption_key(Some(new_key.clone()))
+            .await
+            .unwrap();
+        let storage_manager = StorageManager::new(memory.clone(), new_key).await.unwrap();
+        assert_eq!(storage_manager.get::<u32>("custom-record").await.unwrap(), Some(42));
+
+        storage_manager.delete("custom-record").await.unwrap();
+        assert!(!storage_manager
+            .storage
+            .record_keys()
+            .await
+            .contains(&"custom-record".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn finish_interrupted_rotation() {
+        let memory = Memory::default();
+        let key = StorageKey::from([1; 32]);
+
+        let mut storage_manager = StorageManager::new(memory.clone(), None).await.unwrap();
+        storage_manager.save_account(&AccountDetails::mock()).await.unwrap();
+
+        // Simulate a rotation that got interrupted after the new encryption was recorded and one record was moved
+        let (encryption, cipher) = StorageEncryption::new(&key).unwrap();
+        let mut pending_rotation = Vec::new();
+        let mut record_keys = storage_manager.storage.record_keys().await;
+        record_keys.push(RECORD_KEYS_KEY.to_owned());
+        for record_key in record_keys {
+            if let Some(record) = memory.get_bytes(&record_key).await.unwrap() {
+                memory
+                    .set_bytes(&rotation_key(&record_key), &cipher.encrypt(&record).unwrap())
+                    .await
+                    .unwrap();
+                pending_rotation.push(record_key);
+            }
+        }
+        let account_key = format!("{ACCOUNT_INDEXATION_KEY}0");
+        let rotated_record = memory.get_bytes(&rotation_key(&account_key)).await.unwrap().unwrap();
+        memory.set_bytes(&account_key, &rotated_record).await.unwrap();
+        memory.delete(&rotation_key(&account_key)).await.unwrap();
+        memory
+            .set(
+                DATABASE_SCHEMA_VERSION_KEY,
+                &DatabaseSchema {
+                    version: DATABASE_SCHEMA_VERSION,
+                    encryption: Some(encryption),
+                    pending_rotation,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut storage_manager = StorageManager::new(memory.clone(), key).await.unwrap();
+        assert_eq!(storage_manager.get_accounts().await.unwrap().len(), 1);
+        assert!(memory.get_bytes(&rotation_key(&account_key)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn upgrade_legacy_encrypted_database() {
+        let memory = Memory::default();
+        let key = [1; 32];
+
+        let legacy_storage = Storage {
+            inner: Box::new(memory.clone()),
+            cipher: RecordCipher::Legacy(zeroize::Zeroizing::new(key)),
+            record_keys: Default::default(),
+        };
+        migrate(&legacy_storage).await.unwrap();
+        legacy_storage
+            .set(
+                DATABASE_SCHEMA_VERSION_KEY,
+                &crate::wallet::storage::encryption::LEGACY_DATABASE_SCHEMA_VERSION,
+            )
+            .await
+            .unwrap();
+        let account_details = AccountDetails::mock();
+        legacy_storage.set(ACCOUNTS_INDEXATION_KEY, &[0]).await.unwrap();
+        legacy_storage
+            .set(
+                &format!("{ACCOUNT_INDEXATION_KEY}0"),
+                &AccountDetailsDto::from(&account_details),
+            )
+            .await
+            .unwrap();
+        // Legacy databases have no index of the record keys
+        memory.delete(RECORD_KEYS_KEY).await.unwrap();
+
+        assert!(StorageManager::new(memory.clone(), StorageKey::from([2; 32]))
+            .await
+            .is_err());
+
+        let mut storage_manager = StorageManager::new(memory.clone(), StorageKey::from(key))
+            .await
+            .unwrap();
+        assert_eq!(storage_manager.get_accounts().await.unwrap().len(), 1);
+        let schema = memory
+            .get::<DatabaseSchema>(DATABASE_SCHEMA_VERSION_KEY)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(schema.encryption.is_some());
+
+        let mut storage_manager = StorageManager::new(memory, StorageKey::from(key)).await.unwrap();
+        assert_eq!(storage_manager.get_accounts().await.unwrap()[0].alias(), "Alice");
+    }
 }
@@ -3,8 +3,14 @@
 
 /// Storage adapter.
 pub mod adapter;
+/// Storage functions related to the address book.
+mod address_book;
 /// Storage constants.
 pub mod constants;
+/// Storage encryption.
+mod encryption;
+/// Storage journal of transactions in progress.
+pub(crate) mod journal;
 /// Storage kind.
 mod kind;
 /// Storage manager.
@@ -16,19 +22,67 @@ mod options;
 #[cfg_attr(docsrs, doc(cfg(feature = "participation")))]
 mod participation;
 
+use std::collections::BTreeSet;
+
 use async_trait::async_trait;
-use crypto::ciphers::chacha;
-use zeroize::Zeroizing;
+use tokio::sync::Mutex;
 
-use self::adapter::DynStorageAdapter;
+#[cfg(feature = "sqlite")]
+pub use self::adapter::sqlite::{StoredOutputsQuery, StoredTransactionsQuery};
 pub(crate) use self::manager::StorageManager;
-pub use self::{kind::StorageKind, options::StorageOptions};
+use self::{adapter::DynStorageAdapter, constants::RECORD_KEYS_KEY, encryption::RecordCipher};
+pub use self::{encryption::StorageKey, kind::StorageKind, options::StorageOptions};
 use crate::client::storage::StorageAdapter;
 
 #[derive(Debug)]
 pub struct Storage {
     pub(crate) inner: Box<dyn DynStorageAdapter>,
-    encryption_key: Option<Zeroizing<[u8; 32]>>,
+    cipher: RecordCipher,
+    // Keys of the records written through the storage, so that they can all be re-encrypted
+    record_keys: Mutex<BTreeSet<String>>,
+}
+
+impl Storage {
+    /// Loads the index of the record keys, returns whether the storage has one.
+    pub(crate) async fn load_record_keys(&self) -> crate::wallet::Result<bool> {
+        match self.get::<BTreeSet<String>>(RECORD_KEYS_KEY).await? {
+            Some(record_keys) => {
+                *self.record_keys.lock().await = record_keys;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns the keys of all records written through the storage.
+    pub(crate) async fn record_keys(&self) -> Vec<String> {
+        self.record_keys.lock().await.iter().cloned().collect()
+    }
+
+    /// Adds keys to the index of the record keys and persists it if any of them is new.
+    pub(crate) async fn register_record_keys(
+        &self,
+        keys: impl IntoIterator<Item = String> + Send,
+    ) -> crate::wallet::Result<()> {
+        let mut record_keys = self.record_keys.lock().await;
+        let len = record_keys.len();
+        record_keys.extend(keys);
+        if record_keys.len() != len {
+            self.write_record_keys(&record_keys).await?;
+        }
+        Ok(())
+    }
+
+    // The index is written directly to the adapter, as it doesn't list itself.
+    async fn write_record_keys(&self, record_keys: &BTreeSet<String>) -> crate::wallet::Result<()> {
+        self.inner
+            .as_ref()
+            .set_bytes(
+                RECORD_KEYS_KEY,
+                &self.cipher.encrypt(&serde_json::to_vec(record_keys)?)?,
+            )
+            .await
+    }
 }
 
 #[async_trait]
@@ -37,29 +91,25 @@ impl StorageAdapter for Storage {
 
     async fn get_bytes(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
         match self.inner.as_ref().get_bytes(key).await? {
-            Some(record) => {
-                if let Some(encryption_key) = &self.encryption_key {
-                    return Ok(Some(chacha::aead_decrypt(encryption_key.as_ref(), &record)?));
-                }
-
-                Ok(Some(record))
-            }
+            Some(record) => Ok(Some(self.cipher.decrypt(record)?)),
             None => Ok(None),
         }
     }
 
     async fn set_bytes(&self, key: &str, record: &[u8]) -> Result<(), Self::Error> {
-        if let Some(encryption_key) = &self.encryption_key {
-            let encrypted_bytes = chacha::aead_encrypt(encryption_key.as_ref(), record)?;
-            self.inner.as_ref().set_bytes(key, &encrypted_bytes).await?
-        } else {
-            self.inner.as_ref().set_bytes(key, record).await?
+        if !self.record_keys.lock().await.contains(key) {
+            self.register_record_keys([key.to_owned()]).await?;
         }
-        Ok(())
+        self.inner.as_ref().set_bytes(key, &self.cipher.encrypt(record)?).await
     }
 
     async fn delete(&self, key: &str) -> Result<(), Self::Error> {
-        self.inner.as_ref().delete(key).await
+        self.inner.as_ref().delete(key).await?;
+        let mut record_keys = self.record_keys.lock().await;
+        if record_keys.remove(key) {
+            self.write_record_keys(&record_keys).await?;
+        }
+        Ok(())
     }
 }
 
@@ -82,7 +132,8 @@ mod tests {
 
         let storage = Storage {
             inner: Box::<Memory>::default(),
-            encryption_key: None,
+            cipher: RecordCipher::Plain,
+            record_keys: Default::default(),
         };
 
         let rec = Record {
@@ -93,8 +144,11 @@ mod tests {
         storage.set("key", &rec).await.unwrap();
         assert_eq!(Some(rec), storage.get::<Record>("key").await.unwrap());
 
+        assert_eq!(storage.record_keys().await, ["key"]);
+
         storage.delete("key").await.unwrap();
         assert_eq!(None, storage.get::<Record>("key").await.unwrap());
+        assert!(storage.record_keys().await.is_empty());
     }
 
     #[cfg(feature = "rand")]
@@ -110,7 +164,8 @@ mod tests {
         let encryption_key = crate::types::block::rand::bytes::rand_bytes_array::<32>();
         let storage = Storage {
             inner: Box::<Memory>::default(),
-            encryption_key: Some(Zeroizing::new(encryption_key)),
+            cipher: RecordCipher::Envelope(zeroize::Zeroizing::new(encryption_key)),
+            record_keys: Default::default(),
         };
 
         let rec = Record {
@@ -6,7 +6,10 @@ use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
-use crate::wallet::storage::{constants::default_storage_path, StorageKind};
+use crate::{
+    client::Password,
+    wallet::storage::{constants::default_storage_path, StorageKey, StorageKind},
+};
 
 #[cfg(feature = "storage")]
 #[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
@@ -14,8 +17,12 @@ use crate::wallet::storage::{constants::default_storage_path, StorageKind};
 #[serde(rename_all = "camelCase")]
 pub struct StorageOptions {
     pub(crate) path: PathBuf,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    // Never written to the storage, as it's the key the storage is encrypted with
+    #[serde(default, skip_serializing)]
     pub(crate) encryption_key: Option<Zeroizing<[u8; 32]>>,
+    // Never written to the storage, the password needs to be provided every time the wallet is built
+    #[serde(skip)]
+    pub(crate) encryption_password: Option<Password>,
     pub(crate) kind: StorageKind,
 }
 
@@ -25,6 +32,7 @@ impl Default for StorageOptions {
         Self {
             path: default_storage_path().into(),
             encryption_key: None,
+            encryption_password: None,
             kind: StorageKind::default(),
         }
     }
@@ -36,6 +44,7 @@ impl StorageOptions {
         Self {
             path,
             encryption_key: None,
+            encryption_password: None,
             kind,
         }
     }
@@ -46,6 +55,13 @@ impl StorageOptions {
         self
     }
 
+    /// Adds a password to the [`StorageOptions`], the key encrypting the storage is derived from it. An encryption key
+    /// takes precedence over the password.
+    pub fn with_encryption_password(mut self, password: impl Into<Password>) -> Self {
+        self.encryption_password = Some(password.into());
+        self
+    }
+
     /// Returns the path of the [`StorageOptions`];
     pub fn path(&self) -> &Path {
         &self.path
@@ -56,6 +72,23 @@ impl StorageOptions {
         self.encryption_key.as_deref()
     }
 
+    /// Returns the key the storage is encrypted with, if any.
+    pub(crate) fn storage_key(&self) -> Option<StorageKey> {
+        self.encryption_key
+            .clone()
+            .map(StorageKey::Key)
+            .or_else(|| self.encryption_password.clone().map(StorageKey::Password))
+    }
+
+    /// Replaces the key the storage is encrypted with.
+    pub(crate) fn set_storage_key(&mut self, storage_key: Option<StorageKey>) {
+        (self.encryption_key, self.encryption_password) = match storage_key {
+            Some(StorageKey::Key(key)) => (Some(key), None),
+            Some(StorageKey::Password(password)) => (None, Some(password)),
+            None => (None, None),
+        };
+    }
+
     /// Returns the kind of the [`StorageOptions`];
     pub fn kind(&self) -> StorageKind {
         self.kind
@@ -1,7 +1,7 @@
 // Copyright 2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use super::manager::StorageManager;
 use crate::{
@@ -11,8 +11,11 @@ use crate::{
         block::output::OutputId,
     },
     wallet::{
-        account::operations::participation::ParticipationEventWithNodes,
-        storage::constants::{PARTICIPATION_CACHED_OUTPUTS, PARTICIPATION_EVENTS},
+        account::operations::participation::{manager::ParticipationManagerOptions, ParticipationEventWithNodes},
+        storage::constants::{
+            PARTICIPATION_CACHED_OUTPUTS, PARTICIPATION_EVENTS, PARTICIPATION_MANAGER_OPTIONS,
+            PARTICIPATION_WARNED_EVENTS,
+        },
     },
 };
 
@@ -110,6 +113,58 @@ impl StorageManager {
             .await?
             .unwrap_or_default())
     }
+
+    pub(crate) async fn set_participation_manager_options(
+        &self,
+        account_index: u32,
+        options: Option<&ParticipationManagerOptions>,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("set_participation_manager_options");
+
+        let key = format!("{PARTICIPATION_MANAGER_OPTIONS}{account_index}");
+        match options {
+            Some(options) => self.storage.set(&key, options).await?,
+            None => self.storage.delete(&key).await?,
+        }
+
+        Ok(())
+    }
+
+    pub(crate) async fn get_participation_manager_options(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<Option<ParticipationManagerOptions>> {
+        log::debug!("get_participation_manager_options");
+
+        self.storage
+            .get(&format!("{PARTICIPATION_MANAGER_OPTIONS}{account_index}"))
+            .await
+    }
+
+    pub(crate) async fn set_participation_warned_events(
+        &self,
+        account_index: u32,
+        event_ids: &HashSet<ParticipationEventId>,
+    ) -> crate::wallet::Result<()> {
+        log::debug!("set_participation_warned_events");
+
+        self.storage
+            .set(&format!("{PARTICIPATION_WARNED_EVENTS}{account_index}"), event_ids)
+            .await
+    }
+
+    pub(crate) async fn get_participation_warned_events(
+        &self,
+        account_index: u32,
+    ) -> crate::wallet::Result<HashSet<ParticipationEventId>> {
+        log::debug!("get_participation_warned_events");
+
+        Ok(self
+            .storage
+            .get(&format!("{PARTICIPATION_WARNED_EVENTS}{account_index}"))
+            .await?
+            .unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
@@ -172,4 +227,34 @@ mod tests {
             outputs_participation
         );
     }
+
+    #[tokio::test]
+    async fn set_get_participation_manager_options() {
+        let storage_manager = StorageManager::new(Memory::default(), None).await.unwrap();
+        assert!(storage_manager
+            .get_participation_manager_options(0)
+            .await
+            .unwrap()
+            .is_none());
+
+        let options = ParticipationManagerOptions::default();
+        storage_manager
+            .set_participation_manager_options(0, Some(&options))
+            .await
+            .unwrap();
+        assert_eq!(
+            storage_manager.get_participation_manager_options(0).await.unwrap(),
+            Some(options)
+        );
+
+        storage_manager
+            .set_participation_manager_options(0, None)
+            .await
+            .unwrap();
+        assert!(storage_manager
+            .get_participation_manager_options(0)
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
@@ -0,0 +1,648 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Property-based harness checking the ledger invariants of the transactions created by input selection.
+//!
+//! Random valid UTXO sets, targets, burns and remainder addresses are generated with the `rand` module. Input
+//! selection is run on them and the selected transaction is signed and semantically validated: amounts must be
+//! balanced, native tokens conserved, chain transitions valid and storage deposits met. A scenario violating an
+//! invariant is shrunk to a minimal one before it's reported.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crypto::keys::bip44::Bip44;
+use iota_sdk::{
+    client::{
+        api::{
+            input_selection::{Burn, InputSelection},
+            verify_semantic, GetAddressesOptions, PreparedTransactionData,
+        },
+        constants::SHIMMER_COIN_TYPE,
+        secret::{types::InputSigningData, SecretManage, SecretManager},
+        Client,
+    },
+    types::block::{
+        address::{Address, AliasAddress},
+        input::{Input, UtxoInput},
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, GovernorAddressUnlockCondition, ImmutableAliasAddressUnlockCondition,
+                StateControllerAddressUnlockCondition,
+            },
+            AliasId, AliasOutputBuilder, BasicOutputBuilder, ChainId, FoundryId, FoundryOutputBuilder,
+            InputsCommitment, NativeToken, NativeTokensBuilder, NftId, NftOutputBuilder, Output, OutputId,
+            OutputMetadata, Rent, SimpleTokenScheme, TokenId, TokenScheme,
+        },
+        payload::{
+            transaction::{RegularTransactionEssence, TransactionEssence},
+            TransactionPayload,
+        },
+        protocol::{protocol_parameters, ProtocolParameters},
+        rand::{
+            block::rand_block_id,
+            bool::rand_bool,
+            bytes::rand_bytes_array,
+            number::rand_number_range,
+            output::{rand_alias_id, rand_output_id},
+        },
+        semantic::ConflictReason,
+    },
+};
+
+/// The number of random scenarios checked by a run.
+const CASES: usize = 64;
+/// The number of generated addresses, the last one isn't owned by the sender.
+const ADDRESSES: usize = 4;
+/// The time at which the transactions are selected and validated.
+const TIMESTAMP: u32 = 100;
+/// The serial number of the foundries controlled by the generated aliases.
+const FOUNDRY_SERIAL_NUMBER: u32 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum InputSpec {
+    Basic {
+        output_id: OutputId,
+        owner: usize,
+        extra_amount: u64,
+        native_tokens: Vec<(TokenId, u64)>,
+    },
+    Nft {
+        output_id: OutputId,
+        owner: usize,
+        extra_amount: u64,
+        nft_id: NftId,
+    },
+    Alias {
+        output_id: OutputId,
+        owner: usize,
+        extra_amount: u64,
+        alias_id: AliasId,
+        state_index: u32,
+        // The output id and the minted tokens of a foundry controlled by the alias.
+        foundry: Option<(OutputId, u64)>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum TargetSpec {
+    Basic {
+        recipient: usize,
+        extra_amount: u64,
+        native_tokens: Vec<(TokenId, u64)>,
+    },
+    Nft {
+        recipient: usize,
+        nft_id: NftId,
+    },
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct BurnSpec {
+    aliases: Vec<AliasId>,
+    nfts: Vec<NftId>,
+    native_tokens: Vec<(TokenId, u64)>,
+}
+
+impl BurnSpec {
+    fn is_empty(&self) -> bool {
+        self.aliases.is_empty() && self.nfts.is_empty() && self.native_tokens.is_empty()
+    }
+}
+
+/// A random set of unspent outputs and the transaction to select from them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Scenario {
+    inputs: Vec<InputSpec>,
+    targets: Vec<TargetSpec>,
+    burn: BurnSpec,
+    // The owned address receiving the remainder, the address of the first input if none.
+    remainder: Option<usize>,
+}
+
+impl Scenario {
+    fn generate() -> Self {
+        // Tokens without a foundry in the UTXO set, which can only be transferred or burned.
+        let mut tokens = (0..2).map(|_| TokenId::new(rand_bytes_array())).collect::<Vec<_>>();
+        let foreign_tokens = tokens.clone();
+        let mut inputs = Vec::new();
+        let mut nft_ids = Vec::new();
+        let mut alias_ids = Vec::new();
+
+        for _ in 0..rand_number_range(1..8) {
+            let output_id = rand_output_id();
+            let owner = rand_number_range(0..ADDRESSES - 1);
+            let extra_amount = rand_number_range(0..3_000_000);
+
+            match rand_number_range(0..10) {
+                0..=5 => {}
+                6 | 7 => {
+                    let nft_id = NftId::new(rand_bytes_array());
+                    nft_ids.push(nft_id);
+                    inputs.push(InputSpec::Nft {
+                        output_id,
+                        owner,
+                        extra_amount,
+                        nft_id,
+                    });
+                    continue;
+                }
+                _ => {
+                    let alias_id = rand_alias_id();
+                    let foundry = rand_bool().then(|| (rand_output_id(), rand_number_range(1_000..1_000_000)));
+                    if foundry.is_some() {
+                        tokens.push(foundry_token_id(alias_id));
+                    }
+                    alias_ids.push(alias_id);
+                    inputs.push(InputSpec::Alias {
+                        output_id,
+                        owner,
+                        extra_amount,
+                        alias_id,
+                        state_index: rand_number_range(0..10),
+                        foundry,
+                    });
+                    continue;
+                }
+            }
+            inputs.push(InputSpec::Basic {
+                output_id,
+                owner,
+                extra_amount,
+                native_tokens: rand_native_tokens(&tokens),
+            });
+        }
+
+        // Targets and burns mostly use tokens held by the inputs, so that input selection can fulfill them.
+        let mut held_tokens = BTreeMap::<TokenId, u64>::new();
+        for input in &inputs {
+            if let InputSpec::Basic { native_tokens, .. } = input {
+                for (token_id, amount) in native_tokens {
+                    *held_tokens.entry(*token_id).or_default() += amount;
+                }
+            }
+        }
+        let target_count = rand_number_range(1..4);
+        let mut targets = Vec::new();
+        for _ in 0..target_count {
+            let native_tokens = held_tokens
+                .iter()
+                .filter(|_| rand_bool())
+                .map(|(token_id, amount)| (*token_id, rand_number_range(1..=(amount / target_count).max(1))))
+                .collect();
+            targets.push(TargetSpec::Basic {
+                recipient: rand_number_range(0..ADDRESSES),
+                extra_amount: rand_number_range(0..1_000_000),
+                native_tokens,
+            });
+        }
+        if !nft_ids.is_empty() && rand_bool() {
+            targets.push(TargetSpec::Nft {
+                recipient: rand_number_range(0..ADDRESSES),
+                nft_id: nft_ids.remove(rand_number_range(0..nft_ids.len())),
+            });
+        }
+
+        let mut burn = BurnSpec::default();
+        if !nft_ids.is_empty() && rand_number_range(0..4) == 0 {
+            burn.nfts.push(nft_ids[rand_number_range(0..nft_ids.len())]);
+        }
+        if !alias_ids.is_empty() && rand_number_range(0..4) == 0 {
+            burn.aliases.push(alias_ids[rand_number_range(0..alias_ids.len())]);
+        }
+        if rand_number_range(0..3) == 0 {
+            let token_id = foreign_tokens[rand_number_range(0..foreign_tokens.len())];
+            let held = held_tokens.get(&token_id).copied().unwrap_or_default();
+            burn.native_tokens
+                .push((token_id, rand_number_range(1..=(held / (2 * target_count)).max(1))));
+        }
+
+        Self {
+            inputs,
+            targets,
+            burn,
+            remainder: rand_bool().then(|| rand_number_range(0..ADDRESSES - 1)),
+        }
+    }
+
+    /// Returns the scenarios which are one step simpler than this one.
+    fn simplifications(&self) -> Vec<Self> {
+        let mut simplifications = Vec::new();
+        let mut push = |simplify: &dyn Fn(&mut Self)| {
+            let mut scenario = self.clone();
+            simplify(&mut scenario);
+            // Simplifications which don't change anything would never end the shrinking.
+            if &scenario != self {
+                simplifications.push(scenario);
+            }
+        };
+
+        for index in 0..self.inputs.len() {
+            push(&|scenario| {
+                scenario.inputs.remove(index);
+            });
+        }
+        for index in 0..self.targets.len() {
+            push(&|scenario| {
+                scenario.targets.remove(index);
+            });
+        }
+        if !self.burn.is_empty() {
+            push(&|scenario| scenario.burn = BurnSpec::default());
+        }
+        if self.remainder.is_some() {
+            push(&|scenario| scenario.remainder = None);
+        }
+        for index in 0..self.inputs.len() {
+            push(&|scenario| match &mut scenario.inputs[index] {
+                InputSpec::Basic { native_tokens, .. } => native_tokens.clear(),
+                InputSpec::Alias { foundry, .. } => *foundry = None,
+                InputSpec::Nft { .. } => {}
+            });
+            push(&|scenario| match &mut scenario.inputs[index] {
+                InputSpec::Basic { extra_amount, .. }
+                | InputSpec::Nft { extra_amount, .. }
+                | InputSpec::Alias { extra_amount, .. } => *extra_amount /= 2,
+            });
+        }
+        for index in 0..self.targets.len() {
+            push(&|scenario| {
+                if let TargetSpec::Basic {
+                    extra_amount,
+                    native_tokens,
+                    ..
+                } = &mut scenario.targets[index]
+                {
+                    native_tokens.clear();
+                    *extra_amount /= 2;
+                }
+            });
+        }
+
+        simplifications
+    }
+}
+
+/// The keys owning the generated outputs.
+struct Context {
+    secret_manager: SecretManager,
+    addresses: Vec<Address>,
+    protocol_parameters: ProtocolParameters,
+}
+
+impl Context {
+    fn owned_addresses(&self) -> Vec<Address> {
+        self.addresses[..ADDRESSES - 1].to_vec()
+    }
+
+    // Builds an output with the minimum storage deposit and an extra amount.
+    fn with_storage_deposit(&self, extra_amount: u64, build: impl Fn(u64) -> Output) -> Output {
+        let minimum = build(1).rent_cost(self.protocol_parameters.rent_structure());
+        build(minimum + extra_amount)
+    }
+
+    fn inputs(&self, scenario: &Scenario) -> Vec<InputSigningData> {
+        let token_supply = self.protocol_parameters.token_supply();
+        let mut inputs = Vec::new();
+
+        for input in &scenario.inputs {
+            let (output_id, owner, output) = match input {
+                InputSpec::Basic {
+                    output_id,
+                    owner,
+                    extra_amount,
+                    native_tokens,
+                } => (
+                    output_id,
+                    owner,
+                    self.with_storage_deposit(*extra_amount, |amount| {
+                        BasicOutputBuilder::new_with_amount(amount)
+                            .add_unlock_condition(AddressUnlockCondition::new(self.addresses[*owner]))
+                            .with_native_tokens(native_tokens_builder(native_tokens).finish().unwrap())
+                            .finish_output(token_supply)
+                            .unwrap()
+                    }),
+                ),
+                InputSpec::Nft {
+                    output_id,
+                    owner,
+                    extra_amount,
+                    nft_id,
+                } => (
+                    output_id,
+                    owner,
+                    self.with_storage_deposit(*extra_amount, |amount| {
+                        NftOutputBuilder::new_with_amount(amount, *nft_id)
+                            .add_unlock_condition(AddressUnlockCondition::new(self.addresses[*owner]))
+                            .finish_output(token_supply)
+                            .unwrap()
+                    }),
+                ),
+                InputSpec::Alias {
+                    output_id,
+                    owner,
+                    extra_amount,
+                    alias_id,
+                    state_index,
+                    foundry,
+                } => {
+                    if let Some((foundry_output_id, minted_tokens)) = foundry {
+                        let output = self.with_storage_deposit(0, |amount| {
+                            FoundryOutputBuilder::new_with_amount(
+                                amount,
+                                FOUNDRY_SERIAL_NUMBER,
+                                TokenScheme::Simple(
+                                    SimpleTokenScheme::new(*minted_tokens, 0, 2 * *minted_tokens).unwrap(),
+                                ),
+                            )
+                            .add_unlock_condition(ImmutableAliasAddressUnlockCondition::new(AliasAddress::new(
+                                *alias_id,
+                            )))
+                            .finish_output(token_supply)
+                            .unwrap()
+                        });
+                        // Foundries are unlocked by their alias.
+                        inputs.push(input_signing_data(output, *foundry_output_id, None));
+                    }
+                    (
+                        output_id,
+                        owner,
+                        self.with_storage_deposit(*extra_amount, |amount| {
+                            AliasOutputBuilder::new_with_amount(amount, *alias_id)
+                                .with_state_index(*state_index)
+                                .with_foundry_counter(foundry.map_or(0, |_| FOUNDRY_SERIAL_NUMBER))
+                                .add_unlock_condition(StateControllerAddressUnlockCondition::new(
+                                    self.addresses[*owner],
+                                ))
+                                .add_unlock_condition(GovernorAddressUnlockCondition::new(self.addresses[*owner]))
+                                .finish_output(token_supply)
+                                .unwrap()
+                        }),
+                    )
+                }
+            };
+            inputs.push(input_signing_data(
+                output,
+                *output_id,
+                Some(Bip44::new(SHIMMER_COIN_TYPE).with_address_index(*owner as u32)),
+            ));
+        }
+
+        inputs
+    }
+
+    fn targets(&self, scenario: &Scenario) -> Vec<Output> {
+        let token_supply = self.protocol_parameters.token_supply();
+
+        scenario
+            .targets
+            .iter()
+            .map(|target| match target {
+                TargetSpec::Basic {
+                    recipient,
+                    extra_amount,
+                    native_tokens,
+                } => self.with_storage_deposit(*extra_amount, |amount| {
+                    BasicOutputBuilder::new_with_amount(amount)
+                        .add_unlock_condition(AddressUnlockCondition::new(self.addresses[*recipient]))
+                        .with_native_tokens(native_tokens_builder(native_tokens).finish().unwrap())
+                        .finish_output(token_supply)
+                        .unwrap()
+                }),
+                TargetSpec::Nft { recipient, nft_id } => self.with_storage_deposit(0, |amount| {
+                    NftOutputBuilder::new_with_amount(amount, *nft_id)
+                        .add_unlock_condition(AddressUnlockCondition::new(self.addresses[*recipient]))
+                        .finish_output(token_supply)
+                        .unwrap()
+                }),
+            })
+            .collect()
+    }
+
+    /// Selects, signs and validates the transaction of a scenario. Returns whether input selection accepted the
+    /// scenario, or the violated invariant.
+    async fn check(&self, scenario: &Scenario) -> Result<bool, String> {
+        let targets = self.targets(scenario);
+        let mut burn = Burn::new()
+            .set_aliases(scenario.burn.aliases.iter().copied().collect())
+            .set_nfts(scenario.burn.nfts.iter().copied().collect());
+        for (token_id, amount) in &scenario.burn.native_tokens {
+            burn = burn.add_native_token(*token_id, *amount);
+        }
+        let remainder_address = scenario.remainder.map(|owner| self.addresses[owner]);
+
+        let selection = InputSelection::new(
+            self.inputs(scenario),
+            targets.clone(),
+            self.owned_addresses(),
+            self.protocol_parameters.clone(),
+        )
+        .remainder_address(remainder_address)
+        .burn((!scenario.burn.is_empty()).then_some(burn))
+        .timestamp(TIMESTAMP)
+        .select();
+        // Input selection may reject scenarios, e.g. because of insufficient funds, but mustn't create invalid
+        // transactions.
+        let Ok(selected) = selection else {
+            return Ok(false);
+        };
+
+        for target in &targets {
+            if !selected.outputs.contains(target) {
+                return Err(format!("target {target:?} isn't an output"));
+            }
+        }
+        if let (Some(remainder_address), Some(remainder)) = (remainder_address, &selected.remainder) {
+            if remainder.address != remainder_address {
+                return Err(format!("remainder sent to {:?}", remainder.address));
+            }
+        }
+
+        let input_amount = selected.inputs.iter().map(|input| input.output.amount()).sum::<u64>();
+        let output_amount = selected.outputs.iter().map(Output::amount).sum::<u64>();
+        if input_amount != output_amount {
+            return Err(format!("unbalanced amounts: {input_amount} in, {output_amount} out"));
+        }
+
+        let mut native_token_balances = BTreeMap::<TokenId, i128>::new();
+        for (native_tokens, sign) in selected
+            .inputs
+            .iter()
+            .map(|input| (input.output.native_tokens(), 1))
+            .chain(selected.outputs.iter().map(|output| (output.native_tokens(), -1)))
+        {
+            for native_token in native_tokens.into_iter().flat_map(|native_tokens| native_tokens.iter()) {
+                *native_token_balances.entry(*native_token.token_id()).or_default() +=
+                    sign * native_token.amount().as_u128() as i128;
+            }
+        }
+        // Tokens of transitioned foundries may be minted or melted, which the semantic validation checks.
+        let foundry_tokens = selected
+            .inputs
+            .iter()
+            .filter_map(|input| match &input.output {
+                Output::Foundry(foundry) => Some(TokenId::from(foundry.id())),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        for (token_id, balance) in native_token_balances {
+            let burned = scenario
+                .burn
+                .native_tokens
+                .iter()
+                .filter(|(burned_token_id, _)| burned_token_id == &token_id)
+                .map(|(_, amount)| *amount as i128)
+                .sum::<i128>();
+            if !foundry_tokens.contains(&token_id) && balance != burned {
+                return Err(format!(
+                    "{token_id} not conserved: {balance} burned instead of {burned}"
+                ));
+            }
+        }
+
+        let output_chains = selected
+            .outputs
+            .iter()
+            .filter_map(Output::chain_id)
+            .collect::<HashSet<_>>();
+        for input in &selected.inputs {
+            let chain_id = match &input.output {
+                Output::Alias(alias) => ChainId::from(alias.alias_id_non_null(input.output_id())),
+                Output::Nft(nft) => ChainId::from(nft.nft_id_non_null(input.output_id())),
+                _ => continue,
+            };
+            let burned = match chain_id {
+                ChainId::Alias(alias_id) => scenario.burn.aliases.contains(&alias_id),
+                ChainId::Nft(nft_id) => scenario.burn.nfts.contains(&nft_id),
+                _ => false,
+            };
+            if burned == output_chains.contains(&chain_id) {
+                return Err(format!("chain {chain_id} burned: {}", !burned));
+            }
+        }
+
+        for output in &selected.outputs {
+            if let Err(error) = output.verify_storage_deposit(
+                *self.protocol_parameters.rent_structure(),
+                self.protocol_parameters.token_supply(),
+            ) {
+                return Err(format!("storage deposit of {output:?} not met: {error}"));
+            }
+        }
+
+        let essence = RegularTransactionEssence::builder(
+            self.protocol_parameters.network_id(),
+            InputsCommitment::new(selected.inputs.iter().map(|i| &i.output)),
+        )
+        .with_inputs(
+            selected
+                .inputs
+                .iter()
+                .map(|input| Input::Utxo(UtxoInput::from(*input.output_id())))
+                .collect::<Vec<_>>(),
+        )
+        .with_outputs(selected.outputs)
+        .finish_with_params(self.protocol_parameters.clone())
+        .map_err(|error| format!("invalid essence: {error}"))?;
+        let prepared_transaction_data = PreparedTransactionData {
+            essence: TransactionEssence::Regular(essence),
+            inputs_data: selected.inputs,
+            remainder: selected.remainder,
+        };
+        let unlocks = self
+            .secret_manager
+            .sign_transaction_essence(&prepared_transaction_data, Some(TIMESTAMP))
+            .await
+            .map_err(|error| format!("signing failed: {error}"))?;
+        let transaction_payload = TransactionPayload::new(prepared_transaction_data.essence.clone(), unlocks)
+            .map_err(|error| format!("invalid transaction payload: {error}"))?;
+
+        match verify_semantic(&prepared_transaction_data.inputs_data, &transaction_payload, TIMESTAMP) {
+            Ok(ConflictReason::None) => Ok(true),
+            Ok(conflict) => Err(format!("conflicting transaction: {conflict:?}")),
+            Err(error) => Err(format!("semantic validation failed: {error}")),
+        }
+    }
+
+    /// Simplifies a scenario violating an invariant as long as the simpler scenario still violates one.
+    async fn shrink(&self, mut scenario: Scenario, mut violation: String) -> (Scenario, String) {
+        'shrink: loop {
+            for simplification in scenario.simplifications() {
+                if let Err(simplification_violation) = self.check(&simplification).await {
+                    scenario = simplification;
+                    violation = simplification_violation;
+                    continue 'shrink;
+                }
+            }
+            return (scenario, violation);
+        }
+    }
+}
+
+fn input_signing_data(output: Output, output_id: OutputId, chain: Option<Bip44>) -> InputSigningData {
+    InputSigningData {
+        output,
+        output_metadata: OutputMetadata::new(rand_block_id(), output_id, false, None, None, None, 0, 0, 0),
+        chain,
+    }
+}
+
+fn foundry_token_id(alias_id: AliasId) -> TokenId {
+    TokenId::from(FoundryId::build(
+        &AliasAddress::new(alias_id),
+        FOUNDRY_SERIAL_NUMBER,
+        SimpleTokenScheme::KIND,
+    ))
+}
+
+fn rand_native_tokens(tokens: &[TokenId]) -> Vec<(TokenId, u64)> {
+    tokens
+        .iter()
+        .filter(|_| rand_number_range(0..3) == 0)
+        .map(|token_id| (*token_id, rand_number_range(1..1_000)))
+        .collect()
+}
+
+fn native_tokens_builder(native_tokens: &[(TokenId, u64)]) -> NativeTokensBuilder {
+    let mut builder = NativeTokensBuilder::new();
+    for (token_id, amount) in native_tokens {
+        builder
+            .add_native_token(NativeToken::new(*token_id, *amount).unwrap())
+            .unwrap();
+    }
+    builder
+}
+
+#[tokio::test]
+async fn input_selection_ledger_invariants() {
+    let secret_manager = SecretManager::try_from_mnemonic(Client::generate_mnemonic().unwrap()).unwrap();
+    let addresses = secret_manager
+        .generate_ed25519_addresses(
+            GetAddressesOptions::default()
+                .with_coin_type(SHIMMER_COIN_TYPE)
+                .with_range(0..ADDRESSES as u32),
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(Address::from)
+        .collect();
+    let context = Context {
+        secret_manager,
+        addresses,
+        protocol_parameters: protocol_parameters(),
+    };
+
+    let mut accepted = 0;
+    for _ in 0..CASES {
+        let scenario = Scenario::generate();
+        match context.check(&scenario).await {
+            Ok(is_accepted) => accepted += is_accepted as usize,
+            Err(violation) => {
+                let (scenario, violation) = context.shrink(scenario, violation).await;
+                panic!("{violation}, minimal scenario: {scenario:#?}");
+            }
+        }
+    }
+    // Guards against a generator only producing scenarios rejected by input selection.
+    assert!(accepted > 0, "no scenario was accepted by input selection");
+}
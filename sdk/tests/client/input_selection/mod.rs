@@ -6,6 +6,7 @@ mod basic_outputs;
 mod burn;
 mod expiration;
 mod foundry_outputs;
+mod invariants;
 mod native_tokens;
 mod nft_outputs;
 mod outputs;
@@ -0,0 +1,218 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        api::GetAddressesOptions, node_api::indexer::query_parameters::QueryParameter, secret::SecretManager, Client,
+        Result,
+    },
+    types::{
+        api::core::response::LedgerInclusionState,
+        block::{
+            address::ToBech32Ext,
+            output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder},
+            payload::Payload,
+            semantic::ConflictReason,
+        },
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::utils::mock_node::MockNode;
+
+async fn setup(mock_node: &MockNode) -> Result<(Client, SecretManager)> {
+    let client = Client::builder().with_node(&mock_node.url())?.finish().await?;
+    let secret_manager = SecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+
+    Ok((client, secret_manager))
+}
+
+#[tokio::test]
+async fn mock_node_tagged_data_block() -> Result<()> {
+    let mock_node = MockNode::start();
+    let (client, _) = setup(&mock_node).await?;
+
+    let info = client.get_info().await?.node_info;
+    assert_eq!(info.protocol.network_name(), "mock");
+    assert_eq!(info.status.latest_milestone.index, 1);
+
+    let block = client
+        .build_block()
+        .with_tag(b"mock".to_vec())
+        .with_data(b"node".to_vec())
+        .finish()
+        .await?;
+    assert_eq!(client.get_block(&block.id()).await?, block);
+    assert_eq!(client.get_tips().await?, [block.id()]);
+    let metadata = client.get_block_metadata(&block.id()).await?;
+    assert!(metadata.ledger_inclusion_state.is_none());
+
+    assert_eq!(mock_node.produce_milestone(), 2);
+    let metadata = client.get_block_metadata(&block.id()).await?;
+    assert_eq!(metadata.referenced_by_milestone_index, Some(2));
+    assert_eq!(
+        metadata.ledger_inclusion_state,
+        Some(LedgerInclusionState::NoTransaction)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_milestone_interval() -> Result<()> {
+    let mock_node = MockNode::builder()
+        .with_milestone_interval(std::time::Duration::from_millis(100))
+        .finish();
+    let (client, _) = setup(&mock_node).await?;
+
+    let block = client.build_block().with_tag(b"mock".to_vec()).finish().await?;
+    let blocks = client.retry_until_included(&block.id(), Some(1), Some(5)).await?;
+    assert_eq!(blocks, [(block.id(), block)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_transactions() -> Result<()> {
+    let mock_node = MockNode::start();
+    let (client, secret_manager) = setup(&mock_node).await?;
+    let token_supply = client.get_token_supply().await?;
+    let bech32_hrp = client.get_bech32_hrp().await?;
+
+    let addresses = secret_manager
+        .generate_ed25519_addresses(GetAddressesOptions::from_client(&client).await?.with_range(0..2))
+        .await?;
+    let funding_output_id = mock_node.fund(addresses[0], 2_000_000);
+    assert_eq!(
+        client
+            .basic_output_ids([QueryParameter::Address(addresses[0].to_bech32(bech32_hrp))])
+            .await?
+            .items,
+        [funding_output_id]
+    );
+
+    // Both transactions select the funding output, as it's unspent until the next milestone
+    let mut blocks = Vec::new();
+    for amount in [1_000_000, 2_000_000] {
+        let output = BasicOutputBuilder::new_with_amount(amount)
+            .add_unlock_condition(AddressUnlockCondition::new(addresses[1]))
+            .finish_output(token_supply)?;
+        blocks.push(
+            client
+                .build_block()
+                .with_secret_manager(&secret_manager)
+                .with_outputs([output])?
+                .finish()
+                .await?,
+        );
+    }
+    mock_node.produce_milestone();
+
+    let metadata = client.get_block_metadata(&blocks[0].id()).await?;
+    assert_eq!(metadata.ledger_inclusion_state, Some(LedgerInclusionState::Included));
+    let metadata = client.get_block_metadata(&blocks[1].id()).await?;
+    assert_eq!(metadata.ledger_inclusion_state, Some(LedgerInclusionState::Conflicting));
+    assert_eq!(
+        metadata.conflict_reason,
+        Some(ConflictReason::InputUtxoAlreadySpentInThisMilestone as u8)
+    );
+
+    let Some(Payload::Transaction(transaction_payload)) = blocks[0].payload() else {
+        panic!("missing transaction payload");
+    };
+    assert_eq!(
+        client.get_included_block(&transaction_payload.id()).await?.id(),
+        blocks[0].id()
+    );
+    assert!(client.get_output(&funding_output_id).await?.metadata().is_spent());
+    let output_ids = client
+        .basic_output_ids([QueryParameter::UnlockableByAddress(addresses[1].to_bech32(bech32_hrp))])
+        .await?;
+    assert_eq!(output_ids.ledger_index, 2);
+    assert_eq!(output_ids.items.len(), 1);
+    assert_eq!(output_ids.items[0].transaction_id(), &transaction_payload.id());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn mock_node_indexer_pages() -> Result<()> {
+    let mock_node = MockNode::start();
+    let (client, secret_manager) = setup(&mock_node).await?;
+    let bech32_hrp = client.get_bech32_hrp().await?;
+
+    let address = secret_manager
+        .generate_ed25519_addresses(GetAddressesOptions::from_client(&client).await?.with_range(0..1))
+        .await?[0];
+    let output_ids = (0..5)
+        .map(|_| mock_node.fund(address, 1_000_000))
+        .collect::<std::collections::BTreeSet<_>>();
+
+    let page = client
+        .basic_output_ids([
+            QueryParameter::Address(address.to_bech32(bech32_hrp)),
+            QueryParameter::PageSize(2),
+        ])
+        .await?;
+    assert_eq!(page.items.len(), 5);
+    assert_eq!(
+        page.items.into_iter().collect::<std::collections::BTreeSet<_>>(),
+        output_ids
+    );
+
+    let page = client
+        .basic_output_ids([
+            QueryParameter::Address(address.to_bech32(bech32_hrp)),
+            QueryParameter::PageSize(2),
+            QueryParameter::Cursor(String::new()),
+        ])
+        .await?;
+    assert_eq!(page.items.len(), 2);
+    assert!(page.cursor.is_some());
+
+    assert!(client
+        .basic_output_ids([QueryParameter::HasNativeTokens(true)])
+        .await?
+        .items
+        .is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "mqtt")]
+#[tokio::test]
+async fn mock_node_mqtt() -> Result<()> {
+    use iota_sdk::client::mqtt::{BrokerOptions, MqttPayload, Topic};
+
+    let mock_node = MockNode::start();
+    let client = Client::builder()
+        .with_node(&mock_node.url())?
+        .with_mqtt_broker_options(BrokerOptions::new().use_ws(false).port(mock_node.mqtt_port()))
+        .finish()
+        .await?;
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    client
+        .subscribe([Topic::new("milestone-info/latest")?], move |event| {
+            sender.send(event.clone()).ok();
+        })
+        .await?;
+
+    // The subscription is only active once the broker received it
+    let event = loop {
+        let index = mock_node.produce_milestone();
+        if let Ok(Some(event)) = tokio::time::timeout(std::time::Duration::from_millis(200), receiver.recv()).await {
+            break (index, event);
+        }
+        assert!(index < 50, "no milestone was published");
+    };
+    let MqttPayload::Json(milestone_info) = event.1.payload else {
+        panic!("milestone info is JSON");
+    };
+    // The event can be of an earlier milestone than the one produced last
+    assert!(milestone_info["index"]
+        .as_u64()
+        .is_some_and(|index| index <= event.0 as u64));
+
+    Ok(())
+}
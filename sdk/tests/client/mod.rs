@@ -10,9 +10,12 @@ mod high_level;
 mod input_selection;
 mod input_signing_data;
 mod mnemonic;
+mod mock_node;
 #[cfg(feature = "mqtt")]
 mod mqtt;
 mod node_api;
+mod protocol_upgrade;
+mod rent_optimizer;
 mod secret_manager;
 mod signing;
 mod transactions;
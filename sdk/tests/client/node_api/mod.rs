@@ -5,6 +5,7 @@ mod core;
 mod indexer;
 #[cfg(feature = "mqtt")]
 mod mqtt;
+mod stream;
 
 use iota_sdk::{
     client::{
@@ -0,0 +1,161 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use futures::TryStreamExt;
+use iota_sdk::{
+    client::{
+        node_api::indexer::query_parameters::{
+            AliasOutputQueryParameters, AnyOutputQueryParameters, BasicOutputQueryParameters,
+            FoundryOutputQueryParameters, NftOutputQueryParameters,
+        },
+        Client, Result,
+    },
+    types::block::{
+        address::{Address, AliasAddress, Ed25519Address, ToBech32Ext},
+        output::{
+            unlock_condition::{
+                AddressUnlockCondition, GovernorAddressUnlockCondition, ImmutableAliasAddressUnlockCondition,
+                StateControllerAddressUnlockCondition,
+            },
+            AliasId, AliasOutputBuilder, FoundryOutputBuilder, NftId, NftOutputBuilder, SimpleTokenScheme, TokenScheme,
+        },
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::utils::mock_node::MockNode;
+
+#[tokio::test]
+async fn output_pages_streams() -> Result<()> {
+    let mock_node = MockNode::start();
+    let client = Client::builder().with_node(&mock_node.url())?.finish().await?;
+    let token_supply = client.get_token_supply().await?;
+    let hrp = client.get_bech32_hrp().await?;
+    let address = Address::from(Ed25519Address::new([1; 32]));
+    let bech32_address = address.to_bech32(hrp);
+
+    let funded_output_ids = [1_000_000, 2_000_000, 3_000_000, 4_000_000, 5_000_000]
+        .into_iter()
+        .map(|amount| mock_node.fund(address, amount))
+        .collect::<HashSet<_>>();
+    // Outputs of other addresses don't match the queries
+    mock_node.fund(Ed25519Address::new([2; 32]), 1_000_000);
+
+    let pages = client
+        .output_id_pages(
+            BasicOutputQueryParameters::new()
+                .with_address(bech32_address)
+                .with_page_size(2),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(pages.iter().map(|page| page.items.len()).collect::<Vec<_>>(), [2, 2, 1]);
+    assert!(pages[..2].iter().all(|page| page.cursor.is_some()));
+    assert!(pages[2].cursor.is_none());
+    assert_eq!(
+        pages.iter().flat_map(|page| page.items.clone()).collect::<HashSet<_>>(),
+        funded_output_ids
+    );
+
+    // The query is resumed from the cursor of a page
+    let resumed_pages = client
+        .output_id_pages(
+            BasicOutputQueryParameters::new()
+                .with_address(bech32_address)
+                .with_cursor(pages[0].cursor.clone().unwrap()),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        resumed_pages.iter().map(|page| page.items.clone()).collect::<Vec<_>>(),
+        pages[1..].iter().map(|page| page.items.clone()).collect::<Vec<_>>()
+    );
+
+    let output_pages = client
+        .output_pages(
+            BasicOutputQueryParameters::new()
+                .with_address(bech32_address)
+                .with_page_size(3),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(output_pages.len(), 2);
+    assert_eq!(
+        output_pages
+            .iter()
+            .flat_map(|page| page.items.iter().map(|output| output.output().amount()))
+            .sum::<u64>(),
+        15_000_000
+    );
+
+    let outputs = client
+        .basic_outputs_stream(
+            BasicOutputQueryParameters::new()
+                .with_address(bech32_address)
+                .with_page_size(2),
+        )
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(
+        outputs
+            .iter()
+            .map(|output| *output.metadata().output_id())
+            .collect::<HashSet<_>>(),
+        funded_output_ids
+    );
+
+    // Chain outputs are returned by the streams of their kind
+    let alias_id = AliasId::new([1; 32]);
+    let alias_address = Address::from(AliasAddress::new(alias_id)).to_bech32(hrp);
+    mock_node.add_output(
+        AliasOutputBuilder::new_with_amount(1_000_000, alias_id)
+            .add_unlock_condition(StateControllerAddressUnlockCondition::new(address))
+            .add_unlock_condition(GovernorAddressUnlockCondition::new(address))
+            .finish_output(token_supply)?,
+    );
+    mock_node.add_output(
+        FoundryOutputBuilder::new_with_amount(1_000_000, 1, TokenScheme::Simple(SimpleTokenScheme::new(0, 0, 100)?))
+            .add_unlock_condition(ImmutableAliasAddressUnlockCondition::new(AliasAddress::new(alias_id)))
+            .finish_output(token_supply)?,
+    );
+    mock_node.add_output(
+        NftOutputBuilder::new_with_amount(1_000_000, NftId::new([1; 32]))
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(token_supply)?,
+    );
+
+    let alias_outputs = client
+        .alias_outputs_stream(AliasOutputQueryParameters::new().with_state_controller(bech32_address))
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(alias_outputs.len(), 1);
+    assert!(alias_outputs[0].output().is_alias());
+
+    let foundry_outputs = client
+        .foundry_outputs_stream(FoundryOutputQueryParameters::new().with_alias_address(alias_address))
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(foundry_outputs.len(), 1);
+    assert!(foundry_outputs[0].output().is_foundry());
+
+    let nft_outputs = client
+        .nft_outputs_stream(NftOutputQueryParameters::new().with_address(bech32_address))
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(nft_outputs.len(), 1);
+    assert!(nft_outputs[0].output().is_nft());
+
+    assert_eq!(
+        client
+            .outputs_stream(AnyOutputQueryParameters::new().with_unlockable_by_address(bech32_address))
+            .try_collect::<Vec<_>>()
+            .await?
+            .len(),
+        // The alias output is unlockable by its state controller, the nft output by its address
+        funded_output_ids.len() + 2
+    );
+
+    Ok(())
+}
@@ -0,0 +1,242 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_sdk::{
+    client::{Client, NetworkInfo, ProtocolParametersUpgrade, Result},
+    types::block::{
+        payload::milestone::{
+            MilestoneEssence, MilestoneIndex, MilestoneOption, MilestoneOptions, MilestonePayload,
+            ParametersMilestoneOption,
+        },
+        protocol::ProtocolParameters,
+        rand::{
+            milestone::{rand_merkle_root, rand_milestone_id},
+            parents::rand_parents,
+            signature::rand_signature,
+        },
+        Error,
+    },
+};
+use packable::PackableExt;
+use pretty_assertions::assert_eq;
+
+use crate::utils::mock_node::MockNode;
+
+fn upgraded_protocol_parameters(protocol_parameters: &ProtocolParameters, byte_cost: u32) -> ProtocolParameters {
+    ProtocolParameters::new(
+        protocol_parameters.protocol_version(),
+        protocol_parameters.network_name().to_string(),
+        *protocol_parameters.bech32_hrp(),
+        protocol_parameters.min_pow_score(),
+        protocol_parameters.below_max_depth(),
+        protocol_parameters.rent_structure().with_byte_cost(byte_cost),
+        protocol_parameters.token_supply(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn parameters_milestone_option_protocol_parameters() {
+    let protocol_parameters = upgraded_protocol_parameters(&ProtocolParameters::default(), 1000);
+    let option = ParametersMilestoneOption::new(
+        10.into(),
+        protocol_parameters.protocol_version(),
+        protocol_parameters.pack_to_vec(),
+    )
+    .unwrap();
+    assert_eq!(option.protocol_parameters().unwrap(), protocol_parameters);
+
+    let upgrade = ProtocolParametersUpgrade::try_from(&option).unwrap();
+    assert_eq!(upgrade.target_milestone_index, 10);
+    assert_eq!(upgrade.protocol_parameters, protocol_parameters);
+
+    let option = ParametersMilestoneOption::new(
+        10.into(),
+        protocol_parameters.protocol_version() + 1,
+        protocol_parameters.pack_to_vec(),
+    )
+    .unwrap();
+    assert_eq!(
+        option.protocol_parameters().unwrap_err(),
+        Error::ProtocolVersionMismatch {
+            expected: protocol_parameters.protocol_version() + 1,
+            actual: protocol_parameters.protocol_version(),
+        }
+    );
+
+    let option = ParametersMilestoneOption::new(10.into(), protocol_parameters.protocol_version(), [1, 2, 3]).unwrap();
+    assert!(option.protocol_parameters().is_err());
+}
+
+#[test]
+fn network_info_protocol_parameters_upgrades() {
+    let mut network_info = NetworkInfo::default();
+    let protocol_parameters = network_info.protocol_parameters.clone();
+    let upgrade_1 = upgraded_protocol_parameters(&protocol_parameters, 1000);
+    let upgrade_2 = upgraded_protocol_parameters(&protocol_parameters, 2000);
+
+    // Without a known milestone index nothing is switched.
+    network_info.schedule_protocol_parameters_upgrade(ProtocolParametersUpgrade {
+        target_milestone_index: 20,
+        protocol_parameters: upgrade_2.clone(),
+    });
+    network_info.schedule_protocol_parameters_upgrade(ProtocolParametersUpgrade {
+        target_milestone_index: 10,
+        protocol_parameters: upgrade_1.clone(),
+    });
+    assert_eq!(network_info.protocol_parameters, protocol_parameters);
+    assert_eq!(
+        network_info
+            .protocol_parameters_upgrades
+            .iter()
+            .map(|upgrade| upgrade.target_milestone_index)
+            .collect::<Vec<_>>(),
+        [10, 20]
+    );
+
+    assert!(!network_info.update_latest_milestone_index(9));
+    assert_eq!(network_info.protocol_parameters, protocol_parameters);
+
+    assert!(network_info.update_latest_milestone_index(10));
+    assert_eq!(network_info.protocol_parameters, upgrade_1);
+    assert_eq!(network_info.protocol_parameters_upgrades.len(), 1);
+
+    // An older milestone index is ignored.
+    assert!(!network_info.update_latest_milestone_index(5));
+    assert_eq!(network_info.latest_milestone_index, Some(10));
+
+    // Multiple reached upgrades switch to the latest one.
+    network_info.schedule_protocol_parameters_upgrade(ProtocolParametersUpgrade {
+        target_milestone_index: 15,
+        protocol_parameters: upgraded_protocol_parameters(&protocol_parameters, 1500),
+    });
+    assert!(network_info.update_latest_milestone_index(25));
+    assert_eq!(network_info.protocol_parameters, upgrade_2);
+    assert!(network_info.protocol_parameters_upgrades.is_empty());
+
+    // An upgrade with a reached target milestone index is applied right away.
+    network_info.schedule_protocol_parameters_upgrade(ProtocolParametersUpgrade {
+        target_milestone_index: 25,
+        protocol_parameters: upgrade_1.clone(),
+    });
+    assert_eq!(network_info.protocol_parameters, upgrade_1);
+}
+
+#[tokio::test]
+async fn mock_node_protocol_parameters_upgrade() -> Result<()> {
+    let mock_node = MockNode::start();
+    let client = Client::builder()
+        .with_node(&mock_node.url())?
+        .with_node_sync_interval(Duration::from_millis(100))
+        .finish()
+        .await?;
+    let protocol_parameters = client.get_protocol_parameters().await?;
+    assert_eq!(protocol_parameters, mock_node.protocol_parameters());
+    assert!(client.get_protocol_parameters_upgrades().await.is_empty());
+
+    let upgraded = upgraded_protocol_parameters(&protocol_parameters, 1000);
+    let target_milestone_index = client.get_info().await?.node_info.status.latest_milestone.index + 1;
+    mock_node.schedule_protocol_parameters(target_milestone_index, upgraded.clone());
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while client.get_protocol_parameters_upgrades().await.is_empty() {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the announced upgrade was never synced");
+    assert_eq!(
+        client.get_protocol_parameters_upgrades().await,
+        [ProtocolParametersUpgrade {
+            target_milestone_index,
+            protocol_parameters: upgraded.clone(),
+        }]
+    );
+    assert_eq!(client.get_protocol_parameters().await?, protocol_parameters);
+
+    assert_eq!(mock_node.produce_milestone(), target_milestone_index);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while client.get_protocol_parameters().await.unwrap() != upgraded {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the protocol parameters were never switched");
+    assert!(client.get_protocol_parameters_upgrades().await.is_empty());
+
+    Ok(())
+}
+
+fn milestone(index: u32, parameters: Option<ParametersMilestoneOption>) -> MilestonePayload {
+    MilestonePayload::new(
+        MilestoneEssence::new(
+            MilestoneIndex(index),
+            0,
+            ProtocolParameters::default().protocol_version(),
+            rand_milestone_id(),
+            rand_parents(),
+            rand_merkle_root(),
+            rand_merkle_root(),
+            [],
+            MilestoneOptions::from_vec(parameters.into_iter().map(MilestoneOption::Parameters).collect()).unwrap(),
+        )
+        .unwrap(),
+        [rand_signature()],
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn process_milestone() -> Result<()> {
+    let mock_node = MockNode::start();
+    let client = Client::builder().with_node(&mock_node.url())?.finish().await?;
+    let protocol_parameters = client.get_protocol_parameters().await?;
+    let latest_milestone_index = client.get_info().await?.node_info.status.latest_milestone.index;
+
+    let upgraded = upgraded_protocol_parameters(&protocol_parameters, 1000);
+    let announcement = |target_milestone_index: u32, protocol_parameters: &ProtocolParameters| {
+        ParametersMilestoneOption::new(
+            target_milestone_index.into(),
+            protocol_parameters.protocol_version(),
+            protocol_parameters.pack_to_vec(),
+        )
+        .unwrap()
+    };
+
+    // The announced upgrade is scheduled
+    assert_eq!(
+        client
+            .process_milestone(&milestone(
+                latest_milestone_index + 1,
+                Some(announcement(latest_milestone_index + 2, &upgraded))
+            ))
+            .await?,
+        None
+    );
+    assert_eq!(client.get_protocol_parameters_upgrades().await.len(), 1);
+
+    // The protocol parameters are switched once the target milestone index is reached
+    assert_eq!(
+        client
+            .process_milestone(&milestone(latest_milestone_index + 2, None))
+            .await?,
+        Some(upgraded.clone())
+    );
+    assert!(client.get_protocol_parameters_upgrades().await.is_empty());
+
+    // An older milestone doesn't switch back to outdated protocol parameters
+    assert_eq!(
+        client
+            .process_milestone(&milestone(
+                latest_milestone_index + 1,
+                Some(announcement(latest_milestone_index + 1, &protocol_parameters))
+            ))
+            .await?,
+        None
+    );
+    assert_eq!(client.get_protocol_parameters().await?, upgraded);
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use iota_sdk::{
+    client::api::{RentOptimizer, RentSuggestion},
+    types::block::{
+        address::Bech32Address,
+        output::{
+            feature::{MetadataFeature, TagFeature},
+            unlock_condition::{AddressUnlockCondition, StorageDepositReturnUnlockCondition},
+            BasicOutputBuilder, NativeToken, Output, Rent, RentStructure, TokenId,
+        },
+    },
+};
+use pretty_assertions::assert_eq;
+
+use super::{BECH32_ADDRESS_ED25519_0, BECH32_ADDRESS_ED25519_1, TOKEN_ID_1, TOKEN_ID_2, TOKEN_SUPPLY};
+
+fn optimizer() -> RentOptimizer {
+    RentOptimizer::new(RentStructure::default(), TOKEN_SUPPLY)
+}
+
+fn native_token(token_id: &str, amount: u64) -> NativeToken {
+    NativeToken::new(TokenId::from_str(token_id).unwrap(), amount).unwrap()
+}
+
+// A basic output to the address, with the minimum storage deposit if no amount is given.
+fn basic_output(
+    address: &str,
+    amount: Option<u64>,
+    native_tokens: impl IntoIterator<Item = NativeToken>,
+    tag: Option<&[u8]>,
+) -> Output {
+    let mut builder = BasicOutputBuilder::new_with_minimum_storage_deposit(RentStructure::default())
+        .add_unlock_condition(AddressUnlockCondition::new(
+            Bech32Address::try_from_str(address).unwrap(),
+        ))
+        .with_native_tokens(native_tokens);
+    if let Some(tag) = tag {
+        builder = builder.add_feature(TagFeature::new(tag).unwrap());
+    }
+    if let Some(amount) = amount {
+        builder = builder.with_amount(amount);
+    }
+    builder.finish_output(TOKEN_SUPPLY).unwrap()
+}
+
+#[test]
+fn merge_outputs_to_same_recipient() {
+    let outputs = [
+        basic_output(BECH32_ADDRESS_ED25519_0, Some(1_000_000), [], None),
+        basic_output(BECH32_ADDRESS_ED25519_1, Some(1_000_000), [], None),
+        basic_output(BECH32_ADDRESS_ED25519_0, Some(2_000_000), [], None),
+    ];
+    let optimizer = optimizer();
+
+    let suggestions = optimizer.suggest(&outputs).unwrap();
+    assert_eq!(
+        suggestions,
+        [RentSuggestion::MergeOutputs {
+            outputs: vec![0, 2],
+            saved_deposit: outputs[0].rent_cost(&RentStructure::default()),
+        }]
+    );
+
+    // The amounts are kept, the merged output takes the place of the first one.
+    let optimized = optimizer.optimize(outputs.clone()).unwrap();
+    assert_eq!(
+        optimized,
+        [
+            basic_output(BECH32_ADDRESS_ED25519_0, Some(3_000_000), [], None),
+            outputs[1].clone()
+        ]
+    );
+    assert_eq!(
+        optimizer.storage_deposit(&outputs) - optimizer.storage_deposit(&optimized),
+        suggestions[0].saved_deposit()
+    );
+}
+
+#[test]
+fn merge_native_token_carriers() {
+    let outputs = [
+        basic_output(BECH32_ADDRESS_ED25519_0, None, [native_token(TOKEN_ID_1, 10)], None),
+        basic_output(BECH32_ADDRESS_ED25519_0, None, [native_token(TOKEN_ID_1, 20)], None),
+        basic_output(BECH32_ADDRESS_ED25519_0, None, [native_token(TOKEN_ID_2, 30)], None),
+    ];
+
+    // The merged output needs a lower storage deposit, but keeps the amounts of the outputs.
+    let optimizer = optimizer();
+    let optimized = optimizer.optimize(outputs.clone()).unwrap();
+    let amount = outputs.iter().map(Output::amount).sum::<u64>();
+    assert_eq!(
+        optimized,
+        [basic_output(
+            BECH32_ADDRESS_ED25519_0,
+            Some(amount),
+            [native_token(TOKEN_ID_1, 30), native_token(TOKEN_ID_2, 30)],
+            None
+        )]
+    );
+    assert!(optimizer.storage_deposit(&optimized) < optimizer.storage_deposit(&outputs));
+}
+
+#[test]
+fn move_native_tokens() {
+    let outputs = [
+        basic_output(
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            [native_token(TOKEN_ID_1, 10)],
+            Some(b"a"),
+        ),
+        basic_output(
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            [native_token(TOKEN_ID_1, 20)],
+            Some(b"b"),
+        ),
+    ];
+    let optimizer = optimizer();
+
+    let suggestions = optimizer.suggest(&outputs).unwrap();
+    assert!(matches!(
+        &suggestions[0],
+        RentSuggestion::MoveNativeTokens { outputs, saved_deposit } if outputs == &[0, 1] && *saved_deposit > 0
+    ));
+
+    // The output receiving the native tokens is raised to its storage deposit, the other one keeps its amount.
+    let optimized = optimizer.optimize(outputs.clone()).unwrap();
+    assert_eq!(
+        optimized,
+        [
+            basic_output(
+                BECH32_ADDRESS_ED25519_0,
+                None,
+                [native_token(TOKEN_ID_1, 30)],
+                Some(b"a")
+            ),
+            basic_output(BECH32_ADDRESS_ED25519_0, Some(outputs[1].amount()), [], Some(b"b")),
+        ]
+    );
+    assert_eq!(
+        optimizer.storage_deposit(&outputs) - optimizer.storage_deposit(&optimized),
+        suggestions[0].saved_deposit()
+    );
+}
+
+#[test]
+fn drop_features_is_only_suggested() {
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(
+            Bech32Address::try_from_str(BECH32_ADDRESS_ED25519_0).unwrap(),
+        ))
+        .add_feature(MetadataFeature::new([0; 100]).unwrap())
+        .add_feature(TagFeature::new(b"tag").unwrap())
+        .finish_output(TOKEN_SUPPLY)
+        .unwrap();
+    let optimizer = optimizer();
+
+    let suggestions = optimizer.suggest(&[output.clone()]).unwrap();
+    assert_eq!(suggestions.len(), 1);
+    let RentSuggestion::DropFeatures {
+        output: index,
+        features,
+        saved_deposit,
+    } = &suggestions[0]
+    else {
+        panic!("expected a DropFeatures suggestion");
+    };
+    assert_eq!(*index, 0);
+    assert_eq!(features, &[MetadataFeature::KIND, TagFeature::KIND]);
+    assert!(*saved_deposit > 0);
+
+    assert_eq!(optimizer.optimize([output.clone()]).unwrap(), [output]);
+}
+
+#[test]
+fn storage_deposit_return_outputs_are_kept() {
+    let address = Bech32Address::try_from_str(BECH32_ADDRESS_ED25519_0).unwrap();
+    let return_address = Bech32Address::try_from_str(BECH32_ADDRESS_ED25519_1).unwrap();
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(address))
+        .add_unlock_condition(StorageDepositReturnUnlockCondition::new(return_address, 100_000, TOKEN_SUPPLY).unwrap())
+        .finish_output(TOKEN_SUPPLY)
+        .unwrap();
+    let outputs = [output.clone(), output];
+    let optimizer = optimizer();
+
+    assert!(optimizer.suggest(&outputs).unwrap().is_empty());
+    assert_eq!(optimizer.optimize(outputs.clone()).unwrap(), outputs);
+}
+
+#[test]
+fn rent_suggestion_serde() {
+    let suggestion = RentSuggestion::MergeOutputs {
+        outputs: vec![0, 2],
+        saved_deposit: 42_600,
+    };
+    let json = serde_json::to_value(&suggestion).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({ "type": "mergeOutputs", "outputs": [0, 2], "savedDeposit": "42600" })
+    );
+    assert_eq!(serde_json::from_value::<RentSuggestion>(json).unwrap(), suggestion);
+}
+
+#[test]
+fn optimize_never_reduces_amounts() {
+    let outputs = [
+        basic_output(BECH32_ADDRESS_ED25519_0, None, [native_token(TOKEN_ID_1, 10)], None),
+        basic_output(BECH32_ADDRESS_ED25519_0, Some(2_000_000), [], None),
+        basic_output(
+            BECH32_ADDRESS_ED25519_0,
+            None,
+            [native_token(TOKEN_ID_2, 20)],
+            Some(b"tag"),
+        ),
+    ];
+    let optimizer = optimizer();
+
+    let optimized = optimizer.optimize(outputs.clone()).unwrap();
+    assert!(optimized.iter().map(Output::amount).sum::<u64>() >= outputs.iter().map(Output::amount).sum::<u64>());
+}
@@ -0,0 +1,147 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::types::block::{
+    codec::{Error, PackableKind, Packed},
+    output::{unlock_condition::AddressUnlockCondition, BasicOutput, Output},
+    payload::TaggedDataPayload,
+    protocol::protocol_parameters,
+    rand::{address::rand_address, parents::rand_parents},
+    unlock::{ReferenceUnlock, Unlock},
+    BlockBuilder,
+};
+use packable::PackableExt;
+use pretty_assertions::assert_eq;
+
+fn basic_output() -> Output {
+    Output::Basic(
+        BasicOutput::build_with_amount(1_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(rand_address()))
+            .finish_with_params(protocol_parameters().token_supply())
+            .unwrap(),
+    )
+}
+
+#[test]
+fn block_round_trip() {
+    let protocol_parameters = protocol_parameters();
+    let block = BlockBuilder::new(rand_parents())
+        .with_payload(TaggedDataPayload::new(b"codec".to_vec(), b"data".to_vec()).unwrap())
+        .finish()
+        .unwrap();
+
+    let packed = Packed::from_hex(&prefix_hex::encode(block.pack_to_vec()), None, &protocol_parameters).unwrap();
+    assert_eq!(packed.kind(), PackableKind::Block);
+    assert_eq!(packed, Packed::Block(block.clone()));
+
+    let from_json = Packed::from_json(packed.to_json(), None, &protocol_parameters).unwrap();
+    assert_eq!(from_json, Packed::Block(block.clone()));
+    assert_eq!(from_json.pack_to_vec(), block.pack_to_vec());
+}
+
+#[test]
+fn output_round_trip() {
+    let protocol_parameters = protocol_parameters();
+    let output = basic_output();
+    let bytes = output.pack_to_vec();
+
+    let packed = Packed::unpack(&bytes, Some(PackableKind::Output), &protocol_parameters).unwrap();
+    assert_eq!(packed, Packed::Output(output.clone()));
+    // The hex prefix is optional.
+    assert_eq!(
+        Packed::from_hex(&prefix_hex::encode(&bytes)[2..], None, &protocol_parameters).unwrap(),
+        packed
+    );
+    assert_eq!(
+        Packed::from_json(packed.to_json(), Some(PackableKind::Output), &protocol_parameters)
+            .unwrap()
+            .to_hex(),
+        prefix_hex::encode(bytes)
+    );
+}
+
+#[test]
+fn unlock_round_trip() {
+    let protocol_parameters = protocol_parameters();
+    let unlock = Unlock::Reference(ReferenceUnlock::new(3).unwrap());
+
+    let packed = Packed::unpack(&unlock.pack_to_vec(), Some(PackableKind::Unlock), &protocol_parameters).unwrap();
+    assert_eq!(packed, Packed::Unlock(unlock.clone()));
+    assert_eq!(packed.to_json(), serde_json::json!({ "type": 1, "reference": 3 }));
+    assert_eq!(
+        Packed::from_json(packed.to_json(), Some(PackableKind::Unlock), &protocol_parameters).unwrap(),
+        packed
+    );
+}
+
+#[test]
+fn unpack_error_offset() {
+    let protocol_parameters = protocol_parameters();
+    let mut bytes = basic_output().pack_to_vec();
+    // The amount follows the one byte output kind and exceeds the token supply.
+    bytes[1..9].copy_from_slice(&u64::MAX.to_le_bytes());
+
+    let error = Packed::unpack(&bytes, Some(PackableKind::Output), &protocol_parameters).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Unpack {
+            kind: PackableKind::Output,
+            offset: 9,
+            ..
+        }
+    ));
+    assert!(error
+        .to_string()
+        .starts_with("failed to unpack output at byte offset 9: "));
+
+    let bytes = basic_output().pack_to_vec();
+    assert_eq!(
+        Packed::unpack(
+            &bytes[..bytes.len() - 1],
+            Some(PackableKind::Output),
+            &protocol_parameters
+        )
+        .unwrap_err(),
+        Error::Unpack {
+            kind: PackableKind::Output,
+            offset: bytes.len() - 1,
+            message: "unexpected end of bytes".to_string(),
+        }
+    );
+}
+
+#[test]
+fn trailing_bytes() {
+    let protocol_parameters = protocol_parameters();
+    let mut bytes = basic_output().pack_to_vec();
+    let len = bytes.len();
+    bytes.push(0);
+
+    assert_eq!(
+        Packed::unpack(&bytes, Some(PackableKind::Output), &protocol_parameters).unwrap_err(),
+        Error::TrailingBytes {
+            kind: PackableKind::Output,
+            offset: len,
+            remaining: 1,
+        }
+    );
+}
+
+#[test]
+fn invalid_input() {
+    let protocol_parameters = protocol_parameters();
+
+    assert_eq!("Essence".parse::<PackableKind>().unwrap(), PackableKind::Essence);
+    assert_eq!(
+        "milestone".parse::<PackableKind>().unwrap_err(),
+        Error::UnknownKind("milestone".to_string())
+    );
+    assert!(matches!(
+        Packed::from_hex("0xzz", None, &protocol_parameters),
+        Err(Error::Hex(_))
+    ));
+    assert!(matches!(
+        Packed::from_json(serde_json::json!({ "foo": 1 }), None, &protocol_parameters),
+        Err(Error::Json { kind: None, .. })
+    ));
+}
@@ -6,6 +6,7 @@ mod api;
 #[cfg(feature = "pow")]
 mod block;
 mod block_id;
+mod codec;
 mod ed25519_signature;
 mod foundry_id;
 mod input;
@@ -18,6 +19,7 @@ mod output_id;
 mod parents;
 mod payload;
 mod receipt_milestone_option;
+mod render;
 mod rent;
 mod tagged_data_payload;
 mod tail_transaction_hash;
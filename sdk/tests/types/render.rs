@@ -0,0 +1,202 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::types::block::{
+    address::{Address, Ed25519Address, ToBech32Ext},
+    input::{Input, UtxoInput},
+    output::{unlock_condition::AddressUnlockCondition, BasicOutput, Output, Rent},
+    payload::{
+        transaction::{RegularTransactionEssence, TransactionEssence, TransactionId, TransactionPayload},
+        Payload, TaggedDataPayload,
+    },
+    protocol::protocol_parameters,
+    rand::{output::rand_inputs_commitment, parents::rand_parents},
+    render::{RenderFormat, RenderNode, Renderer},
+    signature::{Ed25519Signature, Signature},
+    unlock::{ReferenceUnlock, SignatureUnlock, Unlock, Unlocks},
+    BlockBuilder,
+};
+use pretty_assertions::assert_eq;
+
+const TRANSACTION_ID: &str = "0x52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb0407d1e2c649";
+const ED25519_ADDRESS: &str = "0x52fdfc072182654f163f5f0f9a621d729566c74d10037c4d7bbb0407d1e2c649";
+const ED25519_PUBLIC_KEY: &str = "0x1da5ddd11ba3f961acab68fafee3177d039875eaa94ac5fdbff8b53f0c50bfb9";
+const ED25519_SIGNATURE: &str = "0xc6a40edf9a089f42c18f4ebccb35fe4b578d93b879e99b87f63573324a710d3456b03fb6d1fcc027e6401cbd9581f790ee3ed7a3f68e9c225fcb9f1cd7b7110d";
+
+fn transaction_payload() -> (TransactionPayload, Output) {
+    let protocol_parameters = protocol_parameters();
+    let transaction_id = TransactionId::new(prefix_hex::decode(TRANSACTION_ID).unwrap());
+    let address = Address::from(Ed25519Address::new(prefix_hex::decode(ED25519_ADDRESS).unwrap()));
+    let output = Output::Basic(
+        BasicOutput::build_with_amount(1_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_with_params(protocol_parameters.token_supply())
+            .unwrap(),
+    );
+    let essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(protocol_parameters.network_id(), rand_inputs_commitment())
+            .with_inputs([
+                Input::Utxo(UtxoInput::new(transaction_id, 0).unwrap()),
+                Input::Utxo(UtxoInput::new(transaction_id, 1).unwrap()),
+            ])
+            .add_output(output.clone())
+            .finish_with_params(&protocol_parameters)
+            .unwrap(),
+    );
+    let signature = Ed25519Signature::try_from_bytes(
+        prefix_hex::decode(ED25519_PUBLIC_KEY).unwrap(),
+        prefix_hex::decode(ED25519_SIGNATURE).unwrap(),
+    )
+    .unwrap();
+    let unlocks = Unlocks::new([
+        Unlock::Signature(SignatureUnlock::from(Signature::from(signature))),
+        Unlock::Reference(ReferenceUnlock::new(0).unwrap()),
+    ])
+    .unwrap();
+
+    (TransactionPayload::new(essence, unlocks).unwrap(), output)
+}
+
+#[test]
+fn render_tagged_data_block() {
+    let renderer = Renderer::from(&protocol_parameters());
+    let block = BlockBuilder::new(rand_parents())
+        .with_payload(TaggedDataPayload::new(b"render".to_vec(), vec![0xff, 0x00]).unwrap())
+        .finish()
+        .unwrap();
+
+    let node = renderer.block(&block);
+    assert_eq!(node.value(), Some(block.id().to_string().as_str()));
+    let payload = node.child("Tagged data payload").unwrap();
+    // Printable tags are decoded, binary data is shown as hex.
+    assert_eq!(payload.child("Tag").unwrap().value(), Some("\"render\""));
+    assert_eq!(payload.child("Data").unwrap().value(), Some("0xff00"));
+    assert_eq!(node.child("Parents").unwrap().children().len(), block.parents().len());
+}
+
+#[test]
+fn render_transaction_payload() {
+    let protocol_parameters = protocol_parameters();
+    let renderer = Renderer::from(&protocol_parameters);
+    let (payload, output) = transaction_payload();
+
+    let node = renderer.payload(&Payload::from(payload.clone()));
+    assert_eq!(node.value(), Some(payload.id().to_string().as_str()));
+
+    let output_node = &node.child("Outputs").unwrap().children()[0];
+    assert_eq!(output_node.value(), Some("Basic"));
+    assert_eq!(
+        output_node.child("Storage deposit").unwrap().value(),
+        Some(
+            output
+                .rent_cost(protocol_parameters.rent_structure())
+                .to_string()
+                .as_str()
+        )
+    );
+    let address = output.unlock_conditions().unwrap().address().unwrap().address();
+    assert_eq!(
+        output_node
+            .child("Unlock conditions")
+            .unwrap()
+            .child("Address")
+            .unwrap()
+            .value(),
+        Some(
+            address
+                .to_bech32(*protocol_parameters.bech32_hrp())
+                .to_string()
+                .as_str()
+        )
+    );
+
+    // Each unlock is mapped to the input it unlocks.
+    let unlocks = node.child("Unlocks").unwrap().children();
+    assert_eq!(unlocks[0].value(), Some("Signature"));
+    assert_eq!(
+        unlocks[0].child("Public key").unwrap().value(),
+        Some(ED25519_PUBLIC_KEY)
+    );
+    assert_eq!(unlocks[1].value(), Some("Reference"));
+    assert_eq!(
+        unlocks[1].child("Unlocks input").unwrap().value(),
+        Some(format!("#1 {TRANSACTION_ID}0100").as_str())
+    );
+    assert_eq!(unlocks[1].child("Same signature as").unwrap().value(), Some("unlock 0"));
+}
+
+#[test]
+fn render_formats() {
+    let node = RenderNode::new("Root")
+        .add_child(RenderNode::with_value("Amount", 10))
+        .add_child(RenderNode::new("Children").add_child(RenderNode::with_value("Tag", "\"a\"")));
+
+    assert_eq!(
+        node.render(RenderFormat::Text),
+        "Root\n  Amount: 10\n  Children\n    Tag: \"a\"\n"
+    );
+    assert_eq!(
+        node.render(RenderFormat::Markdown),
+        "- **Root**\n  - **Amount**: `10`\n  - **Children**\n    - **Tag**: `\"a\"`\n"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&node.render(RenderFormat::Json)).unwrap(),
+        serde_json::json!({
+            "label": "Root",
+            "children": [
+                { "label": "Amount", "value": "10" },
+                { "label": "Children", "children": [{ "label": "Tag", "value": "\"a\"" }] }
+            ]
+        })
+    );
+    assert_eq!(node.to_string(), node.render(RenderFormat::Text));
+}
+
+#[cfg(feature = "irc_30")]
+#[test]
+fn render_irc_30_metadata() {
+    use iota_sdk::types::block::{
+        address::AliasAddress,
+        output::{
+            feature::{Irc30Metadata, MetadataFeature},
+            unlock_condition::ImmutableAliasAddressUnlockCondition,
+            AliasId, FoundryOutput, SimpleTokenScheme, TokenScheme,
+        },
+    };
+
+    let protocol_parameters = protocol_parameters();
+    let metadata = Irc30Metadata::new("FooCoin", "FOO", 3);
+    let output = Output::Foundry(
+        FoundryOutput::build_with_amount(
+            1_000_000,
+            1,
+            TokenScheme::Simple(SimpleTokenScheme::new(100, 0, 100).unwrap()),
+        )
+        .add_unlock_condition(ImmutableAliasAddressUnlockCondition::new(AliasAddress::new(
+            AliasId::null(),
+        )))
+        .add_immutable_feature(MetadataFeature::try_from(metadata).unwrap())
+        .finish_with_params(protocol_parameters.token_supply())
+        .unwrap(),
+    );
+
+    let node = Renderer::from(&protocol_parameters).output(&output);
+    let irc_30 = node
+        .child("Immutable features")
+        .unwrap()
+        .child("Metadata")
+        .unwrap()
+        .child("IRC-30 metadata")
+        .unwrap();
+    assert_eq!(irc_30.child("Name").unwrap().value(), Some("FooCoin"));
+    assert_eq!(irc_30.child("Symbol").unwrap().value(), Some("FOO"));
+    assert_eq!(irc_30.child("Decimals").unwrap().value(), Some("3"));
+    assert_eq!(
+        node.child("Token scheme")
+            .unwrap()
+            .child("Maximum supply")
+            .unwrap()
+            .value(),
+        Some("100")
+    );
+}
@@ -0,0 +1,392 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal HTTP/1.1 server of the mock node, serving the routes of the core and indexer API.
+
+use std::{str::FromStr, sync::Arc};
+
+use iota_sdk::types::{
+    api::{
+        core::response::{
+            BaseTokenResponse, ConfirmedMilestoneResponse, InfoResponse, LatestMilestoneResponse, MetricsResponse,
+            OutputWithMetadataResponse, PendingProtocolParameter, RoutesResponse, StatusResponse, SubmitBlockResponse,
+            TipsResponse,
+        },
+        plugins::indexer::OutputIdsResponse,
+    },
+    block::{
+        output::{AliasId, ChainId, FoundryId, NftId, OutputId},
+        payload::{
+            milestone::{MilestoneId, ParametersMilestoneOption},
+            transaction::TransactionId,
+        },
+        Block, BlockDto, BlockId,
+    },
+    TryFromDto,
+};
+use packable::PackableExt;
+use serde::Serialize;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+};
+
+use super::{
+    indexer::{self, OutputKind},
+    ledger::Ledger,
+    Shared,
+};
+
+/// The content type of packed blocks and outputs.
+const SERIALIZER_V1: &str = "application/vnd.iota.serializer-v1";
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    // Whether the request body is packed or packed bytes were requested.
+    raw: bool,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body: serde_json::to_vec(value).unwrap(),
+        }
+    }
+
+    fn ok(value: &impl Serialize) -> Self {
+        Self::json(200, value)
+    }
+
+    fn bytes(body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            content_type: SERIALIZER_V1,
+            body,
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::json(
+            status,
+            &serde_json::json!({ "error": { "code": status.to_string(), "message": message.into() } }),
+        )
+    }
+
+    fn not_found() -> Self {
+        Self::error(404, "not found")
+    }
+}
+
+/// Serves the HTTP connections of a listener.
+pub(crate) async fn serve(listener: TcpListener, shared: Arc<Shared>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let shared = shared.clone();
+        tokio::spawn(async move {
+            handle_connection(stream, &shared).await.ok();
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, shared: &Shared) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    while let Some(request) = read_request(&mut reader).await? {
+        let response = handle_request(shared, &request);
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            response.status,
+            reason_phrase(response.status),
+            response.content_type,
+            response.body.len()
+        );
+        writer.write_all(head.as_bytes()).await?;
+        writer.write_all(&response.body).await?;
+        writer.flush().await?;
+    }
+
+    Ok(())
+}
+
+async fn read_request(reader: &mut BufReader<impl AsyncReadExt + Unpin>) -> std::io::Result<Option<Request>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.trim_matches('/').to_string(),
+        query: query
+            .split('&')
+            .filter(|parameter| !parameter.is_empty())
+            .map(|parameter| {
+                let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+                (percent_decode(key), percent_decode(value))
+            })
+            .collect(),
+        raw: false,
+        body: Vec::new(),
+    };
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        let value = value.trim();
+        match name.to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.parse().unwrap_or_default(),
+            "accept" | "content-type" => request.raw |= value.contains(SERIALIZER_V1),
+            _ => {}
+        }
+    }
+    request.body.resize(content_length, 0);
+    reader.read_exact(&mut request.body).await?;
+
+    Ok(Some(request))
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1..i + 3)) {
+            (b'%', Some(hex)) if std::str::from_utf8(hex).is_ok_and(|hex| u8::from_str_radix(hex, 16).is_ok()) => {
+                // PANIC: checked above.
+                decoded.push(u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap());
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    }
+}
+
+fn handle_request(shared: &Shared, request: &Request) -> Response {
+    let segments = request.path.split('/').collect::<Vec<_>>();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["health"]) => Response::json(200, &()),
+        ("GET", ["api", "routes"]) => Response::ok(&RoutesResponse {
+            routes: vec!["core/v2".to_string(), "indexer/v1".to_string(), "mqtt/v1".to_string()],
+        }),
+        ("POST", ["api", "core", "v2", "blocks"]) => submit_block(shared, request),
+        ("GET", ["api", "core", "v2", ..]) => {
+            let ledger = shared.ledger.lock().unwrap();
+            core_route(&ledger, request, &segments[3..])
+        }
+        ("GET", ["api", "indexer", "v1", "outputs", ..]) => {
+            let ledger = shared.ledger.lock().unwrap();
+            indexer_route(&ledger, request, &segments[4..])
+        }
+        _ => Response::not_found(),
+    }
+}
+
+fn submit_block(shared: &Shared, request: &Request) -> Response {
+    let mut ledger = shared.ledger.lock().unwrap();
+    let block = if request.raw {
+        Block::unpack_strict(&request.body, ledger.protocol_parameters()).map_err(|e| format!("{e:?}"))
+    } else {
+        serde_json::from_slice::<BlockDto>(&request.body)
+            .map_err(|e| e.to_string())
+            .and_then(|dto| {
+                Block::try_from_dto_with_params(dto, ledger.protocol_parameters()).map_err(|e| e.to_string())
+            })
+    };
+
+    match block.and_then(|block| ledger.submit_block(block)) {
+        Ok((block_id, messages)) => {
+            for message in messages {
+                shared.messages.send(message).ok();
+            }
+            Response::json(201, &SubmitBlockResponse { block_id })
+        }
+        Err(message) => Response::error(400, message),
+    }
+}
+
+fn core_route(ledger: &Ledger, request: &Request, segments: &[&str]) -> Response {
+    match segments {
+        ["info"] => Response::ok(&info(ledger)),
+        ["tips"] => Response::ok(&TipsResponse { tips: ledger.tips() }),
+        ["blocks", block_id] => with_id(block_id, |block_id: BlockId| {
+            ledger.block(&block_id).map(|block| block_response(block, request.raw))
+        }),
+        ["blocks", block_id, "metadata"] => with_id(block_id, |block_id: BlockId| {
+            ledger.block_metadata(&block_id).map(Response::ok)
+        }),
+        ["outputs", output_id] => with_id(output_id, |output_id: OutputId| {
+            ledger.output(&output_id).map(|output| {
+                if request.raw {
+                    Response::bytes(output.output().pack_to_vec())
+                } else {
+                    Response::ok(&OutputWithMetadataResponse::from(&output))
+                }
+            })
+        }),
+        ["outputs", output_id, "metadata"] => with_id(output_id, |output_id: OutputId| {
+            ledger.output(&output_id).map(|output| Response::ok(output.metadata()))
+        }),
+        ["transactions", transaction_id, "included-block"] => {
+            with_id(transaction_id, |transaction_id: TransactionId| {
+                ledger
+                    .included_block_id(&transaction_id)
+                    .and_then(|block_id| ledger.block(block_id))
+                    .map(|block| block_response(block, request.raw))
+            })
+        }
+        ["transactions", transaction_id, "included-block", "metadata"] => {
+            with_id(transaction_id, |transaction_id: TransactionId| {
+                ledger
+                    .included_block_id(&transaction_id)
+                    .and_then(|block_id| ledger.block_metadata(block_id))
+                    .map(Response::ok)
+            })
+        }
+        ["milestones", "by-index", index, "utxo-changes"] => with_id(index, |index: u32| {
+            ledger
+                .milestone(index)
+                .map(|milestone| Response::ok(&milestone.utxo_changes()))
+        }),
+        ["milestones", milestone_id, "utxo-changes"] => with_id(milestone_id, |milestone_id: MilestoneId| {
+            ledger
+                .milestone_by_id(&milestone_id)
+                .map(|milestone| Response::ok(&milestone.utxo_changes()))
+        }),
+        _ => Response::not_found(),
+    }
+}
+
+fn indexer_route(ledger: &Ledger, request: &Request, segments: &[&str]) -> Response {
+    let kind = match segments {
+        [] => OutputKind::Any,
+        ["basic"] => OutputKind::Basic,
+        ["alias"] => OutputKind::Alias,
+        ["foundry"] => OutputKind::Foundry,
+        ["nft"] => OutputKind::Nft,
+        ["alias", alias_id] => return chain_route(ledger, alias_id, |id: AliasId| ChainId::Alias(id)),
+        ["foundry", foundry_id] => return chain_route(ledger, foundry_id, |id: FoundryId| ChainId::Foundry(id)),
+        ["nft", nft_id] => return chain_route(ledger, nft_id, |id: NftId| ChainId::Nft(id)),
+        _ => return Response::not_found(),
+    };
+
+    match indexer::output_ids(ledger, kind, &request.query) {
+        Ok(response) => Response::ok(&response),
+        Err(message) => Response::error(400, message),
+    }
+}
+
+fn chain_route<T: FromStr>(ledger: &Ledger, id: &str, chain_id: impl Fn(T) -> ChainId) -> Response {
+    with_id(id, |id: T| {
+        indexer::chain_output_id(ledger, chain_id(id)).map(|output_id| {
+            Response::ok(&OutputIdsResponse {
+                ledger_index: ledger.ledger_index(),
+                cursor: None,
+                items: vec![output_id],
+            })
+        })
+    })
+}
+
+// Parses the id of a route, responding with 400 if it's invalid and 404 if nothing was found.
+fn with_id<T: FromStr>(id: &str, f: impl FnOnce(T) -> Option<Response>) -> Response {
+    match id.parse::<T>() {
+        Ok(id) => f(id).unwrap_or_else(Response::not_found),
+        Err(_) => Response::error(400, format!("invalid id {id}")),
+    }
+}
+
+fn block_response(block: &Block, raw: bool) -> Response {
+    if raw {
+        Response::bytes(block.pack_to_vec())
+    } else {
+        Response::ok(&BlockDto::from(block))
+    }
+}
+
+fn info(ledger: &Ledger) -> InfoResponse {
+    let milestone = ledger.latest_milestone();
+    let protocol_parameters = ledger.protocol_parameters();
+
+    InfoResponse {
+        name: "mock-node".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        status: StatusResponse {
+            is_healthy: true,
+            latest_milestone: LatestMilestoneResponse {
+                index: milestone.index,
+                timestamp: Some(milestone.timestamp),
+                milestone_id: Some(milestone.milestone_id),
+            },
+            confirmed_milestone: ConfirmedMilestoneResponse {
+                index: milestone.index,
+                timestamp: Some(milestone.timestamp),
+                milestone_id: Some(milestone.milestone_id),
+            },
+            pruning_index: 0,
+        },
+        supported_protocol_versions: vec![protocol_parameters.protocol_version()],
+        protocol: protocol_parameters.clone(),
+        pending_protocol_parameters: ledger
+            .protocol_parameters_upgrades()
+            .iter()
+            .map(
+                |(target_milestone_index, protocol_parameters)| PendingProtocolParameter {
+                    kind: ParametersMilestoneOption::KIND,
+                    target_milestone_index: *target_milestone_index,
+                    protocol_version: protocol_parameters.protocol_version(),
+                    params: prefix_hex::encode(protocol_parameters.pack_to_vec()),
+                },
+            )
+            .collect(),
+        base_token: BaseTokenResponse {
+            name: "Shimmer".to_string(),
+            ticker_symbol: "SMR".to_string(),
+            unit: "SMR".to_string(),
+            subunit: Some("glow".to_string()),
+            decimals: 6,
+            use_metric_prefix: false,
+        },
+        metrics: MetricsResponse {
+            blocks_per_second: 0.0,
+            referenced_blocks_per_second: 0.0,
+            referenced_rate: 0.0,
+        },
+        features: vec!["pow".to_string()],
+    }
+}
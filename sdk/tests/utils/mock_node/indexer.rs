@@ -0,0 +1,240 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Indexer queries of the mock node.
+
+use std::str::FromStr;
+
+use iota_sdk::types::{
+    api::plugins::indexer::OutputIdsResponse,
+    block::{
+        address::{Address, Bech32Address},
+        output::{ChainId, Output, OutputId},
+    },
+};
+
+use super::ledger::Ledger;
+
+/// The amount of output ids returned per page if the query has no page size.
+const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// The output kinds of the indexer routes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum OutputKind {
+    Any,
+    Basic,
+    Alias,
+    Foundry,
+    Nft,
+}
+
+impl OutputKind {
+    fn matches(&self, output: &Output) -> bool {
+        matches!(
+            (self, output),
+            (
+                Self::Any,
+                Output::Basic(_) | Output::Alias(_) | Output::Foundry(_) | Output::Nft(_)
+            ) | (Self::Basic, Output::Basic(_))
+                | (Self::Alias, Output::Alias(_))
+                | (Self::Foundry, Output::Foundry(_))
+                | (Self::Nft, Output::Nft(_))
+        )
+    }
+}
+
+/// Returns a page of the ids of the unspent outputs matching the query, ordered by the milestone which booked them.
+pub(crate) fn output_ids(
+    ledger: &Ledger,
+    kind: OutputKind,
+    query: &[(String, String)],
+) -> Result<OutputIdsResponse, String> {
+    let mut page_size = DEFAULT_PAGE_SIZE;
+    let mut start = None;
+    for (key, value) in query {
+        match key.as_str() {
+            "pageSize" => page_size = parse(value)?,
+            // The cursor is the position of the first output of the page and the page size, an empty one requests
+            // the first page.
+            "cursor" if value.is_empty() => {}
+            "cursor" => {
+                let mut parts = value.split('.');
+                match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                    (Some(milestone_index), Some(output_id), Some(cursor_page_size), None) => {
+                        start = Some((parse::<u32>(milestone_index)?, parse::<OutputId>(output_id)?));
+                        page_size = parse(cursor_page_size)?;
+                    }
+                    _ => return Err(format!("invalid cursor {value}")),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut outputs = Vec::new();
+    for (output_id, output, milestone_index_booked, milestone_timestamp_booked) in ledger.unspent_outputs() {
+        if !kind.matches(output) {
+            continue;
+        }
+        let mut matches = true;
+        for (key, value) in query {
+            matches &= matches_parameter(output, milestone_timestamp_booked, key, value)?;
+        }
+        if matches {
+            outputs.push((milestone_index_booked, *output_id));
+        }
+    }
+    outputs.sort();
+
+    let mut outputs = outputs
+        .into_iter()
+        .skip_while(|position| start.is_some_and(|start| position < &start))
+        .peekable();
+    let items = outputs
+        .by_ref()
+        .take(page_size.max(1))
+        .map(|(_, output_id)| output_id)
+        .collect();
+
+    Ok(OutputIdsResponse {
+        ledger_index: ledger.ledger_index(),
+        cursor: outputs
+            .peek()
+            .map(|(milestone_index, output_id)| format!("{milestone_index}.{output_id}.{page_size}")),
+        items,
+    })
+}
+
+/// Returns the id of the unspent output of a chain.
+pub(crate) fn chain_output_id(ledger: &Ledger, chain_id: ChainId) -> Option<OutputId> {
+    ledger
+        .unspent_outputs()
+        .find(|(output_id, output, _, _)| {
+            output
+                .chain_id()
+                .is_some_and(|id| id.or_from_output_id(output_id) == chain_id)
+        })
+        .map(|(output_id, _, _, _)| *output_id)
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid query parameter value {value}"))
+}
+
+fn address(value: &str) -> Result<Address, String> {
+    Bech32Address::try_from_str(value)
+        .map(Bech32Address::into_inner)
+        .map_err(|_| format!("invalid address {value}"))
+}
+
+fn matches_parameter(output: &Output, milestone_timestamp_booked: u32, key: &str, value: &str) -> Result<bool, String> {
+    let unlock_conditions = output.unlock_conditions();
+    let features = output.features();
+    let native_token_count = output.native_tokens().map_or(0, |native_tokens| native_tokens.len());
+
+    Ok(match key {
+        "address" => {
+            let address = address(value)?;
+            unlock_conditions
+                .and_then(|u| u.address())
+                .is_some_and(|u| u.address() == &address)
+        }
+        "aliasAddress" => {
+            let address = address(value)?;
+            matches!(output, Output::Foundry(foundry) if Address::from(*foundry.alias_address()) == address)
+        }
+        "createdAfter" => milestone_timestamp_booked > parse(value)?,
+        "createdBefore" => milestone_timestamp_booked < parse(value)?,
+        "expirationReturnAddress" => {
+            let address = address(value)?;
+            unlock_conditions
+                .and_then(|u| u.expiration())
+                .is_some_and(|u| u.return_address() == &address)
+        }
+        "expiresAfter" => {
+            let timestamp = parse::<u32>(value)?;
+            unlock_conditions
+                .and_then(|u| u.expiration())
+                .is_some_and(|u| u.timestamp() > timestamp)
+        }
+        "expiresBefore" => {
+            let timestamp = parse::<u32>(value)?;
+            unlock_conditions
+                .and_then(|u| u.expiration())
+                .is_some_and(|u| u.timestamp() < timestamp)
+        }
+        "governor" => {
+            let address = address(value)?;
+            unlock_conditions
+                .and_then(|u| u.governor_address())
+                .is_some_and(|u| u.address() == &address)
+        }
+        "hasExpiration" => unlock_conditions.and_then(|u| u.expiration()).is_some() == parse::<bool>(value)?,
+        "hasNativeTokens" => (native_token_count > 0) == parse::<bool>(value)?,
+        "hasStorageDepositReturn" => {
+            unlock_conditions.and_then(|u| u.storage_deposit_return()).is_some() == parse::<bool>(value)?
+        }
+        "hasTimelock" => unlock_conditions.and_then(|u| u.timelock()).is_some() == parse::<bool>(value)?,
+        "issuer" => {
+            let address = address(value)?;
+            output
+                .immutable_features()
+                .and_then(|f| f.issuer())
+                .is_some_and(|f| f.address() == &address)
+        }
+        "maxNativeTokenCount" => native_token_count <= parse(value)?,
+        "minNativeTokenCount" => native_token_count >= parse(value)?,
+        "sender" => {
+            let address = address(value)?;
+            features
+                .and_then(|f| f.sender())
+                .is_some_and(|f| f.address() == &address)
+        }
+        "stateController" => {
+            let address = address(value)?;
+            unlock_conditions
+                .and_then(|u| u.state_controller_address())
+                .is_some_and(|u| u.address() == &address)
+        }
+        "storageDepositReturnAddress" => {
+            let address = address(value)?;
+            unlock_conditions
+                .and_then(|u| u.storage_deposit_return())
+                .is_some_and(|u| u.return_address() == &address)
+        }
+        "tag" => features
+            .and_then(|f| f.tag())
+            .is_some_and(|f| prefix_hex::encode(f.tag()).eq_ignore_ascii_case(value)),
+        "timelockedAfter" => {
+            let timestamp = parse::<u32>(value)?;
+            unlock_conditions
+                .and_then(|u| u.timelock())
+                .is_some_and(|u| u.timestamp() > timestamp)
+        }
+        "timelockedBefore" => {
+            let timestamp = parse::<u32>(value)?;
+            unlock_conditions
+                .and_then(|u| u.timelock())
+                .is_some_and(|u| u.timestamp() < timestamp)
+        }
+        "unlockableByAddress" => {
+            let address = address(value)?;
+            unlock_conditions.is_some_and(|u| {
+                [
+                    u.address().map(|u| u.address()),
+                    u.state_controller_address().map(|u| u.address()),
+                    u.governor_address().map(|u| u.address()),
+                    u.immutable_alias_address().map(|u| u.address()),
+                    u.expiration().map(|u| u.return_address()),
+                ]
+                .into_iter()
+                .flatten()
+                .any(|unlock_address| unlock_address == &address)
+            })
+        }
+        "cursor" | "pageSize" => true,
+        _ => return Err(format!("unknown query parameter {key}")),
+    })
+}
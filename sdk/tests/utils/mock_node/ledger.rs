@@ -0,0 +1,486 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-memory ledger of the mock node, which applies the transactions of the submitted blocks when a milestone is
+//! produced.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crypto::hashes::{blake2b::Blake2b256, Digest};
+use iota_sdk::types::{
+    api::core::response::{
+        BlockMetadataResponse, LedgerInclusionState, OutputWithMetadataResponse, UtxoChangesResponse,
+    },
+    block::{
+        address::ToBech32Ext,
+        input::Input,
+        output::{Output, OutputId, OutputMetadata, OutputWithMetadata},
+        parent::Parents,
+        payload::{
+            milestone::MilestoneId,
+            transaction::{TransactionEssence, TransactionId},
+            Payload, TransactionPayload,
+        },
+        protocol::ProtocolParameters,
+        semantic::{semantic_validation, ConflictReason, ValidationContext},
+        Block, BlockBuilder, BlockId,
+    },
+};
+use packable::PackableExt;
+
+/// A message published on a MQTT topic.
+pub(crate) type Message = (String, Vec<u8>);
+
+/// The maximum amount of tips returned to the clients.
+const MAX_TIPS: usize = 8;
+
+/// A milestone of the mock node with the UTXO changes it applied.
+#[derive(Debug, Clone)]
+pub(crate) struct Milestone {
+    pub(crate) index: u32,
+    pub(crate) timestamp: u32,
+    pub(crate) milestone_id: MilestoneId,
+    pub(crate) created_outputs: Vec<OutputId>,
+    pub(crate) consumed_outputs: Vec<OutputId>,
+}
+
+impl Milestone {
+    pub(crate) fn utxo_changes(&self) -> UtxoChangesResponse {
+        UtxoChangesResponse {
+            index: self.index,
+            created_outputs: self.created_outputs.clone(),
+            consumed_outputs: self.consumed_outputs.clone(),
+        }
+    }
+
+    fn info_message(&self, topic: &str) -> Message {
+        let milestone_info = serde_json::json!({
+            "index": self.index,
+            "timestamp": self.timestamp,
+            "milestoneId": self.milestone_id,
+        });
+
+        (topic.to_string(), milestone_info.to_string().into_bytes())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StoredOutput {
+    output: Output,
+    block_id: BlockId,
+    milestone_index_booked: u32,
+    milestone_timestamp_booked: u32,
+    // The milestone index and timestamp and the transaction which spent the output.
+    spent: Option<(u32, u32, TransactionId)>,
+}
+
+#[derive(Debug, Clone)]
+struct StoredBlock {
+    block: Block,
+    metadata: BlockMetadataResponse,
+}
+
+/// The ledger state of the mock node.
+#[derive(Debug)]
+pub(crate) struct Ledger {
+    protocol_parameters: ProtocolParameters,
+    // Protocol parameters which become active at a milestone index, ordered by the index.
+    protocol_parameters_upgrades: Vec<(u32, ProtocolParameters)>,
+    milestones: Vec<Milestone>,
+    outputs: BTreeMap<OutputId, StoredOutput>,
+    blocks: HashMap<BlockId, StoredBlock>,
+    included_blocks: HashMap<TransactionId, BlockId>,
+    // Blocks which are not referenced by a milestone yet, in the order they were submitted.
+    pending_blocks: Vec<BlockId>,
+    tips: Vec<BlockId>,
+    genesis_transaction_count: u32,
+}
+
+impl Ledger {
+    /// Creates a new ledger with a first milestone at the given timestamp.
+    pub(crate) fn new(protocol_parameters: ProtocolParameters, timestamp: u32) -> Self {
+        let mut ledger = Self {
+            protocol_parameters,
+            protocol_parameters_upgrades: Vec::new(),
+            milestones: Vec::new(),
+            outputs: BTreeMap::new(),
+            blocks: HashMap::new(),
+            included_blocks: HashMap::new(),
+            pending_blocks: Vec::new(),
+            tips: Vec::new(),
+            genesis_transaction_count: 0,
+        };
+        ledger.produce_milestone(timestamp);
+        ledger
+    }
+
+    pub(crate) fn protocol_parameters(&self) -> &ProtocolParameters {
+        &self.protocol_parameters
+    }
+
+    pub(crate) fn protocol_parameters_upgrades(&self) -> &[(u32, ProtocolParameters)] {
+        &self.protocol_parameters_upgrades
+    }
+
+    /// Announces protocol parameters which become active when the milestone with the target index is produced.
+    pub(crate) fn schedule_protocol_parameters(
+        &mut self,
+        target_milestone_index: u32,
+        protocol_parameters: ProtocolParameters,
+    ) {
+        self.protocol_parameters_upgrades
+            .push((target_milestone_index, protocol_parameters));
+        self.protocol_parameters_upgrades.sort_by_key(|(index, _)| *index);
+    }
+
+    pub(crate) fn latest_milestone(&self) -> &Milestone {
+        // PANIC: the ledger is created with a first milestone.
+        self.milestones.last().unwrap()
+    }
+
+    pub(crate) fn milestone(&self, index: u32) -> Option<&Milestone> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.milestones.get(position as usize))
+    }
+
+    pub(crate) fn milestone_by_id(&self, milestone_id: &MilestoneId) -> Option<&Milestone> {
+        self.milestones
+            .iter()
+            .find(|milestone| &milestone.milestone_id == milestone_id)
+    }
+
+    pub(crate) fn ledger_index(&self) -> u32 {
+        self.latest_milestone().index
+    }
+
+    pub(crate) fn tips(&self) -> Vec<BlockId> {
+        self.tips.clone()
+    }
+
+    /// Books an output at the latest milestone, created by a deterministic genesis transaction.
+    pub(crate) fn add_output(&mut self, output: Output) -> OutputId {
+        self.genesis_transaction_count += 1;
+        let transaction_id =
+            TransactionId::new(Blake2b256::digest(self.genesis_transaction_count.to_le_bytes()).into());
+        // PANIC: 0 is a valid output index.
+        let output_id = OutputId::new(transaction_id, 0).unwrap();
+        let milestone = self.latest_milestone().clone();
+
+        self.outputs.insert(
+            output_id,
+            StoredOutput {
+                output,
+                block_id: BlockId::null(),
+                milestone_index_booked: milestone.index,
+                milestone_timestamp_booked: milestone.timestamp,
+                spent: None,
+            },
+        );
+        // PANIC: the ledger is created with a first milestone.
+        self.milestones.last_mut().unwrap().created_outputs.push(output_id);
+
+        output_id
+    }
+
+    pub(crate) fn output(&self, output_id: &OutputId) -> Option<OutputWithMetadata> {
+        self.outputs.get(output_id).map(|stored| {
+            OutputWithMetadata::new(
+                stored.output.clone(),
+                OutputMetadata::new(
+                    stored.block_id,
+                    *output_id,
+                    stored.spent.is_some(),
+                    stored.spent.map(|(index, _, _)| index),
+                    stored.spent.map(|(_, timestamp, _)| timestamp),
+                    stored.spent.map(|(_, _, transaction_id)| transaction_id),
+                    stored.milestone_index_booked,
+                    stored.milestone_timestamp_booked,
+                    self.ledger_index(),
+                ),
+            )
+        })
+    }
+
+    /// Returns the unspent outputs with the index and timestamp of the milestone which booked them.
+    pub(crate) fn unspent_outputs(&self) -> impl Iterator<Item = (&OutputId, &Output, u32, u32)> {
+        self.outputs
+            .iter()
+            .filter(|(_, stored)| stored.spent.is_none())
+            .map(|(output_id, stored)| {
+                (
+                    output_id,
+                    &stored.output,
+                    stored.milestone_index_booked,
+                    stored.milestone_timestamp_booked,
+                )
+            })
+    }
+
+    pub(crate) fn block(&self, block_id: &BlockId) -> Option<&Block> {
+        self.blocks.get(block_id).map(|stored| &stored.block)
+    }
+
+    pub(crate) fn block_metadata(&self, block_id: &BlockId) -> Option<&BlockMetadataResponse> {
+        self.blocks.get(block_id).map(|stored| &stored.metadata)
+    }
+
+    pub(crate) fn included_block_id(&self, transaction_id: &TransactionId) -> Option<&BlockId> {
+        self.included_blocks.get(transaction_id)
+    }
+
+    /// Stores a block until the next milestone references it. Returns an error message if the block isn't valid for
+    /// the network.
+    pub(crate) fn submit_block(&mut self, block: Block) -> Result<(BlockId, Vec<Message>), String> {
+        let block_id = block.id();
+
+        if self.blocks.contains_key(&block_id) {
+            return Ok((block_id, Vec::new()));
+        }
+        if block.protocol_version() != self.protocol_parameters.protocol_version() {
+            return Err(format!("invalid protocol version {}", block.protocol_version()));
+        }
+        let mut topics = vec!["blocks".to_string()];
+        match block.payload() {
+            Some(Payload::Transaction(transaction_payload)) => {
+                let TransactionEssence::Regular(essence) = transaction_payload.essence();
+                if essence.network_id() != self.protocol_parameters.network_id() {
+                    return Err(format!("invalid network id {}", essence.network_id()));
+                }
+                topics.push("blocks/transaction".to_string());
+            }
+            Some(Payload::TaggedData(_)) => topics.push("blocks/tagged-data".to_string()),
+            Some(_) => return Err("only transaction and tagged data payloads can be submitted".to_string()),
+            None => {}
+        }
+
+        let parents = block.parents().to_vec();
+        self.tips.retain(|tip| !parents.contains(tip));
+        self.tips.push(block_id);
+        if self.tips.len() > MAX_TIPS {
+            self.tips.remove(0);
+        }
+        self.pending_blocks.push(block_id);
+
+        let bytes = block.pack_to_vec();
+        self.blocks.insert(
+            block_id,
+            StoredBlock {
+                block,
+                metadata: BlockMetadataResponse {
+                    block_id,
+                    parents,
+                    is_solid: true,
+                    referenced_by_milestone_index: None,
+                    milestone_index: None,
+                    ledger_inclusion_state: None,
+                    conflict_reason: None,
+                    white_flag_index: None,
+                    should_promote: Some(false),
+                    should_reattach: Some(false),
+                },
+            },
+        );
+
+        Ok((
+            block_id,
+            topics.into_iter().map(|topic| (topic, bytes.clone())).collect(),
+        ))
+    }
+
+    /// Produces a milestone which references all pending blocks and applies their transactions in the order they were
+    /// submitted. Returns the messages for the MQTT topics.
+    pub(crate) fn produce_milestone(&mut self, timestamp: u32) -> Vec<Message> {
+        let index = self.milestones.len() as u32 + 1;
+        let reached = self
+            .protocol_parameters_upgrades
+            .partition_point(|(target_milestone_index, _)| *target_milestone_index <= index);
+        if let Some((_, protocol_parameters)) = self.protocol_parameters_upgrades.drain(..reached).next_back() {
+            self.protocol_parameters = protocol_parameters;
+        }
+        let mut messages = Vec::new();
+        let mut created_outputs = Vec::new();
+        let mut consumed_outputs = Vec::new();
+
+        for (white_flag_index, block_id) in std::mem::take(&mut self.pending_blocks).into_iter().enumerate() {
+            let block = self.blocks[&block_id].block.clone();
+            let (ledger_inclusion_state, conflict_reason) = match block.payload() {
+                Some(Payload::Transaction(transaction_payload)) => {
+                    match self.apply_transaction(block_id, transaction_payload, index, timestamp) {
+                        Ok((created, consumed)) => {
+                            self.included_blocks.insert(transaction_payload.id(), block_id);
+                            messages.push((
+                                format!("transactions/{}/included-block", transaction_payload.id()),
+                                block.pack_to_vec(),
+                            ));
+                            created_outputs.extend(created);
+                            consumed_outputs.extend(consumed);
+                            (LedgerInclusionState::Included, None)
+                        }
+                        Err(conflict_reason) => (LedgerInclusionState::Conflicting, Some(conflict_reason as u8)),
+                    }
+                }
+                _ => (LedgerInclusionState::NoTransaction, None),
+            };
+
+            // PANIC: the block was stored when it was submitted.
+            let metadata = &mut self.blocks.get_mut(&block_id).unwrap().metadata;
+            metadata.referenced_by_milestone_index = Some(index);
+            metadata.ledger_inclusion_state = Some(ledger_inclusion_state);
+            metadata.conflict_reason = conflict_reason;
+            metadata.white_flag_index = Some(white_flag_index as u32);
+            metadata.should_promote = None;
+            metadata.should_reattach = None;
+            let metadata = serde_json::to_vec(metadata).unwrap();
+            messages.push((format!("block-metadata/{block_id}"), metadata.clone()));
+            messages.push(("block-metadata/referenced".to_string(), metadata));
+        }
+
+        // The milestone block references all tips, so the next blocks only need to reference it.
+        let parents = if self.tips.is_empty() {
+            vec![BlockId::null()]
+        } else {
+            std::mem::take(&mut self.tips)
+        };
+        // PANIC: the tips are at most `MAX_TIPS` different block ids.
+        let milestone_block = BlockBuilder::new(Parents::from_vec(parents.clone()).unwrap())
+            .with_protocol_version(self.protocol_parameters.protocol_version())
+            .finish()
+            .unwrap();
+        let milestone_block_id = milestone_block.id();
+        self.blocks.insert(
+            milestone_block_id,
+            StoredBlock {
+                block: milestone_block,
+                metadata: BlockMetadataResponse {
+                    block_id: milestone_block_id,
+                    parents,
+                    is_solid: true,
+                    referenced_by_milestone_index: Some(index),
+                    milestone_index: Some(index),
+                    ledger_inclusion_state: Some(LedgerInclusionState::NoTransaction),
+                    conflict_reason: None,
+                    white_flag_index: None,
+                    should_promote: None,
+                    should_reattach: None,
+                },
+            },
+        );
+        self.tips.push(milestone_block_id);
+
+        let milestone = Milestone {
+            index,
+            timestamp,
+            milestone_id: MilestoneId::new(*milestone_block_id),
+            created_outputs,
+            consumed_outputs,
+        };
+        self.milestones.push(milestone.clone());
+
+        for output_id in milestone.created_outputs.iter().chain(&milestone.consumed_outputs) {
+            messages.extend(self.output_messages(output_id));
+        }
+        messages.push(milestone.info_message("milestone-info/latest"));
+        messages.push(milestone.info_message("milestone-info/confirmed"));
+
+        messages
+    }
+
+    // Validates a transaction against the ledger and books its outputs. Returns the created and consumed outputs.
+    fn apply_transaction(
+        &mut self,
+        block_id: BlockId,
+        transaction_payload: &TransactionPayload,
+        index: u32,
+        timestamp: u32,
+    ) -> Result<(Vec<OutputId>, Vec<OutputId>), ConflictReason> {
+        let transaction_id = transaction_payload.id();
+        let TransactionEssence::Regular(essence) = transaction_payload.essence();
+
+        let mut inputs = Vec::new();
+        for input in essence.inputs() {
+            let Input::Utxo(input) = input else {
+                return Err(ConflictReason::SemanticValidationFailed);
+            };
+            let stored = self
+                .outputs
+                .get(input.output_id())
+                .ok_or(ConflictReason::InputUtxoNotFound)?;
+            match stored.spent {
+                Some((spent_index, _, _)) if spent_index == index => {
+                    return Err(ConflictReason::InputUtxoAlreadySpentInThisMilestone);
+                }
+                Some(_) => return Err(ConflictReason::InputUtxoAlreadySpent),
+                None => inputs.push((*input.output_id(), stored.output.clone())),
+            }
+        }
+
+        let inputs = inputs
+            .iter()
+            .map(|(output_id, output)| (output_id, output))
+            .collect::<Vec<_>>();
+        let context = ValidationContext::new(
+            &transaction_id,
+            essence,
+            inputs.iter().copied(),
+            transaction_payload.unlocks(),
+            timestamp,
+        );
+        match semantic_validation(context, &inputs, transaction_payload.unlocks()) {
+            Ok(ConflictReason::None) => {}
+            Ok(conflict_reason) => return Err(conflict_reason),
+            Err(_) => return Err(ConflictReason::SemanticValidationFailed),
+        }
+
+        let consumed_outputs = inputs.iter().map(|(output_id, _)| **output_id).collect::<Vec<_>>();
+        for output_id in &consumed_outputs {
+            // PANIC: the inputs were found above.
+            self.outputs.get_mut(output_id).unwrap().spent = Some((index, timestamp, transaction_id));
+        }
+        let created_outputs = essence
+            .outputs()
+            .iter()
+            .enumerate()
+            .map(|(output_index, output)| {
+                // PANIC: the amount of outputs of a transaction is bounded by the valid output indexes.
+                let output_id = OutputId::new(transaction_id, output_index as u16).unwrap();
+                self.outputs.insert(
+                    output_id,
+                    StoredOutput {
+                        output: output.clone(),
+                        block_id,
+                        milestone_index_booked: index,
+                        milestone_timestamp_booked: timestamp,
+                        spent: None,
+                    },
+                );
+                output_id
+            })
+            .collect();
+
+        Ok((created_outputs, consumed_outputs))
+    }
+
+    // The messages of the output topics for a created or spent output.
+    fn output_messages(&self, output_id: &OutputId) -> Vec<Message> {
+        let Some(output) = self.output(output_id) else {
+            return Vec::new();
+        };
+        let payload = serde_json::to_vec(&OutputWithMetadataResponse::from(&output)).unwrap();
+        let mut messages = vec![(format!("outputs/{output_id}"), payload.clone())];
+
+        if let Some(address_unlock_condition) = output.output().unlock_conditions().and_then(|u| u.address()) {
+            let bech32_address = address_unlock_condition
+                .address()
+                .to_bech32(*self.protocol_parameters.bech32_hrp());
+            let topic = if output.metadata().is_spent() {
+                format!("outputs/unlock/address/{bech32_address}/spent")
+            } else {
+                format!("outputs/unlock/address/{bech32_address}")
+            };
+            messages.push((topic, payload));
+        }
+
+        messages
+    }
+}
@@ -0,0 +1,206 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! In-process mock node, serving the core and indexer API and the MQTT topics of a node from an in-memory ledger, so
+//! the client and the wallet can be tested without a network.
+//!
+//! Submitted blocks are only referenced by the next milestone, which is produced on demand or in an interval. The
+//! transactions are then applied in the order they were submitted and semantically validated with the timestamp of the
+//! milestone. Output and milestone ids are deterministic, milestone timestamps follow the local time, as the client
+//! rejects nodes whose time isn't synced.
+
+mod http;
+mod indexer;
+mod ledger;
+mod mqtt;
+
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use iota_sdk::types::block::{
+    address::Address,
+    output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output, OutputId, RentStructure},
+    protocol::ProtocolParameters,
+};
+use tokio::{net::TcpListener, runtime::Runtime, sync::broadcast};
+
+use self::ledger::{Ledger, Message};
+
+/// The state shared by the servers of the mock node.
+struct Shared {
+    ledger: Mutex<Ledger>,
+    messages: broadcast::Sender<Message>,
+}
+
+impl Shared {
+    fn produce_milestone(&self) -> u32 {
+        let mut ledger = self.ledger.lock().unwrap();
+        let timestamp = unix_timestamp_now().max(ledger.latest_milestone().timestamp + 1);
+        for message in ledger.produce_milestone(timestamp) {
+            self.messages.send(message).ok();
+        }
+        ledger.ledger_index()
+    }
+}
+
+/// Builder of a [`MockNode`].
+pub(crate) struct MockNodeBuilder {
+    protocol_parameters: ProtocolParameters,
+    milestone_interval: Option<Duration>,
+}
+
+impl MockNodeBuilder {
+    /// Produces milestones in an interval, additionally to the ones produced on demand.
+    pub(crate) fn with_milestone_interval(mut self, milestone_interval: Duration) -> Self {
+        self.milestone_interval.replace(milestone_interval);
+        self
+    }
+
+    /// Starts the servers of the mock node on random local ports.
+    pub(crate) fn finish(self) -> MockNode {
+        let (messages, _) = broadcast::channel(1024);
+        let shared = Arc::new(Shared {
+            ledger: Mutex::new(Ledger::new(self.protocol_parameters, unix_timestamp_now())),
+            messages,
+        });
+        let http_listener = bind();
+        let mqtt_listener = bind();
+        let http_address = http_listener.local_addr().unwrap();
+        let mqtt_address = mqtt_listener.local_addr().unwrap();
+
+        // The node runs on its own runtime, so it keeps serving while a test blocks its runtime.
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let http_shared = shared.clone();
+        runtime.spawn(async move {
+            http::serve(TcpListener::from_std(http_listener).unwrap(), http_shared).await;
+        });
+        let messages = shared.messages.clone();
+        runtime.spawn(async move {
+            mqtt::serve(TcpListener::from_std(mqtt_listener).unwrap(), messages).await;
+        });
+        if let Some(milestone_interval) = self.milestone_interval {
+            let shared = shared.clone();
+            runtime.spawn(async move {
+                loop {
+                    tokio::time::sleep(milestone_interval).await;
+                    shared.produce_milestone();
+                }
+            });
+        }
+
+        MockNode {
+            shared,
+            http_address,
+            mqtt_address,
+            runtime: Some(runtime),
+        }
+    }
+}
+
+/// A mock node with an in-memory ledger, which stops when it's dropped.
+pub(crate) struct MockNode {
+    shared: Arc<Shared>,
+    http_address: SocketAddr,
+    mqtt_address: SocketAddr,
+    runtime: Option<Runtime>,
+}
+
+impl MockNode {
+    /// Creates a builder of a mock node.
+    pub(crate) fn builder() -> MockNodeBuilder {
+        MockNodeBuilder {
+            // PANIC: the parameters are valid.
+            protocol_parameters: ProtocolParameters::new(
+                ProtocolParameters::default().protocol_version(),
+                "mock".to_string(),
+                "rms",
+                0,
+                15,
+                RentStructure::default(),
+                1_813_620_509_061_365,
+            )
+            .unwrap(),
+            milestone_interval: None,
+        }
+    }
+
+    /// Starts a mock node with the default protocol parameters.
+    pub(crate) fn start() -> Self {
+        Self::builder().finish()
+    }
+
+    /// Returns the URL of the node API.
+    pub(crate) fn url(&self) -> String {
+        format!("http://{}", self.http_address)
+    }
+
+    /// Returns the port of the MQTT broker, which only supports plain TCP connections.
+    pub(crate) fn mqtt_port(&self) -> u16 {
+        self.mqtt_address.port()
+    }
+
+    /// Returns the protocol parameters of the network.
+    pub(crate) fn protocol_parameters(&self) -> ProtocolParameters {
+        self.shared.ledger.lock().unwrap().protocol_parameters().clone()
+    }
+
+    /// Announces protocol parameters which become active when the milestone with the target index is produced.
+    pub(crate) fn schedule_protocol_parameters(
+        &self,
+        target_milestone_index: u32,
+        protocol_parameters: ProtocolParameters,
+    ) {
+        self.shared
+            .ledger
+            .lock()
+            .unwrap()
+            .schedule_protocol_parameters(target_milestone_index, protocol_parameters);
+    }
+
+    /// Adds an unspent output to the ledger, e.g. to fund an address.
+    pub(crate) fn add_output(&self, output: Output) -> OutputId {
+        self.shared.ledger.lock().unwrap().add_output(output)
+    }
+
+    /// Adds a basic output with an amount to the ledger, which can be unlocked by an address.
+    pub(crate) fn fund(&self, address: impl Into<Address>, amount: u64) -> OutputId {
+        let output = BasicOutputBuilder::new_with_amount(amount)
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(self.protocol_parameters().token_supply())
+            .unwrap();
+
+        self.add_output(output)
+    }
+
+    /// Produces a milestone which references and applies all blocks submitted since the previous one. Returns the
+    /// index of the milestone.
+    pub(crate) fn produce_milestone(&self) -> u32 {
+        self.shared.produce_milestone()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        // Dropping a runtime blocks, which isn't allowed in the asynchronous tests.
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+fn bind() -> std::net::TcpListener {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_nonblocking(true).unwrap();
+    listener
+}
+
+fn unix_timestamp_now() -> u32 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as u32
+}
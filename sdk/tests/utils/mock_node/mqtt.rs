@@ -0,0 +1,165 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Minimal MQTT 3.1.1 broker of the mock node, which publishes the messages of the node with QoS 0 to the subscribed
+//! clients.
+
+use std::sync::{Arc, Mutex};
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, mpsc},
+};
+
+use super::ledger::Message;
+
+const CONNECT: u8 = 1;
+const PUBLISH: u8 = 3;
+const SUBSCRIBE: u8 = 8;
+const UNSUBSCRIBE: u8 = 10;
+const PINGREQ: u8 = 12;
+const DISCONNECT: u8 = 14;
+
+/// Serves the MQTT connections of a listener.
+pub(crate) async fn serve(listener: TcpListener, messages: broadcast::Sender<Message>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_connection(stream, messages.subscribe()));
+    }
+}
+
+async fn handle_connection(stream: TcpStream, mut messages: broadcast::Receiver<Message>) {
+    let (mut reader, mut writer) = stream.into_split();
+    let subscriptions = Arc::new(Mutex::new(Vec::<String>::new()));
+    let (reply_sender, mut replies) = mpsc::unbounded_channel::<Vec<u8>>();
+
+    // Packets are read on a separate task, as reading them isn't cancellation safe.
+    let reader_subscriptions = subscriptions.clone();
+    let reader_task = tokio::spawn(async move {
+        while let Ok((packet_type, body)) = read_packet(&mut reader).await {
+            let reply = match packet_type {
+                CONNECT => vec![0x20, 0x02, 0x00, 0x00],
+                SUBSCRIBE => {
+                    let (packet_id, filters) = parse_filters(&body, true);
+                    let granted_qos = vec![0; filters.len()];
+                    reader_subscriptions.lock().unwrap().extend(filters);
+                    packet(0x90, &[&packet_id, granted_qos.as_slice()].concat())
+                }
+                UNSUBSCRIBE => {
+                    let (packet_id, filters) = parse_filters(&body, false);
+                    reader_subscriptions
+                        .lock()
+                        .unwrap()
+                        .retain(|subscription| !filters.contains(subscription));
+                    packet(0xb0, &packet_id)
+                }
+                PINGREQ => vec![0xd0, 0x00],
+                DISCONNECT => break,
+                // Messages published by clients are ignored.
+                PUBLISH => continue,
+                _ => break,
+            };
+            if reply_sender.send(reply).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        let bytes = tokio::select! {
+            reply = replies.recv() => match reply {
+                Some(reply) => reply,
+                None => break,
+            },
+            message = messages.recv() => match message {
+                Ok((topic, payload)) => {
+                    let subscribed = subscriptions
+                        .lock()
+                        .unwrap()
+                        .iter()
+                        .any(|filter| topic_matches(filter, &topic));
+                    if !subscribed {
+                        continue;
+                    }
+                    let mut body = (topic.len() as u16).to_be_bytes().to_vec();
+                    body.extend(topic.as_bytes());
+                    body.extend(payload);
+                    packet(0x30, &body)
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        };
+        if writer.write_all(&bytes).await.is_err() {
+            break;
+        }
+    }
+
+    reader_task.abort();
+}
+
+// Reads the type and the body of a packet.
+async fn read_packet(reader: &mut (impl AsyncRead + Unpin)) -> std::io::Result<(u8, Vec<u8>)> {
+    let header = reader.read_u8().await?;
+    let mut length = 0usize;
+    for shift in (0..28).step_by(7) {
+        let byte = reader.read_u8().await?;
+        length |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).await?;
+
+    Ok((header >> 4, body))
+}
+
+// Parses the packet id and the topic filters of a (un)subscribe packet.
+fn parse_filters(body: &[u8], with_qos: bool) -> (Vec<u8>, Vec<String>) {
+    let packet_id = body.get(..2).unwrap_or_default().to_vec();
+    let mut filters = Vec::new();
+    let mut rest = body.get(2..).unwrap_or_default();
+    while rest.len() >= 2 {
+        let length = u16::from_be_bytes([rest[0], rest[1]]) as usize;
+        let Some(filter) = rest.get(2..2 + length) else {
+            break;
+        };
+        filters.push(String::from_utf8_lossy(filter).into_owned());
+        rest = rest.get(2 + length + with_qos as usize..).unwrap_or_default();
+    }
+
+    (packet_id, filters)
+}
+
+// Encodes a packet with its remaining length.
+fn packet(header: u8, body: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![header];
+    let mut length = body.len();
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        bytes.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+    bytes.extend(body);
+    bytes
+}
+
+fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut topic_levels = topic.split('/');
+    for filter_level in filter.split('/') {
+        match (filter_level, topic_levels.next()) {
+            ("#", _) => return true,
+            ("+", Some(_)) => {}
+            (filter_level, Some(topic_level)) if filter_level == topic_level => {}
+            _ => return false,
+        }
+    }
+    topic_levels.next().is_none()
+}
@@ -1,4 +1,6 @@
 // Copyright 2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "client")]
+pub(crate) mod mock_node;
 mod serde;
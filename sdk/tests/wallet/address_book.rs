@@ -0,0 +1,99 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::{
+        address::{Bech32Address, NftAddress, ToBech32Ext},
+        output::NftId,
+    },
+    wallet::{AddressBookEntry, AddressBookTarget, AddressOrLabel, Error, Result, SendNftParams, SendParams},
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn address_book_resolve_labels() -> Result<()> {
+    let storage_path = "test-storage/address_book_resolve_labels";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = wallet.create_account().finish().await?;
+
+    let address = Bech32Address::try_from_str("rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu")?;
+    let nft_id = NftId::new([1; NftId::LENGTH]);
+
+    assert!(wallet
+        .set_address_book_entry(AddressBookEntry::new("alice", AddressBookTarget::Address(address)).add_tag("payroll"))
+        .await?
+        .is_none());
+    wallet
+        .set_address_book_entry(AddressBookEntry::new("artwork", AddressBookTarget::Nft(nft_id)))
+        .await?;
+
+    let address_book = wallet.address_book().await;
+    assert_eq!(address_book.len(), 2);
+    assert_eq!(address_book.entries_with_tag("payroll").count(), 1);
+
+    // Labels are kept in the params and resolved when the transaction is prepared
+    let send_params = SendParams::new(1_000_000, "alice")?;
+    assert_eq!(send_params.address(), &AddressOrLabel::Label("alice".to_owned()));
+    let send_params = SendParams::new(1_000_000, address.to_string())?;
+    assert_eq!(send_params.address(), &AddressOrLabel::Address(address));
+
+    let send_nft_params = SendNftParams::new("artwork", account.resolve_nft_id("artwork").await?)?;
+    assert_eq!(send_nft_params.nft_id(), &nft_id);
+    assert_eq!(
+        account.resolve_address(send_nft_params.address().clone()).await?,
+        NftAddress::new(nft_id).to_bech32(account.client().get_bech32_hrp().await?)
+    );
+
+    // Bech32 addresses and ids are still accepted
+    assert_eq!(account.resolve_address(&address.to_string()).await?, address);
+    assert_eq!(account.resolve_nft_id(&nft_id.to_string()).await?, nft_id);
+    assert!(account.resolve_address("bob").await.is_err());
+    assert!(account.resolve_nft_id("alice").await.is_err());
+
+    assert!(wallet.remove_address_book_entry("alice").await?.is_some());
+    assert!(account.resolve_address("alice").await.is_err());
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn address_book_send_to_label() -> Result<()> {
+    let storage_path = "test-storage/address_book_send_to_label";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let recipient = *account_1.addresses().await?[0].address();
+    wallet
+        .set_address_book_entry(AddressBookEntry::new("bob", AddressBookTarget::Address(recipient)))
+        .await?;
+
+    mock_node.fund(*account_0.addresses().await?[0].address(), 5_000_000);
+    account_0.sync(None).await?;
+
+    // Labels that aren't in the address book fail when the transaction is prepared
+    assert!(matches!(
+        account_0
+            .prepare_send([SendParams::new(1_000_000, "carol")?], None)
+            .await,
+        Err(Error::AddressBookResolutionFailed(_))
+    ));
+
+    account_0
+        .send_with_params([SendParams::new(1_000_000, "bob")?], None)
+        .await?;
+    mock_node.produce_milestone();
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), 1_000_000);
+
+    tear_down(storage_path)
+}
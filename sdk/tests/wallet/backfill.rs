@@ -0,0 +1,93 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        node_api::history::{ChronicleHistoryProvider, MemoryHistoryProvider},
+        Client,
+    },
+    wallet::{Error, Result},
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn backfill_history() -> Result<()> {
+    let storage_path_0 = "test-storage/backfill_history_0";
+    let storage_path_1 = "test-storage/backfill_history_1";
+    setup(storage_path_0)?;
+    setup(storage_path_1)?;
+
+    let mock_node = MockNode::start();
+    let mnemonic = Client::generate_mnemonic()?;
+    let wallet = make_wallet(storage_path_0, Some(mnemonic.clone()), Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let recipient_account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    let recipient = *recipient_account.addresses().await?[0].address();
+
+    let funding_output_id = mock_node.fund(address, 2_000_000);
+    account.sync(None).await?;
+    let transaction = account.send(1_000_000, recipient, None).await?;
+    mock_node.produce_milestone();
+    account.sync(None).await?;
+
+    // The same account restored elsewhere only finds the unspent remainder on the regular nodes
+    let restored_wallet = make_wallet(storage_path_1, Some(mnemonic), Some(&mock_node.url())).await?;
+    let restored_account = restored_wallet.create_account().finish().await?;
+    restored_account.sync(None).await?;
+    assert!(restored_account.get_output(&funding_output_id).await.is_none());
+    assert!(restored_account.transactions().await.is_empty());
+
+    let client = restored_account.client();
+    let mut history_provider = MemoryHistoryProvider::new().with_page_size(1);
+    history_provider
+        .add_output(address, client.get_output(&funding_output_id).await?)
+        .add_block(client.get_included_block(&transaction.transaction_id).await?);
+
+    let new_output_ids = restored_account.backfill_history(&history_provider).await?;
+    assert_eq!(new_output_ids, vec![funding_output_id]);
+    let output_data = restored_account.get_output(&funding_output_id).await.unwrap();
+    assert!(output_data.is_spent);
+    let backfilled_transaction = restored_account
+        .get_transaction(&transaction.transaction_id)
+        .await
+        .unwrap();
+    assert!(!backfilled_transaction.incoming);
+    assert_eq!(backfilled_transaction.payload, transaction.payload);
+    assert_eq!(backfilled_transaction.inputs.len(), 1);
+    assert_eq!(
+        *backfilled_transaction.inputs[0].metadata.output_id(),
+        funding_output_id
+    );
+
+    // Backfilling again doesn't add anything
+    assert!(restored_account.backfill_history(&history_provider).await?.is_empty());
+    assert_eq!(restored_account.transactions().await.len(), 1);
+
+    tear_down(storage_path_0)?;
+    tear_down(storage_path_1)
+}
+
+#[tokio::test]
+async fn backfill_history_without_permanode() -> Result<()> {
+    let storage_path = "test-storage/backfill_history_without_permanode";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+
+    // The mock node doesn't provide the explorer API, so the history can't be requested
+    let history_provider = ChronicleHistoryProvider::new(account.client().clone());
+    assert!(matches!(
+        account.backfill_history(&history_provider).await,
+        Err(Error::Client(error)) if matches!(*error, iota_sdk::client::Error::HistoryNotAvailable(_))
+    ));
+
+    tear_down(storage_path)
+}
@@ -122,4 +122,27 @@ fn wallet_events_serde() {
     ));
 
     assert_serde_eq(WalletEvent::TransactionProgress(TransactionProgressEvent::Broadcasting));
+
+    #[cfg(feature = "participation")]
+    {
+        use iota_sdk::{
+            types::api::plugins::participation::types::ParticipationEventId,
+            wallet::events::types::ParticipationWarningEvent,
+        };
+
+        assert_serde_eq(WalletEvent::ParticipationWarning(
+            ParticipationWarningEvent::EventEnding {
+                event_id: ParticipationEventId::new([1; 32]),
+                milestone_index_end: 100,
+                milestones_left: 10,
+            },
+        ));
+
+        assert_serde_eq(WalletEvent::ParticipationWarning(
+            ParticipationWarningEvent::ParticipationEnding {
+                output_id: OutputId::null(),
+                event_ids: vec![ParticipationEventId::new([1; 32])],
+            },
+        ));
+    }
 }
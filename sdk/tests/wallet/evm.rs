@@ -0,0 +1,93 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::signatures::secp256k1_ecdsa::{EvmAddress, RecoverableSignature};
+use iota_sdk::wallet::{Error, Result};
+use pretty_assertions::assert_eq;
+
+use crate::wallet::common::{make_wallet, setup, tear_down};
+
+#[tokio::test]
+async fn evm_address_generation_and_signing() -> Result<()> {
+    let storage_path = "test-storage/evm_address_generation_and_signing";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = wallet.create_account().finish().await?;
+
+    let evm_addresses = account.generate_evm_addresses(2).await?;
+    assert_eq!(evm_addresses.len(), 2);
+    assert_eq!(*evm_addresses[1].key_index(), 1);
+    assert_eq!(account.details().await.evm_addresses(), &evm_addresses);
+    let address = *evm_addresses[1].address();
+
+    // EIP-191
+    let signature = account.sign_evm_message(&address, b"hello").await?;
+    let mut recoverable_signature = signature.signature;
+    recoverable_signature[64] -= 27;
+    let recovered_public_key = RecoverableSignature::try_from_bytes(&recoverable_signature)?
+        .recover_keccak256(b"\x19Ethereum Signed Message:\n5hello")
+        .unwrap();
+    assert_eq!(recovered_public_key.evm_address(), address);
+    assert_eq!(recovered_public_key.to_bytes(), signature.public_key);
+
+    // EIP-155, nonce 0, gas price 1, gas limit 21000 to 0x35..35 with value 1 and chain id 1072
+    let mut transaction = vec![0xdc, 0x80, 0x01, 0x82, 0x52, 0x08, 0x94];
+    transaction.extend([0x35; 20]);
+    transaction.extend([0x01, 0x80]);
+    let signed_transaction = account.sign_evm_transaction(&address, &transaction, 1072).await?;
+    // v = 1072 * 2 + 35 + recovery id, followed by r and s
+    assert_eq!(signed_transaction[0], 0xf8);
+    assert_eq!(&signed_transaction[2..30], &transaction[1..]);
+    assert!([[0x82, 0x08, 0x83], [0x82, 0x08, 0x84]].contains(&[
+        signed_transaction[30],
+        signed_transaction[31],
+        signed_transaction[32]
+    ]));
+    // With the EIP-155 fields the chain id must match
+    let mut eip155_transaction = transaction.clone();
+    eip155_transaction[0] += 5;
+    eip155_transaction.extend([0x82, 0x04, 0x30, 0x80, 0x80]);
+    assert_eq!(
+        account
+            .sign_evm_transaction(&address, &eip155_transaction, 1072)
+            .await?,
+        signed_transaction
+    );
+    assert!(matches!(
+        account.sign_evm_transaction(&address, &eip155_transaction, 1).await,
+        Err(Error::InvalidEvmTransaction(_))
+    ));
+
+    // Addresses that weren't generated by the account can't be used
+    assert!(matches!(
+        account.sign_evm_message(&EvmAddress::from([0; 20]), b"hello").await,
+        Err(Error::EvmAddressNotFoundInAccount(_))
+    ));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+#[cfg(feature = "rocksdb")]
+async fn evm_addresses_are_stored() -> Result<()> {
+    let storage_path = "test-storage/evm_addresses_are_stored";
+    setup(storage_path)?;
+
+    let mnemonic = iota_sdk::client::Client::generate_mnemonic()?;
+    let wallet = make_wallet(storage_path, Some(mnemonic.clone()), None).await?;
+    let account = wallet.create_account().finish().await?;
+    let evm_addresses = account.generate_evm_addresses(1).await?;
+
+    drop(account);
+    drop(wallet);
+
+    let wallet = make_wallet(storage_path, Some(mnemonic), None).await?;
+    let account = wallet.get_account(0).await?;
+    assert_eq!(account.details().await.evm_addresses(), &evm_addresses);
+
+    // New addresses continue after the stored ones
+    assert_eq!(*account.generate_evm_addresses(1).await?[0].key_index(), 1);
+
+    tear_down(storage_path)
+}
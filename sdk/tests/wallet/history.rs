@@ -0,0 +1,97 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::wallet::{
+    account::{HistoryDirection, HistoryExportFormat, HistoryOptions, SyncOptions, TransactionOptions},
+    AddressBookEntry, AddressBookTarget, Result, SendParams,
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn export_history() -> Result<()> {
+    let storage_path = "test-storage/export_history";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let recipient = *account_1.addresses().await?[0].address();
+    wallet
+        .set_address_book_entry(AddressBookEntry::new("bob", AddressBookTarget::Address(recipient)))
+        .await?;
+
+    mock_node.fund(*account_0.addresses().await?[0].address(), 5_000_000);
+    account_0.sync(None).await?;
+    let transaction = account_0
+        .send_with_params(
+            [SendParams::new(1_000_000, recipient)?],
+            TransactionOptions {
+                note: Some("rent, march".to_string()),
+                ..Default::default()
+            },
+        )
+        .await?;
+    mock_node.produce_milestone();
+    account_0.sync(None).await?;
+    account_1
+        .sync(Some(SyncOptions {
+            sync_incoming_transactions: true,
+            ..Default::default()
+        }))
+        .await?;
+
+    let history = account_0.history(None).await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(*history[0].transaction_id(), transaction.transaction_id);
+    assert_eq!(history[0].direction(), HistoryDirection::Outgoing);
+    assert_eq!(history[0].base_coin_amount(), 1_000_000);
+    assert_eq!(history[0].counterparties().len(), 1);
+    assert_eq!(history[0].counterparties()[0].label().as_deref(), Some("bob"));
+
+    let csv = account_0.export_history(HistoryExportFormat::Csv, None).await?;
+    let rows = csv.lines().collect::<Vec<_>>();
+    assert_eq!(rows.len(), 2);
+    assert!(rows[0].starts_with("transactionId,timestamp,date,direction"));
+    assert!(rows[1].starts_with(&transaction.transaction_id.to_string()));
+    assert!(rows[1].contains(&format!("bob ({recipient})")));
+    // The note contains a comma, so it's quoted
+    assert!(rows[1].contains("\"rent, march\""));
+
+    let json_lines = account_0.export_history(HistoryExportFormat::JsonLines, None).await?;
+    assert_eq!(json_lines.lines().count(), 1);
+    let entry = serde_json::from_str::<serde_json::Value>(json_lines.lines().next().unwrap())?;
+    assert_eq!(entry["transactionId"], transaction.transaction_id.to_string());
+    assert_eq!(entry["baseCoinAmount"], "1000000");
+    assert_eq!(entry["note"], "rent, march");
+
+    let history = account_1.history(None).await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].direction(), HistoryDirection::Incoming);
+    assert_eq!(history[0].base_coin_amount(), 1_000_000);
+    let options = HistoryOptions {
+        incoming: false,
+        ..Default::default()
+    };
+    assert!(account_1
+        .export_history(HistoryExportFormat::JsonLines, options)
+        .await?
+        .is_empty());
+    // A range after the transaction only contains the header
+    let options = HistoryOptions::default().with_range(history[0].timestamp() + 1, None);
+    assert_eq!(
+        account_1
+            .export_history(HistoryExportFormat::Csv, options)
+            .await?
+            .lines()
+            .count(),
+        1
+    );
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,141 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::FutureExt;
+use iota_sdk::{
+    client::{
+        constants::SHIMMER_COIN_TYPE,
+        secret::{mnemonic::MnemonicSecretManager, SecretManager},
+        Client,
+    },
+    wallet::{ClientOptions, Error, Result, Wallet, WalletBuilder, WalletHost, WalletHostOptions},
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{setup, tear_down, NODE_LOCAL, NODE_OTHER},
+};
+
+#[allow(unused_variables)]
+fn wallet_builder(storage_path: &str) -> Result<WalletBuilder> {
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+
+    #[allow(unused_mut)]
+    let mut wallet_builder = Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(secret_manager))
+        .with_coin_type(SHIMMER_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        wallet_builder = wallet_builder.with_storage_path(storage_path);
+    }
+
+    Ok(wallet_builder)
+}
+
+#[tokio::test]
+async fn host_wallets() -> Result<()> {
+    let storage_path = "test-storage/host_wallets";
+    setup(storage_path)?;
+
+    let client = Client::builder().with_node(NODE_LOCAL)?.finish().await?;
+    let host = WalletHost::new(client, None);
+
+    let wallet = host
+        .add_wallet("alice", wallet_builder(&format!("{storage_path}/alice"))?)
+        .await?;
+    host.add_wallet("bob", wallet_builder(&format!("{storage_path}/bob"))?)
+        .await?;
+    assert_eq!(host.wallet_ids().await, ["alice", "bob"]);
+
+    // The wallets share the client of the host
+    wallet
+        .set_client_options(ClientOptions::new().with_node(NODE_OTHER)?)
+        .await?;
+    assert_eq!(
+        host.get_wallet("bob")
+            .await?
+            .client_options()
+            .await
+            .node_manager_builder,
+        wallet.client_options().await.node_manager_builder
+    );
+
+    assert!(matches!(
+        host.add_wallet("alice", wallet_builder(&format!("{storage_path}/alice2"))?)
+            .await,
+        Err(Error::WalletAlreadyHosted(id)) if id == "alice"
+    ));
+
+    host.get_wallet("bob").await?;
+    host.remove_wallet("bob").await?;
+    assert!(matches!(
+        host.get_wallet("bob").await,
+        Err(Error::WalletNotHosted(id)) if id == "bob"
+    ));
+    assert_eq!(host.wallet_ids().await, ["alice"]);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn host_sync_fairness() -> Result<()> {
+    let storage_path = "test-storage/host_sync_fairness";
+    setup(storage_path)?;
+
+    let client = Client::builder().with_node(NODE_LOCAL)?.finish().await?;
+    let host = WalletHost::new(
+        client,
+        WalletHostOptions {
+            max_concurrent_syncs: 1,
+            max_wallets_per_round: Some(2),
+            ..Default::default()
+        },
+    );
+    for id in ["a", "b", "c"] {
+        host.add_wallet(id, wallet_builder(&format!("{storage_path}/{id}"))?)
+            .await?;
+    }
+
+    // Wallets without accounts sync without a node
+    let mut synced = Vec::new();
+    for _ in 0..3 {
+        let mut round = host
+            .sync_wallets()
+            .await
+            .into_iter()
+            .map(|(id, result)| result.map(|_| id))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(round.len(), 2);
+        round.sort();
+        synced.push(round);
+    }
+    // Never synced wallets first, then the least recently synced ones
+    assert_eq!(synced, [["a", "b"], ["a", "c"], ["b", "c"]]);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn host_cancelled_round() -> Result<()> {
+    let storage_path = "test-storage/host_cancelled_round";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let client = Client::builder().with_node(&mock_node.url())?.finish().await?;
+    let host = WalletHost::new(client, None);
+    let wallet = host
+        .add_wallet("alice", wallet_builder(&format!("{storage_path}/alice"))?)
+        .await?;
+    wallet.create_account().finish().await?;
+
+    // The round is dropped while the account is synced with the node
+    assert!(host.sync_wallets().now_or_never().is_none());
+
+    // The wallet isn't considered as syncing anymore
+    let round = host.sync_wallets().await;
+    assert_eq!(round.len(), 1);
+    assert!(round[0].1.is_ok());
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,185 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::bip39::Mnemonic;
+use iota_sdk::{
+    client::{
+        constants::SHIMMER_COIN_TYPE,
+        secret::{mnemonic::MnemonicSecretManager, SecretManager},
+        storage::StorageAdapter,
+        Client,
+    },
+    types::{
+        api::core::response::OutputWithMetadataResponse,
+        block::output::{
+            dto::OutputDto, feature::TagFeature, unlock_condition::AddressUnlockCondition, BasicOutputBuilder, OutputId,
+        },
+    },
+    wallet::{
+        account::{
+            types::{InclusionState, Transaction, TransactionDto},
+            TransactionOptions,
+        },
+        storage::{adapter::sqlite::SqliteStorageAdapter, StorageKind, StorageOptions},
+        Account, ClientOptions, Result, Wallet,
+    },
+};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+async fn sqlite_wallet(storage_path: &str, mnemonic: Mnemonic, node_url: &str) -> Result<Wallet> {
+    Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            mnemonic,
+        )?))
+        .with_client_options(ClientOptions::new().with_node(node_url)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_options(StorageOptions::new(storage_path.into(), StorageKind::Sqlite))
+        .finish()
+        .await
+}
+
+// Prepares and signs a transaction spending the output, without submitting it.
+async fn signed_transaction(account: &Account, output_id: OutputId) -> Result<Transaction> {
+    let address = *account.addresses().await?[0].address();
+    let prepared = account
+        .prepare_transaction(
+            [BasicOutputBuilder::new_with_amount(1_000_000)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(account.client().get_token_supply().await?)?],
+            Some(TransactionOptions {
+                custom_inputs: Some(vec![output_id]),
+                ..Default::default()
+            }),
+        )
+        .await?;
+    let signed = account.sign_transaction_essence(&prepared).await?;
+
+    Ok(Transaction {
+        transaction_id: signed.transaction_payload.id(),
+        payload: signed.transaction_payload,
+        block_id: None,
+        inclusion_state: InclusionState::Pending,
+        timestamp: 0,
+        network_id: account.client().get_network_id().await?,
+        incoming: false,
+        note: None,
+        inputs: signed
+            .inputs_data
+            .iter()
+            .map(|input| OutputWithMetadataResponse {
+                metadata: input.output_metadata,
+                output: OutputDto::from(&input.output),
+            })
+            .collect(),
+    })
+}
+
+#[tokio::test]
+async fn recover_journal() -> Result<()> {
+    let storage_path = "test-storage/recover_journal";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let mnemonic = Client::generate_mnemonic()?;
+    let wallet = sqlite_wallet(storage_path, mnemonic.clone(), &mock_node.url()).await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    let output_ids = [
+        mock_node.fund(address, 1_000_000),
+        mock_node.fund(address, 1_000_000),
+        mock_node.fund(address, 1_000_000),
+    ];
+    account.sync(None).await?;
+
+    // The wallet stops while sending three transactions: the first one isn't signed yet, the other ones are signed
+    // but not submitted.
+    let unsigned = signed_transaction(&account, output_ids[0]).await?;
+    let signed = signed_transaction(&account, output_ids[1]).await?;
+    let conflicting = signed_transaction(&account, output_ids[2]).await?;
+    drop(account);
+    drop(wallet);
+    let journal_entry = |transaction: &Transaction, step: serde_json::Value| {
+        json!({
+            "id": prefix_hex::encode(transaction.payload.essence().hash()),
+            "inputs": transaction.inputs.iter().map(|input| input.metadata.output_id()).collect::<Vec<_>>(),
+            "step": step,
+        })
+    };
+    SqliteStorageAdapter::new(storage_path)?
+        .set(
+            "iota-wallet-account-0-journal",
+            &json!([
+                journal_entry(&unsigned, json!({ "type": "inputsLocked" })),
+                journal_entry(
+                    &signed,
+                    json!({ "type": "transactionBuilt", "transaction": TransactionDto::from(&signed) })
+                ),
+                journal_entry(
+                    &conflicting,
+                    json!({ "type": "transactionBuilt", "transaction": TransactionDto::from(&conflicting) })
+                ),
+            ]),
+        )
+        .await?;
+
+    // Meanwhile the third output is spent by another transaction.
+    let other_wallet = make_wallet(
+        "test-storage/recover_journal_other",
+        Some(mnemonic.clone()),
+        Some(&mock_node.url()),
+    )
+    .await?;
+    let other_account = other_wallet.create_account().finish().await?;
+    other_account.sync(None).await?;
+    other_account
+        .send_outputs(
+            [BasicOutputBuilder::new_with_amount(1_000_000)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .add_feature(TagFeature::new(b"conflict")?)
+                .finish_output(mock_node.protocol_parameters().token_supply())?],
+            Some(TransactionOptions {
+                custom_inputs: Some(vec![output_ids[2]]),
+                ..Default::default()
+            }),
+        )
+        .await?;
+    mock_node.produce_milestone();
+
+    // The inputs of the signed transactions stay locked until the journal is recovered by the first sync.
+    let wallet = sqlite_wallet(storage_path, mnemonic, &mock_node.url()).await?;
+    let account = wallet.get_account(0).await?;
+    assert!(!account.details().await.locked_outputs().contains(&output_ids[0]));
+    assert!(account.details().await.locked_outputs().contains(&output_ids[1]));
+    assert!(account.details().await.locked_outputs().contains(&output_ids[2]));
+
+    // The signed transaction is submitted again, the conflicting one is rolled back.
+    account.sync(None).await?;
+    let details = account.details().await;
+    assert_eq!(details.pending_transactions().len(), 1);
+    assert!(details.pending_transactions().contains(&signed.transaction_id));
+    assert!(details.transactions()[&signed.transaction_id].block_id.is_some());
+    assert!(!details.transactions().contains_key(&conflicting.transaction_id));
+    assert!(!details.transactions().contains_key(&unsigned.transaction_id));
+    assert!(!details.locked_outputs().contains(&output_ids[2]));
+    drop(details);
+    assert!(SqliteStorageAdapter::new(storage_path)?
+        .get_bytes("iota-wallet-account-0-journal")
+        .await?
+        .is_none());
+
+    mock_node.produce_milestone();
+    account.sync(None).await?;
+    assert_eq!(
+        account.details().await.transactions()[&signed.transaction_id].inclusion_state,
+        InclusionState::Confirmed
+    );
+
+    tear_down("test-storage/recover_journal_other")?;
+    tear_down(storage_path)
+}
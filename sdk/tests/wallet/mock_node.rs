@@ -0,0 +1,45 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::wallet::{account::types::InclusionState, Result};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn mock_node_send_amount() -> Result<()> {
+    let storage_path = "test-storage/mock_node_send_amount";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let address_0 = *account_0.addresses().await?[0].address();
+    let address_1 = *account_1.addresses().await?[0].address();
+
+    mock_node.fund(address_0, 2_000_000);
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 2_000_000);
+
+    let transaction = account_0.send(1_000_000, address_1, None).await?;
+    mock_node.produce_milestone();
+
+    let balance = account_0.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 1_000_000);
+    assert_eq!(
+        account_0
+            .get_transaction(&transaction.transaction_id)
+            .await
+            .unwrap()
+            .inclusion_state,
+        InclusionState::Confirmed
+    );
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 1_000_000);
+
+    tear_down(storage_path)
+}
@@ -3,7 +3,9 @@
 
 mod account_recovery;
 mod accounts;
+mod address_book;
 mod address_generation;
+mod backfill;
 #[cfg(all(feature = "stronghold", feature = "storage"))]
 mod backup_restore;
 mod balance;
@@ -19,12 +21,28 @@ mod core;
 mod error;
 #[cfg(feature = "events")]
 mod events;
+mod evm;
+mod history;
+mod host;
+#[cfg(all(feature = "sqlite", feature = "storage"))]
+mod journal;
 #[cfg(feature = "stronghold")]
 mod migrate_stronghold_snapshot_v2_to_v3;
+mod mock_node;
 mod native_tokens;
 mod output_preparation;
+#[cfg(feature = "participation")]
+mod participation;
+#[cfg(feature = "events")]
+mod protocol_upgrade;
+mod reclaim_deposits;
+mod storage_deposit_returns;
+#[cfg(all(feature = "sqlite", feature = "storage"))]
+mod storage_encryption;
+mod swap;
 mod syncing;
 mod transactions;
+mod vesting;
 #[cfg(not(target_os = "windows"))]
 #[cfg(feature = "rocksdb")]
 mod wallet_storage;
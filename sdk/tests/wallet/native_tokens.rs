@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use iota_sdk::{
-    wallet::{account::SyncOptions, CreateNativeTokenParams, Result},
+    wallet::{account::SyncOptions, CreateNativeTokenParams, Error, Result},
     U256,
 };
 use pretty_assertions::assert_eq;
 
-use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down},
+};
 
 #[ignore]
 #[tokio::test]
@@ -126,3 +129,133 @@ async fn native_token_foundry_metadata() -> Result<()> {
 
     tear_down(storage_path)
 }
+
+#[ignore]
+#[tokio::test]
+async fn foundry_info_update_metadata_and_destroy() -> Result<()> {
+    let storage_path = "test-storage/foundry_info_update_metadata_and_destroy";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account = &create_accounts_with_funds(&wallet, 1).await?[0];
+
+    let tx = account.create_alias_output(None, None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let create_tx = account
+        .create_native_token(
+            CreateNativeTokenParams {
+                alias_id: None,
+                circulating_supply: U256::from(50),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    account
+        .retry_transaction_until_included(&create_tx.transaction.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let info = account.foundry_info(create_tx.token_id).await?;
+    assert_eq!(info.circulating_supply(), U256::from(50));
+    assert_eq!(info.held_tokens(), U256::from(50));
+    assert_eq!(info.mintable_tokens(), U256::from(50));
+    assert!(info.is_destroyable());
+
+    let tx = account
+        .update_foundry_metadata(create_tx.token_id, Some(b"updated metadata".to_vec()), None)
+        .await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    account.sync(None).await?;
+
+    let foundry_output = account.get_foundry_output(create_tx.token_id).await?;
+    assert_eq!(
+        foundry_output.features().unwrap().metadata().unwrap().data(),
+        b"updated metadata"
+    );
+
+    let tx = account.destroy_foundry(*info.foundry_id(), None).await?;
+    account
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+    let balance = account.sync(None).await?;
+    assert!(balance.foundries().is_empty());
+    assert!(balance.native_tokens().is_empty());
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn foundry_update_metadata_and_destroy_mock_node() -> Result<()> {
+    let storage_path = "test-storage/foundry_update_metadata_and_destroy_mock_node";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let other_account = wallet.create_account().finish().await?;
+    mock_node.fund(*account.addresses().await?[0].address(), 10_000_000);
+    account.sync(None).await?;
+
+    account.create_alias_output(None, None).await?;
+    mock_node.produce_milestone();
+    account.sync(None).await?;
+
+    let create_tx = account
+        .create_native_token(
+            CreateNativeTokenParams {
+                alias_id: None,
+                circulating_supply: U256::from(50),
+                maximum_supply: U256::from(100),
+                foundry_metadata: None,
+            },
+            None,
+        )
+        .await?;
+    mock_node.produce_milestone();
+    account.sync(None).await?;
+
+    let info = account.foundry_info(create_tx.token_id).await?;
+    assert!(info.controlled());
+    assert!(info.is_destroyable());
+
+    // Only the account controlling the foundry can update or destroy it
+    other_account.sync(None).await?;
+    assert!(matches!(
+        other_account
+            .prepare_update_foundry_metadata(create_tx.token_id, Some(b"metadata".to_vec()), None)
+            .await,
+        Err(Error::FoundryOperationFailed(_))
+    ));
+    assert!(matches!(
+        other_account.prepare_destroy_foundry(*info.foundry_id(), None).await,
+        Err(Error::FoundryOperationFailed(_))
+    ));
+
+    account
+        .update_foundry_metadata(create_tx.token_id, Some(b"updated metadata".to_vec()), None)
+        .await?;
+    mock_node.produce_milestone();
+    account.sync(None).await?;
+    let foundry_output = account.get_foundry_output(create_tx.token_id).await?;
+    assert_eq!(
+        foundry_output.features().unwrap().metadata().unwrap().data(),
+        b"updated metadata"
+    );
+
+    account.destroy_foundry(*info.foundry_id(), None).await?;
+    mock_node.produce_milestone();
+    let balance = account.sync(None).await?;
+    assert!(balance.foundries().is_empty());
+    assert!(balance.native_tokens().is_empty());
+
+    tear_down(storage_path)
+}
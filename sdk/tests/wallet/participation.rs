@@ -0,0 +1,191 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::{
+        api::plugins::participation::types::{Participation, ParticipationEventId, Participations, PARTICIPATION_TAG},
+        block::{
+            address::Bech32Address,
+            output::{
+                feature::{MetadataFeature, TagFeature},
+                unlock_condition::AddressUnlockCondition,
+                BasicOutputBuilder, Output,
+            },
+            payload::transaction::TransactionEssence,
+        },
+    },
+    wallet::{
+        account::{ConsolidationParams, ParticipationManagerOptions, ParticipationWarningEvent, TransactionOptions},
+        events::types::{WalletEvent, WalletEventType},
+        Result,
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn participation_manager_options() -> Result<()> {
+    let storage_path = "test-storage/participation_manager_options";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+    let account = wallet.create_account().finish().await?;
+
+    // Disabled by default
+    assert_eq!(account.participation_manager_options().await?, None);
+    assert!(account.manage_participations().await?.is_none());
+
+    let options = ParticipationManagerOptions {
+        renew_votes: false,
+        event_ending_warning_milestones: 100,
+    };
+    account.set_participation_manager_options(Some(options.clone())).await?;
+    assert_eq!(account.participation_manager_options().await?, Some(options));
+
+    // Without a voting output there are no votes to renew
+    assert!(account.prepare_renew_votes().await?.is_none());
+
+    account.set_participation_manager_options(None).await?;
+    assert_eq!(account.participation_manager_options().await?, None);
+
+    tear_down(storage_path)
+}
+
+// A participation output to the address, with the participations as metadata if there are any.
+fn participation_output(
+    address: Bech32Address,
+    amount: u64,
+    participations: Option<Participations>,
+    token_supply: u64,
+) -> Result<Output> {
+    let mut builder = BasicOutputBuilder::new_with_amount(amount)
+        .add_unlock_condition(AddressUnlockCondition::new(address))
+        .add_feature(TagFeature::new(PARTICIPATION_TAG)?);
+    if let Some(participations) = participations {
+        builder = builder.add_feature(MetadataFeature::new(participations.to_bytes().unwrap())?);
+    }
+    Ok(builder.finish_output(token_supply)?)
+}
+
+fn participations(event_id: ParticipationEventId) -> Participations {
+    Participations {
+        participations: vec![Participation {
+            event_id,
+            answers: vec![0],
+        }],
+    }
+}
+
+#[tokio::test]
+async fn consolidation_keeps_participation_outputs() -> Result<()> {
+    let storage_path = "test-storage/consolidation_keeps_participation_outputs";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    let token_supply = mock_node.protocol_parameters().token_supply();
+
+    // The voting output and a second output which still participates.
+    let event_id = ParticipationEventId::new([1; 32]);
+    let participation_output_ids = [
+        mock_node.add_output(participation_output(
+            address,
+            2_000_000,
+            Some(participations(event_id)),
+            token_supply,
+        )?),
+        mock_node.add_output(participation_output(
+            address,
+            1_000_000,
+            Some(participations(event_id)),
+            token_supply,
+        )?),
+    ];
+    for _ in 0..3 {
+        mock_node.fund(address, 1_000_000);
+    }
+    account.sync(None).await?;
+
+    let transaction = account
+        .consolidate_outputs(ConsolidationParams::new().with_force(true).with_output_threshold(2))
+        .await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    assert_eq!(essence.inputs().len(), 3);
+    assert!(essence
+        .inputs()
+        .iter()
+        .all(|input| !participation_output_ids.contains(input.as_utxo().output_id())));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn renew_votes_and_end_participations() -> Result<()> {
+    let storage_path = "test-storage/renew_votes_and_end_participations";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    let token_supply = mock_node.protocol_parameters().token_supply();
+
+    // The voting output lost the participations of the older output.
+    let event_id = ParticipationEventId::new([1; 32]);
+    let participating_output_id = mock_node.add_output(participation_output(
+        address,
+        1_000_000,
+        Some(participations(event_id)),
+        token_supply,
+    )?);
+    let voting_output_id = mock_node.add_output(participation_output(address, 2_000_000, None, token_supply)?);
+    account.sync(None).await?;
+
+    let prepared = account.prepare_renew_votes().await?.unwrap();
+    let TransactionEssence::Regular(essence) = &prepared.essence;
+    assert_eq!(essence.inputs().len(), 1);
+    assert_eq!(essence.inputs()[0].as_utxo().output_id(), &voting_output_id);
+    let metadata = essence.outputs()[0].features().unwrap().metadata().unwrap();
+    assert_eq!(
+        Participations::from_bytes(&mut metadata.data()).unwrap(),
+        participations(event_id)
+    );
+
+    // Spending the participating output as custom input ends its participations.
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+    wallet
+        .listen([WalletEventType::ParticipationWarning], move |event| {
+            if let WalletEvent::ParticipationWarning(warning) = &event.event {
+                sender
+                    .try_send(warning.clone())
+                    .expect("too many ParticipationWarning events");
+            }
+        })
+        .await;
+    account
+        .prepare_transaction(
+            [BasicOutputBuilder::new_with_amount(1_000_000)
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .finish_output(token_supply)?],
+            Some(TransactionOptions {
+                custom_inputs: Some(vec![participating_output_id]),
+                ..Default::default()
+            }),
+        )
+        .await?;
+    assert_eq!(
+        receiver.recv().await.unwrap(),
+        ParticipationWarningEvent::ParticipationEnding {
+            output_id: participating_output_id,
+            event_ids: vec![event_id],
+        }
+    );
+
+    tear_down(storage_path)
+}
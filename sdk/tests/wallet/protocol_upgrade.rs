@@ -0,0 +1,177 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use iota_sdk::{
+    client::{
+        constants::SHIMMER_COIN_TYPE,
+        secret::{mnemonic::MnemonicSecretManager, SecretManager},
+        Client,
+    },
+    types::block::{
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, Output, Rent},
+        payload::transaction::TransactionEssence,
+        protocol::ProtocolParameters,
+    },
+    wallet::{
+        account::SyncOptions,
+        events::types::{WalletEvent, WalletEventType},
+        ClientOptions, Result, Wallet,
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{setup, tear_down},
+};
+
+#[tokio::test]
+async fn protocol_parameters_upgrade_revalidates_transactions() -> Result<()> {
+    let storage_path = "test-storage/protocol_parameters_upgrade_revalidates_transactions";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    // Sync the node info often, so the client notices the upgrade right away.
+    let client_options = ClientOptions::new()
+        .with_node(&mock_node.url())?
+        .with_node_sync_interval(Duration::from_millis(100));
+    #[allow(unused_mut)]
+    let mut wallet_builder = Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            Client::generate_mnemonic()?,
+        )?))
+        .with_client_options(client_options)
+        .with_coin_type(SHIMMER_COIN_TYPE);
+    #[cfg(feature = "storage")]
+    {
+        wallet_builder = wallet_builder.with_storage_path(storage_path);
+    }
+    let wallet = wallet_builder.finish().await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+
+    mock_node.fund(address, 2_000_000);
+    mock_node.fund(address, 2_000_000);
+    account.sync(None).await?;
+
+    let protocol_parameters = mock_node.protocol_parameters();
+    let minimum_storage_deposit_output =
+        BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+            .add_unlock_condition(AddressUnlockCondition::new(address))
+            .finish_output(protocol_parameters.token_supply())?;
+    let pending_transaction = account
+        .send_outputs([minimum_storage_deposit_output.clone()], None)
+        .await?;
+    let prepared_transaction = account
+        .prepare_transaction([minimum_storage_deposit_output.clone()], None)
+        .await?;
+
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+    wallet
+        .listen([WalletEventType::ProtocolParametersUpgrade], move |event| {
+            if let WalletEvent::ProtocolParametersUpgrade(upgrade) = &event.event {
+                sender
+                    .try_send(upgrade.as_ref().clone())
+                    .expect("too many ProtocolParametersUpgrade events");
+            } else {
+                panic!("expected ProtocolParametersUpgrade event")
+            }
+        })
+        .await;
+
+    let upgraded = ProtocolParameters::new(
+        protocol_parameters.protocol_version(),
+        protocol_parameters.network_name().to_string(),
+        *protocol_parameters.bech32_hrp(),
+        protocol_parameters.min_pow_score(),
+        protocol_parameters.below_max_depth(),
+        protocol_parameters
+            .rent_structure()
+            .with_byte_cost(protocol_parameters.rent_structure().byte_cost() * 10),
+        protocol_parameters.token_supply(),
+    )?;
+    let target_milestone_index = account
+        .client()
+        .get_info()
+        .await?
+        .node_info
+        .status
+        .latest_milestone
+        .index
+        + 1;
+    mock_node.schedule_protocol_parameters(target_milestone_index, upgraded.clone());
+    mock_node.produce_milestone();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while account.client().get_protocol_parameters().await.unwrap() != upgraded {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("the protocol parameters were never switched");
+
+    account
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    let event = receiver.recv().await.expect("never received event");
+    assert_eq!(event.previous_protocol_parameters, protocol_parameters);
+    assert_eq!(event.protocol_parameters, upgraded);
+    assert_eq!(
+        event.insufficient_storage_deposit_transactions,
+        [pending_transaction.transaction_id]
+    );
+
+    let revalidated = account
+        .revalidate_prepared_transaction(prepared_transaction.clone(), None)
+        .await?;
+    let TransactionEssence::Regular(essence) = &revalidated.essence;
+    let output = essence
+        .outputs()
+        .iter()
+        .find(|output| Some(*output) != revalidated.remainder.as_ref().map(|remainder| &remainder.output))
+        .unwrap();
+    let rent_cost = minimum_storage_deposit_output.rent_cost(upgraded.rent_structure());
+    assert!(rent_cost > minimum_storage_deposit_output.amount());
+    assert_eq!(output.amount(), rent_cost);
+    assert!(matches!(output, Output::Basic(_)));
+    // The same inputs are used.
+    assert_eq!(
+        revalidated
+            .inputs_data
+            .iter()
+            .map(|input| *input.output_id())
+            .collect::<Vec<_>>(),
+        prepared_transaction
+            .inputs_data
+            .iter()
+            .map(|input| *input.output_id())
+            .collect::<Vec<_>>()
+    );
+
+    // A transaction that is still valid is returned unchanged.
+    assert_eq!(
+        account
+            .revalidate_prepared_transaction(revalidated.clone(), None)
+            .await?,
+        revalidated
+    );
+
+    // The outdated transaction is prepared again when it's submitted.
+    let transaction = account.sign_and_submit_transaction(prepared_transaction, None).await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    assert!(essence.outputs().iter().any(|output| output.amount() == rent_cost));
+    mock_node.produce_milestone();
+    account
+        .sync(Some(SyncOptions {
+            force_syncing: true,
+            ..Default::default()
+        }))
+        .await?;
+    assert!(account.pending_transactions().await.is_empty());
+
+    tear_down(storage_path)
+}
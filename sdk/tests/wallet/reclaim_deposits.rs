@@ -0,0 +1,139 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::{
+        output::{unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeToken, Output, Rent, TokenId},
+        payload::transaction::TransactionEssence,
+    },
+    wallet::{
+        account::{ReclaimDepositsParams, TransactionOptions},
+        Error, Result,
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+#[tokio::test]
+async fn reclaim_deposits() -> Result<()> {
+    let storage_path = "test-storage/reclaim_deposits";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    let token_id_1 = TokenId::new([1; 38]);
+    let token_id_2 = TokenId::new([2; 38]);
+    for native_token in [
+        NativeToken::new(token_id_1, 10)?,
+        NativeToken::new(token_id_1, 20)?,
+        NativeToken::new(token_id_2, 5)?,
+    ] {
+        mock_node.add_output(
+            BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+                .add_unlock_condition(AddressUnlockCondition::new(address))
+                .add_native_token(native_token)
+                .finish_output(protocol_parameters.token_supply())?,
+        );
+    }
+    mock_node.fund(address, 1_000_000);
+
+    let balance = account.sync(None).await?;
+    let total = balance.base_coin().total();
+    let required_storage_deposit = balance.required_storage_deposit().basic();
+
+    let transaction = account.reclaim_deposits(ReclaimDepositsParams::new()).await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    assert_eq!(essence.inputs().len(), 3);
+    assert_eq!(essence.outputs().len(), 1);
+    mock_node.produce_milestone();
+
+    let balance = account.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), total);
+    assert!(balance.required_storage_deposit().basic() < required_storage_deposit);
+    assert_eq!(account.unspent_outputs(None).await?.len(), 2);
+    for (token_id, amount) in [(token_id_1, 30), (token_id_2, 5)] {
+        let native_token = balance
+            .native_tokens()
+            .iter()
+            .find(|native_token| native_token.token_id() == &token_id)
+            .unwrap();
+        assert_eq!(native_token.total(), amount.into());
+    }
+
+    // Only one output holds native tokens now.
+    assert!(matches!(
+        account.prepare_reclaim_deposits(ReclaimDepositsParams::new()).await,
+        Err(Error::NoOutputsToConsolidate {
+            available_outputs: 1,
+            consolidation_threshold: 2,
+        })
+    ));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn send_outputs_optimize_storage_deposits() -> Result<()> {
+    let storage_path = "test-storage/send_outputs_optimize_storage_deposits";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let address_0 = *account_0.addresses().await?[0].address();
+    let address_1 = *account_1.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    mock_node.fund(address_0, 5_000_000);
+    account_0.sync(None).await?;
+
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(address_1))
+        .finish_output(protocol_parameters.token_supply())?;
+    let outputs = [output.clone(), output.clone()];
+
+    let suggestions = account_0.suggest_rent_optimizations(&outputs).await?;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(
+        suggestions[0].saved_deposit(),
+        output.rent_cost(protocol_parameters.rent_structure())
+    );
+
+    let transaction = account_0
+        .send_outputs(
+            outputs,
+            TransactionOptions {
+                optimize_storage_deposits: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let sent = essence
+        .outputs()
+        .iter()
+        .filter(|output| {
+            output
+                .unlock_conditions()
+                .and_then(|unlock_conditions| unlock_conditions.address())
+                .is_some_and(|unlock_condition| unlock_condition.address() == address_1.inner())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sent.len(), 1);
+    assert!(matches!(sent[0], Output::Basic(output) if output.amount() == 2_000_000));
+
+    mock_node.produce_milestone();
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 2_000_000);
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,219 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::{
+        output::{
+            unlock_condition::AddressUnlockCondition, BasicOutputBuilder, MinimumStorageDepositBasicOutput,
+            NativeToken, Output, TokenId,
+        },
+        payload::transaction::TransactionEssence,
+    },
+    wallet::{
+        account::{OutputParams, StorageDepositPolicy, StorageDepositReturnStatus, TransactionOptions},
+        Error, Result, SendNativeTokensParams, SendParams,
+    },
+    U256,
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down},
+};
+
+#[ignore]
+#[tokio::test]
+async fn storage_deposit_policy_and_returns() -> Result<()> {
+    let storage_path = "test-storage/storage_deposit_policy_and_returns";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+    let recipient = *account_1.addresses().await?[0].address();
+
+    let micro_amount = 1;
+
+    // The storage deposit is gifted, so the recipient doesn't need to return anything
+    let tx = account_0
+        .send_with_params(
+            [SendParams::new(micro_amount, recipient)?],
+            TransactionOptions {
+                storage_deposit_policy: Some(StorageDepositPolicy::Gift),
+                allow_micro_amount: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await?;
+    assert!(balance.potentially_locked_outputs().is_empty());
+    let gifted_amount = balance.base_coin().available();
+    assert!(gifted_amount > micro_amount);
+
+    // A limit below the required storage deposit results in a storage deposit return
+    let tx = account_0
+        .send_with_params(
+            [SendParams::new(micro_amount, recipient)?],
+            TransactionOptions {
+                storage_deposit_policy: Some(StorageDepositPolicy::GiftUpToLimit(1)),
+                allow_micro_amount: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    account_0.sync(None).await?;
+    let ledger = account_0.storage_deposit_returns().await?;
+    assert_eq!(ledger.entries().len(), 1);
+    assert_eq!(ledger.entries()[0].status(), StorageDepositReturnStatus::Outstanding);
+    assert_eq!(ledger.outstanding_by_recipient().len(), 1);
+    assert_eq!(
+        ledger.outstanding_by_recipient()[&recipient],
+        ledger.outstanding_amount()
+    );
+    assert_eq!(ledger.lost_amount(), 0);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn storage_deposit_gift_limit() -> Result<()> {
+    let storage_path = "test-storage/storage_deposit_gift_limit";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    let address_0 = *account_0.addresses().await?[0].address();
+    let recipient = *account_1.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+    let rent_structure = *protocol_parameters.rent_structure();
+    let token_supply = protocol_parameters.token_supply();
+
+    let token_id = TokenId::new([1; 38]);
+    mock_node.add_output(
+        BasicOutputBuilder::new_with_minimum_storage_deposit(rent_structure)
+            .add_unlock_condition(AddressUnlockCondition::new(address_0))
+            .add_native_token(NativeToken::new(token_id, 100)?)
+            .finish_output(token_supply)?,
+    );
+    mock_node.fund(address_0, 5_000_000);
+    account_0.sync(None).await?;
+
+    let micro_amount = 1;
+    // Micro amounts need to be allowed, even if their storage deposit is gifted
+    assert!(matches!(
+        account_0
+            .prepare_send(
+                [SendParams::new(micro_amount, recipient)?],
+                TransactionOptions {
+                    storage_deposit_policy: Some(StorageDepositPolicy::Gift),
+                    ..Default::default()
+                },
+            )
+            .await,
+        Err(Error::InsufficientFunds { available, .. }) if available == micro_amount
+    ));
+
+    let missing_storage_deposit =
+        MinimumStorageDepositBasicOutput::new(rent_structure, token_supply).finish()? - micro_amount;
+    // The limit is enough to gift a single storage deposit
+    let options = TransactionOptions {
+        storage_deposit_policy: Some(StorageDepositPolicy::GiftUpToLimit(missing_storage_deposit)),
+        allow_micro_amount: true,
+        ..Default::default()
+    };
+    let has_storage_deposit_return = |output: &Output| {
+        output
+            .unlock_conditions()
+            .is_some_and(|unlock_conditions| unlock_conditions.storage_deposit_return().is_some())
+    };
+
+    let output_params = OutputParams {
+        recipient_address: recipient,
+        amount: micro_amount,
+        assets: None,
+        features: None,
+        unlocks: None,
+        storage_deposit: None,
+    };
+    let outputs = account_0
+        .prepare_outputs([output_params.clone(), output_params], options.clone())
+        .await?;
+    assert!(!has_storage_deposit_return(&outputs[0]));
+    assert!(has_storage_deposit_return(&outputs[1]));
+
+    let transaction = account_0
+        .send_with_params(
+            [
+                SendParams::new(micro_amount, recipient)?,
+                SendParams::new(micro_amount, recipient)?,
+            ],
+            options,
+        )
+        .await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let sent = essence
+        .outputs()
+        .iter()
+        .filter(|output| {
+            output
+                .unlock_conditions()
+                .and_then(|unlock_conditions| unlock_conditions.address())
+                .is_some_and(|unlock_condition| unlock_condition.address() == recipient.inner())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(sent.len(), 2);
+    assert_eq!(
+        sent.iter().filter(|output| has_storage_deposit_return(output)).count(),
+        1
+    );
+    mock_node.produce_milestone();
+
+    account_0.sync(None).await?;
+    let ledger = account_0.storage_deposit_returns().await?;
+    assert_eq!(ledger.entries().len(), 1);
+    assert_eq!(ledger.entries()[0].status(), StorageDepositReturnStatus::Outstanding);
+    assert_eq!(ledger.unknown().count(), 0);
+
+    // Gifted native tokens still return to the sender if the recipient doesn't claim them in time
+    let transaction = account_0
+        .send_native_tokens(
+            [SendNativeTokensParams::new(recipient, [(token_id, U256::from(10))])?],
+            TransactionOptions {
+                storage_deposit_policy: Some(StorageDepositPolicy::Gift),
+                ..Default::default()
+            },
+        )
+        .await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    let sent = essence
+        .outputs()
+        .iter()
+        .find(|output| {
+            output.native_tokens().is_some_and(|native_tokens| {
+                native_tokens
+                    .iter()
+                    .any(|native_token| native_token.amount() == U256::from(10))
+            })
+        })
+        .unwrap();
+    let unlock_conditions = sent.unlock_conditions().unwrap();
+    assert!(unlock_conditions.storage_deposit_return().is_none());
+    assert_eq!(
+        unlock_conditions.expiration().unwrap().return_address(),
+        address_0.inner()
+    );
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,51 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        constants::SHIMMER_COIN_TYPE,
+        secret::{mnemonic::MnemonicSecretManager, SecretManager},
+        Client, Password,
+    },
+    wallet::{
+        storage::{StorageKey, StorageKind, StorageOptions},
+        ClientOptions, Result, Wallet,
+    },
+};
+
+use crate::wallet::common::{setup, tear_down, NODE_LOCAL};
+
+async fn encrypted_wallet(storage_options: StorageOptions) -> Result<Wallet> {
+    Wallet::builder()
+        .with_secret_manager(SecretManager::Mnemonic(MnemonicSecretManager::try_from_mnemonic(
+            Client::generate_mnemonic()?,
+        )?))
+        .with_client_options(ClientOptions::new().with_node(NODE_LOCAL)?)
+        .with_coin_type(SHIMMER_COIN_TYPE)
+        .with_storage_options(storage_options)
+        .finish()
+        .await
+}
+
+#[tokio::test]
+async fn rotate_storage_key() -> Result<()> {
+    let storage_path = "test-storage/rotate_storage_key";
+    setup(storage_path)?;
+
+    let storage_options = StorageOptions::new(storage_path.into(), StorageKind::Sqlite);
+    let wallet = encrypted_wallet(storage_options.clone().with_encryption_key([1; 32])).await?;
+    wallet.create_account().with_alias("Alice").finish().await?;
+
+    let password = Password::from("password".to_owned());
+    wallet.rotate_storage_key(StorageKey::from(password.clone())).await?;
+    drop(wallet);
+
+    // The previous key can't open the storage anymore
+    assert!(encrypted_wallet(storage_options.clone().with_encryption_key([1; 32]))
+        .await
+        .is_err());
+    let wallet = encrypted_wallet(storage_options.with_encryption_password(password)).await?;
+    assert_eq!(wallet.get_account("Alice").await?.alias().await, "Alice");
+
+    tear_down(storage_path)
+}
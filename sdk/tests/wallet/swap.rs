@@ -0,0 +1,326 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    client::{
+        api::{SwapOffer, SwapOfferDto, SwapParty, SwapTransaction, SwapTransactionDto},
+        secret::mnemonic::MnemonicSecretManager,
+        Client,
+    },
+    types::{
+        block::{
+            output::{
+                unlock_condition::AddressUnlockCondition, BasicOutputBuilder, NativeToken, NftId, NftOutputBuilder,
+                TokenId,
+            },
+            payload::transaction::{RegularTransactionEssence, TransactionEssence},
+            semantic::ConflictReason,
+        },
+        TryFromDto,
+    },
+    wallet::{
+        account::{types::Balance, SwapOfferParams},
+        Result,
+    },
+};
+use pretty_assertions::assert_eq;
+use primitive_types::U256;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{make_wallet, setup, tear_down},
+};
+
+fn native_token_total(balance: &Balance, token_id: &TokenId) -> U256 {
+    balance
+        .native_tokens()
+        .iter()
+        .find(|native_token| native_token.token_id() == token_id)
+        .map(|native_token| native_token.total())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn swap_native_tokens_for_base_coins() -> Result<()> {
+    let storage_path = "test-storage/swap_native_tokens_for_base_coins";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let maker = wallet.create_account().finish().await?;
+    let taker = wallet.create_account().finish().await?;
+    let maker_address = *maker.addresses().await?[0].address();
+    let taker_address = *taker.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    let token_id = TokenId::new([1; 38]);
+    mock_node.add_output(
+        BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+            .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+            .add_native_token(NativeToken::new(token_id, 100)?)
+            .finish_output(protocol_parameters.token_supply())?,
+    );
+    mock_node.fund(maker_address, 1_000_000);
+    mock_node.fund(taker_address, 3_000_000);
+    let maker_total = maker.sync(None).await?.base_coin().total();
+    let taker_total = taker.sync(None).await?.base_coin().total();
+
+    // The maker offers 60 tokens for 1 Mi.
+    let desired_output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+        .finish_output(protocol_parameters.token_supply())?;
+    let offer = maker
+        .prepare_swap_offer(
+            SwapOfferParams::new()
+                .with_native_tokens([NativeToken::new(token_id, 60)?])
+                .with_desired_outputs([desired_output.clone()]),
+            None,
+        )
+        .await?;
+    assert_eq!(offer.outputs()[0], desired_output);
+    let TransactionEssence::Regular(offer_essence) = &offer.essence;
+    // What the maker's inputs hold beyond its outputs, besides the desired output.
+    let offered_amount = offer.inputs_data.iter().map(|input| input.output.amount()).sum::<u64>()
+        + desired_output.amount()
+        - offer_essence
+            .outputs()
+            .iter()
+            .map(|output| output.amount())
+            .sum::<u64>();
+
+    // The offer is exchanged out-of-band.
+    let json = serde_json::to_string(&SwapOfferDto::from(&offer))?;
+    let offer = SwapOffer::try_from_dto(serde_json::from_str::<SwapOfferDto>(&json)?)?;
+
+    // An offer can't be accepted without balancing it.
+    assert!(matches!(
+        offer.accept(Vec::new(), [], &protocol_parameters),
+        Err(iota_sdk::client::Error::TransactionSemantic(
+            ConflictReason::CreatedConsumedAmountMismatch
+        ))
+    ));
+
+    let swap_transaction = taker.accept_swap_offer(&offer, None).await?;
+    assert!(!swap_transaction.is_signed());
+    assert!(swap_transaction.unlocks[swap_transaction.inputs_of(SwapParty::Maker)]
+        .iter()
+        .all(Option::is_none));
+
+    let json = serde_json::to_string(&SwapTransactionDto::from(&swap_transaction))?;
+    let swap_transaction = SwapTransaction::try_from_dto(serde_json::from_str::<SwapTransactionDto>(&json)?)?;
+
+    // The maker only signs the transaction if it completes its own offer.
+    let other_offer = SwapOffer::new(
+        offer.inputs_data.clone(),
+        [BasicOutputBuilder::new_with_amount(2_000_000)
+            .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+            .finish_output(protocol_parameters.token_supply())?],
+        &protocol_parameters,
+    )?;
+    assert!(matches!(
+        maker.sign_swap_transaction(&other_offer, swap_transaction.clone()).await,
+        Err(iota_sdk::wallet::Error::Client(error)) if matches!(*error, iota_sdk::client::Error::InvalidSwap(_))
+    ));
+
+    let swap_transaction = maker.sign_swap_transaction(&offer, swap_transaction).await?;
+    assert!(swap_transaction.is_signed());
+    maker.submit_swap_transaction(swap_transaction, None).await?;
+    mock_node.produce_milestone();
+
+    let maker_balance = maker.sync(None).await?;
+    let taker_balance = taker.sync(None).await?;
+    assert_eq!(
+        maker_balance.base_coin().total(),
+        maker_total - offered_amount + 1_000_000
+    );
+    assert_eq!(
+        taker_balance.base_coin().total(),
+        taker_total + offered_amount - 1_000_000
+    );
+    assert_eq!(native_token_total(&maker_balance, &token_id), U256::from(40));
+    assert_eq!(native_token_total(&taker_balance, &token_id), U256::from(60));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn swap_nft_for_native_tokens() -> Result<()> {
+    let storage_path = "test-storage/swap_nft_for_native_tokens";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let maker = wallet.create_account().finish().await?;
+    let taker = wallet.create_account().finish().await?;
+    let maker_address = *maker.addresses().await?[0].address();
+    let taker_address = *taker.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    let nft_id = NftId::new([3; 32]);
+    mock_node.add_output(
+        NftOutputBuilder::new_with_amount(1_000_000, nft_id)
+            .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+            .finish_output(protocol_parameters.token_supply())?,
+    );
+    let token_id = TokenId::new([2; 38]);
+    mock_node.add_output(
+        BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+            .add_unlock_condition(AddressUnlockCondition::new(taker_address))
+            .add_native_token(NativeToken::new(token_id, 50)?)
+            .finish_output(protocol_parameters.token_supply())?,
+    );
+    mock_node.fund(taker_address, 2_000_000);
+    maker.sync(None).await?;
+    taker.sync(None).await?;
+
+    // The maker offers the NFT for all 50 tokens of the taker.
+    let desired_output = BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+        .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+        .add_native_token(NativeToken::new(token_id, 50)?)
+        .finish_output(protocol_parameters.token_supply())?;
+    let offer = maker
+        .prepare_swap_offer(
+            SwapOfferParams::new()
+                .with_nft_ids([nft_id])
+                .with_desired_outputs([desired_output]),
+            None,
+        )
+        .await?;
+
+    // The taker signs when accepting and submits after the maker signed.
+    let swap_transaction = taker.accept_swap_offer(&offer, None).await?;
+    let swap_transaction = maker.sign_swap_transaction(&offer, swap_transaction).await?;
+    let transaction = taker.submit_swap_transaction(swap_transaction, None).await?;
+    let TransactionEssence::Regular(essence) = transaction.payload.essence();
+    assert_eq!(essence.inputs().len(), offer.inputs_data.len() + 2);
+    mock_node.produce_milestone();
+
+    let maker_balance = maker.sync(None).await?;
+    let taker_balance = taker.sync(None).await?;
+    assert!(maker_balance.nfts().is_empty());
+    assert_eq!(taker_balance.nfts(), &[nft_id]);
+    assert_eq!(native_token_total(&maker_balance, &token_id), U256::from(50));
+    assert_eq!(native_token_total(&taker_balance, &token_id), U256::from(0));
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn swap_tampered_by_maker() -> Result<()> {
+    let storage_path = "test-storage/swap_tampered_by_maker";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let maker = wallet.create_account().finish().await?;
+    let taker = wallet.create_account().finish().await?;
+    let maker_address = *maker.addresses().await?[0].address();
+    let taker_address = *taker.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    let token_id = TokenId::new([4; 38]);
+    mock_node.add_output(
+        BasicOutputBuilder::new_with_minimum_storage_deposit(*protocol_parameters.rent_structure())
+            .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+            .add_native_token(NativeToken::new(token_id, 100)?)
+            .finish_output(protocol_parameters.token_supply())?,
+    );
+    mock_node.fund(taker_address, 3_000_000);
+    maker.sync(None).await?;
+    taker.sync(None).await?;
+
+    let offer = maker
+        .prepare_swap_offer(
+            SwapOfferParams::new()
+                .with_native_tokens([NativeToken::new(token_id, 100)?])
+                .with_desired_outputs([BasicOutputBuilder::new_with_amount(1_000_000)
+                    .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+                    .finish_output(protocol_parameters.token_supply())?]),
+            None,
+        )
+        .await?;
+    let accepted = taker.accept_swap_offer(&offer, None).await?;
+
+    // The maker redirects the output receiving the offered tokens, the last one, to itself.
+    let TransactionEssence::Regular(essence) = &accepted.prepared_transaction_data.essence;
+    let mut outputs = essence.outputs().to_vec();
+    let received = outputs.pop().unwrap();
+    outputs.push(
+        BasicOutputBuilder::from(received.as_basic())
+            .with_unlock_conditions([AddressUnlockCondition::new(maker_address)])
+            .finish_output(protocol_parameters.token_supply())?,
+    );
+    let mut tampered = accepted.clone();
+    tampered.prepared_transaction_data.essence = TransactionEssence::Regular(
+        RegularTransactionEssence::builder(essence.network_id(), *essence.inputs_commitment())
+            .with_inputs(essence.inputs().to_vec())
+            .with_outputs(outputs)
+            .finish_with_params(&protocol_parameters)?,
+    );
+
+    // The signature of the taker doesn't cover the tampered essence.
+    assert!(matches!(
+        maker.sign_swap_transaction(&offer, tampered.clone()).await,
+        Err(iota_sdk::wallet::Error::Client(error))
+            if matches!(*error, iota_sdk::client::Error::TransactionSemantic(ConflictReason::InvalidSignature))
+    ));
+
+    // The taker doesn't sign a transaction other than the one it accepted.
+    let mut unaccepted = accepted.clone();
+    unaccepted.unlocks = vec![None; unaccepted.unlocks.len()];
+    tampered.unlocks = unaccepted.unlocks.clone();
+    let secret_manager = MnemonicSecretManager::try_from_mnemonic(Client::generate_mnemonic()?)?;
+    assert!(matches!(
+        tampered.sign_as_taker(&secret_manager, &unaccepted, 0).await,
+        Err(iota_sdk::client::Error::InvalidSwap(_))
+    ));
+
+    // The untampered transaction can still be completed.
+    let swap_transaction = maker.sign_swap_transaction(&offer, accepted).await?;
+    maker.submit_swap_transaction(swap_transaction, None).await?;
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn cancel_swap() -> Result<()> {
+    let storage_path = "test-storage/cancel_swap";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let maker = wallet.create_account().finish().await?;
+    let taker = wallet.create_account().finish().await?;
+    let maker_address = *maker.addresses().await?[0].address();
+    let taker_address = *taker.addresses().await?[0].address();
+    let protocol_parameters = mock_node.protocol_parameters();
+
+    mock_node.fund(maker_address, 2_000_000);
+    mock_node.fund(taker_address, 2_000_000);
+    maker.sync(None).await?;
+    taker.sync(None).await?;
+
+    let offer = maker
+        .prepare_swap_offer(
+            SwapOfferParams::new()
+                .with_amount(1_000_000)
+                .with_desired_outputs([BasicOutputBuilder::new_with_amount(500_000)
+                    .add_unlock_condition(AddressUnlockCondition::new(maker_address))
+                    .finish_output(protocol_parameters.token_supply())?]),
+            None,
+        )
+        .await?;
+    let swap_transaction = taker.accept_swap_offer(&offer, None).await?;
+    assert!(!maker.details().await.locked_outputs().is_empty());
+    assert!(!taker.details().await.locked_outputs().is_empty());
+
+    taker.cancel_swap_transaction(&swap_transaction).await?;
+    assert!(taker.details().await.locked_outputs().is_empty());
+    maker.cancel_swap_offer(&offer).await?;
+    assert!(maker.details().await.locked_outputs().is_empty());
+
+    // The unlocked outputs can be used again.
+    maker.send(1_000_000, taker_address, None).await?;
+
+    tear_down(storage_path)
+}
@@ -4,6 +4,8 @@
 use iota_sdk::wallet::{account::TransactionOptions, MintNftParams, Result, SendNftParams, SendParams};
 use pretty_assertions::assert_eq;
 
+#[cfg(feature = "opentelemetry")]
+use crate::utils::mock_node::MockNode;
 use crate::wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down};
 
 #[ignore]
@@ -305,3 +307,85 @@ async fn prepare_transaction_ledger() -> Result<()> {
 
     tear_down(storage_path)
 }
+
+#[cfg(feature = "opentelemetry")]
+#[tokio::test]
+async fn send_transaction_span() -> Result<()> {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+    // Collects the new spans with the name of their parent, and the recorded transaction ids
+    #[derive(Clone, Default)]
+    struct Collector {
+        spans: Arc<Mutex<Vec<(String, Option<String>)>>>,
+        transaction_ids: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Visit for Collector {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "transaction_id" {
+                self.transaction_ids.lock().unwrap().push(format!("{value:?}"));
+            }
+        }
+    }
+
+    impl<S: Subscriber + for<'span> LookupSpan<'span>> Layer<S> for Collector {
+        fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+            let parent = ctx
+                .span(id)
+                .and_then(|span| span.parent())
+                .map(|parent| parent.name().to_string());
+            self.spans
+                .lock()
+                .unwrap()
+                .push((attrs.metadata().name().to_string(), parent));
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            if ctx.span(id).is_some_and(|span| span.name() == "send_transaction") {
+                values.record(&mut self.clone());
+            }
+        }
+    }
+
+    let storage_path = "test-storage/send_transaction_span";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::start();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account = wallet.create_account().finish().await?;
+    let address = *account.addresses().await?[0].address();
+    mock_node.fund(address, 5_000_000);
+    account.sync(None).await?;
+
+    let collector = Collector::default();
+    let _guard = tracing::subscriber::set_default(tracing_subscriber::registry().with(collector.clone()));
+
+    let transaction = account
+        .send_with_params([SendParams::new(1_000_000, address)?], None)
+        .await?;
+
+    // The preparation, signing and submission are correlated by a single transaction span
+    let spans = collector.spans.lock().unwrap().clone();
+    assert_eq!(spans.iter().filter(|(name, _)| name == "send_transaction").count(), 1);
+    for step in ["prepare_transaction", "sign_transaction", "submit_transaction"] {
+        assert!(
+            spans
+                .iter()
+                .any(|(name, parent)| name == step && parent.as_deref() == Some("send_transaction")),
+            "{step} isn't in the transaction span"
+        );
+    }
+    assert_eq!(
+        *collector.transaction_ids.lock().unwrap(),
+        [transaction.transaction_id.to_string()]
+    );
+
+    tear_down(storage_path)
+}
@@ -0,0 +1,263 @@
+// Copyright 2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use iota_sdk::{
+    types::block::output::{
+        unlock_condition::{AddressUnlockCondition, ExpirationUnlockCondition},
+        BasicOutputBuilder,
+    },
+    wallet::{
+        account::{Account, OutputsToClaim, SyncOptions, VestingInstallment},
+        Result, VestingScheduleParams,
+    },
+};
+use pretty_assertions::assert_eq;
+
+use crate::{
+    utils::mock_node::MockNode,
+    wallet::common::{create_accounts_with_funds, make_wallet, setup, tear_down},
+};
+
+#[test]
+fn vesting_schedule_equal_installments() -> Result<()> {
+    let params = VestingScheduleParams::with_equal_installments(
+        "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
+        10_000_001,
+        3,
+        1_000,
+        100,
+    )?;
+
+    assert_eq!(
+        params.installments(),
+        &[
+            VestingInstallment {
+                amount: 3_333_333,
+                unlock_time: 1_000
+            },
+            VestingInstallment {
+                amount: 3_333_333,
+                unlock_time: 1_100
+            },
+            VestingInstallment {
+                amount: 3_333_335,
+                unlock_time: 1_200
+            },
+        ]
+    );
+    assert_eq!(params.total_amount(), 10_000_001);
+
+    assert!(VestingScheduleParams::with_equal_installments(
+        "rms1qpszqzadsym6wpppd6z037dvlejmjuke7s24hm95s9fg9vpua7vluaw60xu",
+        10_000_000,
+        0,
+        1_000,
+        100,
+    )
+    .is_err());
+
+    Ok(())
+}
+
+#[ignore]
+#[tokio::test]
+async fn create_vesting_schedule() -> Result<()> {
+    let storage_path = "test-storage/create_vesting_schedule";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let seconds_until_unlocked = 20;
+    let start_time = account_0.client().get_time_checked().await? + seconds_until_unlocked;
+    let params = VestingScheduleParams::with_equal_installments(
+        account_1.addresses().await?[0].address(),
+        3_000_000,
+        3,
+        start_time,
+        1_000,
+    )?;
+
+    let tx = account_0.create_vesting_schedule(params, None).await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().total(), 3_000_000);
+    assert_eq!(balance.base_coin().available(), 0);
+
+    let time_locked_balance = account_1.time_locked_balance().await?;
+    assert_eq!(time_locked_balance.locked().len(), 3);
+    assert_eq!(time_locked_balance.locked_amount(), 3_000_000);
+    assert_eq!(time_locked_balance.locked_amount_at(start_time), 2_000_000);
+    assert_eq!(time_locked_balance.locked_amount_at(start_time + 2_000), 0);
+
+    // Wait until the first installment is unlocked
+    tokio::time::sleep(std::time::Duration::from_secs(seconds_until_unlocked.into())).await;
+
+    let balance = account_1.sync(None).await?;
+    assert_eq!(balance.base_coin().available(), 1_000_000);
+    assert_eq!(account_1.time_locked_balance().await?.locked_amount(), 2_000_000);
+
+    tear_down(storage_path)
+}
+
+#[ignore]
+#[tokio::test]
+async fn reclaim_expired_outputs() -> Result<()> {
+    let storage_path = "test-storage/reclaim_expired_outputs";
+    setup(storage_path)?;
+
+    let wallet = make_wallet(storage_path, None, None).await?;
+
+    let account_0 = &create_accounts_with_funds(&wallet, 1).await?[0];
+    let account_1 = wallet.create_account().finish().await?;
+
+    let seconds_until_expired = 20;
+    let token_supply = account_0.client().get_token_supply().await?;
+    let outputs = [BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(
+            *account_1.addresses().await?[0].address().as_ref(),
+        ))
+        .add_unlock_condition(ExpirationUnlockCondition::new(
+            *account_0.addresses().await?[0].address().as_ref(),
+            account_0.client().get_time_checked().await? + seconds_until_expired,
+        )?)
+        .finish_output(token_supply)?];
+
+    let tx = account_0.send_outputs(outputs, None).await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    account_0.sync(None).await?;
+    let time_locked_balance = account_0.time_locked_balance().await?;
+    assert_eq!(time_locked_balance.expiring().len(), 1);
+    assert_eq!(time_locked_balance.reclaimable_amount(), 0);
+    assert!(account_0.claimable_outputs(OutputsToClaim::Expired).await?.is_empty());
+
+    // Wait until expired
+    tokio::time::sleep(std::time::Duration::from_secs(seconds_until_expired.into())).await;
+
+    account_0.sync(None).await?;
+    assert_eq!(account_0.time_locked_balance().await?.reclaimable_amount(), 1_000_000);
+
+    let expired_output_ids = account_0.claimable_outputs(OutputsToClaim::Expired).await?;
+    assert_eq!(expired_output_ids.len(), 1);
+    let tx = account_0.claim_outputs(expired_output_ids).await?;
+    account_0
+        .retry_transaction_until_included(&tx.transaction_id, None, None)
+        .await?;
+
+    account_0.sync(None).await?;
+    assert!(account_0.time_locked_balance().await?.expiring().is_empty());
+
+    tear_down(storage_path)
+}
+
+// Adds an output to the ledger which can be unlocked by the recipient account, but returned to the sender account as
+// its expiration time is already reached.
+async fn add_expired_output(mock_node: &MockNode, sender: &Account, recipient: &Account) -> Result<()> {
+    let output = BasicOutputBuilder::new_with_amount(1_000_000)
+        .add_unlock_condition(AddressUnlockCondition::new(
+            *recipient.addresses().await?[0].address().as_ref(),
+        ))
+        .add_unlock_condition(ExpirationUnlockCondition::new(
+            *sender.addresses().await?[0].address().as_ref(),
+            sender.client().get_time_checked().await? - 1,
+        )?)
+        .finish_output(mock_node.protocol_parameters().token_supply())?;
+    mock_node.add_output(output);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn background_syncing_reclaims_expired_outputs() -> Result<()> {
+    let storage_path = "test-storage/background_syncing_reclaims_expired_outputs";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::builder()
+        .with_milestone_interval(std::time::Duration::from_millis(500))
+        .finish();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    add_expired_output(&mock_node, &account_0, &account_1).await?;
+
+    account_0.sync(None).await?;
+    assert_eq!(account_0.time_locked_balance().await?.reclaimable_amount(), 1_000_000);
+
+    // Background syncing without options uses the default sync options of the accounts
+    account_0
+        .set_default_sync_options(SyncOptions {
+            reclaim_expired_outputs: true,
+            ..Default::default()
+        })
+        .await?;
+    wallet
+        .start_background_syncing(None, Some(std::time::Duration::from_secs(1)))
+        .await?;
+
+    let mut reclaimed = false;
+    for _ in 0..30 {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        if account_0.time_locked_balance().await?.expiring().is_empty()
+            && account_0.balance().await?.base_coin().available() == 1_000_000
+        {
+            reclaimed = true;
+            break;
+        }
+    }
+    wallet.stop_background_syncing().await?;
+    assert!(reclaimed, "the expired output wasn't reclaimed");
+    assert_eq!(account_0.transactions().await.len(), 1);
+
+    tear_down(storage_path)
+}
+
+#[tokio::test]
+async fn background_syncing_options_override_default_reclaim() -> Result<()> {
+    let storage_path = "test-storage/background_syncing_options_override_default_reclaim";
+    setup(storage_path)?;
+
+    let mock_node = MockNode::builder()
+        .with_milestone_interval(std::time::Duration::from_millis(500))
+        .finish();
+    let wallet = make_wallet(storage_path, None, Some(&mock_node.url())).await?;
+    let account_0 = wallet.create_account().finish().await?;
+    let account_1 = wallet.create_account().finish().await?;
+    add_expired_output(&mock_node, &account_0, &account_1).await?;
+
+    account_0
+        .set_default_sync_options(SyncOptions {
+            reclaim_expired_outputs: true,
+            ..Default::default()
+        })
+        .await?;
+    // The provided options take precedence over the default sync options
+    wallet
+        .start_background_syncing(Some(SyncOptions::default()), Some(std::time::Duration::from_secs(1)))
+        .await?;
+
+    let mut synced = false;
+    for _ in 0..30 {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        if account_0.time_locked_balance().await?.reclaimable_amount() == 1_000_000 {
+            synced = true;
+            break;
+        }
+    }
+    // Leave time for a round which would reclaim the output
+    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    wallet.stop_background_syncing().await?;
+    assert!(synced, "the expired output wasn't synced");
+    assert_eq!(account_0.time_locked_balance().await?.reclaimable_amount(), 1_000_000);
+    assert!(account_0.transactions().await.is_empty());
+    assert!(!account_0.claimable_outputs(OutputsToClaim::Expired).await?.is_empty());
+
+    tear_down(storage_path)
+}